@@ -0,0 +1,1408 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use std::cmp;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::prelude::*;
+use std::path::Path;
+use std::str;
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use scoped_pool::Pool;
+
+use crate::batch::BatchExecutor;
+use crate::cluster::partition::{set_max_records, set_sample_records, NodePartitions, Partition};
+use crate::cluster::{Cluster, CommandGuard, Node, PartitionMap};
+use crate::commands::admin_command::AdminCommand;
+use crate::commands::{
+    BackgroundQueryCommand, DeleteCommand, ExecuteUDFCommand, ExistsCommand, OperateCommand,
+    QueryCommand, ReadCommand, ScanCommand, TouchCommand, WriteCommand,
+};
+use crate::errors::{Error, ErrorKind, Result, ResultExt};
+use crate::expressions::FilterExpression;
+use crate::info;
+use crate::net::Host;
+use crate::net::ToHosts;
+use crate::operations::{self, Operation, OperationType};
+use crate::policy::{
+    AdminPolicy, BatchPolicy, ClientPolicy, QueryPolicy, ReadPolicy, ScanPolicy, WritePolicy,
+};
+use crate::runtime::{self, Runtime};
+use crate::task::{ExecuteTask, IndexTask, RegisterTask};
+use crate::{
+    BatchDelete, BatchRead, BatchRecord, BatchUDF, BatchWrite, Bin, Bins, CollectionIndexType,
+    IndexInfo, IndexType, Key, PartitionFilter, PartitionStatus, Privilege, Record, Recordset,
+    ResultCode, Role, Statement, UDFLang, UDFMeta, User, Value,
+};
+
+/// Asynchronous client for an Aerospike database cluster, running on the Tokio or async-std
+/// runtime. Requires the `rt-tokio` or the `rt-async-std` feature.
+///
+/// The client offers the same operations as [`Client`](crate::Client), but every command is a
+/// future that sends the request and reads the response over non-blocking sockets, so that no
+/// thread is blocked while waiting for the server. Each node of the cluster keeps a separate
+/// pool of non-blocking connections for the commands of the client. The runtime is selected when
+/// the client is created: a client created within the context of a Tokio runtime uses Tokio,
+/// otherwise the global async-std runtime is used, if that feature is enabled.
+///
+/// Scans and queries run as tasks of the runtime; consume the returned recordset as a `Stream`.
+/// The cluster is still tended by a background thread, and `Node::info` as well as the `Task`
+/// helpers block the calling thread; use `Node::info_async`, `Task::query_status_async` and
+/// `Task::wait_till_complete_async` instead. TLS connections are not supported by the async client yet.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # #[cfg(any(feature = "rt-tokio", feature = "rt-async-std"))]
+/// # mod example {
+/// use aerospike::{as_bin, as_key, AsyncClient, Bins, ClientPolicy, ReadPolicy, WritePolicy};
+///
+/// async fn put_and_get() {
+///     let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap_or("localhost".into());
+///     let client = AsyncClient::new(&ClientPolicy::default(), &hosts).await.unwrap();
+///     let key = as_key!("test", "test", "mykey");
+///     let bin = as_bin!("i", 42);
+///     client.put(&WritePolicy::default(), &key, &[&bin]).await.unwrap();
+///     let record = client.get(&ReadPolicy::default(), &key, Bins::All).await.unwrap();
+///     println!("{}", record);
+/// }
+/// # }
+/// ```
+pub struct AsyncClient {
+    cluster: Arc<Cluster>,
+    // Runs the commands of batches and queries of the blocking client; tasks of the runtime run
+    // them otherwise.
+    thread_pool: Option<Pool>,
+}
+
+impl AsyncClient {
+    /// Initializes the client with suitable hosts to seed the cluster map; see `Client::new`.
+    /// The initial connection to the cluster is established on a blocking thread of the runtime.
+    /// TLS connections are not supported yet; a client policy with a `tls_policy` is rejected
+    /// with an `InvalidArgument` error.
+    #[cfg(any(feature = "rt-tokio", feature = "rt-async-std"))]
+    pub async fn new<H>(policy: &ClientPolicy, hosts: &H) -> Result<Self>
+    where
+        H: ToHosts + Sync + ?Sized,
+    {
+        if policy.tls_policy.is_some() {
+            bail!(ErrorKind::InvalidArgument(
+                "TLS connections are not supported by the AsyncClient".to_string()
+            ));
+        }
+        let hosts = hosts.to_hosts()?;
+        let policy = policy.clone();
+        let runtime = Runtime::current();
+        runtime
+            .spawn_blocking(move || AsyncClient::with_runtime(&policy, &hosts, runtime, None))
+            .await?
+    }
+
+    // Connects to the cluster on the calling thread, for the commands of the given runtime.
+    pub(crate) fn with_runtime(
+        policy: &ClientPolicy,
+        hosts: &[Host],
+        runtime: Runtime,
+        thread_pool: Option<Pool>,
+    ) -> Result<Self> {
+        let cluster = Cluster::new(policy.clone(), hosts, runtime)?;
+        Ok(AsyncClient {
+            cluster,
+            thread_pool,
+        })
+    }
+
+    /// Closes the connection to the Aerospike cluster once the commands in flight finished; see
+    /// `Client::close_gracefully`.
+    pub async fn close_gracefully(&self, timeout: Duration) -> Result<()> {
+        let drained = self.cluster.drain_commands(timeout).await;
+        self.close()?;
+        drained
+    }
+
+    /// Closes the connection to the Aerospike cluster.
+    pub fn close(&self) -> Result<()> {
+        self.cluster.close()?;
+        if let Some(ref thread_pool) = self.thread_pool {
+            thread_pool.shutdown();
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the client is connected to any cluster nodes.
+    pub fn is_connected(&self) -> bool {
+        self.cluster.is_connected()
+    }
+
+    /// Returns the hosts used to seed the cluster; see `Client::seeds`.
+    pub fn seeds(&self) -> Vec<Host> {
+        self.cluster.seeds()
+    }
+
+    /// Adds hosts to seed the cluster with; see `Client::add_seeds`.
+    pub fn add_seeds(&self, hosts: &dyn ToHosts) -> Result<()> {
+        self.cluster.add_seeds(&hosts.to_hosts()?);
+        Ok(())
+    }
+
+    /// Removes hosts used to seed the cluster; see `Client::remove_seeds`.
+    pub fn remove_seeds(&self, hosts: &dyn ToHosts) -> Result<()> {
+        self.cluster.remove_seeds(&hosts.to_hosts()?);
+        Ok(())
+    }
+
+    /// Returns a list of the names of the active server nodes in the cluster.
+    pub fn node_names(&self) -> Vec<String> {
+        self.cluster
+            .nodes()
+            .iter()
+            .map(|node| node.name().to_owned())
+            .collect()
+    }
+
+    /// Return node given its name.
+    pub fn get_node(&self, name: &str) -> Result<Arc<Node>> {
+        self.cluster.get_node_by_name(name)
+    }
+
+    /// Returns a list of active server nodes in the cluster.
+    pub fn nodes(&self) -> Vec<Arc<Node>> {
+        self.cluster.nodes()
+    }
+
+    /// Returns the node of the master replica of the key; see `Client::node_for_key`.
+    pub fn node_for_key(&self, key: &Key) -> Result<Arc<Node>> {
+        self.cluster.get_master_node(&Partition::new_by_key(key))
+    }
+
+    /// Returns a snapshot of the partition map; see `Client::partition_map`.
+    pub fn partition_map(&self) -> PartitionMap {
+        self.cluster.partition_map()
+    }
+
+    /// Send info commands to a random active node of the cluster; see `Client::info`.
+    pub async fn info(
+        &self,
+        policy: &ReadPolicy,
+        commands: &[&str],
+    ) -> Result<HashMap<String, String>> {
+        let node = self.cluster.get_random_node()?;
+        node.info_async(policy.socket_timeout, commands).await
+    }
+
+    /// Read record for the specified key; see `Client::get`.
+    pub async fn get<T>(&self, policy: &ReadPolicy, key: &Key, bins: T) -> Result<Record>
+    where
+        T: Into<Bins>,
+    {
+        let _guard = self.cluster.start_command()?;
+        let bins = bins.into();
+        let mut command = ReadCommand::new(policy, self.cluster.clone(), key, bins);
+        command.execute().await?;
+        Ok(command.record.unwrap())
+    }
+
+    /// Read multiple records for specified batch keys in one batch call; see `Client::batch_get`.
+    pub async fn batch_get<'a>(
+        &self,
+        policy: &BatchPolicy,
+        batch_reads: Vec<BatchRead<'a>>,
+    ) -> Result<Vec<BatchRead<'a>>> {
+        let executor = BatchExecutor::new(self.cluster.clone(), self.thread_pool.clone());
+        if batch_reads
+            .iter()
+            .any(|batch_read| batch_read.ops.is_some())
+        {
+            executor.execute_batch_operate(policy, batch_reads).await
+        } else {
+            executor.execute_batch_read(policy, batch_reads).await
+        }
+    }
+
+    /// Check if multiple record keys exist in one batch call; see `Client::batch_exists`.
+    pub async fn batch_exists(&self, policy: &BatchPolicy, keys: &[Key]) -> Result<Vec<bool>> {
+        let bins = Bins::None;
+        let batch_reads = keys
+            .iter()
+            .map(|key| BatchRead::new(key.clone(), &bins))
+            .collect();
+        let executor = BatchExecutor::new(self.cluster.clone(), self.thread_pool.clone());
+        let results = executor.execute_batch_read(policy, batch_reads).await?;
+        Ok(results
+            .iter()
+            .map(|batch_read| batch_read.result_code == Some(ResultCode::Ok))
+            .collect())
+    }
+
+    /// Read the record headers for multiple keys in one batch call; see
+    /// `Client::batch_get_header`.
+    pub async fn batch_get_header(
+        &self,
+        policy: &BatchPolicy,
+        keys: &[Key],
+    ) -> Result<Vec<Option<Record>>> {
+        let bins = Bins::None;
+        let batch_reads = keys
+            .iter()
+            .map(|key| BatchRead::new(key.clone(), &bins))
+            .collect();
+        let executor = BatchExecutor::new(self.cluster.clone(), self.thread_pool.clone());
+        let results = executor.execute_batch_read(policy, batch_reads).await?;
+        Ok(results
+            .into_iter()
+            .map(|batch_read| batch_read.record)
+            .collect())
+    }
+
+    /// Read multiple records for the specified keys and return a recordset, which is filled by
+    /// a Tokio task per node; see `Client::batch_get_stream`.
+    pub async fn batch_get_stream<T>(
+        &self,
+        policy: &BatchPolicy,
+        keys: &[Key],
+        bins: T,
+    ) -> Result<Arc<Recordset>>
+    where
+        T: Into<Bins>,
+    {
+        let executor = BatchExecutor::new(self.cluster.clone(), self.thread_pool.clone());
+        executor.execute_batch_read_stream(policy, keys, bins.into())
+    }
+
+    /// Execute a mixed batch of reads, writes, deletes and UDF calls in one batch call; see
+    /// `Client::batch_operate`.
+    pub async fn batch_operate<'a>(
+        &self,
+        policy: &BatchPolicy,
+        batch_records: Vec<BatchRecord<'a>>,
+    ) -> Result<Vec<BatchRecord<'a>>> {
+        let executor = BatchExecutor::new(self.cluster.clone(), self.thread_pool.clone());
+        executor.execute_batch_operate(policy, batch_records).await
+    }
+
+    /// Write multiple records for specified batch keys in one batch call; see
+    /// `Client::batch_write`.
+    pub async fn batch_write<'a>(
+        &self,
+        policy: &BatchPolicy,
+        batch_writes: Vec<BatchWrite<'a>>,
+    ) -> Result<Vec<BatchWrite<'a>>> {
+        let executor = BatchExecutor::new(self.cluster.clone(), self.thread_pool.clone());
+        executor.execute_batch_operate(policy, batch_writes).await
+    }
+
+    /// Delete multiple records for specified batch keys in one batch call; see
+    /// `Client::batch_delete`.
+    pub async fn batch_delete<'a>(
+        &self,
+        policy: &BatchPolicy,
+        keys: &[Key],
+    ) -> Result<Vec<BatchDelete<'a>>> {
+        let batch_deletes = keys
+            .iter()
+            .map(|key| BatchDelete::new(key.clone()))
+            .collect();
+        let executor = BatchExecutor::new(self.cluster.clone(), self.thread_pool.clone());
+        executor.execute_batch_operate(policy, batch_deletes).await
+    }
+
+    /// Write record bin(s); see `Client::put`.
+    pub async fn put<'a, 'b, A: AsRef<Bin<'b>> + Sync>(
+        &self,
+        policy: &'a WritePolicy,
+        key: &'a Key,
+        bins: &'a [A],
+    ) -> Result<()> {
+        let _guard = self.cluster.start_command()?;
+        let mut command = WriteCommand::new(
+            policy,
+            self.cluster.clone(),
+            key,
+            bins,
+            OperationType::Write,
+        );
+        command.execute().await
+    }
+
+    /// Add integer bin values to existing record bin values; see `Client::add`.
+    pub async fn add<'a, 'b, A: AsRef<Bin<'b>> + Sync>(
+        &self,
+        policy: &'a WritePolicy,
+        key: &'a Key,
+        bins: &'a [A],
+    ) -> Result<()> {
+        let _guard = self.cluster.start_command()?;
+        let mut command =
+            WriteCommand::new(policy, self.cluster.clone(), key, bins, OperationType::Incr);
+        command.execute().await
+    }
+
+    /// Append bin string values to existing record bin values; see `Client::append`.
+    pub async fn append<'a, 'b, A: AsRef<Bin<'b>> + Sync>(
+        &self,
+        policy: &'a WritePolicy,
+        key: &'a Key,
+        bins: &'a [A],
+    ) -> Result<()> {
+        let _guard = self.cluster.start_command()?;
+        let mut command = WriteCommand::new(
+            policy,
+            self.cluster.clone(),
+            key,
+            bins,
+            OperationType::Append,
+        );
+        command.execute().await
+    }
+
+    /// Prepend bin string values to existing record bin values; see `Client::prepend`.
+    pub async fn prepend<'a, 'b, A: AsRef<Bin<'b>> + Sync>(
+        &self,
+        policy: &'a WritePolicy,
+        key: &'a Key,
+        bins: &'a [A],
+    ) -> Result<()> {
+        let _guard = self.cluster.start_command()?;
+        let mut command = WriteCommand::new(
+            policy,
+            self.cluster.clone(),
+            key,
+            bins,
+            OperationType::Prepend,
+        );
+        command.execute().await
+    }
+
+    /// Delete record for specified key; returns `true` if the record existed. See
+    /// `Client::delete`.
+    pub async fn delete(&self, policy: &WritePolicy, key: &Key) -> Result<bool> {
+        let _guard = self.cluster.start_command()?;
+        let mut command = DeleteCommand::new(policy, self.cluster.clone(), key);
+        command.execute().await?;
+        Ok(command.existed)
+    }
+
+    /// Reset record's time to expiration using the policy's expiration; see `Client::touch`.
+    pub async fn touch(&self, policy: &WritePolicy, key: &Key) -> Result<()> {
+        let _guard = self.cluster.start_command()?;
+        let mut command = TouchCommand::new(policy, self.cluster.clone(), key);
+        command.execute().await
+    }
+
+    /// Reset record's time to expiration and return the record's resulting generation and
+    /// expiration; see `Client::touch_with_response`.
+    pub async fn touch_with_response(&self, policy: &WritePolicy, key: &Key) -> Result<Record> {
+        let _guard = self.cluster.start_command()?;
+        let ops = [operations::touch(), operations::get_header()];
+        self.operate(policy, key, &ops).await
+    }
+
+    /// Determine if a record key exists.
+    pub async fn exists(&self, policy: &WritePolicy, key: &Key) -> Result<bool> {
+        let _guard = self.cluster.start_command()?;
+        let mut command = ExistsCommand::new(policy, self.cluster.clone(), key);
+        command.execute().await?;
+        Ok(command.exists)
+    }
+
+    /// Perform multiple read/write operations on a single key in one batch call; see
+    /// `Client::operate`.
+    pub async fn operate(
+        &self,
+        policy: &WritePolicy,
+        key: &Key,
+        ops: &[Operation<'_>],
+    ) -> Result<Record> {
+        let _guard = self.cluster.start_command()?;
+        let mut command = OperateCommand::new(policy, self.cluster.clone(), key, ops);
+        command.execute().await?;
+        Ok(command.read_command.record.unwrap())
+    }
+
+    /// Register a package containing user-defined functions (UDF) with the cluster; see
+    /// `Client::register_udf`.
+    pub async fn register_udf(
+        &self,
+        policy: &WritePolicy,
+        udf_body: &[u8],
+        udf_name: &str,
+        language: UDFLang,
+    ) -> Result<RegisterTask> {
+        let udf_body = base64::encode(udf_body);
+
+        let cmd = format!(
+            "udf-put:filename={};content={};content-len={};udf-type={};",
+            udf_name,
+            udf_body,
+            udf_body.len(),
+            language
+        );
+        let node = self.cluster.get_random_node()?;
+        let response = node
+            .info_async(policy.base_policy.socket_timeout, &[&cmd])
+            .await?;
+
+        if let Some(msg) = response.get("error") {
+            let msg = base64::decode(msg)?;
+            let msg = str::from_utf8(&msg)?;
+            bail!(
+                "UDF Registration failed: {}, file: {}, line: {}, message: {}",
+                response.get("error").unwrap_or(&"-".to_string()),
+                response.get("file").unwrap_or(&"-".to_string()),
+                response.get("line").unwrap_or(&"-".to_string()),
+                msg
+            );
+        }
+
+        Ok(RegisterTask::new(
+            Arc::clone(&self.cluster),
+            udf_name.to_string(),
+        ))
+    }
+
+    /// Register a package containing user-defined functions (UDF) from a file; see
+    /// `Client::register_udf_from_file`.
+    pub async fn register_udf_from_file(
+        &self,
+        policy: &WritePolicy,
+        client_path: &str,
+        udf_name: &str,
+        language: UDFLang,
+    ) -> Result<RegisterTask> {
+        let path = Path::new(client_path);
+        let mut file = File::open(&path)?;
+        let mut udf_body: Vec<u8> = vec![];
+        file.read_to_end(&mut udf_body)?;
+
+        self.register_udf(policy, &udf_body, udf_name, language)
+            .await?;
+        Ok(RegisterTask::with_content(
+            Arc::clone(&self.cluster),
+            udf_name.to_string(),
+            &udf_body,
+        ))
+    }
+
+    /// Remove a user-defined function (UDF) module from the server.
+    pub async fn remove_udf(
+        &self,
+        policy: &WritePolicy,
+        udf_name: &str,
+        language: UDFLang,
+    ) -> Result<()> {
+        let cmd = format!("udf-remove:filename={}.{};", udf_name, language);
+        let node = self.cluster.get_random_node()?;
+        let response = node
+            .info_async(policy.base_policy.socket_timeout, &[&cmd])
+            .await?;
+
+        match response.get(&cmd).map(String::as_str) {
+            Some("ok") => Ok(()),
+            _ => bail!("UDF Remove failed: {:?}", response),
+        }
+    }
+
+    /// List the user-defined function (UDF) packages registered with the cluster; see
+    /// `Client::list_udf`.
+    pub async fn list_udf(&self, policy: &ReadPolicy) -> Result<Vec<UDFMeta>> {
+        let node = self.cluster.get_random_node()?;
+        let response = node
+            .info_async(policy.socket_timeout, &["udf-list"])
+            .await?;
+        let response = response.get("udf-list").map_or("", String::as_str);
+        info::parse_list(response)
+            .into_iter()
+            .map(str::parse)
+            .collect()
+    }
+
+    /// Execute a user-defined function on the server and return the results; see
+    /// `Client::execute_udf`.
+    pub async fn execute_udf(
+        &self,
+        policy: &WritePolicy,
+        key: &Key,
+        udf_name: &str,
+        function_name: &str,
+        args: Option<&[Value]>,
+    ) -> Result<Option<Value>> {
+        let _guard = self.cluster.start_command()?;
+        let mut command = ExecuteUDFCommand::new(
+            policy,
+            self.cluster.clone(),
+            key,
+            udf_name,
+            function_name,
+            args,
+        );
+
+        command.execute().await?;
+
+        let record = command.read_command.record.unwrap();
+
+        // User defined functions don't have to return a value.
+        if record.bins.is_empty() {
+            return Ok(None);
+        }
+
+        for (key, value) in &record.bins {
+            if key.contains("SUCCESS") {
+                return Ok(Some(value.clone()));
+            } else if key.contains("FAILURE") {
+                bail!("{:?}", value);
+            }
+        }
+
+        Err("Invalid UDF return value".into())
+    }
+
+    /// Execute a user-defined function on multiple records in one batch call; see
+    /// `Client::batch_execute_udf`.
+    pub async fn batch_execute_udf<'a>(
+        &self,
+        policy: &BatchPolicy,
+        keys: &[Key],
+        udf_name: &'a str,
+        function_name: &'a str,
+        args: Option<&'a [Value]>,
+    ) -> Result<Vec<BatchUDF<'a>>> {
+        let batch_udfs = keys
+            .iter()
+            .map(|key| BatchUDF::new(key.clone(), udf_name, function_name, args))
+            .collect();
+        let executor = BatchExecutor::new(self.cluster.clone(), self.thread_pool.clone());
+        executor.execute_batch_operate(policy, batch_udfs).await
+    }
+
+    /// Read all records in the specified namespace and set and return a recordset, which is
+    /// filled by up to `policy.max_concurrent_nodes` Tokio tasks; see `Client::scan`.
+    pub async fn scan<T>(
+        &self,
+        policy: &ScanPolicy,
+        namespace: &str,
+        set_name: &str,
+        bins: T,
+    ) -> Result<Arc<Recordset>>
+    where
+        T: Into<Bins>,
+    {
+        let guard = self.cluster.start_command()?;
+        let bins = bins.into();
+        let mut node_partitions = self
+            .cluster
+            .nodes()
+            .into_iter()
+            .map(|node| {
+                let partitions = self.cluster.node_partitions(node.as_ref(), namespace);
+                (node, NodePartitions::new(partitions))
+            })
+            .collect();
+        if policy.sample {
+            set_sample_records(&mut node_partitions, policy.max_records);
+        } else {
+            set_max_records(&mut node_partitions, policy.max_records);
+        }
+
+        let recordset = Arc::new(Recordset::new(
+            policy.record_queue_size,
+            node_partitions.len(),
+        ));
+        if node_partitions.is_empty() {
+            recordset.close();
+        }
+        scan_nodes(
+            self.cluster.runtime(),
+            &guard,
+            policy,
+            node_partitions,
+            namespace,
+            set_name,
+            &bins,
+            &recordset,
+        );
+        Ok(recordset)
+    }
+
+    /// Read all records in the specified namespace and set for one node only; see
+    /// `Client::scan_node`.
+    pub async fn scan_node<T>(
+        &self,
+        policy: &ScanPolicy,
+        node: Arc<Node>,
+        namespace: &str,
+        set_name: &str,
+        bins: T,
+    ) -> Result<Arc<Recordset>>
+    where
+        T: Into<Bins>,
+    {
+        let guard = self.cluster.start_command()?;
+        if !node.is_active() {
+            bail!(ErrorKind::InvalidNode(format!(
+                "Node {} is not active",
+                node.name()
+            )));
+        }
+        let mut partitions =
+            NodePartitions::new(self.cluster.node_partitions(node.as_ref(), namespace));
+        partitions.max_records = policy.max_records;
+        let recordset = Arc::new(Recordset::new(policy.record_queue_size, 1));
+        scan_nodes(
+            self.cluster.runtime(),
+            &guard,
+            policy,
+            vec![(node, partitions)],
+            namespace,
+            set_name,
+            &bins.into(),
+            &recordset,
+        );
+        Ok(recordset)
+    }
+
+    /// Read all records in the given partition range of the specified namespace and set; see
+    /// `Client::scan_partitions`.
+    pub async fn scan_partitions<T>(
+        &self,
+        policy: &ScanPolicy,
+        partition_filter: &PartitionFilter,
+        namespace: &str,
+        set_name: &str,
+        bins: T,
+    ) -> Result<Arc<Recordset>>
+    where
+        T: Into<Bins>,
+    {
+        let guard = self.cluster.start_command()?;
+        let bins = bins.into();
+        let mut partition_filter = partition_filter.clone();
+        partition_filter.init_partitions()?;
+        let mut node_partitions = node_partitions(&self.cluster, namespace, &partition_filter);
+        if policy.sample {
+            set_sample_records(&mut node_partitions, policy.max_records);
+        } else {
+            set_max_records(&mut node_partitions, policy.max_records);
+        }
+
+        let recordset = Arc::new(Recordset::new(
+            policy.record_queue_size,
+            node_partitions.len(),
+        ));
+        recordset.set_partition_filter(partition_filter);
+        if node_partitions.is_empty() {
+            recordset.close();
+        }
+        scan_nodes(
+            self.cluster.runtime(),
+            &guard,
+            policy,
+            node_partitions,
+            namespace,
+            set_name,
+            &bins,
+            &recordset,
+        );
+        Ok(recordset)
+    }
+
+    /// Execute a query on all server nodes and return a recordset, which is filled by up to
+    /// `policy.max_concurrent_nodes` Tokio tasks; see `Client::query`.
+    pub async fn query(
+        &self,
+        policy: &QueryPolicy,
+        statement: Statement,
+    ) -> Result<Arc<Recordset>> {
+        let guard = self.cluster.start_command()?;
+        statement.validate()?;
+        let statement = Arc::new(statement);
+
+        let mut node_partitions = self
+            .cluster
+            .nodes()
+            .into_iter()
+            .map(|node| {
+                let partitions = self
+                    .cluster
+                    .node_partitions(node.as_ref(), &statement.namespace);
+                (node, NodePartitions::new(partitions))
+            })
+            .collect();
+        set_max_records(&mut node_partitions, policy.max_records);
+
+        let recordset = Arc::new(Recordset::new(
+            policy.record_queue_size,
+            node_partitions.len(),
+        ));
+        if node_partitions.is_empty() {
+            recordset.close();
+        }
+        query_nodes(
+            self.cluster.runtime(),
+            &guard,
+            self.thread_pool.as_ref(),
+            policy,
+            node_partitions,
+            &statement,
+            &recordset,
+        );
+        Ok(recordset)
+    }
+
+    /// Execute a query without secondary index filter on the given partition range; see
+    /// `Client::query_partitions`.
+    pub async fn query_partitions(
+        &self,
+        policy: &QueryPolicy,
+        partition_filter: &PartitionFilter,
+        statement: Statement,
+    ) -> Result<Arc<Recordset>> {
+        let guard = self.cluster.start_command()?;
+        statement.validate()?;
+        if !statement.is_scan() {
+            bail!(ErrorKind::InvalidArgument(
+                "Partition queries do not support secondary index filters".to_string()
+            ));
+        }
+        let statement = Arc::new(statement);
+
+        let mut partition_filter = partition_filter.clone();
+        partition_filter.init_partitions()?;
+        let mut node_partitions =
+            node_partitions(&self.cluster, &statement.namespace, &partition_filter);
+        set_max_records(&mut node_partitions, policy.max_records);
+
+        let recordset = Arc::new(Recordset::new(
+            policy.record_queue_size,
+            node_partitions.len(),
+        ));
+        recordset.set_partition_filter(partition_filter);
+        if node_partitions.is_empty() {
+            recordset.close();
+        }
+        query_nodes(
+            self.cluster.runtime(),
+            &guard,
+            self.thread_pool.as_ref(),
+            policy,
+            node_partitions,
+            &statement,
+            &recordset,
+        );
+        Ok(recordset)
+    }
+
+    /// Execute a query on a single server node; see `Client::query_node`.
+    pub async fn query_node(
+        &self,
+        policy: &QueryPolicy,
+        node: Arc<Node>,
+        statement: Statement,
+    ) -> Result<Arc<Recordset>> {
+        let guard = self.cluster.start_command()?;
+        statement.validate()?;
+
+        if !node.is_active() {
+            bail!(ErrorKind::InvalidNode(format!(
+                "Node {} is not active",
+                node.name()
+            )));
+        }
+        let mut partitions = NodePartitions::new(
+            self.cluster
+                .node_partitions(node.as_ref(), &statement.namespace),
+        );
+        partitions.max_records = policy.max_records;
+        let recordset = Arc::new(Recordset::new(policy.record_queue_size, 1));
+        query_nodes(
+            self.cluster.runtime(),
+            &guard,
+            self.thread_pool.as_ref(),
+            policy,
+            vec![(node, partitions)],
+            &Arc::new(statement),
+            &recordset,
+        );
+        Ok(recordset)
+    }
+
+    /// Execute a query and aggregate the matching records with the given Lua stream UDF on the
+    /// server nodes; see `Client::query_aggregate`.
+    pub async fn query_aggregate(
+        &self,
+        policy: &QueryPolicy,
+        mut statement: Statement,
+        package_name: &str,
+        function_name: &str,
+        args: Option<&[Value]>,
+    ) -> Result<Arc<Recordset>> {
+        statement.set_aggregate_function(package_name, function_name, args);
+        self.query(policy, statement).await
+    }
+
+    /// Apply the operations to all records matched by the query on the server, as a background
+    /// job; see `Client::query_execute`.
+    pub async fn query_execute(
+        &self,
+        policy: &WritePolicy,
+        statement: Statement,
+        operations: &[Operation<'_>],
+    ) -> Result<ExecuteTask> {
+        let _guard = self.cluster.start_command()?;
+        statement.validate()?;
+        if statement.aggregation.is_some() {
+            bail!(ErrorKind::InvalidArgument(
+                "Background queries do not support aggregation functions".to_string()
+            ));
+        }
+        if operations.is_empty() || !operations.iter().all(Operation::is_write) {
+            bail!(ErrorKind::InvalidArgument(
+                "Background queries require one or more write operations".to_string()
+            ));
+        }
+
+        let task_id = rand::random::<u64>();
+        for node in self.cluster.nodes() {
+            let partitions = self
+                .cluster
+                .node_partitions(node.as_ref(), &statement.namespace);
+            let mut command = BackgroundQueryCommand::new(
+                policy,
+                node,
+                &statement,
+                operations,
+                task_id,
+                NodePartitions::new(partitions),
+            );
+            command.execute().await?;
+        }
+        Ok(ExecuteTask::new(self.cluster.clone(), task_id))
+    }
+
+    /// Apply the operations to all records of the specified namespace and set on the server, as
+    /// a background job; see `Client::scan_execute`.
+    pub async fn scan_execute(
+        &self,
+        policy: &WritePolicy,
+        namespace: &str,
+        set_name: &str,
+        operations: &[Operation<'_>],
+    ) -> Result<ExecuteTask> {
+        let statement = Statement::new(namespace, set_name, Bins::None);
+        self.query_execute(policy, statement, operations).await
+    }
+
+    /// Removes all records in the specified namespace/set efficiently; see `Client::truncate`.
+    pub async fn truncate(
+        &self,
+        policy: &WritePolicy,
+        namespace: &str,
+        set_name: &str,
+        before_nanos: i64,
+    ) -> Result<()> {
+        let mut cmd = String::with_capacity(160);
+        cmd.push_str("truncate:namespace=");
+        cmd.push_str(namespace);
+
+        if !set_name.is_empty() {
+            cmd.push_str(";set=");
+            cmd.push_str(set_name);
+        }
+
+        if before_nanos > 0 {
+            cmd.push_str(";lut=");
+            cmd.push_str(&format!("{}", before_nanos));
+        }
+
+        let node = self.cluster.get_principal_node()?;
+        AsyncClient::send_node_info_cmd(&node, &cmd, policy)
+            .await
+            .chain_err(|| "Error truncating ns/set")
+    }
+
+    /// Set the filter expression that XDR uses to select the records of `namespace` shipped to
+    /// `datacenter`; see `Client::set_xdr_filter`.
+    pub async fn set_xdr_filter(
+        &self,
+        policy: &WritePolicy,
+        datacenter: &str,
+        namespace: &str,
+        filter: Option<&FilterExpression>,
+    ) -> Result<()> {
+        let exp = match filter {
+            Some(filter) => filter.base64()?,
+            None => "null".to_string(),
+        };
+        let cmd = format!(
+            "xdr-set-filter:dc={};namespace={};exp={}",
+            datacenter, namespace, exp
+        );
+        self.send_info_cmd(&cmd, policy)
+            .await
+            .chain_err(|| "Error setting XDR filter")
+    }
+
+    /// Create a secondary index on a bin containing scalar values; see `Client::create_index`.
+    pub async fn create_index(
+        &self,
+        policy: &WritePolicy,
+        namespace: &str,
+        set_name: &str,
+        bin_name: &str,
+        index_name: &str,
+        index_type: IndexType,
+    ) -> Result<IndexTask> {
+        self.create_complex_index(
+            policy,
+            namespace,
+            set_name,
+            bin_name,
+            index_name,
+            index_type,
+            CollectionIndexType::Default,
+        )
+        .await
+    }
+
+    /// Create a complex secondary index on a bin containing scalar, list or map values; see
+    /// `Client::create_complex_index`.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_complex_index(
+        &self,
+        policy: &WritePolicy,
+        namespace: &str,
+        set_name: &str,
+        bin_name: &str,
+        index_name: &str,
+        index_type: IndexType,
+        collection_index_type: CollectionIndexType,
+    ) -> Result<IndexTask> {
+        let cit_str: String = if let CollectionIndexType::Default = collection_index_type {
+            "".to_string()
+        } else {
+            format!("indextype={};", collection_index_type)
+        };
+        let cmd = format!(
+            "sindex-create:ns={};set={};indexname={};numbins=1;{}indexdata={},{};\
+             priority=normal",
+            namespace, set_name, index_name, cit_str, bin_name, index_type
+        );
+        self.send_info_cmd(&cmd, policy)
+            .await
+            .chain_err(|| "Error creating index")?;
+        Ok(IndexTask::new(
+            Arc::clone(&self.cluster),
+            namespace.to_string(),
+            index_name.to_string(),
+        ))
+    }
+
+    /// Delete secondary index.
+    pub async fn drop_index(
+        &self,
+        policy: &WritePolicy,
+        namespace: &str,
+        set_name: &str,
+        index_name: &str,
+    ) -> Result<()> {
+        let set_name: String = if let "" = set_name {
+            "".to_string()
+        } else {
+            format!("set={};", set_name)
+        };
+        let cmd = format!(
+            "sindex-delete:ns={};{}indexname={}",
+            namespace, set_name, index_name
+        );
+        self.send_info_cmd(&cmd, policy)
+            .await
+            .chain_err(|| "Error dropping index")
+    }
+
+    /// List the secondary indexes defined in the specified namespace; see
+    /// `Client::list_indexes`.
+    pub async fn list_indexes(
+        &self,
+        policy: &ReadPolicy,
+        namespace: &str,
+    ) -> Result<Vec<IndexInfo>> {
+        let cmd = format!("sindex-list:ns={}", namespace);
+        let node = self.cluster.get_random_node()?;
+        let response = node.info_async(policy.socket_timeout, &[&cmd]).await?;
+        let response = response.get(&cmd).map_or("", String::as_str);
+
+        if response.starts_with("FAIL:") || response.starts_with("ERROR:") {
+            bail!(ErrorKind::BadResponse(format!(
+                "Error listing indexes: {}",
+                response
+            )));
+        }
+        info::parse_list(response)
+            .into_iter()
+            .map(str::parse)
+            .collect()
+    }
+
+    /// Create a user with the given password and roles; see `Client::create_user`.
+    pub async fn create_user(
+        &self,
+        policy: &AdminPolicy,
+        user: &str,
+        password: &str,
+        roles: &[&str],
+    ) -> Result<()> {
+        AdminCommand::create_user(&self.cluster, policy, user, password, roles).await
+    }
+
+    /// Remove a user from the cluster.
+    pub async fn drop_user(&self, policy: &AdminPolicy, user: &str) -> Result<()> {
+        AdminCommand::drop_user(&self.cluster, policy, user).await
+    }
+
+    /// Set the password of a user, without knowing the current password; see
+    /// `Client::set_password`.
+    pub async fn set_password(
+        &self,
+        policy: &AdminPolicy,
+        user: &str,
+        password: &str,
+    ) -> Result<()> {
+        AdminCommand::set_password(&self.cluster, policy, user, password).await
+    }
+
+    /// Change the password of a user; see `Client::change_password`.
+    pub async fn change_password(
+        &self,
+        policy: &AdminPolicy,
+        user: &str,
+        password: &str,
+    ) -> Result<()> {
+        match self.cluster.user_password() {
+            Some((ref current_user, _)) if current_user == user => {
+                AdminCommand::change_password(&self.cluster, policy, user, password).await?;
+                self.cluster
+                    .set_user_password(user.to_string(), AdminCommand::hash_password(password)?);
+                Ok(())
+            }
+            _ => AdminCommand::set_password(&self.cluster, policy, user, password).await,
+        }
+    }
+
+    /// Add roles to a user.
+    pub async fn grant_roles(
+        &self,
+        policy: &AdminPolicy,
+        user: &str,
+        roles: &[&str],
+    ) -> Result<()> {
+        AdminCommand::grant_roles(&self.cluster, policy, user, roles).await
+    }
+
+    /// Remove roles from a user.
+    pub async fn revoke_roles(
+        &self,
+        policy: &AdminPolicy,
+        user: &str,
+        roles: &[&str],
+    ) -> Result<()> {
+        AdminCommand::revoke_roles(&self.cluster, policy, user, roles).await
+    }
+
+    /// Create a user-defined role with the given privileges, whitelist and quotas; see
+    /// `Client::create_role`.
+    pub async fn create_role(
+        &self,
+        policy: &AdminPolicy,
+        role: &str,
+        privileges: &[Privilege],
+        whitelist: &[&str],
+        read_quota: u32,
+        write_quota: u32,
+    ) -> Result<()> {
+        AdminCommand::create_role(
+            &self.cluster,
+            policy,
+            role,
+            privileges,
+            whitelist,
+            read_quota,
+            write_quota,
+        )
+        .await
+    }
+
+    /// Remove a user-defined role.
+    pub async fn drop_role(&self, policy: &AdminPolicy, role: &str) -> Result<()> {
+        AdminCommand::drop_role(&self.cluster, policy, role).await
+    }
+
+    /// Set the whitelist of a user-defined role; see `Client::set_whitelist`.
+    pub async fn set_whitelist(
+        &self,
+        policy: &AdminPolicy,
+        role: &str,
+        whitelist: &[&str],
+    ) -> Result<()> {
+        AdminCommand::set_whitelist(&self.cluster, policy, role, whitelist).await
+    }
+
+    /// Set the read and write quotas of a user-defined role; see `Client::set_quotas`.
+    pub async fn set_quotas(
+        &self,
+        policy: &AdminPolicy,
+        role: &str,
+        read_quota: u32,
+        write_quota: u32,
+    ) -> Result<()> {
+        AdminCommand::set_quotas(&self.cluster, policy, role, read_quota, write_quota).await
+    }
+
+    /// Add privileges to a user-defined role.
+    pub async fn grant_privileges(
+        &self,
+        policy: &AdminPolicy,
+        role: &str,
+        privileges: &[Privilege],
+    ) -> Result<()> {
+        AdminCommand::grant_privileges(&self.cluster, policy, role, privileges).await
+    }
+
+    /// Remove privileges from a user-defined role.
+    pub async fn revoke_privileges(
+        &self,
+        policy: &AdminPolicy,
+        role: &str,
+        privileges: &[Privilege],
+    ) -> Result<()> {
+        AdminCommand::revoke_privileges(&self.cluster, policy, role, privileges).await
+    }
+
+    /// Retrieve a user, with its roles and current statistics; see `Client::query_user`.
+    pub async fn query_user(&self, policy: &AdminPolicy, user: &str) -> Result<Option<User>> {
+        let users = AdminCommand::query_users(&self.cluster, policy, Some(user)).await?;
+        Ok(users.into_iter().next())
+    }
+
+    /// Retrieve all users, with their roles and current statistics.
+    pub async fn query_users(&self, policy: &AdminPolicy) -> Result<Vec<User>> {
+        AdminCommand::query_users(&self.cluster, policy, None).await
+    }
+
+    /// Retrieve a role, with its privileges, whitelist and quotas; see `Client::query_role`.
+    pub async fn query_role(&self, policy: &AdminPolicy, role: &str) -> Result<Option<Role>> {
+        let roles = AdminCommand::query_roles(&self.cluster, policy, Some(role)).await?;
+        Ok(roles.into_iter().next())
+    }
+
+    /// Retrieve all roles, including the predefined roles, with their privileges, whitelists and
+    /// quotas.
+    pub async fn query_roles(&self, policy: &AdminPolicy) -> Result<Vec<Role>> {
+        AdminCommand::query_roles(&self.cluster, policy, None).await
+    }
+
+    async fn send_info_cmd(&self, cmd: &str, policy: &WritePolicy) -> Result<()> {
+        let node = self.cluster.get_random_node()?;
+        AsyncClient::send_node_info_cmd(&node, cmd, policy).await
+    }
+
+    async fn send_node_info_cmd(node: &Node, cmd: &str, policy: &WritePolicy) -> Result<()> {
+        let response = node
+            .info_async(policy.base_policy.socket_timeout, &[cmd])
+            .await?;
+        check_info_response(&response)
+    }
+}
+
+// Scan the partitions of each node, on at most `policy.max_concurrent_nodes` threads, or tasks of
+// the runtime of the cluster, at a time. The threads only hold a weak reference to the recordset
+// and stop taking up further nodes once the recordset has been closed or dropped by the consumer.
+// Each thread holds a clone of the command guard, so that a graceful close waits for the scan.
+#[allow(clippy::too_many_arguments)]
+fn scan_nodes(
+    runtime: Runtime,
+    guard: &CommandGuard,
+    policy: &ScanPolicy,
+    node_partitions: Vec<(Arc<Node>, NodePartitions)>,
+    namespace: &str,
+    set_name: &str,
+    bins: &Bins,
+    recordset: &Arc<Recordset>,
+) {
+    let threads = concurrent_nodes(policy.max_concurrent_nodes, node_partitions.len());
+    let jobs = Arc::new(Mutex::new(node_partitions.into_iter()));
+    for _ in 0..threads {
+        let jobs = jobs.clone();
+        let recordset = Arc::downgrade(recordset);
+        let policy = policy.to_owned();
+        let namespace = namespace.to_owned();
+        let set_name = set_name.to_owned();
+        let bins = bins.clone();
+        let guard = guard.clone();
+
+        runtime.spawn(async move {
+            let _guard = guard;
+            let next_job = || jobs.lock().next();
+            while let Some((node, partitions)) = next_job() {
+                let mut command = match recordset.upgrade() {
+                    Some(recordset) if recordset.is_active() => ScanCommand::new(
+                        &policy,
+                        node,
+                        &namespace,
+                        &set_name,
+                        bins.clone(),
+                        recordset,
+                        partitions,
+                    ),
+                    _ => break,
+                };
+                if let Err(err) = command.execute().await {
+                    push_node_error(&recordset, err);
+                }
+            }
+        });
+    }
+}
+
+// Query the partitions of each node, on at most `policy.max_concurrent_nodes` threads of the
+// thread pool, or tasks of the runtime of the cluster without a thread pool, at a time.
+fn query_nodes(
+    runtime: Runtime,
+    guard: &CommandGuard,
+    thread_pool: Option<&Pool>,
+    policy: &QueryPolicy,
+    node_partitions: Vec<(Arc<Node>, NodePartitions)>,
+    statement: &Arc<Statement>,
+    recordset: &Arc<Recordset>,
+) {
+    let threads = concurrent_nodes(policy.max_concurrent_nodes, node_partitions.len());
+    let jobs = Arc::new(Mutex::new(node_partitions.into_iter()));
+    for _ in 0..threads {
+        let jobs = jobs.clone();
+        let recordset = Arc::downgrade(recordset);
+        let policy = policy.to_owned();
+        let statement = statement.clone();
+        let guard = guard.clone();
+
+        let job = async move {
+            let _guard = guard;
+            let next_job = || jobs.lock().next();
+            while let Some((node, partitions)) = next_job() {
+                let mut command = match recordset.upgrade() {
+                    Some(recordset) if recordset.is_active() => {
+                        QueryCommand::new(&policy, node, statement.clone(), recordset, partitions)
+                    }
+                    _ => break,
+                };
+                if let Err(err) = command.execute().await {
+                    push_node_error(&recordset, err);
+                }
+            }
+        };
+        match thread_pool {
+            Some(thread_pool) => thread_pool.spawn(move || runtime::block_on(job)),
+            None => runtime.spawn(job),
+        }
+    }
+}
+
+// Collect the partitions of the filter that remain to be scanned for each node. Partitions
+// that have been scanned partially are resumed by the digest of their last record.
+fn node_partitions(
+    cluster: &Cluster,
+    namespace: &str,
+    partition_filter: &PartitionFilter,
+) -> Vec<(Arc<Node>, NodePartitions)> {
+    let mut node_partitions = vec![];
+    for node in cluster.nodes() {
+        let mut partitions = NodePartitions::default();
+        for partition_id in cluster.node_partitions(node.as_ref(), namespace) {
+            if !partition_filter.contains(partition_id) {
+                continue;
+            }
+            match partition_filter.status(partition_id) {
+                Some(status) if status.done => (),
+                Some(PartitionStatus {
+                    digest: Some(digest),
+                    ..
+                }) => partitions.digests.push(*digest),
+                _ => partitions.partitions.push(partition_id),
+            }
+        }
+        if !partitions.is_empty() {
+            node_partitions.push((node, partitions));
+        }
+    }
+    node_partitions
+}
+
+// Checks the response of an info command that returns "ok", or the result code of the failure.
+fn check_info_response(response: &HashMap<String, String>) -> Result<()> {
+    if let Some(v) = response.values().next() {
+        if v.to_uppercase() == "OK" {
+            return Ok(());
+        } else if v.starts_with("FAIL:") {
+            let result = v.split(':').nth(1).unwrap().parse::<u8>()?;
+            bail!(ErrorKind::ServerError(ResultCode::from(result)));
+        }
+    }
+
+    bail!(ErrorKind::BadResponse(
+        "Unexpected info command response".to_string()
+    ))
+}
+
+// Number of nodes to scan or query in parallel; zero means all nodes.
+fn concurrent_nodes(max_concurrent_nodes: usize, nodes: usize) -> usize {
+    if max_concurrent_nodes == 0 {
+        nodes
+    } else {
+        cmp::min(max_concurrent_nodes, nodes)
+    }
+}
+
+// Errors of a node are returned through the recordset, unless the consumer has stopped reading.
+fn push_node_error(recordset: &Weak<Recordset>, err: Error) {
+    if let Some(recordset) = recordset.upgrade() {
+        if recordset.is_active() {
+            recordset.push(Err(err));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AsyncClient;
+    use crate::operations;
+    use crate::policy::{AdminPolicy, BatchPolicy, ReadPolicy, ScanPolicy, WritePolicy};
+    use crate::{Bins, Key};
+    use std::future::Future;
+
+    fn assert_send<F: Future + Send>(_: F) {}
+
+    // The futures of the client have to be sendable, to be spawned on a multi-threaded runtime.
+    #[allow(dead_code)]
+    fn futures_are_send(client: &AsyncClient, key: &Key, keys: &[Key]) {
+        let bin = as_bin!("a", 1);
+        assert_send(client.get(&ReadPolicy::default(), key, Bins::All));
+        assert_send(client.put(&WritePolicy::default(), key, &[&bin]));
+        assert_send(client.batch_exists(&BatchPolicy::default(), keys));
+        assert_send(client.scan(&ScanPolicy::default(), "test", "test", Bins::All));
+        assert_send(client.operate(&WritePolicy::default(), key, &[operations::get()]));
+        assert_send(client.info(&ReadPolicy::default(), &["build"]));
+        assert_send(client.query_users(&AdminPolicy::default()));
+    }
+}
@@ -0,0 +1,253 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use crate::commands::buffer::{
+    INFO1_GET_ALL, INFO1_NOBINDATA, INFO1_READ, INFO2_CREATE_ONLY, INFO2_DELETE,
+    INFO2_DURABLE_DELETE, INFO2_GENERATION, INFO2_GENERATION_GT, INFO2_RESPOND_ALL_OPS,
+    INFO2_WRITE, INFO3_COMMIT_MASTER, INFO3_CREATE_OR_REPLACE, INFO3_REPLACE_ONLY,
+    INFO3_UPDATE_ONLY,
+};
+use crate::expressions::FilterExpression;
+use crate::operations::{Operation, OperationBin, OperationType};
+use crate::policy::{
+    BatchDeletePolicy, BatchUDFPolicy, BatchWritePolicy, CommitLevel, GenerationPolicy,
+    RecordExistsAction,
+};
+use crate::{Bins, Value};
+
+// Returns true if any of the operations modifies the record.
+pub fn has_write(operations: &[Operation]) -> bool {
+    operations.iter().any(Operation::is_write)
+}
+
+// User-defined function call of a batch UDF record.
+pub struct BatchUDFCall<'a> {
+    pub package_name: &'a str,
+    pub function_name: &'a str,
+    pub args: Option<&'a [Value]>,
+}
+
+// Wire attributes of a single record in a batch operate command.
+pub struct BatchAttr<'a> {
+    pub filter_expression: Option<&'a FilterExpression>,
+    pub operations: &'a [Operation<'a>],
+    pub bin_names: &'a [String],
+    pub udf: Option<BatchUDFCall<'a>>,
+    pub read_attr: u8,
+    pub write_attr: u8,
+    pub info_attr: u8,
+    pub generation: u16,
+    pub expiration: u32,
+    pub send_key: bool,
+    pub has_write: bool,
+}
+
+impl<'a> BatchAttr<'a> {
+    pub fn read(bins: &'a Bins) -> Self {
+        let mut attr = BatchAttr {
+            filter_expression: None,
+            operations: &[],
+            bin_names: &[],
+            udf: None,
+            read_attr: INFO1_READ,
+            write_attr: 0,
+            info_attr: 0,
+            generation: 0,
+            expiration: 0,
+            send_key: false,
+            has_write: false,
+        };
+
+        match *bins {
+            Bins::All => attr.read_attr |= INFO1_GET_ALL,
+            Bins::None => attr.read_attr |= INFO1_NOBINDATA,
+            Bins::Some(ref bin_names) => attr.bin_names = bin_names,
+        }
+        attr
+    }
+
+    pub fn read_ops(operations: &'a [Operation<'a>]) -> Self {
+        let mut attr = BatchAttr {
+            filter_expression: None,
+            operations,
+            bin_names: &[],
+            udf: None,
+            read_attr: 0,
+            write_attr: 0,
+            info_attr: 0,
+            generation: 0,
+            expiration: 0,
+            send_key: false,
+            has_write: false,
+        };
+
+        attr.adjust_read(operations);
+        attr
+    }
+
+    pub fn write(policy: Option<&'a BatchWritePolicy>, operations: &'a [Operation<'a>]) -> Self {
+        let default_policy = BatchWritePolicy::default();
+        let write_policy = policy.unwrap_or(&default_policy);
+
+        let mut attr = BatchAttr {
+            filter_expression: policy.and_then(|policy| policy.filter_expression.as_ref()),
+            operations,
+            bin_names: &[],
+            udf: None,
+            read_attr: 0,
+            write_attr: INFO2_WRITE | INFO2_RESPOND_ALL_OPS,
+            info_attr: 0,
+            generation: 0,
+            expiration: write_policy.expiration.into(),
+            send_key: write_policy.send_key,
+            has_write: true,
+        };
+
+        match write_policy.record_exists_action {
+            RecordExistsAction::Update => (),
+            RecordExistsAction::UpdateOnly => attr.info_attr |= INFO3_UPDATE_ONLY,
+            RecordExistsAction::Replace => attr.info_attr |= INFO3_CREATE_OR_REPLACE,
+            RecordExistsAction::ReplaceOnly => attr.info_attr |= INFO3_REPLACE_ONLY,
+            RecordExistsAction::CreateOnly => attr.write_attr |= INFO2_CREATE_ONLY,
+        }
+
+        attr.set_generation(&write_policy.generation_policy, write_policy.generation);
+        attr.set_commit(&write_policy.commit_level, write_policy.durable_delete);
+        attr.adjust_read(operations);
+        attr
+    }
+
+    pub fn delete(policy: Option<&'a BatchDeletePolicy>) -> Self {
+        let default_policy = BatchDeletePolicy::default();
+        let delete_policy = policy.unwrap_or(&default_policy);
+
+        let mut attr = BatchAttr {
+            filter_expression: policy.and_then(|policy| policy.filter_expression.as_ref()),
+            operations: &[],
+            bin_names: &[],
+            udf: None,
+            read_attr: 0,
+            write_attr: INFO2_WRITE | INFO2_RESPOND_ALL_OPS | INFO2_DELETE,
+            info_attr: 0,
+            generation: 0,
+            expiration: 0,
+            send_key: delete_policy.send_key,
+            has_write: true,
+        };
+
+        attr.set_generation(&delete_policy.generation_policy, delete_policy.generation);
+        attr.set_commit(&delete_policy.commit_level, delete_policy.durable_delete);
+        attr
+    }
+
+    pub fn udf(policy: Option<&'a BatchUDFPolicy>, udf: BatchUDFCall<'a>) -> Self {
+        let default_policy = BatchUDFPolicy::default();
+        let udf_policy = policy.unwrap_or(&default_policy);
+
+        let mut attr = BatchAttr {
+            filter_expression: policy.and_then(|policy| policy.filter_expression.as_ref()),
+            operations: &[],
+            bin_names: &[],
+            udf: Some(udf),
+            read_attr: 0,
+            write_attr: INFO2_WRITE,
+            info_attr: 0,
+            generation: 0,
+            expiration: udf_policy.expiration.into(),
+            send_key: udf_policy.send_key,
+            has_write: true,
+        };
+
+        attr.set_commit(&udf_policy.commit_level, udf_policy.durable_delete);
+        attr
+    }
+
+    fn set_generation(&mut self, generation_policy: &GenerationPolicy, generation: u32) {
+        match *generation_policy {
+            GenerationPolicy::None => (),
+            GenerationPolicy::ExpectGenEqual => {
+                self.generation = generation as u16;
+                self.write_attr |= INFO2_GENERATION;
+            }
+            GenerationPolicy::ExpectGenGreater => {
+                self.generation = generation as u16;
+                self.write_attr |= INFO2_GENERATION_GT;
+            }
+        }
+    }
+
+    fn set_commit(&mut self, commit_level: &CommitLevel, durable_delete: bool) {
+        if *commit_level == CommitLevel::CommitMaster {
+            self.info_attr |= INFO3_COMMIT_MASTER;
+        }
+
+        if durable_delete {
+            self.write_attr |= INFO2_DURABLE_DELETE;
+        }
+    }
+
+    // Add the read flags required by the read operations of a batch record.
+    fn adjust_read(&mut self, operations: &[Operation]) {
+        let mut read_header = false;
+        let mut read_bin = false;
+
+        for operation in operations {
+            match *operation {
+                Operation {
+                    op: OperationType::Read,
+                    bin: OperationBin::None,
+                    ..
+                } => read_header = true,
+                Operation {
+                    op: OperationType::Read,
+                    bin: OperationBin::All,
+                    ..
+                } => {
+                    self.read_attr |= INFO1_GET_ALL;
+                    read_bin = true;
+                }
+                Operation {
+                    op: OperationType::Read,
+                    ..
+                }
+                | Operation {
+                    op: OperationType::CdtRead,
+                    ..
+                }
+                | Operation {
+                    op: OperationType::BitRead,
+                    ..
+                }
+                | Operation {
+                    op: OperationType::HllRead,
+                    ..
+                }
+                | Operation {
+                    op: OperationType::ExpRead,
+                    ..
+                } => read_bin = true,
+                _ => (),
+            }
+        }
+
+        if read_header || read_bin {
+            self.read_attr |= INFO1_READ;
+        }
+
+        if read_header && !read_bin {
+            self.read_attr |= INFO1_NOBINDATA;
+        }
+    }
+}
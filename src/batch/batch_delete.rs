@@ -0,0 +1,98 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use crate::batch::batch_attr::BatchAttr;
+use crate::batch::BatchEntry;
+use crate::errors::Result;
+use crate::policy::BatchDeletePolicy;
+use crate::{Key, Record, ResultCode};
+
+/// Key used in batch delete commands.
+pub struct BatchDelete<'a> {
+    /// Key.
+    pub key: Key,
+
+    /// Optional delete policy for this key. If not set, the `BatchDeletePolicy` defaults are used.
+    pub policy: Option<&'a BatchDeletePolicy>,
+
+    /// Will contain the record metadata after the batch delete, if the delete succeeded.
+    pub record: Option<Record>,
+
+    /// Will contain the result code of the delete after the batch command has been executed.
+    pub result_code: Option<ResultCode>,
+
+    /// Whether the delete may have completed even though an error was returned, e.g. when the
+    /// command timed out after it had been sent to the server.
+    pub in_doubt: bool,
+}
+
+impl<'a> BatchDelete<'a> {
+    /// Create a new `BatchDelete` instance for the given key.
+    pub const fn new(key: Key) -> Self {
+        BatchDelete {
+            key,
+            policy: None,
+            record: None,
+            result_code: None,
+            in_doubt: false,
+        }
+    }
+
+    /// Create a new `BatchDelete` instance for the given key, using the given delete policy for
+    /// this key.
+    pub const fn with_policy(policy: &'a BatchDeletePolicy, key: Key) -> Self {
+        BatchDelete {
+            key,
+            policy: Some(policy),
+            record: None,
+            result_code: None,
+            in_doubt: false,
+        }
+    }
+}
+
+impl<'a> BatchEntry for BatchDelete<'a> {
+    fn key(&self) -> &Key {
+        &self.key
+    }
+
+    fn batch_attr(&self) -> Result<BatchAttr<'_>> {
+        Ok(BatchAttr::delete(self.policy))
+    }
+
+    fn match_header(&self, other: &Self) -> bool {
+        let key = &self.key;
+        let other_key = &other.key;
+        (key.namespace == other_key.namespace)
+            && (key.set_name == other_key.set_name)
+            && match (self.policy, other.policy) {
+                (Some(policy), Some(other_policy)) => {
+                    std::ptr::eq(policy, other_policy) && !policy.send_key
+                }
+                (None, None) => true,
+                _ => false,
+            }
+    }
+
+    fn result_code(&self) -> Option<ResultCode> {
+        self.result_code
+    }
+
+    fn set_result(&mut self, result_code: ResultCode, record: Option<Record>, in_doubt: bool) {
+        self.result_code = Some(result_code);
+        self.in_doubt = in_doubt;
+        self.record = record;
+    }
+}
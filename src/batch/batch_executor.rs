@@ -16,101 +16,273 @@
 use std::cell::UnsafeCell;
 use std::cmp;
 use std::collections::HashMap;
+use std::slice::IterMut;
 use std::sync::Arc;
 
 use parking_lot::Mutex;
 use scoped_pool::Pool;
 
-use crate::batch::BatchRead;
+use crate::batch::{BatchEntry, BatchRead};
 use crate::cluster::partition::Partition;
 use crate::cluster::{Cluster, Node};
-use crate::commands::BatchReadCommand;
+use crate::commands::batch_read_command::execute_batch_command;
+use crate::commands::{BatchCommand, BatchOperateCommand, BatchReadCommand};
 use crate::errors::{Error, Result};
-use crate::policy::{BatchPolicy, Concurrency};
-use crate::Key;
+use crate::policy::{BatchPolicy, Concurrency, Policy, Replica};
+use crate::query::Recordset;
+use crate::runtime;
+use crate::{Bins, Key};
 
 pub struct BatchExecutor {
     cluster: Arc<Cluster>,
-    thread_pool: Pool,
+    // Without a thread pool, the commands of a batch run concurrently on the task of the caller.
+    thread_pool: Option<Pool>,
 }
 
 impl BatchExecutor {
-    pub fn new(cluster: Arc<Cluster>, thread_pool: Pool) -> Self {
+    pub fn new(cluster: Arc<Cluster>, thread_pool: Option<Pool>) -> Self {
         BatchExecutor {
             cluster,
             thread_pool,
         }
     }
 
-    pub fn execute_batch_read<'a>(
+    pub async fn execute_batch_read<'a>(
         &self,
         policy: &BatchPolicy,
         batch_reads: Vec<BatchRead<'a>>,
     ) -> Result<Vec<BatchRead<'a>>> {
-        let mut batch_nodes = self.get_batch_nodes(&batch_reads)?;
+        let _guard = self.cluster.start_command()?;
+        let keys: Vec<&Key> = batch_reads
+            .iter()
+            .map(|batch_read| &batch_read.key)
+            .collect();
+        let lanes = self.get_batch_lanes(policy, &keys, policy.replica())?;
         let batch_reads = SharedSlice::new(batch_reads);
-        let jobs = batch_nodes
-            .drain()
-            .map(|(node, offsets)| {
-                BatchReadCommand::new(policy, node, batch_reads.clone(), offsets)
+        let jobs = lanes
+            .into_iter()
+            .map(|(node, chunks)| {
+                chunks
+                    .into_iter()
+                    .map(|offsets| {
+                        BatchReadCommand::new(policy, node.clone(), batch_reads.clone(), offsets)
+                    })
+                    .collect()
             })
             .collect();
-        self.execute_batch_jobs(jobs, &policy.concurrency)?;
+        self.execute_batch_jobs(jobs, policy).await?;
         batch_reads.into_inner()
     }
 
-    fn execute_batch_jobs(
+    pub fn execute_batch_read_stream(
+        &self,
+        policy: &BatchPolicy,
+        keys: &[Key],
+        bins: Bins,
+    ) -> Result<Arc<Recordset>> {
+        let guard = self.cluster.start_command()?;
+        let key_refs: Vec<&Key> = keys.iter().collect();
+        let lanes = self.get_batch_lanes(policy, &key_refs, policy.replica())?;
+        let recordset = Arc::new(Recordset::new(policy.record_queue_size, lanes.len()));
+        if lanes.is_empty() {
+            recordset.close();
+        }
+        for (node, chunks) in lanes {
+            let chunks: Vec<Vec<Key>> = chunks
+                .iter()
+                .map(|offsets| offsets.iter().map(|idx| keys[*idx].clone()).collect())
+                .collect();
+            let recordset = Arc::downgrade(&recordset);
+            let policy = policy.clone();
+            let bins = bins.clone();
+            let guard = guard.clone();
+
+            self.cluster.runtime().spawn(async move {
+                let _guard = guard;
+                for keys in chunks {
+                    // stop once the consumer has closed or dropped the recordset
+                    let active = recordset.upgrade().map_or(false, |rs| rs.is_active());
+                    if !active {
+                        break;
+                    }
+                    let offsets = (0..keys.len()).collect();
+                    let batch_reads = SharedSlice::new(
+                        keys.into_iter()
+                            .map(|key| BatchRead::new(key, &bins))
+                            .collect(),
+                    );
+                    let mut command =
+                        BatchReadCommand::new(&policy, node.clone(), batch_reads, offsets);
+                    command.set_recordset(recordset.clone());
+                    if let Err(err) = execute_batch_command(&policy, &mut command).await {
+                        if let Some(recordset) = recordset.upgrade() {
+                            recordset.push(Err(err));
+                        }
+                        if !policy.respond_all_keys {
+                            break;
+                        }
+                    }
+                }
+                if let Some(recordset) = recordset.upgrade() {
+                    recordset.signal_end();
+                }
+            });
+        }
+        Ok(recordset)
+    }
+
+    pub async fn execute_batch_operate<T: BatchEntry>(
+        &self,
+        policy: &BatchPolicy,
+        records: Vec<T>,
+    ) -> Result<Vec<T>> {
+        let _guard = self.cluster.start_command()?;
+        // reject invalid records before any of them are sent
+        let mut has_write = false;
+        for record in &records {
+            has_write |= record.batch_attr()?.has_write;
+        }
+        // only batches of reads may go to replicas
+        let replica = if has_write {
+            Replica::Master
+        } else {
+            policy.replica()
+        };
+        let keys: Vec<&Key> = records.iter().map(BatchEntry::key).collect();
+        let lanes = self.get_batch_lanes(policy, &keys, replica)?;
+        let records = SharedSlice::new(records);
+        let jobs = lanes
+            .into_iter()
+            .map(|(node, chunks)| {
+                chunks
+                    .into_iter()
+                    .map(|offsets| {
+                        BatchOperateCommand::new(policy, node.clone(), records.clone(), offsets)
+                    })
+                    .collect()
+            })
+            .collect();
+        self.execute_batch_jobs(jobs, policy).await?;
+        records.into_inner()
+    }
+
+    // Each job is a sequence of commands to the same node, which are executed one after the other
+    // by a single thread, or a single future without a thread pool.
+    async fn execute_batch_jobs<C: BatchCommand + Send>(
         &self,
-        mut jobs: Vec<BatchReadCommand>,
-        concurrency: &Concurrency,
+        mut jobs: Vec<Vec<C>>,
+        policy: &BatchPolicy,
     ) -> Result<()> {
-        let threads = match *concurrency {
+        let threads = match policy.concurrency {
             Concurrency::Sequential => 1,
             Concurrency::Parallel => jobs.len(),
             Concurrency::MaxThreads(max) => cmp::min(max, jobs.len()),
         };
-        let jobs = Arc::new(Mutex::new(jobs.iter_mut()));
-        let last_err: Arc<Mutex<Option<Error>>> = Arc::default();
-        self.thread_pool.scoped(|scope| {
-            for _ in 0..threads {
-                let last_err = last_err.clone();
-                let jobs = jobs.clone();
-                scope.execute(move || {
-                    let next_job = || jobs.lock().next();
-                    while let Some(cmd) = next_job() {
-                        if let Err(err) = cmd.execute() {
-                            *last_err.lock() = Some(err);
-                            jobs.lock().all(|_| true); // consume the remaining jobs
-                        };
-                    }
-                });
+        let jobs = Mutex::new(jobs.iter_mut());
+        let last_err = Mutex::new(None);
+        match self.thread_pool {
+            Some(ref thread_pool) => thread_pool.scoped(|scope| {
+                for _ in 0..threads {
+                    scope.execute(|| runtime::block_on(execute_jobs(&jobs, policy, &last_err)));
+                }
+            }),
+            None => {
+                let workers = (0..threads)
+                    .map(|_| execute_jobs(&jobs, policy, &last_err))
+                    .collect();
+                runtime::join_all(workers).await;
             }
-        });
-        match Arc::try_unwrap(last_err).unwrap().into_inner() {
+        }
+        match last_err.into_inner() {
             None => Ok(()),
             Some(err) => Err(err),
         }
     }
 
-    fn get_batch_nodes<'a>(
+    // Group the keys by node and split each node's keys into chunks of at most
+    // `policy.max_keys_per_command` keys. The chunks of a node are distributed over at most
+    // `policy.max_commands_per_node` lanes, whose chunks are sent one after the other.
+    fn get_batch_lanes(
         &self,
-        batch_reads: &[BatchRead<'a>],
+        policy: &BatchPolicy,
+        keys: &[&Key],
+        replica: Replica,
+    ) -> Result<Vec<(Arc<Node>, Vec<Vec<usize>>)>> {
+        let mut lanes = vec![];
+        for (node, offsets) in self.get_batch_nodes(keys, replica)? {
+            for chunks in split_lanes(
+                offsets,
+                policy.max_keys_per_command,
+                policy.max_commands_per_node,
+            ) {
+                lanes.push((node.clone(), chunks));
+            }
+        }
+        Ok(lanes)
+    }
+
+    fn get_batch_nodes(
+        &self,
+        keys: &[&Key],
+        replica: Replica,
     ) -> Result<HashMap<Arc<Node>, Vec<usize>>> {
         let mut map = HashMap::new();
-        for (idx, batch_read) in batch_reads.iter().enumerate() {
-            let node = self.node_for_key(&batch_read.key)?;
+        for (idx, key) in keys.iter().enumerate() {
+            let node = self.node_for_key(key, replica)?;
             map.entry(node).or_insert_with(Vec::new).push(idx);
         }
         Ok(map)
     }
 
-    fn node_for_key(&self, key: &Key) -> Result<Arc<Node>> {
+    fn node_for_key(&self, key: &Key, replica: Replica) -> Result<Arc<Node>> {
         let partition = Partition::new_by_key(key);
-        let node = self.cluster.get_node(&partition)?;
+        let node = self.cluster.get_read_node(&partition, replica, 0)?;
         Ok(node)
     }
 }
 
+// Executes the jobs one after the other, until all jobs have been taken.
+async fn execute_jobs<C: BatchCommand>(
+    jobs: &Mutex<IterMut<'_, Vec<C>>>,
+    policy: &BatchPolicy,
+    last_err: &Mutex<Option<Error>>,
+) {
+    let next_job = || jobs.lock().next();
+    while let Some(cmds) = next_job() {
+        for cmd in cmds {
+            if let Err(err) = execute_batch_command(policy, cmd).await {
+                if !cmd.set_node_error(&err) {
+                    *last_err.lock() = Some(err);
+                }
+                if !policy.respond_all_keys {
+                    jobs.lock().all(|_| true); // consume the remaining jobs
+                    return;
+                }
+            };
+        }
+    }
+}
+
+// Split the offsets into chunks of at most `max_keys` offsets, and distribute the chunks
+// round-robin over at most `max_lanes` lanes. Zero means no limit.
+fn split_lanes(offsets: Vec<usize>, max_keys: usize, max_lanes: usize) -> Vec<Vec<Vec<usize>>> {
+    let chunks: Vec<Vec<usize>> = if max_keys == 0 || offsets.len() <= max_keys {
+        vec![offsets]
+    } else {
+        offsets.chunks(max_keys).map(<[usize]>::to_vec).collect()
+    };
+    let lane_count = if max_lanes == 0 {
+        chunks.len()
+    } else {
+        cmp::min(max_lanes, chunks.len())
+    };
+    let mut lanes = vec![Vec::new(); lane_count];
+    for (idx, chunk) in chunks.into_iter().enumerate() {
+        lanes[idx % lane_count].push(chunk);
+    }
+    lanes
+}
+
 // A slice with interior mutability, that can be shared across threads. The threads are required to
 // ensure that no member of the slice is accessed by more than one thread. No runtime checks are
 // performed by the slice to guarantee this.
@@ -157,3 +329,32 @@ impl<T> SharedSlice<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::split_lanes;
+
+    #[test]
+    fn split_lanes_unlimited() {
+        let lanes = split_lanes(vec![0, 1, 2, 3, 4], 0, 0);
+        assert_eq!(lanes, vec![vec![vec![0, 1, 2, 3, 4]]]);
+    }
+
+    #[test]
+    fn split_lanes_chunks() {
+        let lanes = split_lanes(vec![0, 1, 2, 3, 4], 2, 0);
+        assert_eq!(
+            lanes,
+            vec![vec![vec![0, 1]], vec![vec![2, 3]], vec![vec![4]]]
+        );
+    }
+
+    #[test]
+    fn split_lanes_max_lanes() {
+        let lanes = split_lanes(vec![0, 1, 2, 3, 4], 2, 2);
+        assert_eq!(lanes, vec![vec![vec![0, 1], vec![4]], vec![vec![2, 3]]]);
+
+        let lanes = split_lanes(vec![0, 1, 2, 3, 4], 2, 1);
+        assert_eq!(lanes, vec![vec![vec![0, 1], vec![2, 3], vec![4]]]);
+    }
+}
@@ -13,13 +13,21 @@
 // License for the specific language governing permissions and limitations under
 // the License.
 
+use crate::batch::batch_attr::{self, BatchAttr};
+use crate::batch::BatchEntry;
+use crate::errors::{ErrorKind, Result};
+use crate::operations::Operation;
 use crate::Bins;
 use crate::Key;
 use crate::Record;
+use crate::ResultCode;
 #[cfg(feature = "serialization")]
 use serde::Serialize;
 
+static NO_BINS: Bins = Bins::None;
+
 /// Key and bin names used in batch read commands where variable bins are needed for each key.
+/// Instead of bin names, each key can also specify its own read operations.
 #[cfg_attr(feature = "serialization", derive(Serialize))]
 pub struct BatchRead<'a> {
     /// Key.
@@ -28,8 +36,18 @@ pub struct BatchRead<'a> {
     /// Bins to retrieve for this key.
     pub bins: &'a Bins,
 
+    /// Optional read operations to execute on this key instead of reading the selected bins.
+    /// Requires Aerospike Server version >= 6.0.
+    #[cfg_attr(feature = "serialization", serde(skip))]
+    pub ops: Option<&'a [Operation<'a>]>,
+
     /// Will contain the record after the batch read operation.
     pub record: Option<Record>,
+
+    /// Will contain the result code of the read after the batch command has been executed, e.g.
+    /// `KeyNotFoundError` if the record does not exist.
+    #[cfg_attr(feature = "serialization", serde(skip))]
+    pub result_code: Option<ResultCode>,
 }
 
 impl<'a> BatchRead<'a> {
@@ -38,7 +56,32 @@ impl<'a> BatchRead<'a> {
         BatchRead {
             key,
             bins,
+            ops: None,
             record: None,
+            result_code: None,
+        }
+    }
+
+    /// Create a new `BatchRead` instance for the given key and read operations, e.g. a map
+    /// `get_by_key` operation. The operations must not contain any write operations.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use aerospike::*;
+    /// use aerospike::operations::maps;
+    ///
+    /// let map_key = as_val!("name");
+    /// let ops = vec![maps::get_by_key("map", &map_key, MapReturnType::Value)];
+    /// let batch_read = BatchRead::with_ops(as_key!("test", "test", 1), &ops);
+    /// ```
+    pub const fn with_ops(key: Key, ops: &'a [Operation<'a>]) -> Self {
+        BatchRead {
+            key,
+            bins: &NO_BINS,
+            ops: Some(ops),
+            record: None,
+            result_code: None,
         }
     }
 
@@ -51,3 +94,41 @@ impl<'a> BatchRead<'a> {
             && (self.bins == other.bins)
     }
 }
+
+impl<'a> BatchEntry for BatchRead<'a> {
+    fn key(&self) -> &Key {
+        &self.key
+    }
+
+    fn batch_attr(&self) -> Result<BatchAttr<'_>> {
+        match self.ops {
+            Some(ops) => {
+                if batch_attr::has_write(ops) {
+                    bail!(ErrorKind::InvalidArgument(
+                        "Batch read operations must not contain a write".to_string()
+                    ));
+                }
+                Ok(BatchAttr::read_ops(ops))
+            }
+            None => Ok(BatchAttr::read(self.bins)),
+        }
+    }
+
+    fn match_header(&self, other: &Self) -> bool {
+        let same_ops = match (self.ops, other.ops) {
+            (Some(ops), Some(other_ops)) => std::ptr::eq(ops, other_ops),
+            (None, None) => true,
+            _ => false,
+        };
+        same_ops && BatchRead::match_header(self, other, true)
+    }
+
+    fn result_code(&self) -> Option<ResultCode> {
+        self.result_code
+    }
+
+    fn set_result(&mut self, result_code: ResultCode, record: Option<Record>, _in_doubt: bool) {
+        self.result_code = Some(result_code);
+        self.record = record;
+    }
+}
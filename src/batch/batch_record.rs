@@ -0,0 +1,155 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use crate::batch::batch_attr::BatchAttr;
+use crate::batch::{BatchDelete, BatchEntry, BatchRead, BatchUDF, BatchWrite};
+use crate::errors::Result;
+use crate::{Key, Record, ResultCode};
+
+/// Record of a mixed batch command. Reads, writes, deletes and UDF calls on different keys can be
+/// combined in a single `batch_operate` call; each record keeps its own policy and receives its
+/// own result.
+pub enum BatchRecord<'a> {
+    /// Read bins of a record.
+    Read(BatchRead<'a>),
+
+    /// Execute operations, including at least one write, on a record.
+    Write(BatchWrite<'a>),
+
+    /// Delete a record.
+    Delete(BatchDelete<'a>),
+
+    /// Execute a user-defined function on a record.
+    UDF(BatchUDF<'a>),
+}
+
+impl<'a> BatchRecord<'a> {
+    /// Key of the record.
+    pub const fn key(&self) -> &Key {
+        match *self {
+            BatchRecord::Read(ref batch_read) => &batch_read.key,
+            BatchRecord::Write(ref batch_write) => &batch_write.key,
+            BatchRecord::Delete(ref batch_delete) => &batch_delete.key,
+            BatchRecord::UDF(ref batch_udf) => &batch_udf.key,
+        }
+    }
+
+    /// Record returned by the server after the batch command has been executed, if any.
+    pub fn record(&self) -> Option<&Record> {
+        match *self {
+            BatchRecord::Read(ref batch_read) => batch_read.record.as_ref(),
+            BatchRecord::Write(ref batch_write) => batch_write.record.as_ref(),
+            BatchRecord::Delete(ref batch_delete) => batch_delete.record.as_ref(),
+            BatchRecord::UDF(ref batch_udf) => batch_udf.record.as_ref(),
+        }
+    }
+
+    /// Result code returned by the server for this record, or `None` if the record has not been
+    /// processed, e.g. because the batch was aborted before it reached the record's node.
+    pub const fn result_code(&self) -> Option<ResultCode> {
+        match *self {
+            BatchRecord::Read(ref batch_read) => batch_read.result_code,
+            BatchRecord::Write(ref batch_write) => batch_write.result_code,
+            BatchRecord::Delete(ref batch_delete) => batch_delete.result_code,
+            BatchRecord::UDF(ref batch_udf) => batch_udf.result_code,
+        }
+    }
+
+    /// Whether a write, delete or UDF call may have completed even though an error was returned.
+    /// Always false for reads.
+    pub const fn in_doubt(&self) -> bool {
+        match *self {
+            BatchRecord::Read(_) => false,
+            BatchRecord::Write(ref batch_write) => batch_write.in_doubt,
+            BatchRecord::Delete(ref batch_delete) => batch_delete.in_doubt,
+            BatchRecord::UDF(ref batch_udf) => batch_udf.in_doubt,
+        }
+    }
+}
+
+impl<'a> From<BatchRead<'a>> for BatchRecord<'a> {
+    fn from(batch_read: BatchRead<'a>) -> Self {
+        BatchRecord::Read(batch_read)
+    }
+}
+
+impl<'a> From<BatchWrite<'a>> for BatchRecord<'a> {
+    fn from(batch_write: BatchWrite<'a>) -> Self {
+        BatchRecord::Write(batch_write)
+    }
+}
+
+impl<'a> From<BatchDelete<'a>> for BatchRecord<'a> {
+    fn from(batch_delete: BatchDelete<'a>) -> Self {
+        BatchRecord::Delete(batch_delete)
+    }
+}
+
+impl<'a> From<BatchUDF<'a>> for BatchRecord<'a> {
+    fn from(batch_udf: BatchUDF<'a>) -> Self {
+        BatchRecord::UDF(batch_udf)
+    }
+}
+
+impl<'a> BatchEntry for BatchRecord<'a> {
+    fn key(&self) -> &Key {
+        BatchRecord::key(self)
+    }
+
+    fn batch_attr(&self) -> Result<BatchAttr<'_>> {
+        match *self {
+            BatchRecord::Read(ref batch_read) => batch_read.batch_attr(),
+            BatchRecord::Write(ref batch_write) => batch_write.batch_attr(),
+            BatchRecord::Delete(ref batch_delete) => batch_delete.batch_attr(),
+            BatchRecord::UDF(ref batch_udf) => batch_udf.batch_attr(),
+        }
+    }
+
+    fn match_header(&self, other: &Self) -> bool {
+        match (self, other) {
+            (BatchRecord::Read(ref batch_read), BatchRecord::Read(ref other)) => {
+                BatchEntry::match_header(batch_read, other)
+            }
+            (BatchRecord::Write(ref batch_write), BatchRecord::Write(ref other)) => {
+                batch_write.match_header(other)
+            }
+            (BatchRecord::Delete(ref batch_delete), BatchRecord::Delete(ref other)) => {
+                batch_delete.match_header(other)
+            }
+            _ => false,
+        }
+    }
+
+    fn result_code(&self) -> Option<ResultCode> {
+        BatchRecord::result_code(self)
+    }
+
+    fn set_result(&mut self, result_code: ResultCode, record: Option<Record>, in_doubt: bool) {
+        match *self {
+            BatchRecord::Read(ref mut batch_read) => {
+                batch_read.set_result(result_code, record, in_doubt)
+            }
+            BatchRecord::Write(ref mut batch_write) => {
+                batch_write.set_result(result_code, record, in_doubt)
+            }
+            BatchRecord::Delete(ref mut batch_delete) => {
+                batch_delete.set_result(result_code, record, in_doubt)
+            }
+            BatchRecord::UDF(ref mut batch_udf) => {
+                batch_udf.set_result(result_code, record, in_doubt)
+            }
+        }
+    }
+}
@@ -0,0 +1,136 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use crate::batch::batch_attr::{BatchAttr, BatchUDFCall};
+use crate::batch::BatchEntry;
+use crate::errors::Result;
+use crate::policy::BatchUDFPolicy;
+use crate::{Key, Record, ResultCode, Value};
+
+/// Key and user-defined function (UDF) call used in batch UDF commands.
+pub struct BatchUDF<'a> {
+    /// Key.
+    pub key: Key,
+
+    /// Optional UDF policy for this key. If not set, the `BatchUDFPolicy` defaults are used.
+    pub policy: Option<&'a BatchUDFPolicy>,
+
+    /// Name of the UDF package.
+    pub udf_name: &'a str,
+
+    /// Name of the function to execute.
+    pub function_name: &'a str,
+
+    /// Optional function arguments.
+    pub args: Option<&'a [Value]>,
+
+    /// Will contain the record after the batch command has been executed. The function's return
+    /// value is stored in the "SUCCESS" bin; if the function failed, the error is stored in the
+    /// "FAILURE" bin.
+    pub record: Option<Record>,
+
+    /// Will contain the result code of the UDF execution after the batch command has been
+    /// executed.
+    pub result_code: Option<ResultCode>,
+
+    /// Whether the UDF call may have completed even though an error was returned, e.g. when the
+    /// command timed out after it had been sent to the server.
+    pub in_doubt: bool,
+}
+
+impl<'a> BatchUDF<'a> {
+    /// Create a new `BatchUDF` instance for the given key and function call.
+    pub const fn new(
+        key: Key,
+        udf_name: &'a str,
+        function_name: &'a str,
+        args: Option<&'a [Value]>,
+    ) -> Self {
+        BatchUDF {
+            key,
+            policy: None,
+            udf_name,
+            function_name,
+            args,
+            record: None,
+            result_code: None,
+            in_doubt: false,
+        }
+    }
+
+    /// Create a new `BatchUDF` instance for the given key and function call, using the given UDF
+    /// policy for this key.
+    pub const fn with_policy(
+        policy: &'a BatchUDFPolicy,
+        key: Key,
+        udf_name: &'a str,
+        function_name: &'a str,
+        args: Option<&'a [Value]>,
+    ) -> Self {
+        BatchUDF {
+            key,
+            policy: Some(policy),
+            udf_name,
+            function_name,
+            args,
+            record: None,
+            result_code: None,
+            in_doubt: false,
+        }
+    }
+
+    /// Return value of the function, if it was executed successfully and returned a value.
+    pub fn return_value(&self) -> Option<&Value> {
+        self.record
+            .as_ref()
+            .and_then(|record| record.bins.get("SUCCESS"))
+    }
+
+    /// Error message of the function, if its execution failed.
+    pub fn error(&self) -> Option<&Value> {
+        self.record
+            .as_ref()
+            .and_then(|record| record.bins.get("FAILURE"))
+    }
+}
+
+impl<'a> BatchEntry for BatchUDF<'a> {
+    fn key(&self) -> &Key {
+        &self.key
+    }
+
+    fn batch_attr(&self) -> Result<BatchAttr<'_>> {
+        let udf = BatchUDFCall {
+            package_name: self.udf_name,
+            function_name: self.function_name,
+            args: self.args,
+        };
+        Ok(BatchAttr::udf(self.policy, udf))
+    }
+
+    fn match_header(&self, _other: &Self) -> bool {
+        false
+    }
+
+    fn result_code(&self) -> Option<ResultCode> {
+        self.result_code
+    }
+
+    fn set_result(&mut self, result_code: ResultCode, record: Option<Record>, in_doubt: bool) {
+        self.result_code = Some(result_code);
+        self.in_doubt = in_doubt;
+        self.record = record;
+    }
+}
@@ -0,0 +1,116 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use crate::batch::batch_attr::{self, BatchAttr};
+use crate::batch::BatchEntry;
+use crate::errors::{ErrorKind, Result};
+use crate::operations::Operation;
+use crate::policy::BatchWritePolicy;
+use crate::{Key, Record, ResultCode};
+
+/// Key and operations used in batch write commands. The operations must contain at least one
+/// write operation; read operations may be added to return values in the same call.
+pub struct BatchWrite<'a> {
+    /// Key.
+    pub key: Key,
+
+    /// Optional write policy for this key. If not set, the `BatchWritePolicy` defaults are used.
+    pub policy: Option<&'a BatchWritePolicy>,
+
+    /// Operations to execute on this key.
+    pub ops: &'a [Operation<'a>],
+
+    /// Will contain the record after the batch write, including the results of any read
+    /// operations, if the write succeeded.
+    pub record: Option<Record>,
+
+    /// Will contain the result code of the write after the batch command has been executed.
+    pub result_code: Option<ResultCode>,
+
+    /// Whether the write may have completed even though an error was returned, e.g. when the
+    /// command timed out after it had been sent to the server.
+    pub in_doubt: bool,
+}
+
+impl<'a> BatchWrite<'a> {
+    /// Create a new `BatchWrite` instance for the given key and operations.
+    pub const fn new(key: Key, ops: &'a [Operation<'a>]) -> Self {
+        BatchWrite {
+            key,
+            policy: None,
+            ops,
+            record: None,
+            result_code: None,
+            in_doubt: false,
+        }
+    }
+
+    /// Create a new `BatchWrite` instance for the given key and operations, using the given write
+    /// policy for this key.
+    pub const fn with_policy(
+        policy: &'a BatchWritePolicy,
+        key: Key,
+        ops: &'a [Operation<'a>],
+    ) -> Self {
+        BatchWrite {
+            key,
+            policy: Some(policy),
+            ops,
+            record: None,
+            result_code: None,
+            in_doubt: false,
+        }
+    }
+}
+
+impl<'a> BatchEntry for BatchWrite<'a> {
+    fn key(&self) -> &Key {
+        &self.key
+    }
+
+    fn batch_attr(&self) -> Result<BatchAttr<'_>> {
+        if !batch_attr::has_write(self.ops) {
+            bail!(ErrorKind::InvalidArgument(
+                "Batch write operations do not contain a write".to_string()
+            ));
+        }
+        Ok(BatchAttr::write(self.policy, self.ops))
+    }
+
+    fn match_header(&self, other: &Self) -> bool {
+        let key = &self.key;
+        let other_key = &other.key;
+        (key.namespace == other_key.namespace)
+            && (key.set_name == other_key.set_name)
+            && std::ptr::eq(self.ops, other.ops)
+            && match (self.policy, other.policy) {
+                (Some(policy), Some(other_policy)) => {
+                    std::ptr::eq(policy, other_policy) && !policy.send_key
+                }
+                (None, None) => true,
+                _ => false,
+            }
+    }
+
+    fn result_code(&self) -> Option<ResultCode> {
+        self.result_code
+    }
+
+    fn set_result(&mut self, result_code: ResultCode, record: Option<Record>, in_doubt: bool) {
+        self.result_code = Some(result_code);
+        self.in_doubt = in_doubt;
+        self.record = record;
+    }
+}
@@ -13,8 +13,35 @@
 // License for the specific language governing permissions and limitations under
 // the License.
 
+pub mod batch_attr;
+pub mod batch_delete;
 pub mod batch_executor;
 pub mod batch_read;
+pub mod batch_record;
+pub mod batch_udf;
+pub mod batch_write;
 
+pub use self::batch_delete::BatchDelete;
 pub use self::batch_executor::BatchExecutor;
 pub use self::batch_read::BatchRead;
+pub use self::batch_record::BatchRecord;
+pub use self::batch_udf::BatchUDF;
+pub use self::batch_write::BatchWrite;
+
+use crate::batch::batch_attr::BatchAttr;
+use crate::errors::Result;
+use crate::{Key, Record, ResultCode};
+
+// Record of a batch operate command; each record carries its own attributes and receives its
+// own result.
+pub trait BatchEntry {
+    fn key(&self) -> &Key;
+    fn batch_attr(&self) -> Result<BatchAttr<'_>>;
+
+    // Whether the record can reuse the namespace, set, attributes and operations of the previous
+    // record in the batch.
+    fn match_header(&self, other: &Self) -> bool;
+
+    fn result_code(&self) -> Option<ResultCode>;
+    fn set_result(&mut self, result_code: ResultCode, record: Option<Record>, in_doubt: bool);
+}
@@ -13,30 +13,28 @@
 // License for the specific language governing permissions and limitations under
 // the License.
 
-use std::fs::File;
-use std::io::prelude::*;
-use std::path::Path;
-use std::str;
+use std::collections::HashMap;
 use std::sync::Arc;
-use std::thread;
+use std::time::Duration;
 use std::vec::Vec;
 
 use scoped_pool::Pool;
 
-use crate::batch::BatchExecutor;
-use crate::cluster::{Cluster, Node};
-use crate::commands::{
-    DeleteCommand, ExecuteUDFCommand, ExistsCommand, OperateCommand, QueryCommand, ReadCommand,
-    ScanCommand, TouchCommand, WriteCommand,
+use crate::async_client::AsyncClient;
+use crate::cluster::{Node, PartitionMap};
+use crate::errors::Result;
+use crate::expressions::FilterExpression;
+use crate::net::{Host, ToHosts};
+use crate::operations::Operation;
+use crate::policy::{
+    AdminPolicy, BatchPolicy, ClientPolicy, QueryPolicy, ReadPolicy, ScanPolicy, WritePolicy,
 };
-use crate::errors::{ErrorKind, Result, ResultExt};
-use crate::net::ToHosts;
-use crate::operations::{Operation, OperationType};
-use crate::policy::{BatchPolicy, ClientPolicy, QueryPolicy, ReadPolicy, ScanPolicy, WritePolicy};
-use crate::task::{IndexTask, RegisterTask};
+use crate::runtime::{self, Runtime};
+use crate::task::{ExecuteTask, IndexTask, RegisterTask};
 use crate::{
-    BatchRead, Bin, Bins, CollectionIndexType, IndexType, Key, Record, Recordset, ResultCode,
-    Statement, UDFLang, Value,
+    BatchDelete, BatchRead, BatchRecord, BatchUDF, BatchWrite, Bin, Bins, CollectionIndexType,
+    IndexInfo, IndexType, Key, PartitionFilter, Privilege, Record, Recordset, Role, Statement,
+    UDFLang, UDFMeta, User, Value,
 };
 
 /// Instantiate a Client instance to access an Aerospike database cluster and perform database
@@ -52,9 +50,13 @@ use crate::{
 /// Each record may have multiple bins, unless the Aerospike server nodes are configured as
 /// "single-bin". In "multi-bin" mode, partial records may be written or read by specifying the
 /// relevant subset of bins.
+///
+/// The client runs the commands of the async client to completion on the calling thread, over
+/// blocking sockets. Requires the `sync` feature, which is enabled by default.
 pub struct Client {
-    cluster: Arc<Cluster>,
-    thread_pool: Pool,
+    // The commands are shared with the async client; the blocking client runs them to completion
+    // on the calling thread.
+    core: AsyncClient,
 }
 
 unsafe impl Send for Client {}
@@ -70,7 +72,9 @@ impl Client {
     /// - Add these nodes to the cluster map
     ///
     /// In most cases, only one host is necessary to seed the cluster. The remaining hosts are
-    /// added as future seeds in case of a complete network failure.
+    /// added as future seeds in case of a complete network failure: if none of the nodes respond
+    /// to several consecutive tends, the cluster is seeded again, resolving the host names of the
+    /// seeds anew.
     ///
     /// If one connection succeeds, the client is ready to process database requests. If all
     /// connections fail and the policy's `fail_
@@ -82,7 +86,8 @@ impl Client {
     /// 10.0.0.1:3000,10.0.0.2:3000,10.0.0.3:3000
     /// ```
     ///
-    /// Port 3000 is used by default if the port number is omitted for any of the hosts.
+    /// Port 3000 is used by default if the port number is omitted for any of the hosts. IPv6
+    /// addresses are enclosed in brackets, e.g. `[2001:db8::1]:3000`.
     ///
     /// # Examples
     ///
@@ -96,44 +101,93 @@ impl Client {
     /// ```
     pub fn new(policy: &ClientPolicy, hosts: &dyn ToHosts) -> Result<Self> {
         let hosts = hosts.to_hosts()?;
-        let cluster = Cluster::new(policy.clone(), &hosts)?;
         let thread_pool = Pool::new(policy.thread_pool_size);
+        let core = AsyncClient::with_runtime(policy, &hosts, Runtime::Blocking, Some(thread_pool))?;
+        Ok(Client { core })
+    }
 
-        Ok(Client {
-            cluster,
-            thread_pool,
-        })
+    /// Closes the connection to the Aerospike cluster gracefully: new commands are refused,
+    /// while the commands in flight, including scans, queries and batch streams, are given up to
+    /// `timeout` to finish before the connection pools and the tend thread are shut down. Returns
+    /// a `Timeout` error if commands were still in flight when the connection was closed.
+    pub fn close_gracefully(&self, timeout: Duration) -> Result<()> {
+        runtime::block_on(self.core.close_gracefully(timeout))
     }
 
     /// Closes the connection to the Aerospike cluster.
     pub fn close(&self) -> Result<()> {
-        self.cluster.close()?;
-        self.thread_pool.shutdown();
-        Ok(())
+        self.core.close()
     }
 
     /// Returns `true` if the client is connected to any cluster nodes.
     pub fn is_connected(&self) -> bool {
-        self.cluster.is_connected()
+        self.core.is_connected()
+    }
+
+    /// Returns the hosts used to seed the cluster.
+    pub fn seeds(&self) -> Vec<Host> {
+        self.core.seeds()
+    }
+
+    /// Adds hosts to seed the cluster with, e.g. after the cluster moved to new hosts. Seeds are
+    /// used when the client is not connected to any node of the cluster; host names are resolved
+    /// each time the cluster is seeded, so that changed addresses are picked up.
+    pub fn add_seeds(&self, hosts: &dyn ToHosts) -> Result<()> {
+        self.core.add_seeds(hosts)
+    }
+
+    /// Removes hosts used to seed the cluster. Nodes the client is connected to are not affected.
+    pub fn remove_seeds(&self, hosts: &dyn ToHosts) -> Result<()> {
+        self.core.remove_seeds(hosts)
     }
 
     /// Returns a list of the names of the active server nodes in the cluster.
     pub fn node_names(&self) -> Vec<String> {
-        self.cluster
-            .nodes()
-            .iter()
-            .map(|node| node.name().to_owned())
-            .collect()
+        self.core.node_names()
     }
 
     /// Return node given its name.
     pub fn get_node(&self, name: &str) -> Result<Arc<Node>> {
-        self.cluster.get_node_by_name(name)
+        self.core.get_node(name)
+    }
+
+    /// Send info commands to a random active node of the cluster and return the responses, keyed
+    /// by command. Use `Node::info` to send the commands to a specific node, and the helpers of
+    /// the [`info`](crate::info) module to parse the responses.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate aerospike;
+    /// # use aerospike::*;
+    ///
+    /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap_or("localhost".into());
+    /// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+    /// let response = client.info(&ReadPolicy::default(), &["namespaces"]).unwrap();
+    /// for namespace in info::parse_list(&response["namespaces"]) {
+    ///     println!("Namespace: {}", namespace);
+    /// }
+    /// ```
+    pub fn info(&self, policy: &ReadPolicy, commands: &[&str]) -> Result<HashMap<String, String>> {
+        runtime::block_on(self.core.info(policy, commands))
     }
 
     /// Returns a list of active server nodes in the cluster.
     pub fn nodes(&self) -> Vec<Arc<Node>> {
-        self.cluster.nodes()
+        self.core.nodes()
+    }
+
+    /// Returns the node of the master replica of the partition that stores the record of the
+    /// key, e.g. to run work next to the data. Fails if no node owns the partition.
+    pub fn node_for_key(&self, key: &Key) -> Result<Arc<Node>> {
+        self.core.node_for_key(key)
+    }
+
+    /// Returns a snapshot of the nodes of each partition replica, by namespace. The snapshot is
+    /// not updated when the cluster changes. Use `Key::partition_id` to find the partitions of a
+    /// record.
+    pub fn partition_map(&self) -> PartitionMap {
+        self.core.partition_map()
     }
 
     /// Read record for the specified key. Depending on the bins value provided, all record bins,
@@ -185,17 +239,19 @@ impl Client {
     where
         T: Into<Bins>,
     {
-        let bins = bins.into();
-        let mut command = ReadCommand::new(policy, self.cluster.clone(), key, bins);
-        command.execute()?;
-        Ok(command.record.unwrap())
+        runtime::block_on(self.core.get(policy, key, bins))
     }
 
     /// Read multiple record for specified batch keys in one batch call. This method allows
     /// different namespaces/bins to be requested for each key in the batch. If the `BatchRead` key
-    /// field is not found, the corresponding record field will be `None`. The policy can be used
-    /// to specify timeouts and maximum concurrent threads. This method requires Aerospike Server
-    /// version >= 3.6.0.
+    /// field is not found, the corresponding record field will be `None`. Each `BatchRead` also
+    /// receives the result code for its key, so an error on one key does not fail the whole batch.
+    /// The policy can be used to specify timeouts and maximum concurrent threads. This method
+    /// requires Aerospike Server version >= 3.6.0.
+    ///
+    /// Batch reads created with `BatchRead::with_ops` apply read operations to their record
+    /// instead of selecting bins; such batches are sent with the batch operate protocol, which
+    /// requires Aerospike Server version >= 6.0.
     ///
     /// # Examples
     ///
@@ -230,8 +286,222 @@ impl Client {
         policy: &BatchPolicy,
         batch_reads: Vec<BatchRead<'a>>,
     ) -> Result<Vec<BatchRead<'a>>> {
-        let executor = BatchExecutor::new(self.cluster.clone(), self.thread_pool.clone());
-        executor.execute_batch_read(policy, batch_reads)
+        runtime::block_on(self.core.batch_get(policy, batch_reads))
+    }
+
+    /// Check if multiple record keys exist in one batch call. The result contains a flag for each
+    /// key, in the same order as the keys. No bin data is returned by the server, so this is
+    /// considerably cheaper than reading the records. The policy can be used to specify timeouts
+    /// and maximum concurrent threads.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use aerospike::*;
+    ///
+    /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap_or("localhost".into());
+    /// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+    /// let keys: Vec<Key> = (0..10).map(|i| as_key!("test", "test", i)).collect();
+    /// match client.batch_exists(&BatchPolicy::default(), &keys) {
+    ///     Ok(exists) => {
+    ///       for (key, exists) in keys.iter().zip(exists) {
+    ///         println!("{:?} => {}", key, exists);
+    ///       }
+    ///     }
+    ///     Err(err)
+    ///         => println!("Error executing batch request: {}", err),
+    /// }
+    /// ```
+    pub fn batch_exists(&self, policy: &BatchPolicy, keys: &[Key]) -> Result<Vec<bool>> {
+        runtime::block_on(self.core.batch_exists(policy, keys))
+    }
+
+    /// Read the record headers (generation and expiration) for multiple keys in one batch call,
+    /// without any bin data. The result contains an entry for each key, in the same order as the
+    /// keys; the entry is `None` if the record does not exist. The policy can be used to specify
+    /// timeouts and maximum concurrent threads.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use aerospike::*;
+    ///
+    /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap_or("localhost".into());
+    /// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+    /// let keys: Vec<Key> = (0..10).map(|i| as_key!("test", "test", i)).collect();
+    /// match client.batch_get_header(&BatchPolicy::default(), &keys) {
+    ///     Ok(headers) => {
+    ///       for (key, header) in keys.iter().zip(headers) {
+    ///         match header {
+    ///           Some(record) => println!("{:?} => generation {}", key, record.generation),
+    ///           None => println!("No such record: {:?}", key),
+    ///         }
+    ///       }
+    ///     }
+    ///     Err(err)
+    ///         => println!("Error executing batch request: {}", err),
+    /// }
+    /// ```
+    pub fn batch_get_header(
+        &self,
+        policy: &BatchPolicy,
+        keys: &[Key],
+    ) -> Result<Vec<Option<Record>>> {
+        runtime::block_on(self.core.batch_get_header(policy, keys))
+    }
+
+    /// Read multiple records for the specified keys and return a record iterator. Unlike
+    /// `batch_get`, the results are not collected in memory: each node's batch command runs in a
+    /// separate thread and puts its records on a queue as the node responds, while the calling
+    /// thread concurrently pops records off the queue through the record iterator. At most
+    /// `policy.record_queue_size` records are queued at any time. Records are returned in no
+    /// particular order and carry their key; keys that do not exist are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use aerospike::*;
+    ///
+    /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap_or("localhost".into());
+    /// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+    /// let keys: Vec<Key> = (0..100_000).map(|i| as_key!("test", "test", i)).collect();
+    /// match client.batch_get_stream(&BatchPolicy::default(), &keys, Bins::All) {
+    ///     Ok(records) => {
+    ///         for record in &*records {
+    ///             match record {
+    ///                 Ok(record) => println!("{:?} => {:?}", record.key, record.bins),
+    ///                 Err(err) => println!("Error executing batch request: {}", err),
+    ///             }
+    ///         }
+    ///     },
+    ///     Err(err) => println!("Failed to execute batch request: {}", err),
+    /// }
+    /// ```
+    pub fn batch_get_stream<T>(
+        &self,
+        policy: &BatchPolicy,
+        keys: &[Key],
+        bins: T,
+    ) -> Result<Arc<Recordset>>
+    where
+        T: Into<Bins>,
+    {
+        runtime::block_on(self.core.batch_get_stream(policy, keys, bins))
+    }
+
+    /// Execute a mixed batch of reads, writes, deletes and UDF calls in one batch call. Each
+    /// `BatchRecord` keeps its own policy; the keys are grouped by node and each node receives a
+    /// single batch command. After the call, each `BatchRecord` contains its own result. Errors
+    /// on individual keys and failures of a node, e.g. timeouts, are reported in the result code
+    /// of the affected records instead of failing the whole call; use `BatchRecord::in_doubt` to
+    /// find writes that may have been applied despite the error. This method requires Aerospike
+    /// Server version >= 6.0.
+    ///
+    /// # Examples
+    ///
+    /// Read one record, update another and delete a third in a single client request.
+    ///
+    /// ```rust
+    /// # use aerospike::*;
+    ///
+    /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap_or("localhost".into());
+    /// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+    /// let bins = Bins::All;
+    /// let bin = as_bin!("status", "processed");
+    /// let ops = vec![operations::put(&bin)];
+    /// let batch = vec![
+    ///     BatchRecord::from(BatchRead::new(as_key!("test", "test", 1), &bins)),
+    ///     BatchRecord::from(BatchWrite::new(as_key!("test", "test", 2), &ops)),
+    ///     BatchRecord::from(BatchDelete::new(as_key!("test", "test", 3))),
+    /// ];
+    /// match client.batch_operate(&BatchPolicy::default(), batch) {
+    ///     Ok(results) => {
+    ///       for result in results {
+    ///         println!("{:?} => {:?}", result.key(), result.record());
+    ///       }
+    ///     }
+    ///     Err(err)
+    ///         => println!("Error executing batch request: {}", err),
+    /// }
+    /// ```
+    pub fn batch_operate<'a>(
+        &self,
+        policy: &BatchPolicy,
+        batch_records: Vec<BatchRecord<'a>>,
+    ) -> Result<Vec<BatchRecord<'a>>> {
+        runtime::block_on(self.core.batch_operate(policy, batch_records))
+    }
+
+    /// Write multiple records for specified batch keys in one batch call. Each `BatchWrite`
+    /// specifies the operations to execute on its key, and optionally its own
+    /// `BatchWritePolicy`. The keys are grouped by node and each node receives a single batch
+    /// command. After the call, each `BatchWrite` contains the result code of its write and, if
+    /// the write succeeded, the record with the results of any read operations. This method
+    /// requires Aerospike Server version >= 6.0.
+    ///
+    /// # Examples
+    ///
+    /// Write a bin on multiple records in a single client request.
+    ///
+    /// ```rust
+    /// # use aerospike::*;
+    ///
+    /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap_or("localhost".into());
+    /// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+    /// let bin = as_bin!("status", "processed");
+    /// let ops = vec![operations::put(&bin)];
+    /// let mut batch_writes = vec![];
+    /// for i in 0..10 {
+    ///   let key = as_key!("test", "test", i);
+    ///   batch_writes.push(BatchWrite::new(key, &ops));
+    /// }
+    /// match client.batch_write(&BatchPolicy::default(), batch_writes) {
+    ///     Ok(results) => {
+    ///       for result in results {
+    ///         println!("{:?} => {:?}", result.key, result.result_code);
+    ///       }
+    ///     }
+    ///     Err(err)
+    ///         => println!("Error executing batch request: {}", err),
+    /// }
+    /// ```
+    pub fn batch_write<'a>(
+        &self,
+        policy: &BatchPolicy,
+        batch_writes: Vec<BatchWrite<'a>>,
+    ) -> Result<Vec<BatchWrite<'a>>> {
+        runtime::block_on(self.core.batch_write(policy, batch_writes))
+    }
+
+    /// Delete multiple records for specified batch keys in one batch call. The result contains a
+    /// `BatchDelete` for each key, in the same order as the keys, holding the result code of the
+    /// delete: `ResultCode::Ok` if the record was deleted, `ResultCode::KeyNotFoundError` if it
+    /// did not exist. This method requires Aerospike Server version >= 6.0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use aerospike::*;
+    ///
+    /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap_or("localhost".into());
+    /// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+    /// let keys: Vec<Key> = (0..10).map(|i| as_key!("test", "test", i)).collect();
+    /// match client.batch_delete(&BatchPolicy::default(), &keys) {
+    ///     Ok(results) => {
+    ///       for result in results {
+    ///         println!("{:?} => {:?}", result.key, result.result_code);
+    ///       }
+    ///     }
+    ///     Err(err)
+    ///         => println!("Error executing batch request: {}", err),
+    /// }
+    /// ```
+    pub fn batch_delete<'a>(
+        &self,
+        policy: &BatchPolicy,
+        keys: &[Key],
+    ) -> Result<Vec<BatchDelete<'a>>> {
+        runtime::block_on(self.core.batch_delete(policy, keys))
     }
 
     /// Write record bin(s). The policy specifies the transaction timeout, record expiration and
@@ -276,14 +546,9 @@ impl Client {
         key: &'a Key,
         bins: &'a [A],
     ) -> Result<()> {
-        let mut command = WriteCommand::new(
-            policy,
-            self.cluster.clone(),
-            key,
-            bins,
-            OperationType::Write,
-        );
-        command.execute()
+        // the bins are passed by reference, so that the future does not require `A: Sync`
+        let bins: Vec<&Bin> = bins.iter().map(AsRef::as_ref).collect();
+        runtime::block_on(self.core.put(policy, key, &bins))
     }
 
     /// Add integer bin values to existing record bin values. The policy specifies the transaction
@@ -314,9 +579,8 @@ impl Client {
         key: &'a Key,
         bins: &'a [A],
     ) -> Result<()> {
-        let mut command =
-            WriteCommand::new(policy, self.cluster.clone(), key, bins, OperationType::Incr);
-        command.execute()
+        let bins: Vec<&Bin> = bins.iter().map(AsRef::as_ref).collect();
+        runtime::block_on(self.core.add(policy, key, &bins))
     }
 
     /// Append bin string values to existing record bin values. The policy specifies the
@@ -328,14 +592,8 @@ impl Client {
         key: &'a Key,
         bins: &'a [A],
     ) -> Result<()> {
-        let mut command = WriteCommand::new(
-            policy,
-            self.cluster.clone(),
-            key,
-            bins,
-            OperationType::Append,
-        );
-        command.execute()
+        let bins: Vec<&Bin> = bins.iter().map(AsRef::as_ref).collect();
+        runtime::block_on(self.core.append(policy, key, &bins))
     }
 
     /// Prepend bin string values to existing record bin values. The policy specifies the
@@ -347,14 +605,8 @@ impl Client {
         key: &'a Key,
         bins: &'a [A],
     ) -> Result<()> {
-        let mut command = WriteCommand::new(
-            policy,
-            self.cluster.clone(),
-            key,
-            bins,
-            OperationType::Prepend,
-        );
-        command.execute()
+        let bins: Vec<&Bin> = bins.iter().map(AsRef::as_ref).collect();
+        runtime::block_on(self.core.prepend(policy, key, &bins))
     }
 
     /// Delete record for specified key. The policy specifies the transaction timeout.
@@ -377,9 +629,7 @@ impl Client {
     /// }
     /// ```
     pub fn delete(&self, policy: &WritePolicy, key: &Key) -> Result<bool> {
-        let mut command = DeleteCommand::new(policy, self.cluster.clone(), key);
-        command.execute()?;
-        Ok(command.existed)
+        runtime::block_on(self.core.delete(policy, key))
     }
 
     /// Reset record's time to expiration using the policy's expiration. Fail if the record does
@@ -403,15 +653,37 @@ impl Client {
     /// }
     /// ```
     pub fn touch(&self, policy: &WritePolicy, key: &Key) -> Result<()> {
-        let mut command = TouchCommand::new(policy, self.cluster.clone(), key);
-        command.execute()
+        runtime::block_on(self.core.touch(policy, key))
+    }
+
+    /// Reset record's time to expiration using the policy's expiration and return the record's
+    /// resulting generation and expiration in the same database call. No bins are returned. Fail
+    /// if the record does not exist.
+    ///
+    /// # Examples
+    ///
+    /// Reset a record's time to expiration and read back the new time-to-live.
+    ///
+    /// ```rust
+    /// # use aerospike::*;
+    ///
+    /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap_or("localhost".into());
+    /// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+    /// let key = as_key!("test", "test", "mykey");
+    /// let mut policy = WritePolicy::default();
+    /// policy.expiration = policy::Expiration::Seconds(3600);
+    /// match client.touch_with_response(&policy, &key) {
+    ///     Ok(record) => println!("Record expires in {:?}", record.time_to_live()),
+    ///     Err(err) => println!("Error writing record: {}", err),
+    /// }
+    /// ```
+    pub fn touch_with_response(&self, policy: &WritePolicy, key: &Key) -> Result<Record> {
+        runtime::block_on(self.core.touch_with_response(policy, key))
     }
 
     /// Determine if a record key exists. The policy can be used to specify timeouts.
     pub fn exists(&self, policy: &WritePolicy, key: &Key) -> Result<bool> {
-        let mut command = ExistsCommand::new(policy, self.cluster.clone(), key);
-        command.execute()?;
-        Ok(command.exists)
+        runtime::block_on(self.core.exists(policy, key))
     }
 
     /// Perform multiple read/write operations on a single key in one batch call.
@@ -442,9 +714,7 @@ impl Client {
     /// }
     /// ```
     pub fn operate(&self, policy: &WritePolicy, key: &Key, ops: &[Operation]) -> Result<Record> {
-        let mut command = OperateCommand::new(policy, self.cluster.clone(), key, ops);
-        command.execute()?;
-        Ok(command.read_command.record.unwrap())
+        runtime::block_on(self.core.operate(policy, key, ops))
     }
 
     /// Register a package containing user-defined functions (UDF) with the cluster. This
@@ -496,34 +766,7 @@ impl Client {
         udf_name: &str,
         language: UDFLang,
     ) -> Result<RegisterTask> {
-        let udf_body = base64::encode(udf_body);
-
-        let cmd = format!(
-            "udf-put:filename={};content={};content-len={};udf-type={};",
-            udf_name,
-            udf_body,
-            udf_body.len(),
-            language
-        );
-        let node = self.cluster.get_random_node()?;
-        let response = node.info(policy.base_policy.timeout, &[&cmd])?;
-
-        if let Some(msg) = response.get("error") {
-            let msg = base64::decode(msg)?;
-            let msg = str::from_utf8(&msg)?;
-            bail!(
-                "UDF Registration failed: {}, file: {}, line: {}, message: {}",
-                response.get("error").unwrap_or(&"-".to_string()),
-                response.get("file").unwrap_or(&"-".to_string()),
-                response.get("line").unwrap_or(&"-".to_string()),
-                msg
-            );
-        }
-
-        Ok(RegisterTask::new(
-            Arc::clone(&self.cluster),
-            udf_name.to_string(),
-        ))
+        runtime::block_on(self.core.register_udf(policy, udf_body, udf_name, language))
     }
 
     /// Register a package containing user-defined functions (UDF) with the cluster. This
@@ -531,7 +774,27 @@ impl Client {
     /// the UDF package with a single, random cluster node; from there a copy will get distributed
     /// to all other cluster nodes automatically.
     ///
+    /// The returned task verifies the registration: it only completes once every node reports
+    /// the hash of the local file content for the package, so that waiting on the task also
+    /// guards against a previously registered version of the package or a corrupted upload.
+    ///
     /// Lua is the only supported scripting laungauge for UDFs at the moment.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate aerospike;
+    /// # use aerospike::*;
+    /// # use std::time::Duration;
+    ///
+    /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap_or("localhost".into());
+    /// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+    /// let task = client
+    ///     .register_udf_from_file(&WritePolicy::default(), "udf/example.lua", "example.lua",
+    ///                             UDFLang::Lua)
+    ///     .unwrap();
+    /// task.wait_till_complete(Some(Duration::from_secs(10))).unwrap();
+    /// ```
     pub fn register_udf_from_file(
         &self,
         policy: &WritePolicy,
@@ -539,12 +802,10 @@ impl Client {
         udf_name: &str,
         language: UDFLang,
     ) -> Result<RegisterTask> {
-        let path = Path::new(client_path);
-        let mut file = File::open(&path)?;
-        let mut udf_body: Vec<u8> = vec![];
-        file.read_to_end(&mut udf_body)?;
-
-        self.register_udf(policy, &udf_body, udf_name, language)
+        runtime::block_on(
+            self.core
+                .register_udf_from_file(policy, client_path, udf_name, language),
+        )
     }
 
     /// Remove a user-defined function (UDF) module from the server.
@@ -554,15 +815,26 @@ impl Client {
         udf_name: &str,
         language: UDFLang,
     ) -> Result<()> {
-        let cmd = format!("udf-remove:filename={}.{};", udf_name, language);
-        let node = self.cluster.get_random_node()?;
-        // Sample response: {"udf-remove:filename=file_name.LUA;": "ok"}
-        let response = node.info(policy.base_policy.timeout, &[&cmd])?;
+        runtime::block_on(self.core.remove_udf(policy, udf_name, language))
+    }
 
-        match response.get(&cmd).map(String::as_str) {
-            Some("ok") => Ok(()),
-            _ => bail!("UDF Remove failed: {:?}", response),
-        }
+    /// List the user-defined function (UDF) packages registered with the cluster, as reported by a
+    /// random node.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate aerospike;
+    /// # use aerospike::*;
+    ///
+    /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap_or("localhost".into());
+    /// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+    /// for udf in client.list_udf(&ReadPolicy::default()).unwrap() {
+    ///     println!("{} ({}): {}", udf.filename, udf.language, udf.hash);
+    /// }
+    /// ```
+    pub fn list_udf(&self, policy: &ReadPolicy) -> Result<Vec<UDFMeta>> {
+        runtime::block_on(self.core.list_udf(policy))
     }
 
     /// Execute a user-defined function on the server and return the results. The function operates
@@ -575,40 +847,58 @@ impl Client {
         function_name: &str,
         args: Option<&[Value]>,
     ) -> Result<Option<Value>> {
-        let mut command = ExecuteUDFCommand::new(
-            policy,
-            self.cluster.clone(),
-            key,
-            udf_name,
-            function_name,
-            args,
-        );
-
-        command.execute()?;
-
-        let record = command.read_command.record.unwrap();
-
-        // User defined functions don't have to return a value.
-        if record.bins.is_empty() {
-            return Ok(None);
-        }
-
-        for (key, value) in &record.bins {
-            if key.contains("SUCCESS") {
-                return Ok(Some(value.clone()));
-            } else if key.contains("FAILURE") {
-                bail!("{:?}", value);
-            }
-        }
+        runtime::block_on(
+            self.core
+                .execute_udf(policy, key, udf_name, function_name, args),
+        )
+    }
 
-        Err("Invalid UDF return value".into())
+    /// Execute a user-defined function on multiple records in one batch call. The UDF package
+    /// name is required to locate the UDF. The result contains a `BatchUDF` for each key, in the
+    /// same order as the keys, holding the result code and the function's return value or error
+    /// for that key. This method requires Aerospike Server version >= 6.0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use aerospike::*;
+    ///
+    /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap_or("localhost".into());
+    /// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+    /// let keys: Vec<Key> = (0..10).map(|i| as_key!("test", "test", i)).collect();
+    /// let args = [as_val!(2)];
+    /// match client.batch_execute_udf(&BatchPolicy::default(), &keys, "udf", "func", Some(&args)) {
+    ///     Ok(results) => {
+    ///       for result in results {
+    ///         match result.error() {
+    ///           None => println!("{:?} => {:?}", result.key, result.return_value()),
+    ///           Some(err) => println!("{:?} failed: {}", result.key, err),
+    ///         }
+    ///       }
+    ///     }
+    ///     Err(err)
+    ///         => println!("Error executing batch request: {}", err),
+    /// }
+    /// ```
+    pub fn batch_execute_udf<'a>(
+        &self,
+        policy: &BatchPolicy,
+        keys: &[Key],
+        udf_name: &'a str,
+        function_name: &'a str,
+        args: Option<&'a [Value]>,
+    ) -> Result<Vec<BatchUDF<'a>>> {
+        runtime::block_on(
+            self.core
+                .batch_execute_udf(policy, keys, udf_name, function_name, args),
+        )
     }
 
     /// Read all records in the specified namespace and set and return a record iterator. The scan
     /// executor puts records on a queue in separate threads. The calling thread concurrently pops
     /// records off the queue through the record iterator. Up to `policy.max_concurrent_nodes`
-    /// nodes are scanned in parallel. If concurrent nodes is set to zero, the server nodes are
-    /// read in series.
+    /// nodes are scanned in parallel. If concurrent nodes is set to zero, all server nodes are
+    /// read in parallel.
     ///
     /// # Examples
     ///
@@ -642,33 +932,32 @@ impl Client {
     where
         T: Into<Bins>,
     {
-        let bins = bins.into();
-        let nodes = self.cluster.nodes();
-        let recordset = Arc::new(Recordset::new(policy.record_queue_size, nodes.len()));
-        for node in nodes {
-            let partitions = self.cluster.node_partitions(node.as_ref(), namespace);
-            let node = node.clone();
-            let recordset = recordset.clone();
-            let policy = policy.to_owned();
-            let namespace = namespace.to_owned();
-            let set_name = set_name.to_owned();
-            let bins = bins.clone();
-
-            thread::spawn(move || {
-                let mut command = ScanCommand::new(
-                    &policy, node, &namespace, &set_name, bins, recordset, partitions,
-                );
-                command.execute().unwrap();
-            });
-        }
-        Ok(recordset)
+        runtime::block_on(self.core.scan(policy, namespace, set_name, bins))
     }
 
     /// Read all records in the specified namespace and set for one node only and return a record
-    /// iterator. The scan executor puts records on a queue in separate threads. The calling thread
-    /// concurrently pops records off the queue through the record iterator. Up to
-    /// `policy.max_concurrent_nodes` nodes are scanned in parallel. If concurrent nodes is set to
-    /// zero, the server nodes are read in series.
+    /// iterator. The scan executor puts the node's records on a queue in a separate thread. The
+    /// calling thread concurrently pops records off the queue through the record iterator. Errors
+    /// of the scan are returned through the record iterator as well.
+    ///
+    /// # Examples
+    ///
+    /// Scan the cluster one node at a time, looking up the nodes by name.
+    ///
+    /// ```rust
+    /// # use aerospike::*;
+    ///
+    /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap_or("localhost".into());
+    /// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+    /// for name in client.node_names() {
+    ///     let node = client.get_node(&name).unwrap();
+    ///     let records = client
+    ///         .scan_node(&ScanPolicy::default(), node, "test", "demo", Bins::All)
+    ///         .unwrap();
+    ///     let count = (&*records).filter(Result::is_ok).count();
+    ///     println!("Node {}: {} records", name, count);
+    /// }
+    /// ```
     pub fn scan_node<T>(
         &self,
         policy: &ScanPolicy,
@@ -680,28 +969,57 @@ impl Client {
     where
         T: Into<Bins>,
     {
-        let partitions = self.cluster.node_partitions(node.as_ref(), namespace);
-        let bins = bins.into();
-        let recordset = Arc::new(Recordset::new(policy.record_queue_size, 1));
-        let t_recordset = recordset.clone();
-        let policy = policy.to_owned();
-        let namespace = namespace.to_owned();
-        let set_name = set_name.to_owned();
-
-        self.thread_pool.spawn(move || {
-            let mut command = ScanCommand::new(
-                &policy,
-                node,
-                &namespace,
-                &set_name,
-                bins,
-                t_recordset,
-                partitions,
-            );
-            command.execute().unwrap();
-        });
-
-        Ok(recordset)
+        runtime::block_on(self.core.scan_node(policy, node, namespace, set_name, bins))
+    }
+
+    /// Read all records in the given partition range of the specified namespace and set and
+    /// return a record iterator. Each server node is scanned for the partitions it owns within the
+    /// range, in a separate thread. Scans of disjoint partition ranges can be run by separate
+    /// processes to share the work of scanning a set.
+    ///
+    /// The scan can be stopped at any time by closing the recordset; `Recordset::partition_filter`
+    /// then returns a filter that resumes the scan after the last record consumed from the
+    /// recordset. This requires Aerospike Server version >= 5.2 for exact resumption. With the
+    /// `serialization` feature, the filter can be written to disk as a checkpoint and used to
+    /// resume the scan from a different process. An error is returned if the partition status of
+    /// a resumed filter does not match its partition range.
+    ///
+    /// # Examples
+    ///
+    /// Scan the first quarter of the partitions.
+    ///
+    /// ```rust
+    /// # use aerospike::*;
+    ///
+    /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap_or("localhost".into());
+    /// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+    /// let filter = PartitionFilter::by_range(0, 1024).unwrap();
+    /// match client.scan_partitions(&ScanPolicy::default(), &filter, "test", "demo", Bins::All) {
+    ///     Ok(records) => {
+    ///         let count = (&*records).filter(Result::is_ok).count();
+    ///         println!("Records: {}", count);
+    ///     },
+    ///     Err(err) => println!("Failed to execute scan: {}", err),
+    /// }
+    /// ```
+    pub fn scan_partitions<T>(
+        &self,
+        policy: &ScanPolicy,
+        partition_filter: &PartitionFilter,
+        namespace: &str,
+        set_name: &str,
+        bins: T,
+    ) -> Result<Arc<Recordset>>
+    where
+        T: Into<Bins>,
+    {
+        runtime::block_on(self.core.scan_partitions(
+            policy,
+            partition_filter,
+            namespace,
+            set_name,
+            bins,
+        ))
     }
 
     /// Execute a query on all server nodes and return a record iterator. The query executor puts
@@ -727,60 +1045,157 @@ impl Client {
     /// }
     /// ```
     pub fn query(&self, policy: &QueryPolicy, statement: Statement) -> Result<Arc<Recordset>> {
-        statement.validate()?;
-        let statement = Arc::new(statement);
-
-        let nodes = self.cluster.nodes();
-        let recordset = Arc::new(Recordset::new(policy.record_queue_size, nodes.len()));
-        for node in nodes {
-            let partitions = self
-                .cluster
-                .node_partitions(node.as_ref(), &statement.namespace);
-            let node = node.clone();
-            let t_recordset = recordset.clone();
-            let policy = policy.to_owned();
-            let statement = statement.clone();
-
-            self.thread_pool.spawn(move || {
-                let mut command =
-                    QueryCommand::new(&policy, node, statement, t_recordset, partitions);
-                command.execute().unwrap();
-            });
-        }
-        Ok(recordset)
+        runtime::block_on(self.core.query(policy, statement))
+    }
+
+    /// Execute a query without secondary index filter on the given partition range and return a
+    /// record iterator. Like `scan_partitions`, the query can be stopped at any time by closing
+    /// the recordset and resumed after the last consumed record with the filter returned by
+    /// `Recordset::partition_filter`. Queries with a secondary index filter cannot be run by
+    /// partition.
+    ///
+    /// # Examples
+    ///
+    /// Read the first page of 100 records of a set, then resume after the last record.
+    ///
+    /// ```rust
+    /// # use aerospike::*;
+    ///
+    /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap_or("localhost".into());
+    /// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+    /// let policy = QueryPolicy::default();
+    /// let stmt = Statement::new("test", "demo", Bins::All);
+    /// let rs = client.query_partitions(&policy, &PartitionFilter::all(), stmt).unwrap();
+    /// let page: Vec<Record> = (&*rs).take(100).filter_map(Result::ok).collect();
+    /// rs.close();
+    ///
+    /// let cursor = rs.partition_filter().unwrap();
+    /// let stmt = Statement::new("test", "demo", Bins::All);
+    /// let rs = client.query_partitions(&policy, &cursor, stmt).unwrap();
+    /// ```
+    pub fn query_partitions(
+        &self,
+        policy: &QueryPolicy,
+        partition_filter: &PartitionFilter,
+        statement: Statement,
+    ) -> Result<Arc<Recordset>> {
+        runtime::block_on(
+            self.core
+                .query_partitions(policy, partition_filter, statement),
+        )
     }
 
     /// Execute a query on a single server node and return a record iterator. The query executor
-    /// puts records on a queue in separate threads. The calling thread concurrently pops records
-    /// off the queue through the record iterator.
+    /// puts the node's records on a queue in a separate thread. The calling thread concurrently
+    /// pops records off the queue through the record iterator. Errors of the query are returned
+    /// through the record iterator as well. Use `get_node` to look up a node by name.
     pub fn query_node(
         &self,
         policy: &QueryPolicy,
         node: Arc<Node>,
         statement: Statement,
     ) -> Result<Arc<Recordset>> {
-        statement.validate()?;
+        runtime::block_on(self.core.query_node(policy, node, statement))
+    }
 
-        let recordset = Arc::new(Recordset::new(policy.record_queue_size, 1));
-        let t_recordset = recordset.clone();
-        let policy = policy.to_owned();
-        let statement = Arc::new(statement);
-        let partitions = self
-            .cluster
-            .node_partitions(node.as_ref(), &statement.namespace);
+    /// Execute a query and aggregate the matching records with the given Lua stream UDF on the
+    /// server nodes. The UDF package has to be registered with the cluster before running the
+    /// query. Each record of the returned record iterator holds one value returned by the stream
+    /// function in its "SUCCESS" bin; errors raised by the function are returned as
+    /// `ErrorKind::UdfBadResponse`.
+    ///
+    /// The client does not embed a Lua runtime. The final reduce step of the stream function is
+    /// therefore not executed on the client, and a `reduce` returns one partial result per server
+    /// node, which the caller has to combine.
+    ///
+    /// # Examples
+    ///
+    /// Count the records of a set with a stream UDF `count` in package "aggregates".
+    ///
+    /// ```rust
+    /// # use aerospike::*;
+    ///
+    /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap_or("localhost".into());
+    /// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+    /// let stmt = Statement::new("test", "demo", Bins::All);
+    /// let rs = client
+    ///     .query_aggregate(&QueryPolicy::default(), stmt, "aggregates", "count", None)
+    ///     .unwrap();
+    /// let mut count = 0;
+    /// for rec in &*rs {
+    ///     let partial: i64 = rec.unwrap().bins["SUCCESS"].clone().into();
+    ///     count += partial;
+    /// }
+    /// println!("Records: {}", count);
+    /// ```
+    pub fn query_aggregate(
+        &self,
+        policy: &QueryPolicy,
+        statement: Statement,
+        package_name: &str,
+        function_name: &str,
+        args: Option<&[Value]>,
+    ) -> Result<Arc<Recordset>> {
+        runtime::block_on(self.core.query_aggregate(
+            policy,
+            statement,
+            package_name,
+            function_name,
+            args,
+        ))
+    }
 
-        self.thread_pool.spawn(move || {
-            let mut command = QueryCommand::new(&policy, node, statement, t_recordset, partitions);
-            command.execute().unwrap();
-        });
+    /// Apply the operations to all records matched by the query on the server, as a background
+    /// job. Only write operations are allowed. Returns once the job has been started on all
+    /// server nodes; use the returned task to wait for the job to complete.
+    ///
+    /// # Examples
+    ///
+    /// Reset the "visits" bin of all records of a set.
+    ///
+    /// ```rust
+    /// # use aerospike::*;
+    ///
+    /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap_or("localhost".into());
+    /// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+    /// let stmt = Statement::new("test", "demo", Bins::None);
+    /// let bin = as_bin!("visits", 0);
+    /// let ops = [operations::put(&bin)];
+    /// let task = client
+    ///     .query_execute(&WritePolicy::default(), stmt, &ops)
+    ///     .unwrap();
+    /// task.wait_till_complete(None).unwrap();
+    /// ```
+    pub fn query_execute(
+        &self,
+        policy: &WritePolicy,
+        statement: Statement,
+        operations: &[Operation],
+    ) -> Result<ExecuteTask> {
+        runtime::block_on(self.core.query_execute(policy, statement, operations))
+    }
 
-        Ok(recordset)
+    /// Apply the operations to all records of the specified namespace and set on the server, as a
+    /// background job. See `query_execute`.
+    pub fn scan_execute(
+        &self,
+        policy: &WritePolicy,
+        namespace: &str,
+        set_name: &str,
+        operations: &[Operation],
+    ) -> Result<ExecuteTask> {
+        runtime::block_on(
+            self.core
+                .scan_execute(policy, namespace, set_name, operations),
+        )
     }
 
     /// Removes all records in the specified namespace/set efficiently.
     ///
-    /// This method is many orders of magnitude faster than deleting records one at a time. It
-    /// requires Aerospike Server version 3.12 or later. See
+    /// This method is many orders of magnitude faster than deleting records one at a time, and
+    /// does not generate a tombstone per record. It requires Aerospike Server version 3.12 or
+    /// later. The command is sent to the principal node of the cluster, which distributes the
+    /// truncation to the other nodes. See
     /// <https://www.aerospike.com/docs/reference/info#truncate> for further info.
     ///
     /// The `set_name` is optional; set to `""` to delete all sets in `namespace`.
@@ -796,26 +1211,49 @@ impl Client {
         set_name: &str,
         before_nanos: i64,
     ) -> Result<()> {
-        let mut cmd = String::with_capacity(160);
-        cmd.push_str("truncate:namespace=");
-        cmd.push_str(namespace);
-
-        if !set_name.is_empty() {
-            cmd.push_str(";set=");
-            cmd.push_str(set_name);
-        }
-
-        if before_nanos > 0 {
-            cmd.push_str(";lut=");
-            cmd.push_str(&format!("{}", before_nanos));
-        }
+        runtime::block_on(
+            self.core
+                .truncate(policy, namespace, set_name, before_nanos),
+        )
+    }
 
-        self.send_info_cmd(&cmd, policy)
-            .chain_err(|| "Error truncating ns/set")
+    /// Set the filter expression that XDR uses to select the records of `namespace` shipped to
+    /// `datacenter`. Pass `None` to remove the filter, so that all records are shipped. Requires
+    /// Aerospike Server version 5.3 or later.
+    ///
+    /// # Examples
+    ///
+    /// Only ship records of set `demo` to datacenter `dc2`.
+    ///
+    /// ```rust
+    /// # extern crate aerospike;
+    /// # use aerospike::*;
+    /// use aerospike::expressions::{eq, set_name, string_val};
+    ///
+    /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap_or("localhost".into());
+    /// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+    /// let filter = eq(set_name(), string_val("demo"));
+    /// match client.set_xdr_filter(&WritePolicy::default(), "dc2", "test", Some(&filter)) {
+    ///     Ok(()) => println!("XDR filter updated"),
+    ///     Err(err) => println!("Failed to set XDR filter: {}", err),
+    /// }
+    /// ```
+    pub fn set_xdr_filter(
+        &self,
+        policy: &WritePolicy,
+        datacenter: &str,
+        namespace: &str,
+        filter: Option<&FilterExpression>,
+    ) -> Result<()> {
+        runtime::block_on(
+            self.core
+                .set_xdr_filter(policy, datacenter, namespace, filter),
+        )
     }
 
     /// Create a secondary index on a bin containing scalar values. This asynchronous server call
-    /// returns before the command is complete.
+    /// returns before the index has been built; use the returned `IndexTask` to wait until the
+    /// index is ready to be queried on all nodes.
     ///
     /// # Examples
     ///
@@ -825,13 +1263,17 @@ impl Client {
     /// ```rust
     /// # extern crate aerospike;
     /// # use aerospike::*;
+    /// # use aerospike::task::Task;
+    /// # use std::time::Duration;
     ///
     /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap_or("localhost".into());
     /// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
     /// match client.create_index(&WritePolicy::default(), "foo", "bar", "baz",
     ///     "idx_foo_bar_baz", IndexType::Numeric) {
+    ///     Ok(task) => {
+    ///         task.wait_till_complete(Some(Duration::from_secs(30))).unwrap();
+    ///     },
     ///     Err(err) => println!("Failed to create index: {}", err),
-    ///     _ => {}
     /// }
     /// ```
     pub fn create_index(
@@ -843,24 +1285,14 @@ impl Client {
         index_name: &str,
         index_type: IndexType,
     ) -> Result<IndexTask> {
-        self.create_complex_index(
-            policy,
-            namespace,
-            set_name,
-            bin_name,
-            index_name,
-            index_type,
-            CollectionIndexType::Default,
-        )?;
-        Ok(IndexTask::new(
-            Arc::clone(&self.cluster),
-            namespace.to_string(),
-            index_name.to_string(),
+        runtime::block_on(self.core.create_index(
+            policy, namespace, set_name, bin_name, index_name, index_type,
         ))
     }
 
     /// Create a complex secondary index on a bin containing scalar, list or map values. This
-    /// asynchronous server call returns before the command is complete.
+    /// asynchronous server call returns before the index has been built; use the returned
+    /// `IndexTask` to wait until the index is ready to be queried on all nodes.
     #[allow(clippy::too_many_arguments)]
     pub fn create_complex_index(
         &self,
@@ -871,19 +1303,16 @@ impl Client {
         index_name: &str,
         index_type: IndexType,
         collection_index_type: CollectionIndexType,
-    ) -> Result<()> {
-        let cit_str: String = if let CollectionIndexType::Default = collection_index_type {
-            "".to_string()
-        } else {
-            format!("indextype={};", collection_index_type)
-        };
-        let cmd = format!(
-            "sindex-create:ns={};set={};indexname={};numbins=1;{}indexdata={},{};\
-             priority=normal",
-            namespace, set_name, index_name, cit_str, bin_name, index_type
-        );
-        self.send_info_cmd(&cmd, policy)
-            .chain_err(|| "Error creating index")
+    ) -> Result<IndexTask> {
+        runtime::block_on(self.core.create_complex_index(
+            policy,
+            namespace,
+            set_name,
+            bin_name,
+            index_name,
+            index_type,
+            collection_index_type,
+        ))
     }
 
     /// Delete secondary index.
@@ -894,34 +1323,226 @@ impl Client {
         set_name: &str,
         index_name: &str,
     ) -> Result<()> {
-        let set_name: String = if let "" = set_name {
-            "".to_string()
-        } else {
-            format!("set={};", set_name)
-        };
-        let cmd = format!(
-            "sindex-delete:ns={};{}indexname={}",
-            namespace, set_name, index_name
-        );
-        self.send_info_cmd(&cmd, policy)
-            .chain_err(|| "Error dropping index")
-    }
-
-    fn send_info_cmd(&self, cmd: &str, policy: &WritePolicy) -> Result<()> {
-        let node = self.cluster.get_random_node()?;
-        let response = node.info(policy.base_policy.timeout, &[cmd])?;
-
-        if let Some(v) = response.values().next() {
-            if v.to_uppercase() == "OK" {
-                return Ok(());
-            } else if v.starts_with("FAIL:") {
-                let result = v.split(':').nth(1).unwrap().parse::<u8>()?;
-                bail!(ErrorKind::ServerError(ResultCode::from(result)));
-            }
-        }
-
-        bail!(ErrorKind::BadResponse(
-            "Unexpected sindex info command response".to_string()
+        runtime::block_on(
+            self.core
+                .drop_index(policy, namespace, set_name, index_name),
+        )
+    }
+
+    /// List the secondary indexes defined in the specified namespace, as reported by a random
+    /// node of the cluster. The `state` of an index shows whether it has been built on that node.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate aerospike;
+    /// # use aerospike::*;
+    ///
+    /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap_or("localhost".into());
+    /// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+    /// match client.list_indexes(&ReadPolicy::default(), "test") {
+    ///     Ok(indexes) => {
+    ///         for index in indexes {
+    ///             println!("{}: {}.{} ({})", index.name, index.set_name, index.bin_name,
+    ///                 index.index_type);
+    ///         }
+    ///     },
+    ///     Err(err) => println!("Failed to list indexes: {}", err),
+    /// }
+    /// ```
+    pub fn list_indexes(&self, policy: &ReadPolicy, namespace: &str) -> Result<Vec<IndexInfo>> {
+        runtime::block_on(self.core.list_indexes(policy, namespace))
+    }
+
+    /// Create a user with the given password and roles. Requires a server with security enabled,
+    /// and a client user with the `user-admin` privilege.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate aerospike;
+    /// # use aerospike::*;
+    ///
+    /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap_or("localhost".into());
+    /// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+    /// match client.create_user(&AdminPolicy::default(), "reader", "secret", &["read"]) {
+    ///     Ok(()) => println!("User created"),
+    ///     Err(err) => println!("Failed to create user: {}", err),
+    /// }
+    /// ```
+    pub fn create_user(
+        &self,
+        policy: &AdminPolicy,
+        user: &str,
+        password: &str,
+        roles: &[&str],
+    ) -> Result<()> {
+        runtime::block_on(self.core.create_user(policy, user, password, roles))
+    }
+
+    /// Remove a user from the cluster.
+    pub fn drop_user(&self, policy: &AdminPolicy, user: &str) -> Result<()> {
+        runtime::block_on(self.core.drop_user(policy, user))
+    }
+
+    /// Set the password of a user, without knowing the current password. Requires a client user
+    /// with the `user-admin` privilege. Use `change_password` to change the password of the user
+    /// the client is authenticated as.
+    pub fn set_password(&self, policy: &AdminPolicy, user: &str, password: &str) -> Result<()> {
+        runtime::block_on(self.core.set_password(policy, user, password))
+    }
+
+    /// Change the password of a user. If the user is the one the client is authenticated as, the
+    /// current password is sent along for verification, and the client uses the new password for
+    /// all connections opened from now on. Otherwise this is the same as `set_password`.
+    pub fn change_password(&self, policy: &AdminPolicy, user: &str, password: &str) -> Result<()> {
+        runtime::block_on(self.core.change_password(policy, user, password))
+    }
+
+    /// Add roles to a user.
+    pub fn grant_roles(&self, policy: &AdminPolicy, user: &str, roles: &[&str]) -> Result<()> {
+        runtime::block_on(self.core.grant_roles(policy, user, roles))
+    }
+
+    /// Remove roles from a user.
+    pub fn revoke_roles(&self, policy: &AdminPolicy, user: &str, roles: &[&str]) -> Result<()> {
+        runtime::block_on(self.core.revoke_roles(policy, user, roles))
+    }
+
+    /// Create a user-defined role with the given privileges, whitelist and quotas.
+    ///
+    /// The whitelist lists the client IP addresses, or address ranges in CIDR notation, users
+    /// with the role may connect from; an empty whitelist allows connections from any address.
+    /// The quotas limit the number of records per second users with the role may read and
+    /// write; 0 means no limit. Quotas are only enforced by servers 5.6 and later with quotas
+    /// enabled in their security configuration.
+    ///
+    /// # Examples
+    ///
+    /// Create a role that can read all namespaces, and write set `demo` of namespace `test` at
+    /// up to 1000 records per second, from the `10.1.0.0/16` network.
+    ///
+    /// ```rust
+    /// # extern crate aerospike;
+    /// # use aerospike::*;
+    ///
+    /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap_or("localhost".into());
+    /// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+    /// let privileges = [
+    ///     Privilege::global(PrivilegeCode::Read),
+    ///     Privilege::new(PrivilegeCode::Write, "test", "demo"),
+    /// ];
+    /// let whitelist = ["10.1.0.0/16"];
+    /// let policy = AdminPolicy::default();
+    /// match client.create_role(&policy, "demo-writer", &privileges, &whitelist, 0, 1000) {
+    ///     Ok(()) => println!("Role created"),
+    ///     Err(err) => println!("Failed to create role: {}", err),
+    /// }
+    /// ```
+    pub fn create_role(
+        &self,
+        policy: &AdminPolicy,
+        role: &str,
+        privileges: &[Privilege],
+        whitelist: &[&str],
+        read_quota: u32,
+        write_quota: u32,
+    ) -> Result<()> {
+        runtime::block_on(self.core.create_role(
+            policy,
+            role,
+            privileges,
+            whitelist,
+            read_quota,
+            write_quota,
         ))
     }
+
+    /// Remove a user-defined role.
+    pub fn drop_role(&self, policy: &AdminPolicy, role: &str) -> Result<()> {
+        runtime::block_on(self.core.drop_role(policy, role))
+    }
+
+    /// Set the whitelist of a user-defined role: the client IP addresses, or address ranges in
+    /// CIDR notation, users with the role may connect from. An empty whitelist allows
+    /// connections from any address.
+    pub fn set_whitelist(
+        &self,
+        policy: &AdminPolicy,
+        role: &str,
+        whitelist: &[&str],
+    ) -> Result<()> {
+        runtime::block_on(self.core.set_whitelist(policy, role, whitelist))
+    }
+
+    /// Set the read and write quotas of a user-defined role, in records per second; 0 removes
+    /// the limit. Quotas are only enforced by servers 5.6 and later with quotas enabled in their
+    /// security configuration.
+    pub fn set_quotas(
+        &self,
+        policy: &AdminPolicy,
+        role: &str,
+        read_quota: u32,
+        write_quota: u32,
+    ) -> Result<()> {
+        runtime::block_on(self.core.set_quotas(policy, role, read_quota, write_quota))
+    }
+
+    /// Add privileges to a user-defined role.
+    pub fn grant_privileges(
+        &self,
+        policy: &AdminPolicy,
+        role: &str,
+        privileges: &[Privilege],
+    ) -> Result<()> {
+        runtime::block_on(self.core.grant_privileges(policy, role, privileges))
+    }
+
+    /// Remove privileges from a user-defined role.
+    pub fn revoke_privileges(
+        &self,
+        policy: &AdminPolicy,
+        role: &str,
+        privileges: &[Privilege],
+    ) -> Result<()> {
+        runtime::block_on(self.core.revoke_privileges(policy, role, privileges))
+    }
+
+    /// Retrieve a user, with its roles and current statistics. Returns `None` if the user does
+    /// not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate aerospike;
+    /// # use aerospike::*;
+    ///
+    /// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap_or("localhost".into());
+    /// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+    /// match client.query_user(&AdminPolicy::default(), "admin") {
+    ///     Ok(Some(user)) => println!("Roles of {}: {:?}", user.user, user.roles),
+    ///     Ok(None) => println!("User does not exist"),
+    ///     Err(err) => println!("Failed to query user: {}", err),
+    /// }
+    /// ```
+    pub fn query_user(&self, policy: &AdminPolicy, user: &str) -> Result<Option<User>> {
+        runtime::block_on(self.core.query_user(policy, user))
+    }
+
+    /// Retrieve all users, with their roles and current statistics.
+    pub fn query_users(&self, policy: &AdminPolicy) -> Result<Vec<User>> {
+        runtime::block_on(self.core.query_users(policy))
+    }
+
+    /// Retrieve a role, with its privileges, whitelist and quotas. Returns `None` if the role
+    /// does not exist.
+    pub fn query_role(&self, policy: &AdminPolicy, role: &str) -> Result<Option<Role>> {
+        runtime::block_on(self.core.query_role(policy, role))
+    }
+
+    /// Retrieve all roles, including the predefined roles, with their privileges, whitelists and
+    /// quotas.
+    pub fn query_roles(&self, policy: &AdminPolicy) -> Result<Vec<Role>> {
+        runtime::block_on(self.core.query_roles(policy))
+    }
 }
@@ -0,0 +1,54 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use std::fmt;
+
+use crate::cluster::Node;
+use crate::errors::Error;
+
+/// Receives notifications about changes of the cluster topology, e.g. to log them or to warm up
+/// application caches when a node joins the cluster. Register a listener with
+/// `ClientPolicy::event_listener`.
+///
+/// The listener is called from the cluster tend thread; implementations should return quickly
+/// and must not block on commands of the client.
+pub trait ClusterEventListener: Send + Sync {
+    /// Called after a node joined the client's view of the cluster.
+    fn node_added(&self, _node: &Node) {}
+
+    /// Called after a node was removed from the client's view of the cluster.
+    fn node_removed(&self, _node: &Node) {}
+
+    /// Called when the last node of the cluster was removed and the client lost its connection
+    /// to the cluster.
+    fn cluster_disconnected(&self) {}
+
+    /// Called when the client connected to the cluster again after `cluster_disconnected`.
+    fn cluster_reconnected(&self) {}
+
+    /// Called when a node failed to respond to the cluster tend.
+    fn node_refresh_failed(&self, _node: &Node, _error: &Error) {}
+
+    /// Called when a cluster tend failed, e.g. because none of the nodes responded. `failures`
+    /// is the number of consecutive failed tends, which can be used to alert when the client
+    /// cannot reach the cluster for a while.
+    fn tend_failed(&self, _error: &Error, _failures: usize) {}
+}
+
+impl fmt::Debug for dyn ClusterEventListener {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("ClusterEventListener")
+    }
+}
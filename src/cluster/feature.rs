@@ -0,0 +1,143 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use std::fmt;
+
+/// Features that a server node advertises in its `features` info response.
+///
+/// Use `Node::supports` to check for a feature, e.g. before using a command that is not supported
+/// by all nodes of a cluster with mixed server versions.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Feature {
+    /// Batch commands that write, delete and apply UDFs to records.
+    BatchAny,
+
+    /// Batch index protocol for batch reads.
+    BatchIndex,
+
+    /// Bitwise operations on blob values.
+    BlobBits,
+
+    /// Operations on list values.
+    CdtList,
+
+    /// Operations on map values.
+    CdtMap,
+
+    /// Cluster stable info command.
+    ClusterStable,
+
+    /// Float values.
+    Float,
+
+    /// Geospatial values and queries.
+    Geo,
+
+    /// Queries of selected partitions.
+    PartitionQuery,
+
+    /// Scans of selected partitions.
+    PartitionScan,
+
+    /// Peers info commands for node discovery.
+    Peers,
+
+    /// Query show info command to monitor queries.
+    QueryShow,
+
+    /// Replicas info command with the partitions of all replicas of the node.
+    Replicas,
+
+    /// Legacy replicas info command with the partitions of all replicas of the node.
+    ReplicasAll,
+
+    /// Truncation of a whole namespace.
+    TruncateNamespace,
+
+    /// User defined functions.
+    Udf,
+}
+
+impl Feature {
+    // Parses a feature name of the info response; unknown features are ignored.
+    pub(crate) fn parse(name: &str) -> Option<Self> {
+        let feature = match name {
+            "batch-any" => Feature::BatchAny,
+            "batch-index" => Feature::BatchIndex,
+            "blob-bits" => Feature::BlobBits,
+            "cdt-list" => Feature::CdtList,
+            "cdt-map" => Feature::CdtMap,
+            "cluster-stable" => Feature::ClusterStable,
+            "float" => Feature::Float,
+            "geo" => Feature::Geo,
+            "pquery" => Feature::PartitionQuery,
+            "pscans" => Feature::PartitionScan,
+            "peers" => Feature::Peers,
+            "query-show" => Feature::QueryShow,
+            "replicas" => Feature::Replicas,
+            "replicas-all" => Feature::ReplicasAll,
+            "truncate-namespace" => Feature::TruncateNamespace,
+            "udf" => Feature::Udf,
+            _ => return None,
+        };
+        Some(feature)
+    }
+
+    /// Returns the name of the feature in the info response.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Feature::BatchAny => "batch-any",
+            Feature::BatchIndex => "batch-index",
+            Feature::BlobBits => "blob-bits",
+            Feature::CdtList => "cdt-list",
+            Feature::CdtMap => "cdt-map",
+            Feature::ClusterStable => "cluster-stable",
+            Feature::Float => "float",
+            Feature::Geo => "geo",
+            Feature::PartitionQuery => "pquery",
+            Feature::PartitionScan => "pscans",
+            Feature::Peers => "peers",
+            Feature::QueryShow => "query-show",
+            Feature::Replicas => "replicas",
+            Feature::ReplicasAll => "replicas-all",
+            Feature::TruncateNamespace => "truncate-namespace",
+            Feature::Udf => "udf",
+        }
+    }
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Feature;
+
+    #[test]
+    fn parse() {
+        for feature in &[
+            Feature::BatchAny,
+            Feature::PartitionQuery,
+            Feature::ReplicasAll,
+        ] {
+            assert_eq!(Feature::parse(feature.name()), Some(*feature));
+        }
+        assert_eq!(Feature::parse("pscans"), Some(Feature::PartitionScan));
+        assert_eq!(Feature::parse("unknown-feature"), None);
+    }
+}
@@ -13,13 +13,18 @@
 // License for the specific language governing permissions and limitations under
 // the License.
 
+mod event_listener;
+mod feature;
 pub mod node;
+mod node_stats;
 pub mod node_validator;
 pub mod partition;
 pub mod partition_tokenizer;
+mod peers;
 
+use std::cmp;
 use std::collections::HashMap;
-use std::sync::atomic::{AtomicBool, AtomicIsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicIsize, AtomicUsize, Ordering};
 use std::sync::mpsc;
 use std::sync::mpsc::{Receiver, Sender, TryRecvError};
 use std::sync::Arc;
@@ -29,7 +34,10 @@ use std::vec::Vec;
 
 use parking_lot::{Mutex, RwLock};
 
+pub use self::event_listener::ClusterEventListener;
+pub use self::feature::Feature;
 pub use self::node::Node;
+pub use self::node_stats::NodeStats;
 
 use self::node_validator::NodeValidator;
 use self::partition::Partition;
@@ -37,7 +45,18 @@ use self::partition_tokenizer::PartitionTokenizer;
 
 use crate::errors::{ErrorKind, Result};
 use crate::net::Host;
-use crate::policy::ClientPolicy;
+use crate::policy::{AuthMode, ClientPolicy, Replica};
+use crate::runtime::{self, Runtime};
+
+// Shortest interval between cluster tends.
+const MIN_TEND_INTERVAL: Duration = Duration::from_millis(10);
+
+// Consecutive failed refreshes of all nodes after which the cluster is seeded again.
+const MAX_SEED_FAILURES: usize = 5;
+
+/// Nodes of the replicas of each partition, by namespace: the master replica comes first, and
+/// each replica is indexed by partition ID.
+pub type PartitionMap = HashMap<String, Vec<Vec<Arc<Node>>>>;
 
 // Cluster encapsulates the aerospike cluster nodes and manages
 // them.
@@ -53,21 +72,68 @@ pub struct Cluster {
     nodes: Arc<RwLock<Vec<Arc<Node>>>>,
 
     // Hints for best node for a partition
-    partition_write_map: Arc<RwLock<HashMap<String, Vec<Arc<Node>>>>>,
+    partition_write_map: Arc<RwLock<PartitionMap>>,
 
     // Random node index.
     node_index: AtomicIsize,
 
+    // Replica index of reads with `Replica::Any`.
+    replica_index: AtomicUsize,
+
+    // Number of tends, to reset the error counts of the nodes after each error rate window.
+    tend_count: AtomicUsize,
+
     client_policy: ClientPolicy,
 
-    tend_channel: Mutex<Sender<()>>,
+    // User name and password hash used to authenticate new connections. Shared with the
+    // connection pools of the nodes, so that a password change applies to all of them.
+    user_password: Arc<RwLock<Option<(String, String)>>>,
+
+    // Runtime of the commands of the client.
+    runtime: Runtime,
+
+    // Handle of the Tokio runtime of the client, to open its connections from the tend thread.
+    #[cfg(feature = "rt-tokio")]
+    tokio_handle: Option<tokio::runtime::Handle>,
+
+    // Racks preferred by reads with `Replica::PreferRack`, in order of preference.
+    rack_ids: Vec<usize>,
+
+    // Whether the cluster lost all nodes after being connected.
+    disconnected: AtomicBool,
+
+    // Number of commands in flight, and whether new commands are refused while the client
+    // closes gracefully.
+    commands: Arc<AtomicUsize>,
+    closing: AtomicBool,
+
+    // Dropped to signal the tend thread to close the nodes and end.
+    tend_channel: Mutex<Option<Sender<()>>>,
     closed: AtomicBool,
 }
 
 impl Cluster {
-    pub fn new(policy: ClientPolicy, hosts: &[Host]) -> Result<Arc<Self>> {
+    pub fn new(policy: ClientPolicy, hosts: &[Host], runtime: Runtime) -> Result<Arc<Self>> {
+        Cluster::validate_security_policy(&policy)?;
+        if policy.min_conns_per_node > policy.max_conns_per_node {
+            bail!(ErrorKind::InvalidArgument(
+                "min_conns_per_node must not exceed max_conns_per_node".to_string()
+            ));
+        }
+        if policy.error_rate_window == 0 {
+            bail!(ErrorKind::InvalidArgument(
+                "error_rate_window must be greater than zero".to_string()
+            ));
+        }
+
+        let rack_ids = if policy.rack_ids.is_empty() {
+            vec![policy.rack_id]
+        } else {
+            policy.rack_ids.clone()
+        };
         let (tx, rx): (Sender<()>, Receiver<()>) = mpsc::channel();
         let cluster = Arc::new(Cluster {
+            user_password: Arc::new(RwLock::new(policy.user_password.clone())),
             client_policy: policy,
 
             seeds: Arc::new(RwLock::new(hosts.to_vec())),
@@ -76,8 +142,22 @@ impl Cluster {
 
             partition_write_map: Arc::new(RwLock::new(HashMap::new())),
             node_index: AtomicIsize::new(0),
-
-            tend_channel: Mutex::new(tx),
+            replica_index: AtomicUsize::new(0),
+            tend_count: AtomicUsize::new(0),
+
+            runtime,
+            #[cfg(feature = "rt-tokio")]
+            tokio_handle: if runtime == Runtime::Tokio {
+                tokio::runtime::Handle::try_current().ok()
+            } else {
+                None
+            },
+            rack_ids,
+            disconnected: AtomicBool::new(false),
+            commands: Arc::new(AtomicUsize::new(0)),
+            closing: AtomicBool::new(false),
+
+            tend_channel: Mutex::new(Some(tx)),
             closed: AtomicBool::new(false),
         });
 
@@ -102,7 +182,8 @@ impl Cluster {
     }
 
     fn tend_thread(cluster: Arc<Cluster>, rx: Receiver<()>) {
-        let tend_interval = cluster.client_policy.tend_interval;
+        let tend_interval = cmp::max(cluster.client_policy.tend_interval, MIN_TEND_INTERVAL);
+        let mut failures = 0;
 
         loop {
             // try to read from the receive channel to see if it hung up
@@ -113,6 +194,12 @@ impl Cluster {
                 Err(TryRecvError::Empty) => {
                     if let Err(err) = cluster.tend() {
                         log_error_chain!(err, "Error tending cluster");
+                        failures += 1;
+                        if let Some(ref listener) = cluster.client_policy.event_listener {
+                            listener.tend_failed(&err, failures);
+                        }
+                    } else {
+                        failures = 0;
                     }
 
                     thread::sleep(tend_interval);
@@ -120,12 +207,9 @@ impl Cluster {
             }
         }
 
-        // close all nodes
-        let nodes = cluster.nodes();
-        for mut node in nodes {
-            if let Some(node) = Arc::get_mut(&mut node) {
-                node.close();
-            }
+        // close all nodes, even if commands still hold on to them
+        for node in cluster.nodes() {
+            node.close();
         }
         cluster.set_nodes(vec![]);
     }
@@ -141,6 +225,14 @@ impl Cluster {
             nodes = self.nodes();
         }
 
+        // Reset the error counts of the nodes at the end of each error rate window.
+        let tend_count = self.tend_count.fetch_add(1, Ordering::Relaxed) + 1;
+        if tend_count % self.client_policy.error_rate_window == 0 {
+            for node in &nodes {
+                node.reset_error_count();
+            }
+        }
+
         let mut friend_list: Vec<Host> = vec![];
         let mut refresh_count = 0;
 
@@ -163,6 +255,9 @@ impl Cluster {
                     Err(err) => {
                         node.increase_failures();
                         warn!("Node `{}` refresh failed: {}", node, err);
+                        if let Some(ref listener) = self.client_policy.event_listener {
+                            listener.node_refresh_failed(&node, &err);
+                        }
                     }
                 }
             }
@@ -178,9 +273,31 @@ impl Cluster {
         let remove_list = self.find_nodes_to_remove(refresh_count);
         self.remove_nodes_and_aliases(remove_list);
 
+        self.balance_connections();
+
+        if refresh_count == 0 {
+            bail!(ErrorKind::Connection(
+                "None of the nodes of the cluster responded".to_string()
+            ));
+        }
         Ok(())
     }
 
+    // Opens the minimum number of connections of new nodes, and closes connections that have been
+    // idle for too long. Connections of a Tokio runtime are opened in the context of the runtime.
+    fn balance_connections(&self) {
+        #[cfg(feature = "rt-tokio")]
+        let _runtime = self
+            .tokio_handle
+            .as_ref()
+            .map(tokio::runtime::Handle::enter);
+        for node in self.nodes() {
+            if let Err(err) = runtime::block_on(node.balance_connections()) {
+                warn!("Node `{}` failed to open connections: {}", node, err);
+            }
+        }
+    }
+
     fn wait_till_stabilized(cluster: Arc<Cluster>) -> Result<()> {
         let timeout = cluster
             .client_policy()
@@ -219,15 +336,74 @@ impl Cluster {
         &self.client_policy.cluster_name
     }
 
+    fn validate_security_policy(policy: &ClientPolicy) -> Result<()> {
+        if cfg!(not(feature = "native-tls")) && policy.tls_policy.is_some() {
+            bail!(ErrorKind::InvalidArgument(
+                "TLS connections require the `native-tls` feature".to_string()
+            ));
+        }
+        match (policy.auth_mode, &policy.tls_policy) {
+            (AuthMode::External, None) => bail!(ErrorKind::InvalidArgument(
+                "External authentication requires TLS connections to send the clear-text \
+                 password; use AuthMode::ExternalInsecure on trusted networks"
+                    .to_string()
+            )),
+            (AuthMode::PKI, None) => bail!(ErrorKind::InvalidArgument(
+                "PKI authentication requires TLS connections with a client certificate".to_string()
+            )),
+            (AuthMode::PKI, Some(tls_policy)) if tls_policy.client_certificate.is_none() => {
+                bail!(ErrorKind::InvalidArgument(
+                    "PKI authentication requires a client certificate".to_string()
+                ))
+            }
+            _ => (),
+        }
+        if policy.auth_mode.is_external()
+            && policy.user_password.is_some()
+            && policy.clear_password.is_none()
+        {
+            bail!(ErrorKind::InvalidArgument(
+                "External authentication requires the clear-text password of the user; set \
+                 the auth mode before calling ClientPolicy::set_user_password"
+                    .to_string()
+            ));
+        }
+        Ok(())
+    }
+
     pub const fn client_policy(&self) -> &ClientPolicy {
         &self.client_policy
     }
 
-    pub fn add_seeds(&self, new_seeds: &[Host]) -> Result<()> {
+    pub const fn runtime(&self) -> Runtime {
+        self.runtime
+    }
+
+    pub fn user_password(&self) -> Option<(String, String)> {
+        self.user_password.read().clone()
+    }
+
+    // Use the new password hash of the user for connections opened from now on.
+    pub fn set_user_password(&self, user: String, password_hash: String) {
+        *self.user_password.write() = Some((user, password_hash));
+    }
+
+    pub fn seeds(&self) -> Vec<Host> {
+        self.seeds.read().clone()
+    }
+
+    pub fn add_seeds(&self, new_seeds: &[Host]) {
         let mut seeds = self.seeds.write();
-        seeds.extend_from_slice(new_seeds);
+        for seed in new_seeds {
+            if !seeds.contains(seed) {
+                seeds.push(seed.clone());
+            }
+        }
+    }
 
-        Ok(())
+    pub fn remove_seeds(&self, old_seeds: &[Host]) {
+        let mut seeds = self.seeds.write();
+        seeds.retain(|seed| !old_seeds.contains(seed));
     }
 
     pub fn alias_exists(&self, host: &Host) -> Result<bool> {
@@ -235,12 +411,12 @@ impl Cluster {
         Ok(aliases.contains_key(host))
     }
 
-    fn set_partitions(&self, partitions: HashMap<String, Vec<Arc<Node>>>) {
+    fn set_partitions(&self, partitions: PartitionMap) {
         let mut partition_map = self.partition_write_map.write();
         *partition_map = partitions;
     }
 
-    fn partitions(&self) -> Arc<RwLock<HashMap<String, Vec<Arc<Node>>>>> {
+    fn partitions(&self) -> Arc<RwLock<PartitionMap>> {
         self.partition_write_map.clone()
     }
 
@@ -249,7 +425,10 @@ impl Cluster {
         let partitions = self.partitions();
         let partitions = partitions.read();
 
-        if let Some(node_array) = partitions.get(namespace) {
+        if let Some(node_array) = partitions
+            .get(namespace)
+            .and_then(|replicas| replicas.first())
+        {
             let mut i = 0;
             for tnode in node_array {
                 if node == tnode.as_ref() {
@@ -263,11 +442,7 @@ impl Cluster {
     }
 
     pub fn update_partitions(&self, node: Arc<Node>) -> Result<()> {
-        let mut conn = node.get_connection(self.client_policy.timeout)?;
-        let tokens = PartitionTokenizer::new(&mut conn).map_err(|e| {
-            conn.invalidate();
-            e
-        })?;
+        let tokens = PartitionTokenizer::new(&node, self.client_policy.timeout)?;
 
         let nmap = tokens.update_partition(self.partitions(), node)?;
         self.set_partitions(nmap);
@@ -357,11 +532,28 @@ impl Cluster {
     }
 
     fn create_node(&self, nv: NodeValidator) -> Node {
-        Node::new(self.client_policy.clone(), Arc::new(nv))
+        Node::new(
+            self.client_policy.clone(),
+            Arc::clone(&self.user_password),
+            Arc::new(nv),
+            self.runtime,
+        )
     }
 
     fn find_nodes_to_remove(&self, refresh_count: usize) -> Vec<Arc<Node>> {
         let nodes = self.nodes();
+
+        // None of the nodes responded to several refreshes in a row, e.g. because the cluster was
+        // rebuilt with new addresses. Remove all nodes, so that the next tend seeds the cluster
+        // again, resolving the host names of the seeds anew.
+        if refresh_count == 0
+            && !nodes.is_empty()
+            && nodes.iter().all(|node| node.failures() > MAX_SEED_FAILURES)
+        {
+            warn!("No node of the cluster is responding; seeding again...");
+            return nodes;
+        }
+
         let mut remove_list: Vec<Arc<Node>> = vec![];
         let cluster_size = nodes.len();
         for node in nodes {
@@ -373,14 +565,6 @@ impl Cluster {
             }
 
             match cluster_size {
-                // Single node clusters rely on whether it responded to info requests.
-                1 if node.failures() > 5 => {
-                    // 5 consecutive info requests failed. Try seeds.
-                    if self.seed_nodes() {
-                        remove_list.push(tnode);
-                    }
-                }
-
                 // Two node clusters require at least one successful refresh before removing.
                 2 if refresh_count == 1 && node.reference_count() == 0 && node.failures() > 0 => {
                     remove_list.push(node)
@@ -449,6 +633,7 @@ impl Cluster {
         let partitions = self.partition_write_map.read();
         (*partitions)
             .values()
+            .flatten()
             .any(|map| map.iter().any(|node| *node == filter))
     }
 
@@ -458,8 +643,18 @@ impl Cluster {
         }
 
         let mut nodes = self.nodes();
+        let was_empty = nodes.is_empty();
         nodes.extend(friend_list.iter().cloned());
-        self.set_nodes(nodes)
+        self.set_nodes(nodes);
+
+        if let Some(ref listener) = self.client_policy.event_listener {
+            for node in friend_list {
+                listener.node_added(node);
+            }
+            if was_empty && self.disconnected.swap(false, Ordering::Relaxed) {
+                listener.cluster_reconnected();
+            }
+        }
     }
 
     fn remove_nodes(&self, nodes_to_remove: &[Arc<Node>]) {
@@ -476,7 +671,17 @@ impl Cluster {
             }
         }
 
-        self.set_nodes(node_array)
+        let is_empty = node_array.is_empty();
+        self.set_nodes(node_array);
+
+        if let Some(ref listener) = self.client_policy.event_listener {
+            for node in nodes_to_remove {
+                listener.node_removed(node);
+            }
+            if is_empty && !self.disconnected.swap(true, Ordering::Relaxed) {
+                listener.cluster_disconnected();
+            }
+        }
     }
 
     pub fn is_connected(&self) -> bool {
@@ -498,11 +703,31 @@ impl Cluster {
         *nodes = new_nodes;
     }
 
+    pub fn partition_map(&self) -> PartitionMap {
+        self.partition_write_map.read().clone()
+    }
+
+    // Node of the master replica of the partition, without falling back to a random node.
+    pub fn get_master_node(&self, partition: &Partition) -> Result<Arc<Node>> {
+        let partitions = self.partition_write_map.read();
+        partitions
+            .get(partition.namespace)
+            .and_then(|replicas| replicas.first())
+            .and_then(|nodes| nodes.get(partition.partition_id))
+            .cloned()
+            .ok_or_else(|| {
+                ErrorKind::InvalidNode(format!("No node found for {}", partition)).into()
+            })
+    }
+
     pub fn get_node(&self, partition: &Partition) -> Result<Arc<Node>> {
         let partitions = self.partitions();
         let partitions = partitions.read();
 
-        if let Some(node_array) = partitions.get(partition.namespace) {
+        if let Some(node_array) = partitions
+            .get(partition.namespace)
+            .and_then(|replicas| replicas.first())
+        {
             if let Some(node) = node_array.get(partition.partition_id) {
                 return Ok(node.clone());
             }
@@ -511,6 +736,57 @@ impl Cluster {
         self.get_random_node()
     }
 
+    // Node to read the partition from, depending on the replica policy of the command and the
+    // number of times the command has been retried.
+    pub fn get_read_node(
+        &self,
+        partition: &Partition,
+        replica: Replica,
+        sequence: usize,
+    ) -> Result<Arc<Node>> {
+        let sequence = match replica {
+            Replica::Master => return self.get_node(partition),
+            Replica::Any => self.replica_index.fetch_add(1, Ordering::Relaxed),
+            Replica::Sequence | Replica::PreferRack => sequence,
+        };
+
+        let mut nodes = self.replica_nodes(partition);
+        if replica == Replica::PreferRack {
+            // stable sort: the replicas on the same rack keep their order
+            let rank = |node: &Arc<Node>| {
+                let rack_id = node.rack_id(partition.namespace);
+                self.rack_ids
+                    .iter()
+                    .position(|&id| Some(id) == rack_id)
+                    .unwrap_or(self.rack_ids.len())
+            };
+            nodes.sort_by_key(rank);
+        }
+
+        if nodes.is_empty() {
+            return self.get_node(partition);
+        }
+        Ok(nodes.swap_remove(sequence % nodes.len()))
+    }
+
+    // Active nodes of the replicas of the partition, starting with the master.
+    fn replica_nodes(&self, partition: &Partition) -> Vec<Arc<Node>> {
+        let partitions = self.partitions();
+        let partitions = partitions.read();
+
+        partitions
+            .get(partition.namespace)
+            .map(|replicas| {
+                replicas
+                    .iter()
+                    .filter_map(|node_array| node_array.get(partition.partition_id))
+                    .filter(|node| node.is_active())
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     pub fn get_random_node(&self) -> Result<Arc<Node>> {
         let node_array = self.nodes();
         let length = node_array.len() as isize;
@@ -527,6 +803,21 @@ impl Cluster {
         bail!("No active node")
     }
 
+    // The principal node of the cluster is the active node with the highest node id. Node names
+    // are the node ids in hex.
+    pub fn get_principal_node(&self) -> Result<Arc<Node>> {
+        let node_id = |node: &Arc<Node>| u64::from_str_radix(node.name(), 16).unwrap_or(0);
+        match self
+            .nodes()
+            .into_iter()
+            .filter(|node| node.is_active())
+            .max_by_key(node_id)
+        {
+            Some(node) => Ok(node),
+            None => bail!("No active node"),
+        }
+    }
+
     pub fn get_node_by_name(&self, node_name: &str) -> Result<Arc<Node>> {
         let node_array = self.nodes();
 
@@ -539,14 +830,70 @@ impl Cluster {
         bail!("Requested node `{}` not found.", node_name)
     }
 
+    // Registers a command in flight until the returned guard, and all of its clones, are dropped.
+    // Fails once the client is closing or closed.
+    pub fn start_command(&self) -> Result<CommandGuard> {
+        // count the command before checking for closing, so that `drain_commands` either waits
+        // for it or the command is refused
+        let guard = CommandGuard::new(self.commands.clone());
+        if self.closing.load(Ordering::SeqCst) || self.closed.load(Ordering::Relaxed) {
+            bail!(ErrorKind::Connection("Client is closed".to_string()));
+        }
+        Ok(guard)
+    }
+
+    // Refuses new commands and waits until the commands in flight finished or the timeout
+    // expired.
+    pub async fn drain_commands(&self, timeout: Duration) -> Result<()> {
+        self.closing.store(true, Ordering::SeqCst);
+        let deadline = Instant::now() + timeout;
+        loop {
+            let commands = self.commands.load(Ordering::SeqCst);
+            if commands == 0 {
+                return Ok(());
+            }
+            if Instant::now() >= deadline {
+                bail!(ErrorKind::Timeout(format!(
+                    "{} commands still in flight after closing timeout",
+                    commands
+                )));
+            }
+            self.runtime.sleep(Duration::from_millis(10)).await;
+        }
+    }
+
     pub fn close(&self) -> Result<()> {
         if !self.closed.load(Ordering::Relaxed) {
             // close tend by closing the channel
-            let tx = self.tend_channel.lock();
-            drop(tx);
+            drop(self.tend_channel.lock().take());
             self.closed.store(true, Ordering::Relaxed);
         }
 
         Ok(())
     }
 }
+
+// Tracks a command in flight; cloned into each task of a command that runs on multiple nodes.
+#[derive(Debug)]
+pub struct CommandGuard {
+    commands: Arc<AtomicUsize>,
+}
+
+impl CommandGuard {
+    fn new(commands: Arc<AtomicUsize>) -> Self {
+        commands.fetch_add(1, Ordering::SeqCst);
+        CommandGuard { commands }
+    }
+}
+
+impl Clone for CommandGuard {
+    fn clone(&self) -> Self {
+        CommandGuard::new(self.commands.clone())
+    }
+}
+
+impl Drop for CommandGuard {
+    fn drop(&mut self) {
+        self.commands.fetch_sub(1, Ordering::SeqCst);
+    }
+}
@@ -13,7 +13,7 @@
 // License for the specific language governing permissions and limitations under
 // the License.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::hash::{Hash, Hasher};
 use std::result::Result as StdResult;
@@ -24,14 +24,18 @@ use std::time::Duration;
 
 use parking_lot::RwLock;
 
-use crate::cluster::node_validator::NodeValidator;
+use crate::cluster::node_validator::{self, NodeValidator};
+use crate::cluster::peers;
+use crate::cluster::{Feature, NodeStats};
 use crate::commands::Message;
-use crate::errors::{ErrorKind, Result, ResultExt};
+use crate::errors::{Error, ErrorKind, Result, ResultExt};
 use crate::net::{ConnectionPool, Host, PooledConnection};
 use crate::policy::ClientPolicy;
+use crate::runtime::{self, Runtime};
 
 pub const PARTITIONS: usize = 4096;
 
+/// A server node of the cluster, as seen by the client.
 #[derive(Debug)]
 pub struct Node {
     client_policy: ClientPolicy,
@@ -41,20 +45,42 @@ pub struct Node {
     address: String,
 
     connection_pool: ConnectionPool,
+    // Non-blocking connections for the commands of async clients. Info commands, e.g. to tend
+    // the cluster, always use the blocking connections of the node.
+    async_pool: Option<ConnectionPool>,
+    runtime: Runtime,
     failures: AtomicUsize,
+    // Errors of commands within the current error rate window.
+    error_count: AtomicUsize,
 
     partition_generation: AtomicIsize,
+    rebalance_generation: AtomicIsize,
+    // Rack of the node for each namespace, if the client is rack aware.
+    racks: RwLock<HashMap<String, usize>>,
     refresh_count: AtomicUsize,
     reference_count: AtomicUsize,
     responded: AtomicBool,
     active: AtomicBool,
-
-    supports_float: AtomicBool,
-    supports_geo: AtomicBool,
+    features: HashSet<Feature>,
 }
 
 impl Node {
-    pub fn new(client_policy: ClientPolicy, nv: Arc<NodeValidator>) -> Self {
+    pub(crate) fn new(
+        client_policy: ClientPolicy,
+        user_password: Arc<RwLock<Option<(String, String)>>>,
+        nv: Arc<NodeValidator>,
+        runtime: Runtime,
+    ) -> Self {
+        let async_pool = if runtime == Runtime::Blocking {
+            None
+        } else {
+            Some(ConnectionPool::new(
+                nv.aliases[0].clone(),
+                client_policy.clone(),
+                Arc::clone(&user_password),
+                runtime,
+            ))
+        };
         Node {
             client_policy: client_policy.clone(),
             name: nv.name.clone(),
@@ -62,58 +88,91 @@ impl Node {
             address: nv.address.to_owned(),
 
             host: nv.aliases[0].clone(),
-            connection_pool: ConnectionPool::new(nv.aliases[0].clone(), client_policy),
+            connection_pool: ConnectionPool::new(
+                nv.aliases[0].clone(),
+                client_policy,
+                user_password,
+                Runtime::Blocking,
+            ),
+            async_pool,
+            runtime,
             failures: AtomicUsize::new(0),
+            error_count: AtomicUsize::new(0),
             partition_generation: AtomicIsize::new(-1),
+            rebalance_generation: AtomicIsize::new(-1),
+            racks: RwLock::new(HashMap::new()),
             refresh_count: AtomicUsize::new(0),
             reference_count: AtomicUsize::new(0),
             responded: AtomicBool::new(false),
             active: AtomicBool::new(true),
-
-            supports_float: AtomicBool::new(nv.supports_float),
-            supports_geo: AtomicBool::new(nv.supports_geo),
+            features: nv.features.clone(),
         }
     }
 
+    /// Returns the address of the node.
     pub fn address(&self) -> &str {
         &self.address
     }
 
+    /// Returns the name of the node.
     pub fn name(&self) -> &str {
         &self.name
     }
 
-    pub const fn client_policy(&self) -> &ClientPolicy {
-        &self.client_policy
-    }
-
+    /// Returns the host the client connects to.
     pub fn host(&self) -> Host {
         self.host.clone()
     }
 
+    pub(crate) const fn runtime(&self) -> Runtime {
+        self.runtime
+    }
+
+    /// Returns whether the node advertised the feature when it joined the cluster.
+    pub fn supports(&self, feature: Feature) -> bool {
+        self.features.contains(&feature)
+    }
+
+    /// Returns whether the node supports float values.
     pub fn supports_float(&self) -> bool {
-        self.supports_float.load(Ordering::Relaxed)
+        self.supports(Feature::Float)
     }
 
+    /// Returns whether the node supports geospatial values.
     pub fn supports_geo(&self) -> bool {
-        self.supports_geo.load(Ordering::Relaxed)
+        self.supports(Feature::Geo)
+    }
+
+    // Info command for the partitions of all replicas on the node, falling back to the master
+    // partitions only on servers that do not support it.
+    pub(crate) fn replicas_name(&self) -> &'static str {
+        if self.supports(Feature::Replicas) {
+            "replicas"
+        } else if self.supports(Feature::ReplicasAll) {
+            "replicas-all"
+        } else {
+            "replicas-master"
+        }
     }
 
-    pub fn reference_count(&self) -> usize {
+    pub(crate) fn reference_count(&self) -> usize {
         self.reference_count.load(Ordering::Relaxed)
     }
 
-    pub fn refresh(&self, current_aliases: HashMap<Host, Arc<Node>>) -> Result<Vec<Host>> {
+    pub(crate) fn refresh(&self, current_aliases: HashMap<Host, Arc<Node>>) -> Result<Vec<Host>> {
         self.reference_count.store(0, Ordering::Relaxed);
         self.responded.store(false, Ordering::Relaxed);
         self.refresh_count.fetch_add(1, Ordering::Relaxed);
 
-        let commands = vec![
+        let mut commands = vec![
             "node",
             "cluster-name",
             "partition-generation",
             self.services_name(),
         ];
+        if self.client_policy.rack_aware {
+            commands.push("rebalance-generation");
+        }
         let info_map = self
             .info(None, &commands)
             .chain_err(|| "Info command failed")?;
@@ -125,13 +184,34 @@ impl Node {
             .chain_err(|| "Failed to add friends")?;
         self.update_partitions(&info_map)
             .chain_err(|| "Failed to update partitions")?;
+        if self.client_policy.rack_aware {
+            self.update_racks(&info_map)
+                .chain_err(|| "Failed to update racks")?;
+        }
         self.reset_failures();
 
         Ok(friends)
     }
 
-    const fn services_name(&self) -> &'static str {
-        if self.client_policy.use_services_alternate {
+    // The services list only contains the clear-text ports of the peers; with TLS, the peers are
+    // listed with their TLS ports and names by the peers command instead. Servers that support
+    // the peers commands list the clear-text peers with them too, including the alternate access
+    // addresses of peers that only configured those.
+    fn services_name(&self) -> &'static str {
+        let alternate = self.client_policy.use_services_alternate;
+        if self.client_policy.tls_policy.is_some() {
+            if alternate {
+                "peers-tls-alt"
+            } else {
+                "peers-tls-std"
+            }
+        } else if self.supports(Feature::Peers) {
+            if alternate {
+                "peers-clear-alt"
+            } else {
+                "peers-clear-std"
+            }
+        } else if alternate {
             "services-alternate"
         } else {
             "services"
@@ -160,22 +240,14 @@ impl Node {
     }
 
     fn verify_cluster_name(&self, info_map: &HashMap<String, String>) -> Result<()> {
-        match self.client_policy.cluster_name {
-            None => Ok(()),
-            Some(ref expected) => match info_map.get("cluster-name") {
-                None => Err(ErrorKind::InvalidNode("Missing cluster name".to_string()).into()),
-                Some(info_name) if info_name == expected => Ok(()),
-                Some(info_name) => {
-                    self.inactivate();
-                    Err(ErrorKind::InvalidNode(format!(
-                        "Cluster name mismatch: expected={},
-                                                           got={}",
-                        expected, info_name
-                    ))
-                    .into())
-                }
-            },
+        if let Some(ref expected) = self.client_policy.cluster_name {
+            if let Err(err) = node_validator::verify_cluster_name(expected, info_map) {
+                // Refuse a node that moved to another cluster, so that it gets removed.
+                self.inactivate();
+                return Err(err);
+            }
         }
+        Ok(())
     }
 
     fn add_friends(
@@ -191,25 +263,16 @@ impl Node {
             Some(friend_string) => friend_string,
         };
 
-        let friend_names = friend_string.split(';');
-        for friend in friend_names {
-            let mut friend_info = friend.split(':');
-            if friend_info.clone().count() != 2 {
-                error!(
-                    "Node info from asinfo:services is malformed. Expected HOST:PORT, but got \
-                     '{}'",
-                    friend
-                );
-                continue;
-            }
+        let hosts = if self.services_name().starts_with("peers") {
+            peers::parse_peers(friend_string)?
+        } else {
+            Node::parse_services(friend_string)?
+        };
 
-            let host = friend_info.next().unwrap();
-            let port = u16::from_str(friend_info.next().unwrap())?;
+        for host in hosts {
             let alias = match self.client_policy.ip_map {
-                Some(ref ip_map) if ip_map.contains_key(host) => {
-                    Host::new(ip_map.get(host).unwrap(), port)
-                }
-                _ => Host::new(host, port),
+                Some(ref ip_map) => Node::translate_host(ip_map, host),
+                None => host,
             };
 
             if current_aliases.contains_key(&alias) {
@@ -222,6 +285,49 @@ impl Node {
         Ok(friends)
     }
 
+    // Translates the address of a peer with the IP map of the client policy. An entry for the
+    // address and port of the peer takes precedence over an entry for its address only, and
+    // replaces the port as well.
+    fn translate_host(ip_map: &HashMap<String, String>, host: Host) -> Host {
+        let translated = ip_map
+            .get(&host.address())
+            .and_then(|address| Host::parse_address(address));
+        if let Some(translated) = translated {
+            return Host {
+                tls_name: host.tls_name,
+                ..translated
+            };
+        }
+        match ip_map.get(&host.name) {
+            Some(name) => Host {
+                tls_name: host.tls_name,
+                ..Host::new(name, host.port)
+            },
+            None => host,
+        }
+    }
+
+    fn parse_services(services: &str) -> Result<Vec<Host>> {
+        let mut hosts = vec![];
+        for friend in services.split(';') {
+            // IPv6 addresses are enclosed in brackets
+            let (host, port) = match friend.rsplit_once(':') {
+                Some((host, port)) if !host.is_empty() => (host, port),
+                _ => {
+                    error!(
+                        "Node info from asinfo:services is malformed. Expected HOST:PORT, but \
+                         got '{}'",
+                        friend
+                    );
+                    continue;
+                }
+            };
+            let port = u16::from_str(port)?;
+            hosts.push(Host::new(host, port));
+        }
+        Ok(hosts)
+    }
+
     fn update_partitions(&self, info_map: &HashMap<String, String>) -> Result<()> {
         match info_map.get("partition-generation") {
             None => bail!(ErrorKind::BadResponse(
@@ -236,10 +342,80 @@ impl Node {
         Ok(())
     }
 
-    pub fn get_connection(&self, timeout: Option<Duration>) -> Result<PooledConnection> {
-        self.connection_pool.get(timeout)
+    // The racks of the node can only change when the partitions are rebalanced.
+    fn update_racks(&self, info_map: &HashMap<String, String>) -> Result<()> {
+        let gen = match info_map.get("rebalance-generation") {
+            None => bail!(ErrorKind::BadResponse(
+                "Missing rebalance generation".to_string()
+            )),
+            Some(gen_string) => gen_string.parse::<isize>()?,
+        };
+        if gen == self.rebalance_generation.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        let info_map = self.info(None, &["rack-ids"])?;
+        match info_map.get("rack-ids") {
+            None => bail!(ErrorKind::BadResponse("Missing rack ids".to_string())),
+            Some(racks) => *self.racks.write() = Node::parse_racks(racks)?,
+        }
+        self.rebalance_generation.store(gen, Ordering::Relaxed);
+        Ok(())
+    }
+
+    // <ns>:<rack-id>;<ns>:<rack-id>;...
+    fn parse_racks(racks: &str) -> Result<HashMap<String, usize>> {
+        let mut map = HashMap::new();
+        for rack in racks.split(';').filter(|rack| !rack.is_empty()) {
+            match rack.split_once(':') {
+                Some((namespace, rack_id)) => {
+                    map.insert(namespace.to_string(), rack_id.parse::<usize>()?);
+                }
+                None => bail!(ErrorKind::BadResponse(format!(
+                    "Invalid rack ids: {}",
+                    racks
+                ))),
+            }
+        }
+        Ok(map)
     }
 
+    /// Returns the rack of the node for the namespace, if the client is rack aware.
+    pub fn rack_id(&self, namespace: &str) -> Option<usize> {
+        self.racks.read().get(namespace).copied()
+    }
+
+    pub(crate) async fn get_connection(
+        &self,
+        timeout: Option<Duration>,
+    ) -> Result<PooledConnection> {
+        let max_error_rate = self.client_policy.max_error_rate;
+        if max_error_rate > 0 && self.error_count.load(Ordering::Relaxed) > max_error_rate {
+            bail!(ErrorKind::MaxErrorRate);
+        }
+        let conn = self
+            .async_pool
+            .as_ref()
+            .unwrap_or(&self.connection_pool)
+            .get(timeout)
+            .await;
+        match conn {
+            Err(Error(ErrorKind::NoMoreConnections, _)) | Ok(_) => (),
+            Err(_) => self.add_error(),
+        }
+        conn
+    }
+
+    // Counts a failed command towards the error rate of the node.
+    pub(crate) fn add_error(&self) {
+        self.error_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn reset_error_count(&self) {
+        self.error_count.store(0, Ordering::Relaxed);
+    }
+
+    /// Returns the number of consecutive failed refreshes of the node.
     pub fn failures(&self) -> usize {
         self.failures.load(Ordering::Relaxed)
     }
@@ -248,7 +424,7 @@ impl Node {
         self.failures.store(0, Ordering::Relaxed)
     }
 
-    pub fn increase_failures(&self) -> usize {
+    pub(crate) fn increase_failures(&self) -> usize {
         self.failures.fetch_add(1, Ordering::Relaxed)
     }
 
@@ -256,37 +432,94 @@ impl Node {
         self.active.store(false, Ordering::Relaxed);
     }
 
+    /// Returns whether the node is still part of the client's view of the cluster.
     pub fn is_active(&self) -> bool {
         self.active.load(Ordering::Relaxed)
     }
 
+    /// Returns all hosts the node is known by.
     pub fn aliases(&self) -> Vec<Host> {
         self.aliases.read().to_vec()
     }
 
-    pub fn add_alias(&self, alias: Host) {
+    pub(crate) fn add_alias(&self, alias: Host) {
         let mut aliases = self.aliases.write();
         aliases.push(alias);
         self.reference_count.fetch_add(1, Ordering::Relaxed);
     }
 
-    pub fn close(&mut self) {
+    // Closes connections for commands that have been idle for too long and opens the minimum
+    // number of connections of the client policy.
+    pub(crate) async fn balance_connections(&self) -> Result<()> {
+        if !self.is_active() {
+            return Ok(());
+        }
+        self.async_pool
+            .as_ref()
+            .unwrap_or(&self.connection_pool)
+            .balance()
+            .await
+    }
+
+    pub(crate) fn close(&self) {
         self.inactivate();
         self.connection_pool.close();
+        if let Some(ref async_pool) = self.async_pool {
+            async_pool.close();
+        }
     }
 
+    /// Send info commands to this node and return the responses, keyed by command. See the
+    /// [`info`](crate::info) module for helpers to parse the responses.
     pub fn info(
         &self,
         timeout: Option<Duration>,
         commands: &[&str],
     ) -> Result<HashMap<String, String>> {
-        let mut conn = self.get_connection(timeout)?;
-        Message::info(&mut conn, commands).map_err(|e| {
-            conn.invalidate();
-            e
-        })
+        runtime::block_on(Node::pool_info(&self.connection_pool, timeout, commands))
+    }
+
+    /// Send info commands to this node without blocking the calling task, over a connection of
+    /// the runtime of the client.
+    pub async fn info_async(
+        &self,
+        timeout: Option<Duration>,
+        commands: &[&str],
+    ) -> Result<HashMap<String, String>> {
+        let pool = self.async_pool.as_ref().unwrap_or(&self.connection_pool);
+        Node::pool_info(pool, timeout, commands).await
+    }
+
+    async fn pool_info(
+        pool: &ConnectionPool,
+        timeout: Option<Duration>,
+        commands: &[&str],
+    ) -> Result<HashMap<String, String>> {
+        let mut conn = pool.get(timeout).await?;
+        match Message::info(&mut conn, commands).await {
+            Ok(response) => {
+                conn.mark_reusable();
+                Ok(response)
+            }
+            Err(err) => {
+                conn.invalidate();
+                Err(err)
+            }
+        }
     }
 
+    /// Query the statistics of this node with the `statistics` info command.
+    pub fn stats(&self, timeout: Option<Duration>) -> Result<NodeStats> {
+        let response = self.info(timeout, &["statistics"])?;
+        match response.get("statistics") {
+            Some(statistics) => Ok(NodeStats::from(statistics.as_str())),
+            None => bail!(ErrorKind::BadResponse(
+                "Missing statistics in info response".to_string()
+            )),
+        }
+    }
+
+    /// Returns the partition generation of the node, which changes when its partitions change.
     pub fn partition_generation(&self) -> isize {
         self.partition_generation.load(Ordering::Relaxed)
     }
@@ -311,3 +544,104 @@ impl fmt::Display for Node {
         format!("{}: {}", self.name, self.host).fmt(f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use parking_lot::RwLock;
+
+    use super::Node;
+    use crate::cluster::node_validator::NodeValidator;
+    use crate::errors::{Error, ErrorKind};
+    use crate::net::Host;
+    use crate::policy::ClientPolicy;
+    use crate::runtime::{self, Runtime};
+
+    #[test]
+    fn max_error_rate() {
+        let mut client_policy = ClientPolicy::default();
+        client_policy.max_error_rate = 2;
+        client_policy.timeout = Some(Duration::from_secs(1));
+        // nothing listens on the port, so that each connection attempt fails
+        let nv = NodeValidator {
+            name: "BB9020011AC4202".to_string(),
+            aliases: vec![Host::new("127.0.0.1", 1)],
+            address: "127.0.0.1:1".to_string(),
+            client_policy: client_policy.clone(),
+            features: HashSet::new(),
+        };
+        let node = Node::new(
+            client_policy,
+            Arc::new(RwLock::new(None)),
+            Arc::new(nv),
+            Runtime::Blocking,
+        );
+        let error_kind = |node: &Node| match runtime::block_on(node.get_connection(None)) {
+            Err(Error(kind, _)) => kind,
+            Ok(_) => panic!("connected to a closed port"),
+        };
+
+        for _ in 0..3 {
+            assert!(!matches!(error_kind(&node), ErrorKind::MaxErrorRate));
+        }
+        assert!(matches!(error_kind(&node), ErrorKind::MaxErrorRate));
+
+        node.reset_error_count();
+        assert!(!matches!(error_kind(&node), ErrorKind::MaxErrorRate));
+    }
+
+    #[test]
+    fn translate_host() {
+        let mut ip_map = HashMap::new();
+        ip_map.insert("172.17.0.2".to_string(), "203.0.113.2".to_string());
+        ip_map.insert("172.17.0.3:3000".to_string(), "127.0.0.1:32768".to_string());
+
+        let host = Node::translate_host(&ip_map, Host::new("172.17.0.2", 3000));
+        assert_eq!(host.address(), "203.0.113.2:3000");
+
+        let host = Node::translate_host(&ip_map, Host::new("172.17.0.3", 3000));
+        assert_eq!(host.address(), "127.0.0.1:32768");
+
+        let host = Node::translate_host(&ip_map, Host::with_tls_name("172.17.0.2", "tls", 4333));
+        assert_eq!(host.address(), "203.0.113.2:4333");
+        assert_eq!(host.tls_name, Some("tls".to_string()));
+
+        ip_map.insert(
+            "[fd00::3]:3000".to_string(),
+            "[2001:db8::3]:3100".to_string(),
+        );
+        let host = Node::translate_host(&ip_map, Host::new("fd00::3", 3000));
+        assert_eq!(host.address(), "[2001:db8::3]:3100");
+
+        let host = Node::translate_host(&ip_map, Host::new("172.17.0.4", 3000));
+        assert_eq!(host.address(), "172.17.0.4:3000");
+    }
+
+    #[test]
+    fn parse_services() {
+        let hosts = Node::parse_services("172.17.0.3:3000;[2001:db8::3]:3100").unwrap();
+        assert_eq!(
+            hosts,
+            vec![
+                Host::new("172.17.0.3", 3000),
+                Host::new("2001:db8::3", 3100)
+            ]
+        );
+        assert!(Node::parse_services("172.17.0.3").unwrap().is_empty());
+        assert!(Node::parse_services("172.17.0.3:x").is_err());
+    }
+
+    #[test]
+    fn parse_racks() {
+        let racks = Node::parse_racks("test:1;bar:2;").unwrap();
+        assert_eq!(racks.len(), 2);
+        assert_eq!(racks["test"], 1);
+        assert_eq!(racks["bar"], 2);
+        assert!(Node::parse_racks("").unwrap().is_empty());
+        assert!(Node::parse_racks("test").is_err());
+        assert!(Node::parse_racks("test:x").is_err());
+    }
+}
@@ -0,0 +1,138 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use std::collections::HashMap;
+
+use crate::info;
+
+/// Statistics of a server node, parsed from the response of the `statistics` info command.
+///
+/// The statistics reported by the server depend on the server version; statistics that are not
+/// reported by the node are `None`. All statistics are available by name in `raw`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NodeStats {
+    /// Number of nodes in the cluster, as seen by this node.
+    pub cluster_size: Option<u64>,
+
+    /// Time in seconds since the server was started.
+    pub uptime: Option<u64>,
+
+    /// Number of open client connections.
+    pub client_connections: Option<u64>,
+
+    /// Number of records stored on the node.
+    pub objects: Option<u64>,
+
+    /// Number of tombstones stored on the node.
+    pub tombstones: Option<u64>,
+
+    /// Number of partitions still to be migrated from this node.
+    pub migrate_partitions_remaining: Option<u64>,
+
+    /// Number of partitions still to be migrated to this node.
+    pub migrate_incoming_remaining: Option<u64>,
+
+    /// Memory used for data, in bytes.
+    pub used_bytes_memory: Option<u64>,
+
+    /// Memory configured for data, in bytes.
+    pub total_bytes_memory: Option<u64>,
+
+    /// Storage device space used for data, in bytes.
+    pub used_bytes_disk: Option<u64>,
+
+    /// Storage device space configured for data, in bytes.
+    pub total_bytes_disk: Option<u64>,
+
+    /// Percentage of free system memory.
+    pub system_free_mem_pct: Option<u64>,
+
+    /// Percentage of total CPU usage of the system.
+    pub system_total_cpu_pct: Option<u64>,
+
+    /// All statistics reported by the node, by name.
+    pub raw: HashMap<String, String>,
+}
+
+impl NodeStats {
+    fn get(&self, names: &[&str]) -> Option<u64> {
+        names
+            .iter()
+            .find_map(|name| self.raw.get(*name))
+            .and_then(|value| value.parse().ok())
+    }
+}
+
+impl<'a> From<&'a str> for NodeStats {
+    /// Parse the `k=v;k=v` response of the `statistics` info command. Statistics that were
+    /// renamed by the server keep being recognized under their older names.
+    fn from(response: &str) -> Self {
+        let mut stats = NodeStats {
+            raw: info::parse_map(response),
+            ..NodeStats::default()
+        };
+        stats.cluster_size = stats.get(&["cluster_size"]);
+        stats.uptime = stats.get(&["uptime"]);
+        stats.client_connections = stats.get(&["client_connections"]);
+        stats.objects = stats.get(&["objects"]);
+        stats.tombstones = stats.get(&["tombstones"]);
+        stats.migrate_partitions_remaining = stats.get(&[
+            "migrate_partitions_remaining",
+            "migrate_tx_partitions_remaining",
+        ]);
+        stats.migrate_incoming_remaining = stats.get(&[
+            "migrate_incoming_remaining",
+            "migrate_rx_partitions_remaining",
+        ]);
+        stats.used_bytes_memory = stats.get(&["used-bytes-memory", "memory_used_bytes"]);
+        stats.total_bytes_memory = stats.get(&["total-bytes-memory"]);
+        stats.used_bytes_disk = stats.get(&["used-bytes-disk", "device_used_bytes"]);
+        stats.total_bytes_disk = stats.get(&["total-bytes-disk", "device_total_bytes"]);
+        stats.system_free_mem_pct = stats.get(&["system_free_mem_pct"]);
+        stats.system_total_cpu_pct = stats.get(&["system_total_cpu_pct"]);
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NodeStats;
+
+    #[test]
+    fn parse_statistics() {
+        let stats = NodeStats::from(
+            "cluster_size=3;uptime=120;client_connections=7;objects=1000;\
+             migrate_partitions_remaining=0;device_used_bytes=4096;system_free_mem_pct=90;\
+             heap_efficiency_pct=100",
+        );
+        assert_eq!(stats.cluster_size, Some(3));
+        assert_eq!(stats.uptime, Some(120));
+        assert_eq!(stats.client_connections, Some(7));
+        assert_eq!(stats.objects, Some(1000));
+        assert_eq!(stats.tombstones, None);
+        assert_eq!(stats.migrate_partitions_remaining, Some(0));
+        assert_eq!(stats.used_bytes_disk, Some(4096));
+        assert_eq!(stats.system_free_mem_pct, Some(90));
+        assert_eq!(stats.raw["heap_efficiency_pct"], "100");
+    }
+
+    #[test]
+    fn parse_invalid_statistics() {
+        let stats = NodeStats::from("cluster_size=unknown;uptime");
+        assert_eq!(stats.cluster_size, None);
+        assert_eq!(stats.uptime, None);
+        assert_eq!(stats.raw.len(), 1);
+    }
+}
@@ -12,42 +12,41 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::{HashMap, HashSet};
 use std::net::ToSocketAddrs;
 use std::str;
 use std::vec::Vec;
 
-use crate::cluster::Cluster;
+use parking_lot::RwLock;
+
+use crate::cluster::{Cluster, Feature};
 use crate::commands::Message;
 use crate::errors::{ErrorKind, Result, ResultExt};
 use crate::net::{Connection, Host};
 use crate::policy::ClientPolicy;
+use crate::runtime::{self, Runtime};
 
 // Validates a Database server node
-#[allow(clippy::struct_excessive_bools)]
 #[derive(Clone)]
 pub struct NodeValidator {
     pub name: String,
     pub aliases: Vec<Host>,
     pub address: String,
     pub client_policy: ClientPolicy,
-    pub supports_float: bool,
-    pub supports_batch_index: bool,
-    pub supports_replicas_all: bool,
-    pub supports_geo: bool,
+    pub features: HashSet<Feature>,
 }
 
 // Generates a node validator
 impl NodeValidator {
     pub fn new(cluster: &Cluster) -> Self {
+        let mut client_policy = cluster.client_policy().clone();
+        client_policy.user_password = cluster.user_password();
         NodeValidator {
             name: "".to_string(),
             aliases: vec![],
             address: "".to_string(),
-            client_policy: cluster.client_policy().clone(),
-            supports_float: false,
-            supports_batch_index: false,
-            supports_replicas_all: false,
-            supports_geo: false,
+            client_policy,
+            features: HashSet::new(),
         }
     }
 
@@ -76,9 +75,18 @@ impl NodeValidator {
     }
 
     fn resolve_aliases(&mut self, host: &Host) -> Result<()> {
-        self.aliases = (host.name.as_ref(), host.port)
-            .to_socket_addrs()?
-            .map(|addr| Host::new(&addr.ip().to_string(), addr.port()))
+        let addrs = match self.client_policy.resolver {
+            Some(ref resolver) => resolver.resolve(host)?,
+            None => (host.name.as_ref(), host.port).to_socket_addrs()?.collect(),
+        };
+        self.aliases = addrs
+            .into_iter()
+            .map(|addr| Host {
+                name: addr.ip().to_string(),
+                port: addr.port(),
+                // verify the certificate against the name of the host, not its address
+                tls_name: Some(host.tls_name.as_ref().unwrap_or(&host.name).clone()),
+            })
             .collect();
         debug!("Resolved aliases for host {}: {:?}", host, self.aliases);
         if self.aliases.is_empty() {
@@ -89,9 +97,18 @@ impl NodeValidator {
     }
 
     fn validate_alias(&mut self, cluster: &Cluster, alias: &Host) -> Result<()> {
-        let mut conn = Connection::new(&alias, &self.client_policy)?;
-        conn.set_timeout(self.client_policy.timeout)?;
-        let info_map = Message::info(&mut conn, &["node", "cluster-name", "features"])?;
+        let info_map = runtime::block_on(async {
+            let mut conn = Connection::new(
+                alias,
+                &self.client_policy,
+                &self.client_policy.user_password,
+                &RwLock::new(None),
+                Runtime::Blocking,
+            )
+            .await?;
+            conn.set_timeout(self.client_policy.timeout)?;
+            Message::info(&mut conn, &["node", "cluster-name", "features"]).await
+        })?;
 
         match info_map.get("node") {
             None => bail!(ErrorKind::InvalidNode(String::from("Missing node name"))),
@@ -99,15 +116,7 @@ impl NodeValidator {
         }
 
         if let Some(ref cluster_name) = *cluster.cluster_name() {
-            match info_map.get("cluster-name") {
-                None => bail!(ErrorKind::InvalidNode(String::from("Missing cluster name"))),
-                Some(info_name) if info_name == cluster_name => {}
-                Some(info_name) => bail!(ErrorKind::InvalidNode(format!(
-                    "Cluster name mismatch: expected={},
-                                                         got={}",
-                    cluster_name, info_name
-                ))),
-            }
+            verify_cluster_name(cluster_name, &info_map)?;
         }
 
         self.address = alias.address();
@@ -120,15 +129,41 @@ impl NodeValidator {
     }
 
     fn set_features(&mut self, features: &str) {
-        let features = features.split(';');
-        for feature in features {
-            match feature {
-                "float" => self.supports_float = true,
-                "batch-index" => self.supports_batch_index = true,
-                "replicas-all" => self.supports_replicas_all = true,
-                "geo" => self.supports_geo = true,
-                _ => (),
-            }
-        }
+        self.features = features.split(';').filter_map(Feature::parse).collect();
+    }
+}
+
+// Checks the cluster name reported by a node against the expected cluster name of the client.
+pub fn verify_cluster_name(expected: &str, info_map: &HashMap<String, String>) -> Result<()> {
+    match info_map.get("cluster-name") {
+        None => bail!(ErrorKind::InvalidNode(String::from("Missing cluster name"))),
+        Some(info_name) if info_name == expected => Ok(()),
+        Some(info_name) => bail!(ErrorKind::InvalidNode(format!(
+            "Cluster name mismatch: expected={}, got={}",
+            expected, info_name
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::verify_cluster_name;
+
+    #[test]
+    fn cluster_name() {
+        let mut info_map = HashMap::new();
+        assert!(verify_cluster_name("prod", &info_map).is_err());
+
+        info_map.insert("cluster-name".to_string(), "staging".to_string());
+        let err = verify_cluster_name("prod", &info_map).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid cluster node: Cluster name mismatch: expected=prod, got=staging"
+        );
+
+        info_map.insert("cluster-name".to_string(), "prod".to_string());
+        assert!(verify_cluster_name("prod", &info_map).is_ok());
     }
 }
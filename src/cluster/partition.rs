@@ -13,11 +13,7 @@
 // limitations under the License.
 
 use std::fmt;
-use std::io::Cursor;
 
-use byteorder::{LittleEndian, ReadBytesExt};
-
-use crate::cluster::node;
 use crate::Key;
 
 // Validates a Database server node
@@ -36,15 +32,9 @@ impl<'a> Partition<'a> {
     }
 
     pub fn new_by_key(key: &'a Key) -> Self {
-        let mut rdr = Cursor::new(&key.digest[0..4]);
-
         Partition {
             namespace: &key.namespace,
-
-            // CAN'T USE MOD directly - mod will give negative numbers.
-            // First AND makes positive and negative correctly, then mod.
-            // For any x, y : x % 2^y = x & (2^y - 1); the second method is twice as fast
-            partition_id: rdr.read_u32::<LittleEndian>().unwrap() as usize & (node::PARTITIONS - 1),
+            partition_id: key.partition_id(),
         }
     }
 }
@@ -60,3 +50,147 @@ impl<'a> fmt::Display for Partition<'a> {
         format!("Partition ({}: {})", self.namespace, self.partition_id).fmt(f)
     }
 }
+
+// Partitions of a scan or query that are sent to the same node.
+#[derive(Debug, Clone, Default)]
+pub struct NodePartitions {
+    // Partitions that are scanned from the start.
+    pub partitions: Vec<u16>,
+
+    // Digests of the last records returned from partitions that are resumed.
+    pub digests: Vec<[u8; 20]>,
+
+    // Maximum number of records to return from the node; zero means no limit.
+    pub max_records: u64,
+}
+
+impl NodePartitions {
+    pub const fn new(partitions: Vec<u16>) -> Self {
+        NodePartitions {
+            partitions,
+            digests: Vec::new(),
+            max_records: 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.partitions.is_empty() && self.digests.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.partitions.len() + self.digests.len()
+    }
+}
+
+// Divide the max. number of records evenly over the nodes. Nodes that would not return any
+// records are dropped. Zero means no limit.
+pub fn set_max_records<T>(node_partitions: &mut Vec<(T, NodePartitions)>, max_records: u64) {
+    if max_records == 0 || node_partitions.is_empty() {
+        return;
+    }
+    let count = node_partitions.len() as u64;
+    let per_node = max_records / count;
+    let remainder = max_records % count;
+    for (idx, (_, partitions)) in node_partitions.iter_mut().enumerate() {
+        partitions.max_records = per_node + u64::from((idx as u64) < remainder);
+    }
+    node_partitions.retain(|(_, partitions)| partitions.max_records > 0);
+}
+
+// Divide the max. number of records evenly over the partitions instead of the nodes, so that
+// every partition contributes about the same number of records to the sample. Partitions that
+// receive one of the remaining records are picked at random, so that small samples are not
+// biased towards the first partitions. Nodes that would not return any records are dropped.
+// Zero means no limit.
+pub fn set_sample_records<T>(node_partitions: &mut Vec<(T, NodePartitions)>, max_records: u64) {
+    let total: usize = node_partitions.iter().map(|(_, p)| p.len()).sum();
+    if max_records == 0 || total == 0 {
+        return;
+    }
+    let per_partition = max_records / total as u64;
+    let remainder = (max_records % total as u64) as usize;
+    let mut extra = rand::seq::index::sample(&mut rand::thread_rng(), total, remainder).into_vec();
+    extra.sort_unstable();
+
+    let mut extra = extra.into_iter().peekable();
+    let mut offset = 0;
+    for (_, partitions) in node_partitions.iter_mut() {
+        offset += partitions.len();
+        let mut node_extra = 0;
+        while extra.peek().map_or(false, |&idx| idx < offset) {
+            extra.next();
+            node_extra += 1;
+        }
+        partitions.max_records = per_partition * partitions.len() as u64 + node_extra;
+    }
+    node_partitions.retain(|(_, partitions)| partitions.max_records > 0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{set_max_records, set_sample_records, NodePartitions};
+
+    fn max_records(node_count: u16, max_records: u64) -> Vec<u64> {
+        let mut node_partitions = (0..node_count)
+            .map(|id| (id, NodePartitions::new(vec![id])))
+            .collect();
+        set_max_records(&mut node_partitions, max_records);
+        node_partitions
+            .iter()
+            .map(|(_, partitions)| partitions.max_records)
+            .collect()
+    }
+
+    #[test]
+    fn set_max_records_unlimited() {
+        assert_eq!(max_records(3, 0), vec![0, 0, 0]);
+    }
+
+    #[test]
+    fn set_max_records_distributes_remainder() {
+        assert_eq!(max_records(3, 10), vec![4, 3, 3]);
+        assert_eq!(max_records(2, 10), vec![5, 5]);
+    }
+
+    #[test]
+    fn set_max_records_drops_idle_nodes() {
+        assert_eq!(max_records(4, 2), vec![1, 1]);
+    }
+
+    fn sample_records(partition_counts: &[u16], max_records: u64) -> Vec<u64> {
+        let mut node_partitions = partition_counts
+            .iter()
+            .map(|&count| ((), NodePartitions::new((0..count).collect())))
+            .collect();
+        set_sample_records(&mut node_partitions, max_records);
+        node_partitions
+            .iter()
+            .map(|(_, partitions)| partitions.max_records)
+            .collect()
+    }
+
+    #[test]
+    fn set_sample_records_unlimited() {
+        assert_eq!(sample_records(&[2, 4], 0), vec![0, 0]);
+    }
+
+    #[test]
+    fn set_sample_records_by_partition_count() {
+        assert_eq!(sample_records(&[1, 3], 40), vec![10, 30]);
+        assert_eq!(sample_records(&[2, 6, 8], 32), vec![4, 12, 16]);
+    }
+
+    #[test]
+    fn set_sample_records_distributes_remainder() {
+        let sample = sample_records(&[4, 4, 4], 17);
+        assert_eq!(sample.iter().sum::<u64>(), 17);
+        assert!(sample.iter().all(|&max| (4..=8).contains(&max)));
+    }
+
+    #[test]
+    fn set_sample_records_drops_idle_nodes() {
+        let sample = sample_records(&[5, 5, 5, 5], 1);
+        assert_eq!(sample.len(), 1);
+        assert_eq!(sample[0], 1);
+    }
+}
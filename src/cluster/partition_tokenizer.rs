@@ -12,34 +12,33 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::hash_map::Entry::{Occupied, Vacant};
-use std::collections::HashMap;
 use std::str;
 use std::sync::Arc;
+use std::time::Duration;
 use std::vec::Vec;
 
 use parking_lot::RwLock;
 
 use crate::cluster::node;
-use crate::cluster::Node;
-use crate::commands::Message;
+use crate::cluster::{Node, PartitionMap};
 use crate::errors::{ErrorKind, Result};
-use crate::net::Connection;
-
-const REPLICAS_NAME: &str = "replicas-master";
 
 // Validates a Database server node
 #[derive(Debug, Clone)]
 pub struct PartitionTokenizer {
     buffer: Vec<u8>,
+    // Info command the partitions were requested with.
+    replicas_name: &'static str,
 }
 
 impl PartitionTokenizer {
-    pub fn new(conn: &mut Connection) -> Result<Self> {
-        let info_map = Message::info(conn, &[REPLICAS_NAME])?;
-        if let Some(buf) = info_map.get(REPLICAS_NAME) {
+    pub fn new(node: &Node, timeout: Option<Duration>) -> Result<Self> {
+        let replicas_name = node.replicas_name();
+        let info_map = node.info(timeout, &[replicas_name])?;
+        if let Some(buf) = info_map.get(replicas_name) {
             return Ok(PartitionTokenizer {
                 buffer: buf.as_bytes().to_owned(),
+                replicas_name,
             });
         }
         bail!(ErrorKind::BadResponse("Missing replicas info".to_string()));
@@ -47,38 +46,135 @@ impl PartitionTokenizer {
 
     pub fn update_partition(
         &self,
-        nmap: Arc<RwLock<HashMap<String, Vec<Arc<Node>>>>>,
+        nmap: Arc<RwLock<PartitionMap>>,
         node: Arc<Node>,
-    ) -> Result<HashMap<String, Vec<Arc<Node>>>> {
+    ) -> Result<PartitionMap> {
         let mut amap = nmap.read().clone();
 
-        // <ns>:<base64-encoded partition map>;<ns>:<base64-encoded partition map>; ...
-        let part_str = str::from_utf8(&self.buffer)?;
-        let mut parts = part_str.trim_end().split(|c| c == ':' || c == ';');
-        loop {
-            match (parts.next(), parts.next()) {
-                (Some(ns), Some(part)) => {
-                    let restore_buffer = base64::decode(part)?;
-                    match amap.entry(ns.to_string()) {
-                        Vacant(entry) => {
-                            entry.insert(vec![node.clone(); node::PARTITIONS]);
-                        }
-                        Occupied(mut entry) => {
-                            for (idx, item) in entry.get_mut().iter_mut().enumerate() {
-                                if restore_buffer[idx >> 3] & (0x80 >> (idx & 7) as u8) != 0 {
-                                    *item = node.clone();
-                                }
-                            }
-                        }
+        for (ns, bitmaps) in self.parse()? {
+            let replicas = amap.entry(ns.to_string()).or_default();
+            for (replica, bitmap) in bitmaps.iter().enumerate() {
+                let restore_buffer = base64::decode(bitmap)?;
+                if restore_buffer.len() < node::PARTITIONS / 8 {
+                    bail!(ErrorKind::BadResponse(
+                        "Error parsing partition info".to_string()
+                    ));
+                }
+                if replica == replicas.len() {
+                    replicas.push(vec![node.clone(); node::PARTITIONS]);
+                    continue;
+                }
+                for (idx, item) in replicas[replica].iter_mut().enumerate() {
+                    if restore_buffer[idx >> 3] & (0x80 >> (idx & 7) as u8) != 0 {
+                        *item = node.clone();
                     }
                 }
-                (None, None) => break,
-                _ => bail!(ErrorKind::BadResponse(
+            }
+        }
+
+        Ok(amap)
+    }
+
+    // Splits the response into the base64-encoded partition bitmaps of each replica, by namespace:
+    //
+    // replicas-master: <ns>:<master>;...
+    // replicas-all:    <ns>:<count>,<master>,<replica>...;...
+    // replicas:        <ns>:<regime>,<count>,<master>,<replica>...;...
+    fn parse(&self) -> Result<Vec<(&str, Vec<&str>)>> {
+        let part_str = str::from_utf8(&self.buffer)?;
+        let mut namespaces = vec![];
+        for part in part_str
+            .trim_end()
+            .split(';')
+            .filter(|part| !part.is_empty())
+        {
+            let (ns, bitmaps) = match part.split_once(':') {
+                Some(split) => split,
+                None => bail!(ErrorKind::BadResponse(
                     "Error parsing partition info".to_string()
                 )),
+            };
+            let mut bitmaps: Vec<&str> = bitmaps.split(',').collect();
+            let header = match self.replicas_name {
+                "replicas" => 2,
+                "replicas-all" => 1,
+                _ => 0,
+            };
+            if header > 0 {
+                let count = bitmaps
+                    .get(header - 1)
+                    .and_then(|count| count.parse::<usize>().ok());
+                match count {
+                    Some(count) if bitmaps.len() == header + count => {
+                        bitmaps.drain(..header);
+                    }
+                    _ => bail!(ErrorKind::BadResponse(
+                        "Error parsing partition info".to_string()
+                    )),
+                }
             }
+            namespaces.push((ns, bitmaps));
         }
+        Ok(namespaces)
+    }
+}
 
-        Ok(amap)
+#[cfg(test)]
+mod tests {
+    use super::PartitionTokenizer;
+
+    fn parse(replicas_name: &'static str, response: &str) -> Vec<(String, Vec<String>)> {
+        let tokenizer = PartitionTokenizer {
+            buffer: response.as_bytes().to_owned(),
+            replicas_name,
+        };
+        tokenizer
+            .parse()
+            .unwrap()
+            .into_iter()
+            .map(|(ns, bitmaps)| {
+                (
+                    ns.to_owned(),
+                    bitmaps.into_iter().map(str::to_owned).collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parse_replicas() {
+        assert_eq!(
+            parse("replicas-master", "test:AA==;bar:AQ==;\n"),
+            vec![
+                ("test".to_owned(), vec!["AA==".to_owned()]),
+                ("bar".to_owned(), vec!["AQ==".to_owned()]),
+            ]
+        );
+        assert_eq!(
+            parse("replicas-all", "test:2,AA==,AQ=="),
+            vec![(
+                "test".to_owned(),
+                vec!["AA==".to_owned(), "AQ==".to_owned()]
+            )]
+        );
+        assert_eq!(
+            parse("replicas", "test:3,2,AA==,AQ==;bar:0,1,AQ=="),
+            vec![
+                (
+                    "test".to_owned(),
+                    vec!["AA==".to_owned(), "AQ==".to_owned()]
+                ),
+                ("bar".to_owned(), vec!["AQ==".to_owned()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_invalid_replicas() {
+        let tokenizer = PartitionTokenizer {
+            buffer: b"test:3,2,AA==".to_vec(),
+            replicas_name: "replicas",
+        };
+        assert!(tokenizer.parse().is_err());
     }
 }
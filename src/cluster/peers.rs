@@ -0,0 +1,168 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use std::iter::Peekable;
+use std::str::Chars;
+
+use crate::errors::{ErrorKind, Result};
+use crate::net::Host;
+
+// Parses the response of the `peers-tls-std`, `peers-tls-alt`, `peers-clear-std` and
+// `peers-clear-alt` info commands into the hosts of the peers of a node. The response has the format
+// `<generation>,<default port>,[[<node name>,<TLS name>,[<address>[:<port>],...]],...]`, where
+// IPv6 addresses are enclosed in brackets.
+pub fn parse_peers(response: &str) -> Result<Vec<Host>> {
+    let mut parser = PeersParser {
+        chars: response.chars().peekable(),
+    };
+
+    let _generation = parser.read_token();
+    parser.expect(',')?;
+    let default_port = parser.read_token().parse::<u16>()?;
+    parser.expect(',')?;
+
+    let mut hosts = vec![];
+    parser.read_list(|parser| {
+        parser.expect('[')?;
+        let _node_name = parser.read_token();
+        parser.expect(',')?;
+        let tls_name = parser.read_token();
+        parser.expect(',')?;
+        parser.read_list(|parser| {
+            let (name, port) = parser.read_address(default_port)?;
+            hosts.push(if tls_name.is_empty() {
+                Host::new(&name, port)
+            } else {
+                Host::with_tls_name(&name, &tls_name, port)
+            });
+            Ok(())
+        })?;
+        parser.expect(']')
+    })?;
+
+    Ok(hosts)
+}
+
+struct PeersParser<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl<'a> PeersParser<'a> {
+    // Reads a bracketed, comma separated list, passing the parser to `read_item` for each item.
+    fn read_list<F>(&mut self, mut read_item: F) -> Result<()>
+    where
+        F: FnMut(&mut Self) -> Result<()>,
+    {
+        self.expect('[')?;
+        if self.chars.peek() == Some(&']') {
+            self.chars.next();
+            return Ok(());
+        }
+        loop {
+            read_item(self)?;
+            match self.chars.next() {
+                Some(',') => (),
+                Some(']') => return Ok(()),
+                _ => bail!(ErrorKind::BadResponse("Malformed peers list".to_string())),
+            }
+        }
+    }
+
+    fn read_address(&mut self, default_port: u16) -> Result<(String, u16)> {
+        let name = if self.chars.peek() == Some(&'[') {
+            self.chars.next();
+            self.chars.by_ref().take_while(|&c| c != ']').collect()
+        } else {
+            self.read_until(&[':', ',', ']'])
+        };
+
+        if self.chars.peek() == Some(&':') {
+            self.chars.next();
+            Ok((name, self.read_token().parse()?))
+        } else {
+            Ok((name, default_port))
+        }
+    }
+
+    fn read_token(&mut self) -> String {
+        self.read_until(&[',', '[', ']'])
+    }
+
+    fn read_until(&mut self, delimiters: &[char]) -> String {
+        let mut token = String::new();
+        while let Some(&c) = self.chars.peek() {
+            if delimiters.contains(&c) {
+                break;
+            }
+            token.push(c);
+            self.chars.next();
+        }
+        token
+    }
+
+    fn expect(&mut self, expected: char) -> Result<()> {
+        match self.chars.next() {
+            Some(c) if c == expected => Ok(()),
+            _ => bail!(ErrorKind::BadResponse(format!(
+                "Malformed peers list: expected '{}'",
+                expected
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_peers;
+
+    #[test]
+    fn parse_peers_list() {
+        let hosts = parse_peers(
+            "12,4333,[[BB9020011AC4202,cluster.tls,[172.17.0.2]],\
+             [BB9030011AC4202,,[172.17.0.3:4334,[2001:db8::3]]]]",
+        )
+        .unwrap();
+        assert_eq!(hosts.len(), 3);
+        assert_eq!(hosts[0].address(), "172.17.0.2:4333");
+        assert_eq!(hosts[0].tls_name, Some("cluster.tls".to_string()));
+        assert_eq!(hosts[1].address(), "172.17.0.3:4334");
+        assert_eq!(hosts[1].tls_name, None);
+        assert_eq!(hosts[2].name, "2001:db8::3");
+        assert_eq!(hosts[2].port, 4333);
+    }
+
+    #[test]
+    fn parse_clear_peers_list() {
+        let hosts = parse_peers(
+            "7,3000,[[BB9020011AC4202,,[203.0.113.2]],[BB9030011AC4202,,[203.0.113.3:3100]]]",
+        )
+        .unwrap();
+        assert_eq!(hosts.len(), 2);
+        assert_eq!(hosts[0].address(), "203.0.113.2:3000");
+        assert_eq!(hosts[0].tls_name, None);
+        assert_eq!(hosts[1].address(), "203.0.113.3:3100");
+    }
+
+    #[test]
+    fn parse_empty_peers_list() {
+        assert!(parse_peers("3,4333,[]").unwrap().is_empty());
+    }
+
+    #[test]
+    fn parse_malformed_peers_list() {
+        assert!(parse_peers("3,4333,[[BB9020011AC4202,,[172.17.0.2]]").is_err());
+        assert!(parse_peers("3,4333").is_err());
+    }
+}
@@ -15,15 +15,17 @@
 #![allow(dead_code)]
 
 use std::str;
+use std::time::{Duration, Instant};
 
 use pwhash::bcrypt::{self, BcryptSetup, BcryptVariant};
 
 use crate::cluster::Cluster;
+use crate::commands::buffer::Buffer;
 use crate::errors::{ErrorKind, Result};
 use crate::net::Connection;
 use crate::net::PooledConnection;
 use crate::policy::AdminPolicy;
-use crate::ResultCode;
+use crate::{Privilege, PrivilegeCode, ResultCode, Role, User};
 
 // Commands
 const AUTHENTICATE: u8 = 0;
@@ -35,6 +37,13 @@ const GRANT_ROLES: u8 = 5;
 const REVOKE_ROLES: u8 = 6;
 const REPLACE_ROLES: u8 = 7;
 const QUERY_USERS: u8 = 9;
+const CREATE_ROLE: u8 = 10;
+const DROP_ROLE: u8 = 11;
+const GRANT_PRIVILEGES: u8 = 12;
+const REVOKE_PRIVILEGES: u8 = 13;
+const QUERY_ROLES: u8 = 16;
+const SET_WHITELIST: u8 = 17;
+const SET_QUOTAS: u8 = 19;
 const LOGIN: u8 = 20;
 
 // Field IDs
@@ -42,7 +51,18 @@ const USER: u8 = 0;
 const PASSWORD: u8 = 1;
 const OLD_PASSWORD: u8 = 2;
 const CREDENTIAL: u8 = 3;
+const CLEAR_PASSWORD: u8 = 4;
+const SESSION_TOKEN: u8 = 5;
+const SESSION_TTL: u8 = 6;
 const ROLES: u8 = 10;
+const ROLE: u8 = 11;
+const PRIVILEGES: u8 = 12;
+const WHITELIST: u8 = 13;
+const READ_QUOTA: u8 = 14;
+const WRITE_QUOTA: u8 = 15;
+const READ_INFO: u8 = 16;
+const WRITE_INFO: u8 = 17;
+const CONNECTIONS: u8 = 18;
 
 // Misc
 const MSG_VERSION: i64 = 0;
@@ -51,7 +71,34 @@ const MSG_TYPE: i64 = 2;
 const HEADER_SIZE: usize = 24;
 const HEADER_REMAINING: usize = 16;
 const RESULT_CODE: usize = 9;
-const QUERY_END: usize = 50;
+const QUERY_END: u8 = 50;
+
+// The client lets a session expire this long before the server does, so that it is not used
+// while it expires.
+const SESSION_EXPIRATION_MARGIN: u64 = 60;
+
+/// Session token returned by the server on login. Connections can be authenticated with the
+/// token instead of the credentials of the user until the session expires.
+#[derive(Debug, Clone)]
+pub struct Session {
+    token: Vec<u8>,
+    expiration: Option<Instant>,
+}
+
+impl Session {
+    fn new(token: Vec<u8>, ttl: Option<Duration>) -> Self {
+        let expiration = ttl.map(|ttl| {
+            let ttl = ttl.as_secs().saturating_sub(SESSION_EXPIRATION_MARGIN);
+            Instant::now() + Duration::from_secs(ttl)
+        });
+        Session { token, expiration }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.expiration
+            .map_or(true, |expiration| Instant::now() < expiration)
+    }
+}
 
 pub struct AdminCommand {}
 
@@ -60,24 +107,19 @@ impl AdminCommand {
         AdminCommand {}
     }
 
-    fn execute(mut conn: PooledConnection) -> Result<()> {
-        // Write the message header
-        conn.buffer.size_buffer()?;
-        let size = conn.buffer.data_offset;
-        conn.buffer.reset_offset()?;
-        AdminCommand::write_size(&mut conn, size as i64)?;
-
+    async fn execute(mut conn: PooledConnection) -> Result<()> {
         // Send command.
-        if let Err(err) = conn.flush() {
+        if let Err(err) = AdminCommand::send(&mut conn).await {
             conn.invalidate();
             return Err(err);
         }
 
         // read header
-        if let Err(err) = conn.read_buffer(HEADER_SIZE) {
+        if let Err(err) = conn.read_buffer(HEADER_SIZE).await {
             conn.invalidate();
             return Err(err);
         }
+        conn.mark_reusable();
 
         let result_code = conn.buffer.read_u8(Some(RESULT_CODE))?;
         let result_code = ResultCode::from(result_code);
@@ -88,34 +130,279 @@ impl AdminCommand {
         Ok(())
     }
 
-    pub fn authenticate(conn: &mut Connection, user: &str, password: &str) -> Result<()> {
-        conn.buffer.resize_buffer(1024)?;
-        conn.buffer.reset_offset()?;
-        AdminCommand::write_header(conn, LOGIN, 2)?;
-        AdminCommand::write_field_str(conn, USER, user)?;
-        AdminCommand::write_field_bytes(conn, CREDENTIAL, password.as_bytes())?;
+    async fn send(conn: &mut Connection) -> Result<()> {
+        // Write the message header
         conn.buffer.size_buffer()?;
         let size = conn.buffer.data_offset;
         conn.buffer.reset_offset()?;
         AdminCommand::write_size(conn, size as i64)?;
 
-        conn.flush()?;
-        conn.read_buffer(HEADER_SIZE)?;
+        conn.flush().await
+    }
+
+    // Sends a query command and passes every record of the response to `parse_record`, with the
+    // field count of the record, until the server signals the end of the query.
+    async fn execute_query<F>(mut conn: PooledConnection, mut parse_record: F) -> Result<()>
+    where
+        F: FnMut(&mut Buffer, u8) -> Result<()>,
+    {
+        let mut res = AdminCommand::send(&mut conn).await;
+        if res.is_ok() {
+            res = AdminCommand::read_query_blocks(&mut conn, &mut parse_record).await;
+        }
+        match res {
+            Ok(()) => conn.mark_reusable(),
+            Err(_) => conn.invalidate(),
+        }
+        res
+    }
+
+    async fn read_query_blocks<F>(conn: &mut Connection, parse_record: &mut F) -> Result<()>
+    where
+        F: FnMut(&mut Buffer, u8) -> Result<()>,
+    {
+        loop {
+            conn.read_buffer(8).await?;
+            let size = conn.buffer.read_msg_size(None)?;
+            if size == 0 {
+                return Ok(());
+            }
+
+            conn.read_buffer(size).await?;
+            while conn.buffer.data_offset < size {
+                let offset = conn.buffer.data_offset;
+                let result_code = conn.buffer.read_u8(Some(offset + 1))?;
+                let field_count = conn.buffer.read_u8(Some(offset + 3))?;
+                conn.buffer.skip_bytes(HEADER_REMAINING);
+
+                match result_code {
+                    0 => parse_record(&mut conn.buffer, field_count)?,
+                    QUERY_END => return Ok(()),
+                    rc => bail!(ErrorKind::ServerError(ResultCode::from(rc))),
+                }
+            }
+        }
+    }
+
+    // Reads the header of a response field; returns the field id and the size of the field data.
+    fn read_field_header(buffer: &mut Buffer) -> Result<(u8, usize)> {
+        let len = buffer.read_u32(None)? as usize;
+        let id = buffer.read_u8(None)?;
+        Ok((id, len.saturating_sub(1)))
+    }
+
+    fn parse_user(buffer: &mut Buffer, field_count: u8) -> Result<User> {
+        let mut user = User::default();
+        for _ in 0..field_count {
+            let (id, len) = AdminCommand::read_field_header(buffer)?;
+            match id {
+                USER => user.user = buffer.read_str(len)?,
+                ROLES => user.roles = AdminCommand::read_roles(buffer)?,
+                READ_INFO => user.read_info = AdminCommand::read_info(buffer)?,
+                WRITE_INFO => user.write_info = AdminCommand::read_info(buffer)?,
+                CONNECTIONS => user.conns_in_use = buffer.read_u32(None)?,
+                _ => buffer.skip_bytes(len),
+            }
+        }
+        Ok(user)
+    }
+
+    fn read_roles(buffer: &mut Buffer) -> Result<Vec<String>> {
+        let count = buffer.read_u8(None)?;
+        let mut roles = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let len = buffer.read_u8(None)?;
+            roles.push(buffer.read_str(len as usize)?);
+        }
+        Ok(roles)
+    }
+
+    fn read_info(buffer: &mut Buffer) -> Result<Vec<u32>> {
+        let count = buffer.read_u8(None)?;
+        let mut info = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            info.push(buffer.read_u32(None)?);
+        }
+        Ok(info)
+    }
+
+    fn parse_role(buffer: &mut Buffer, field_count: u8) -> Result<Role> {
+        let mut role = Role::default();
+        for _ in 0..field_count {
+            let (id, len) = AdminCommand::read_field_header(buffer)?;
+            match id {
+                ROLE => role.name = buffer.read_str(len)?,
+                PRIVILEGES => role.privileges = AdminCommand::read_privileges(buffer)?,
+                WHITELIST => {
+                    role.whitelist = buffer
+                        .read_str(len)?
+                        .split(',')
+                        .filter(|address| !address.is_empty())
+                        .map(String::from)
+                        .collect();
+                }
+                READ_QUOTA => role.read_quota = buffer.read_u32(None)?,
+                WRITE_QUOTA => role.write_quota = buffer.read_u32(None)?,
+                _ => buffer.skip_bytes(len),
+            }
+        }
+        Ok(role)
+    }
+
+    // Privileges with a code unknown to the client are skipped.
+    fn read_privileges(buffer: &mut Buffer) -> Result<Vec<Privilege>> {
+        let count = buffer.read_u8(None)?;
+        let mut privileges = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let code = buffer.read_u8(None)?;
+            let (namespace, set_name) = if code >= PrivilegeCode::Read as u8 {
+                let len = buffer.read_u8(None)?;
+                let namespace = buffer.read_str(len as usize)?;
+                let len = buffer.read_u8(None)?;
+                (namespace, buffer.read_str(len as usize)?)
+            } else {
+                (String::new(), String::new())
+            };
+
+            if let Some(code) = PrivilegeCode::from_u8(code) {
+                privileges.push(Privilege {
+                    code,
+                    namespace,
+                    set_name,
+                });
+            }
+        }
+        Ok(privileges)
+    }
+
+    // Logs in with the hash of the password. With external authentication, the clear-text
+    // password is sent as well, to be verified by the external authentication service. Returns
+    // the session of the login, if the server returned one.
+    pub async fn authenticate(
+        conn: &mut Connection,
+        user: &str,
+        password: &str,
+        clear_password: Option<&str>,
+    ) -> Result<Option<Session>> {
+        conn.buffer.resize_buffer(1024)?;
+        conn.buffer.reset_offset()?;
+        if let Some(clear_password) = clear_password {
+            AdminCommand::write_header(conn, LOGIN, 3)?;
+            AdminCommand::write_field_str(conn, USER, user)?;
+            AdminCommand::write_field_bytes(conn, CREDENTIAL, password.as_bytes())?;
+            AdminCommand::write_field_str(conn, CLEAR_PASSWORD, clear_password)?;
+        } else {
+            AdminCommand::write_header(conn, LOGIN, 2)?;
+            AdminCommand::write_field_str(conn, USER, user)?;
+            AdminCommand::write_field_bytes(conn, CREDENTIAL, password.as_bytes())?;
+        }
+
+        AdminCommand::login(conn).await
+    }
+
+    // Logs in with the TLS client certificate of the connection; the login message carries no
+    // credentials.
+    pub async fn authenticate_pki(conn: &mut Connection) -> Result<Option<Session>> {
+        conn.buffer.resize_buffer(1024)?;
+        conn.buffer.reset_offset()?;
+        AdminCommand::write_header(conn, LOGIN, 0)?;
+
+        AdminCommand::login(conn).await
+    }
+
+    // Authenticates the connection with the token of a previous login session.
+    // The user is `None` with PKI authentication.
+    pub async fn authenticate_session(
+        conn: &mut Connection,
+        user: Option<&str>,
+        session: &Session,
+    ) -> Result<()> {
+        conn.buffer.resize_buffer(1024)?;
+        conn.buffer.reset_offset()?;
+        if let Some(user) = user {
+            AdminCommand::write_header(conn, AUTHENTICATE, 2)?;
+            AdminCommand::write_field_str(conn, USER, user)?;
+        } else {
+            AdminCommand::write_header(conn, AUTHENTICATE, 1)?;
+        }
+        AdminCommand::write_field_bytes(conn, SESSION_TOKEN, &session.token)?;
+
+        AdminCommand::authenticate_connection(conn).await
+    }
+
+    // Authenticates the connection with the hash of the password, for servers that do not
+    // support the login command.
+    pub async fn authenticate_legacy(
+        conn: &mut Connection,
+        user: &str,
+        password: &str,
+    ) -> Result<()> {
+        conn.buffer.resize_buffer(1024)?;
+        conn.buffer.reset_offset()?;
+        AdminCommand::write_header(conn, AUTHENTICATE, 2)?;
+        AdminCommand::write_field_str(conn, USER, user)?;
+        AdminCommand::write_field_bytes(conn, CREDENTIAL, password.as_bytes())?;
+
+        AdminCommand::authenticate_connection(conn).await
+    }
+
+    async fn authenticate_connection(conn: &mut Connection) -> Result<()> {
+        AdminCommand::send(conn).await?;
+        conn.read_buffer(HEADER_SIZE).await?;
+        let result_code = conn.buffer.read_u8(Some(RESULT_CODE))?;
+        let result_code = ResultCode::from(result_code);
+        if result_code != ResultCode::Ok {
+            bail!(ErrorKind::ServerError(result_code));
+        }
+
+        AdminCommand::read_remaining(conn).await?;
+        Ok(())
+    }
+
+    async fn login(conn: &mut Connection) -> Result<Option<Session>> {
+        AdminCommand::send(conn).await?;
+        conn.read_buffer(HEADER_SIZE).await?;
         let result_code = conn.buffer.read_u8(Some(RESULT_CODE))?;
         let result_code = ResultCode::from(result_code);
         if ResultCode::SecurityNotEnabled != result_code && ResultCode::Ok != result_code {
             bail!(ErrorKind::ServerError(result_code));
         }
 
-        // consume the rest of the buffer
+        let field_count = conn.buffer.read_u8(Some(11))?;
+        AdminCommand::read_remaining(conn).await?;
+        if result_code != ResultCode::Ok {
+            return Ok(None);
+        }
+
+        // Servers that do not support sessions do not return a session token.
+        let mut token = None;
+        let mut ttl = None;
+        for _ in 0..field_count {
+            let (id, len) = AdminCommand::read_field_header(&mut conn.buffer)?;
+            match id {
+                SESSION_TOKEN => token = Some(conn.buffer.read_blob(len)?),
+                SESSION_TTL => {
+                    let seconds = conn.buffer.read_u32(None)?;
+                    if seconds > 0 {
+                        ttl = Some(Duration::from_secs(u64::from(seconds)));
+                    }
+                }
+                _ => conn.buffer.skip_bytes(len),
+            }
+        }
+
+        Ok(token.map(|token| Session::new(token, ttl)))
+    }
+
+    // Reads the remainder of a response whose header has been read.
+    async fn read_remaining(conn: &mut Connection) -> Result<()> {
         let sz = conn.buffer.read_u64(Some(0))?;
         let receive_size = (sz & 0xFFFF_FFFF_FFFF) - HEADER_REMAINING as u64;
-        conn.read_buffer(receive_size as usize)?;
-
+        conn.read_buffer(receive_size as usize).await?;
         Ok(())
     }
 
-    pub fn create_user(
+    pub async fn create_user(
         cluster: &Cluster,
         policy: &AdminPolicy,
         user: &str,
@@ -123,7 +410,7 @@ impl AdminCommand {
         roles: &[&str],
     ) -> Result<()> {
         let node = cluster.get_random_node()?;
-        let mut conn = node.get_connection(Some(policy.timeout))?;
+        let mut conn = node.get_connection(Some(policy.timeout)).await?;
 
         conn.buffer.resize_buffer(1024)?;
         conn.buffer.reset_offset()?;
@@ -136,29 +423,29 @@ impl AdminCommand {
         )?;
         AdminCommand::write_roles(&mut conn, roles)?;
 
-        AdminCommand::execute(conn)
+        AdminCommand::execute(conn).await
     }
 
-    pub fn drop_user(cluster: &Cluster, policy: &AdminPolicy, user: &str) -> Result<()> {
+    pub async fn drop_user(cluster: &Cluster, policy: &AdminPolicy, user: &str) -> Result<()> {
         let node = cluster.get_random_node()?;
-        let mut conn = node.get_connection(Some(policy.timeout))?;
+        let mut conn = node.get_connection(Some(policy.timeout)).await?;
 
         conn.buffer.resize_buffer(1024)?;
         conn.buffer.reset_offset()?;
         AdminCommand::write_header(&mut conn, DROP_USER, 1)?;
         AdminCommand::write_field_str(&mut conn, USER, user)?;
 
-        AdminCommand::execute(conn)
+        AdminCommand::execute(conn).await
     }
 
-    pub fn set_password(
+    pub async fn set_password(
         cluster: &Cluster,
         policy: &AdminPolicy,
         user: &str,
         password: &str,
     ) -> Result<()> {
         let node = cluster.get_random_node()?;
-        let mut conn = node.get_connection(Some(policy.timeout))?;
+        let mut conn = node.get_connection(Some(policy.timeout)).await?;
 
         conn.buffer.resize_buffer(1024)?;
         conn.buffer.reset_offset()?;
@@ -170,29 +457,26 @@ impl AdminCommand {
             &AdminCommand::hash_password(password)?,
         )?;
 
-        AdminCommand::execute(conn)
+        AdminCommand::execute(conn).await
     }
 
-    pub fn change_password(
+    pub async fn change_password(
         cluster: &Cluster,
         policy: &AdminPolicy,
         user: &str,
         password: &str,
     ) -> Result<()> {
         let node = cluster.get_random_node()?;
-        let mut conn = node.get_connection(Some(policy.timeout))?;
+        let mut conn = node.get_connection(Some(policy.timeout)).await?;
 
         conn.buffer.resize_buffer(1024)?;
         conn.buffer.reset_offset()?;
         AdminCommand::write_header(&mut conn, CHANGE_PASSWORD, 3)?;
         AdminCommand::write_field_str(&mut conn, USER, user)?;
-        match cluster.client_policy().user_password {
-            Some((_, ref password)) => {
-                AdminCommand::write_field_str(
-                    &mut conn,
-                    OLD_PASSWORD,
-                    &AdminCommand::hash_password(password)?,
-                )?;
+        // the stored password of the cluster is already hashed
+        match cluster.user_password() {
+            Some((_, ref password_hash)) => {
+                AdminCommand::write_field_str(&mut conn, OLD_PASSWORD, password_hash)?;
             }
 
             None => AdminCommand::write_field_str(&mut conn, OLD_PASSWORD, "")?,
@@ -204,17 +488,17 @@ impl AdminCommand {
             &AdminCommand::hash_password(password)?,
         )?;
 
-        AdminCommand::execute(conn)
+        AdminCommand::execute(conn).await
     }
 
-    pub fn grant_roles(
+    pub async fn grant_roles(
         cluster: &Cluster,
         policy: &AdminPolicy,
         user: &str,
         roles: &[&str],
     ) -> Result<()> {
         let node = cluster.get_random_node()?;
-        let mut conn = node.get_connection(Some(policy.timeout))?;
+        let mut conn = node.get_connection(Some(policy.timeout)).await?;
 
         conn.buffer.resize_buffer(1024)?;
         conn.buffer.reset_offset()?;
@@ -222,17 +506,17 @@ impl AdminCommand {
         AdminCommand::write_field_str(&mut conn, USER, user)?;
         AdminCommand::write_roles(&mut conn, roles)?;
 
-        AdminCommand::execute(conn)
+        AdminCommand::execute(conn).await
     }
 
-    pub fn revoke_roles(
+    pub async fn revoke_roles(
         cluster: &Cluster,
         policy: &AdminPolicy,
         user: &str,
         roles: &[&str],
     ) -> Result<()> {
         let node = cluster.get_random_node()?;
-        let mut conn = node.get_connection(Some(policy.timeout))?;
+        let mut conn = node.get_connection(Some(policy.timeout)).await?;
 
         conn.buffer.resize_buffer(1024)?;
         conn.buffer.reset_offset()?;
@@ -240,11 +524,211 @@ impl AdminCommand {
         AdminCommand::write_field_str(&mut conn, USER, user)?;
         AdminCommand::write_roles(&mut conn, roles)?;
 
-        AdminCommand::execute(conn)
+        AdminCommand::execute(conn).await
+    }
+
+    pub async fn create_role(
+        cluster: &Cluster,
+        policy: &AdminPolicy,
+        role: &str,
+        privileges: &[Privilege],
+        whitelist: &[&str],
+        read_quota: u32,
+        write_quota: u32,
+    ) -> Result<()> {
+        let node = cluster.get_random_node()?;
+        let mut conn = node.get_connection(Some(policy.timeout)).await?;
+
+        let mut field_count = 1;
+        if !privileges.is_empty() {
+            field_count += 1;
+        }
+        if !whitelist.is_empty() {
+            field_count += 1;
+        }
+        if read_quota > 0 {
+            field_count += 1;
+        }
+        if write_quota > 0 {
+            field_count += 1;
+        }
+
+        let whitelist = whitelist.join(",");
+        conn.buffer
+            .resize_buffer(1024 + AdminCommand::privileges_size(privileges)? + whitelist.len())?;
+        conn.buffer.reset_offset()?;
+        AdminCommand::write_header(&mut conn, CREATE_ROLE, field_count)?;
+        AdminCommand::write_field_str(&mut conn, ROLE, role)?;
+        if !privileges.is_empty() {
+            AdminCommand::write_privileges(&mut conn, privileges)?;
+        }
+        if !whitelist.is_empty() {
+            AdminCommand::write_field_str(&mut conn, WHITELIST, &whitelist)?;
+        }
+        if read_quota > 0 {
+            AdminCommand::write_field_u32(&mut conn, READ_QUOTA, read_quota)?;
+        }
+        if write_quota > 0 {
+            AdminCommand::write_field_u32(&mut conn, WRITE_QUOTA, write_quota)?;
+        }
+
+        AdminCommand::execute(conn).await
+    }
+
+    pub async fn drop_role(cluster: &Cluster, policy: &AdminPolicy, role: &str) -> Result<()> {
+        let node = cluster.get_random_node()?;
+        let mut conn = node.get_connection(Some(policy.timeout)).await?;
+
+        conn.buffer.resize_buffer(1024)?;
+        conn.buffer.reset_offset()?;
+        AdminCommand::write_header(&mut conn, DROP_ROLE, 1)?;
+        AdminCommand::write_field_str(&mut conn, ROLE, role)?;
+
+        AdminCommand::execute(conn).await
+    }
+
+    pub async fn set_whitelist(
+        cluster: &Cluster,
+        policy: &AdminPolicy,
+        role: &str,
+        whitelist: &[&str],
+    ) -> Result<()> {
+        let node = cluster.get_random_node()?;
+        let mut conn = node.get_connection(Some(policy.timeout)).await?;
+
+        let whitelist = whitelist.join(",");
+        conn.buffer.resize_buffer(1024 + whitelist.len())?;
+        conn.buffer.reset_offset()?;
+        if whitelist.is_empty() {
+            AdminCommand::write_header(&mut conn, SET_WHITELIST, 1)?;
+            AdminCommand::write_field_str(&mut conn, ROLE, role)?;
+        } else {
+            AdminCommand::write_header(&mut conn, SET_WHITELIST, 2)?;
+            AdminCommand::write_field_str(&mut conn, ROLE, role)?;
+            AdminCommand::write_field_str(&mut conn, WHITELIST, &whitelist)?;
+        }
+
+        AdminCommand::execute(conn).await
+    }
+
+    pub async fn set_quotas(
+        cluster: &Cluster,
+        policy: &AdminPolicy,
+        role: &str,
+        read_quota: u32,
+        write_quota: u32,
+    ) -> Result<()> {
+        let node = cluster.get_random_node()?;
+        let mut conn = node.get_connection(Some(policy.timeout)).await?;
+
+        conn.buffer.resize_buffer(1024)?;
+        conn.buffer.reset_offset()?;
+        AdminCommand::write_header(&mut conn, SET_QUOTAS, 3)?;
+        AdminCommand::write_field_str(&mut conn, ROLE, role)?;
+        AdminCommand::write_field_u32(&mut conn, READ_QUOTA, read_quota)?;
+        AdminCommand::write_field_u32(&mut conn, WRITE_QUOTA, write_quota)?;
+
+        AdminCommand::execute(conn).await
+    }
+
+    pub async fn grant_privileges(
+        cluster: &Cluster,
+        policy: &AdminPolicy,
+        role: &str,
+        privileges: &[Privilege],
+    ) -> Result<()> {
+        AdminCommand::change_privileges(cluster, policy, GRANT_PRIVILEGES, role, privileges).await
+    }
+
+    pub async fn revoke_privileges(
+        cluster: &Cluster,
+        policy: &AdminPolicy,
+        role: &str,
+        privileges: &[Privilege],
+    ) -> Result<()> {
+        AdminCommand::change_privileges(cluster, policy, REVOKE_PRIVILEGES, role, privileges).await
+    }
+
+    async fn change_privileges(
+        cluster: &Cluster,
+        policy: &AdminPolicy,
+        command: u8,
+        role: &str,
+        privileges: &[Privilege],
+    ) -> Result<()> {
+        let node = cluster.get_random_node()?;
+        let mut conn = node.get_connection(Some(policy.timeout)).await?;
+
+        conn.buffer
+            .resize_buffer(1024 + AdminCommand::privileges_size(privileges)?)?;
+        conn.buffer.reset_offset()?;
+        AdminCommand::write_header(&mut conn, command, 2)?;
+        AdminCommand::write_field_str(&mut conn, ROLE, role)?;
+        AdminCommand::write_privileges(&mut conn, privileges)?;
+
+        AdminCommand::execute(conn).await
     }
 
     // Utility methods
 
+    pub async fn query_users(
+        cluster: &Cluster,
+        policy: &AdminPolicy,
+        user: Option<&str>,
+    ) -> Result<Vec<User>> {
+        let node = cluster.get_random_node()?;
+        let mut conn = node.get_connection(Some(policy.timeout)).await?;
+
+        conn.buffer.resize_buffer(1024)?;
+        conn.buffer.reset_offset()?;
+        if let Some(user) = user {
+            AdminCommand::write_header(&mut conn, QUERY_USERS, 1)?;
+            AdminCommand::write_field_str(&mut conn, USER, user)?;
+        } else {
+            AdminCommand::write_header(&mut conn, QUERY_USERS, 0)?;
+        }
+
+        let mut users = vec![];
+        AdminCommand::execute_query(conn, |buffer, field_count| {
+            let user = AdminCommand::parse_user(buffer, field_count)?;
+            if !user.user.is_empty() {
+                users.push(user);
+            }
+            Ok(())
+        })
+        .await?;
+        Ok(users)
+    }
+
+    pub async fn query_roles(
+        cluster: &Cluster,
+        policy: &AdminPolicy,
+        role: Option<&str>,
+    ) -> Result<Vec<Role>> {
+        let node = cluster.get_random_node()?;
+        let mut conn = node.get_connection(Some(policy.timeout)).await?;
+
+        conn.buffer.resize_buffer(1024)?;
+        conn.buffer.reset_offset()?;
+        if let Some(role) = role {
+            AdminCommand::write_header(&mut conn, QUERY_ROLES, 1)?;
+            AdminCommand::write_field_str(&mut conn, ROLE, role)?;
+        } else {
+            AdminCommand::write_header(&mut conn, QUERY_ROLES, 0)?;
+        }
+
+        let mut roles = vec![];
+        AdminCommand::execute_query(conn, |buffer, field_count| {
+            let role = AdminCommand::parse_role(buffer, field_count)?;
+            if !role.name.is_empty() {
+                roles.push(role);
+            }
+            Ok(())
+        })
+        .await?;
+        Ok(roles)
+    }
+
     fn write_size(conn: &mut Connection, size: i64) -> Result<()> {
         // Write total size of message which is the current offset.
         let size = (size - 8) | (MSG_VERSION << 56) | (MSG_TYPE << 48);
@@ -286,8 +770,14 @@ impl AdminCommand {
         Ok(())
     }
 
+    fn write_field_u32(conn: &mut Connection, id: u8, val: u32) -> Result<()> {
+        AdminCommand::write_field_header(conn, id, 4)?;
+        conn.buffer.write_u32(val)?;
+        Ok(())
+    }
+
     fn write_roles(conn: &mut Connection, roles: &[&str]) -> Result<()> {
-        let mut size = 0;
+        let mut size = 1; // role count
         for role in roles {
             size += role.len() + 1; // size + len
         }
@@ -302,6 +792,47 @@ impl AdminCommand {
         Ok(())
     }
 
+    // Size of the privileges field; also validates the scope of the privileges.
+    fn privileges_size(privileges: &[Privilege]) -> Result<usize> {
+        let mut size = 1; // privilege count
+        for privilege in privileges {
+            size += 1; // code
+            if privilege.code.can_scope() {
+                if privilege.namespace.is_empty() && !privilege.set_name.is_empty() {
+                    bail!(ErrorKind::InvalidArgument(format!(
+                        "Privilege '{}' has a set scope with an empty namespace",
+                        privilege.code
+                    )));
+                }
+                size += privilege.namespace.len() + privilege.set_name.len() + 2;
+            } else if !privilege.namespace.is_empty() || !privilege.set_name.is_empty() {
+                bail!(ErrorKind::InvalidArgument(format!(
+                    "Global privilege '{}' can not have a namespace or set scope",
+                    privilege.code
+                )));
+            }
+        }
+        Ok(size)
+    }
+
+    fn write_privileges(conn: &mut Connection, privileges: &[Privilege]) -> Result<()> {
+        let size = AdminCommand::privileges_size(privileges)?;
+
+        AdminCommand::write_field_header(conn, PRIVILEGES, size)?;
+        conn.buffer.write_u8(privileges.len() as u8)?;
+        for privilege in privileges {
+            conn.buffer.write_u8(privilege.code as u8)?;
+            if privilege.code.can_scope() {
+                conn.buffer.write_u8(privilege.namespace.len() as u8)?;
+                conn.buffer.write_str(&privilege.namespace)?;
+                conn.buffer.write_u8(privilege.set_name.len() as u8)?;
+                conn.buffer.write_str(&privilege.set_name)?;
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn hash_password(password: &str) -> Result<String> {
         bcrypt::hash_with(
             BcryptSetup {
@@ -314,3 +845,72 @@ impl AdminCommand {
         .map_err(|e| e.into())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{AdminCommand, Session, PRIVILEGES, ROLE, WHITELIST, WRITE_QUOTA};
+    use crate::commands::buffer::Buffer;
+    use crate::{Privilege, PrivilegeCode};
+    use std::time::Duration;
+
+    #[test]
+    fn privileges_size() {
+        let privileges = [
+            Privilege::global(PrivilegeCode::SysAdmin),
+            Privilege::global(PrivilegeCode::Read),
+            Privilege::new(PrivilegeCode::Write, "test", "demo"),
+        ];
+        assert_eq!(AdminCommand::privileges_size(&privileges).unwrap(), 16);
+    }
+
+    #[test]
+    fn invalid_privilege_scope() {
+        let privileges = [Privilege::new(PrivilegeCode::UserAdmin, "test", "")];
+        assert!(AdminCommand::privileges_size(&privileges).is_err());
+
+        let privileges = [Privilege::new(PrivilegeCode::Read, "", "demo")];
+        assert!(AdminCommand::privileges_size(&privileges).is_err());
+    }
+
+    #[test]
+    fn parse_role() {
+        let mut buffer = Buffer::new(1024);
+        buffer.resize_buffer(64).unwrap();
+        buffer.reset_offset().unwrap();
+        for (id, data) in [
+            (ROLE, &b"demo"[..]),
+            (PRIVILEGES, &b"\x03\x01\x0b\x04test\x04demo\x09"[..]),
+            (WHITELIST, &b"10.0.0.1,10.1.0.0/16"[..]),
+            (WRITE_QUOTA, &b"\x00\x00\x01\x00"[..]),
+        ]
+        .iter()
+        {
+            buffer.write_u32(data.len() as u32 + 1).unwrap();
+            buffer.write_u8(*id).unwrap();
+            buffer.write_bytes(data).unwrap();
+        }
+        buffer.reset_offset().unwrap();
+
+        let role = AdminCommand::parse_role(&mut buffer, 4).unwrap();
+        assert_eq!(role.name, "demo");
+        assert_eq!(
+            role.privileges,
+            vec![
+                Privilege::global(PrivilegeCode::SysAdmin),
+                Privilege::new(PrivilegeCode::ReadWrite, "test", "demo"),
+            ]
+        );
+        assert_eq!(role.whitelist, vec!["10.0.0.1", "10.1.0.0/16"]);
+        assert_eq!(role.read_quota, 0);
+        assert_eq!(role.write_quota, 256);
+    }
+
+    #[test]
+    fn session_expiration() {
+        assert!(Session::new(vec![1], None).is_valid());
+        assert!(Session::new(vec![1], Some(Duration::from_secs(3600))).is_valid());
+
+        // sessions expire early on the client
+        assert!(!Session::new(vec![1], Some(Duration::from_secs(30))).is_valid());
+    }
+}
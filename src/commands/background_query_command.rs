@@ -0,0 +1,138 @@
+// Copyright 2015-2023 Aerospike, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::cluster::partition::NodePartitions;
+use crate::cluster::Node;
+use crate::commands::buffer;
+use crate::commands::{Command, SingleCommand};
+use crate::errors::{ErrorKind, Result};
+use crate::net::Connection;
+use crate::operations::Operation;
+use crate::policy::WritePolicy;
+use crate::runtime::Runtime;
+use crate::{ResultCode, Statement};
+
+// Starts a background query or scan on a single node. The server acknowledges the job once it has
+// been started; it does not return any records.
+pub struct BackgroundQueryCommand<'a> {
+    node: Arc<Node>,
+    policy: &'a WritePolicy,
+    statement: &'a Statement,
+    operations: &'a [Operation<'a>],
+    task_id: u64,
+    partitions: NodePartitions,
+}
+
+impl<'a> BackgroundQueryCommand<'a> {
+    pub const fn new(
+        policy: &'a WritePolicy,
+        node: Arc<Node>,
+        statement: &'a Statement,
+        operations: &'a [Operation<'a>],
+        task_id: u64,
+        partitions: NodePartitions,
+    ) -> Self {
+        BackgroundQueryCommand {
+            node,
+            policy,
+            statement,
+            operations,
+            task_id,
+            partitions,
+        }
+    }
+
+    pub async fn execute(&mut self) -> Result<()> {
+        SingleCommand::execute(self.policy, self).await
+    }
+
+    // Parses the records of one message group; returns true once the last record has been read.
+    async fn parse_group(conn: &mut Connection, size: usize) -> Result<bool> {
+        while conn.bytes_read() < size {
+            conn.read_buffer(buffer::MSG_REMAINING_HEADER_SIZE as usize)
+                .await?;
+            let result_code = ResultCode::from(conn.buffer.read_u8(Some(5))?);
+            let info3 = conn.buffer.read_u8(Some(3))?;
+            let field_count = conn.buffer.read_u16(Some(18))?;
+            let op_count = conn.buffer.read_u16(Some(20))?;
+
+            if result_code != ResultCode::Ok && conn.bytes_read() < size {
+                let remaining = size - conn.bytes_read();
+                conn.read_buffer(remaining).await?;
+            }
+
+            match result_code {
+                ResultCode::Ok => (),
+                // no record matched the query
+                ResultCode::KeyNotFoundError => return Ok(true),
+                _ => bail!(ErrorKind::ServerError(result_code)),
+            }
+
+            if info3 & buffer::INFO3_LAST == buffer::INFO3_LAST {
+                return Ok(true);
+            }
+
+            for _ in 0..(field_count + op_count) {
+                conn.read_buffer(4).await?;
+                let len = conn.buffer.read_u32(None)? as usize;
+                conn.read_buffer(len).await?;
+            }
+        }
+        Ok(false)
+    }
+}
+
+impl<'a> Command for BackgroundQueryCommand<'a> {
+    fn write_timeout(&mut self, conn: &mut Connection, timeout: Option<Duration>) -> Result<()> {
+        conn.buffer.write_timeout(timeout);
+        Ok(())
+    }
+
+    async fn write_buffer(&mut self, conn: &mut Connection) -> Result<()> {
+        conn.flush().await
+    }
+
+    fn prepare_buffer(&mut self, conn: &mut Connection) -> Result<()> {
+        conn.buffer.set_background_query(
+            self.policy,
+            self.statement,
+            self.operations,
+            self.task_id,
+            &self.partitions,
+        )
+    }
+
+    fn get_node(&self) -> Result<Arc<Node>> {
+        Ok(self.node.clone())
+    }
+
+    fn runtime(&self) -> Runtime {
+        self.node.runtime()
+    }
+
+    async fn parse_result(&mut self, conn: &mut Connection) -> Result<()> {
+        loop {
+            conn.read_buffer(8).await?;
+            let size = conn.buffer.read_msg_size(None)?;
+            conn.bookmark();
+
+            if size == 0 || BackgroundQueryCommand::parse_group(conn, size).await? {
+                return Ok(());
+            }
+        }
+    }
+}
@@ -0,0 +1,198 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::batch::batch_executor::SharedSlice;
+use crate::batch::BatchEntry;
+use crate::cluster::Node;
+use crate::commands;
+use crate::errors::{Error, ErrorKind, Result};
+use crate::net::Connection;
+use crate::policy::BatchPolicy;
+use crate::runtime::Runtime;
+use crate::{value, Record, ResultCode, Value};
+
+pub struct BatchOperateCommand<'b, T> {
+    policy: &'b BatchPolicy,
+    pub node: Arc<Node>,
+    records: SharedSlice<T>,
+    offsets: Vec<usize>,
+    sent: usize,
+}
+
+impl<'b, T: BatchEntry> BatchOperateCommand<'b, T> {
+    pub fn new(
+        policy: &'b BatchPolicy,
+        node: Arc<Node>,
+        records: SharedSlice<T>,
+        offsets: Vec<usize>,
+    ) -> Self {
+        BatchOperateCommand {
+            policy,
+            node,
+            records,
+            offsets,
+            sent: 0,
+        }
+    }
+
+    async fn parse_group(&mut self, conn: &mut Connection, size: usize) -> Result<bool> {
+        while conn.bytes_read() < size {
+            conn.read_buffer(commands::buffer::MSG_REMAINING_HEADER_SIZE as usize)
+                .await?;
+            if !self.parse_record(conn).await? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    async fn parse_record(&mut self, conn: &mut Connection) -> Result<bool> {
+        let result_code = ResultCode::from(conn.buffer.read_u8(Some(5))?);
+
+        // if cmd is the end marker of the response, do not proceed further
+        let info3 = conn.buffer.read_u8(Some(3))?;
+        if info3 & commands::buffer::INFO3_LAST == commands::buffer::INFO3_LAST {
+            if result_code != ResultCode::Ok {
+                bail!(ErrorKind::ServerError(result_code));
+            }
+            return Ok(false);
+        }
+
+        conn.buffer.skip(6)?;
+        let generation = conn.buffer.read_u32(None)?;
+        let expiration = conn.buffer.read_u32(None)?;
+        let batch_index = conn.buffer.read_u32(None)? as usize;
+        let field_count = conn.buffer.read_u16(None)? as usize; // almost certainly 0
+        let op_count = conn.buffer.read_u16(None)? as usize;
+
+        commands::StreamCommand::parse_key(conn, field_count).await?;
+
+        // every operation returns a result; repeated bins collect them in operation order
+        let mut results: HashMap<String, Vec<Value>> = HashMap::with_capacity(op_count);
+        for _ in 0..op_count {
+            conn.read_buffer(8).await?;
+            let op_size = conn.buffer.read_u32(None)? as usize;
+            conn.buffer.skip(1)?;
+            let particle_type = conn.buffer.read_u8(None)?;
+            conn.buffer.skip(1)?;
+            let name_size = conn.buffer.read_u8(None)? as usize;
+            conn.read_buffer(name_size).await?;
+            let name = conn.buffer.read_str(name_size)?;
+            let particle_bytes_size = op_size - (4 + name_size);
+            conn.read_buffer(particle_bytes_size).await?;
+            let value =
+                value::bytes_to_particle(particle_type, &mut conn.buffer, particle_bytes_size)?;
+            results.entry(name).or_insert_with(Vec::new).push(value);
+        }
+
+        let entry = self
+            .records
+            .get_mut(batch_index)
+            .expect("Invalid batch index");
+
+        // failed UDF calls return the error message in the "FAILURE" bin
+        let record = if result_code == ResultCode::Ok || result_code == ResultCode::UdfBadResponse {
+            let bins = results
+                .into_iter()
+                .map(|(name, mut values)| {
+                    let value = if values.len() == 1 {
+                        values.pop().unwrap()
+                    } else {
+                        Value::List(values)
+                    };
+                    (name, value)
+                })
+                .collect();
+            Some(Record::new(
+                Some(entry.key().clone()),
+                bins,
+                generation,
+                expiration,
+            ))
+        } else {
+            None
+        };
+
+        // a write that failed after the command had to be resent may have been applied before
+        let in_doubt = result_code != ResultCode::Ok && self.sent > 1 && has_write(entry);
+        entry.set_result(result_code, record, in_doubt);
+        Ok(true)
+    }
+}
+
+fn has_write<T: BatchEntry>(entry: &T) -> bool {
+    entry.batch_attr().map_or(false, |attr| attr.has_write)
+}
+
+impl<'b, T: BatchEntry> commands::BatchCommand for BatchOperateCommand<'b, T> {
+    fn set_node_error(&mut self, err: &Error) -> bool {
+        let result_code = match *err.kind() {
+            ErrorKind::ServerError(result_code) => result_code,
+            ErrorKind::Connection(_) | ErrorKind::Timeout(_) | ErrorKind::Io(_) => {
+                ResultCode::Timeout
+            }
+            _ => return false,
+        };
+        for idx in &self.offsets {
+            let entry = self.records.get_mut(*idx).expect("Invalid batch index");
+            if entry.result_code().is_none() {
+                let in_doubt = self.sent > 0 && has_write(entry);
+                entry.set_result(result_code, None, in_doubt);
+            }
+        }
+        true
+    }
+}
+
+impl<'b, T: BatchEntry> commands::Command for BatchOperateCommand<'b, T> {
+    fn write_timeout(&mut self, conn: &mut Connection, timeout: Option<Duration>) -> Result<()> {
+        conn.buffer.write_timeout(timeout);
+        Ok(())
+    }
+
+    async fn write_buffer(&mut self, conn: &mut Connection) -> Result<()> {
+        self.sent += 1;
+        conn.flush().await
+    }
+
+    fn prepare_buffer(&mut self, conn: &mut Connection) -> Result<()> {
+        conn.buffer
+            .set_batch_operate(self.policy, self.records.clone(), self.offsets.as_slice())
+    }
+
+    fn get_node(&self) -> Result<Arc<Node>> {
+        Ok(self.node.clone())
+    }
+
+    fn runtime(&self) -> Runtime {
+        self.node.runtime()
+    }
+
+    async fn parse_result(&mut self, conn: &mut Connection) -> Result<()> {
+        loop {
+            conn.read_buffer(8).await?;
+            let size = conn.buffer.read_msg_size(None)?;
+            conn.bookmark();
+            if size > 0 && !self.parse_group(conn, size as usize).await? {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
@@ -13,8 +13,7 @@
 // limitations under the License.
 
 use std::collections::HashMap;
-use std::sync::Arc;
-use std::thread;
+use std::sync::{Arc, Weak};
 use std::time::{Duration, Instant};
 
 use crate::batch::batch_executor::SharedSlice;
@@ -23,10 +22,13 @@ use crate::commands::{self, Command};
 use crate::errors::{ErrorKind, Result, ResultExt};
 use crate::net::Connection;
 use crate::policy::{BatchPolicy, Policy, PolicyLike};
+use crate::query::Recordset;
+use crate::runtime::Runtime;
 use crate::{value, BatchRead, Record, ResultCode, Value};
 
 struct BatchRecord {
     batch_index: usize,
+    result_code: ResultCode,
     record: Option<Record>,
 }
 
@@ -35,6 +37,7 @@ pub struct BatchReadCommand<'a, 'b> {
     pub node: Arc<Node>,
     batch_reads: SharedSlice<BatchRead<'a>>,
     offsets: Vec<usize>,
+    recordset: Option<Weak<Recordset>>,
 }
 
 impl<'a, 'b> BatchReadCommand<'a, 'b> {
@@ -49,101 +52,44 @@ impl<'a, 'b> BatchReadCommand<'a, 'b> {
             node,
             batch_reads,
             offsets,
+            recordset: None,
         }
     }
 
-    pub fn execute(&mut self) -> Result<()> {
-        let mut iterations = 0;
-        let base_policy = self.policy.base();
-
-        // set timeout outside the loop
-        let deadline = base_policy.deadline();
-
-        // Execute command until successful, timed out or maximum iterations have been reached.
-        loop {
-            iterations += 1;
-
-            // too many retries
-            if let Some(max_retries) = base_policy.max_retries() {
-                if iterations > max_retries + 1 {
-                    bail!(ErrorKind::Connection(format!(
-                        "Timeout after {} tries",
-                        iterations
-                    )));
-                }
-            }
-
-            // Sleep before trying again, after the first iteration
-            if iterations > 1 {
-                if let Some(sleep_between_retries) = base_policy.sleep_between_retries() {
-                    thread::sleep(sleep_between_retries);
-                }
-            }
-
-            // check for command timeout
-            if let Some(deadline) = deadline {
-                if Instant::now() > deadline {
-                    break;
-                }
-            }
-
-            // set command node, so when you return a record it has the node
-            let node = match self.get_node() {
-                Ok(node) => node,
-                Err(_) => continue, // Node is currently inactive. Retry.
-            };
-
-            let mut conn = match node.get_connection(base_policy.timeout()) {
-                Ok(conn) => conn,
-                Err(err) => {
-                    warn!("Node {}: {}", node, err);
-                    continue;
-                }
-            };
-
-            self.prepare_buffer(&mut conn)
-                .chain_err(|| "Failed to prepare send buffer")?;
-            self.write_timeout(&mut conn, base_policy.timeout())
-                .chain_err(|| "Failed to set timeout for send buffer")?;
-
-            // Send command.
-            if let Err(err) = self.write_buffer(&mut conn) {
-                // IO errors are considered temporary anomalies. Retry.
-                // Close socket to flush out possible garbage. Do not put back in pool.
-                conn.invalidate();
-                warn!("Node {}: {}", node, err);
-                continue;
-            }
-
-            // Parse results.
-            if let Err(err) = self.parse_result(&mut conn) {
-                // close the connection
-                // cancelling/closing the batch/multi commands will return an error, which will
-                // close the connection to throw away its data and signal the server about the
-                // situation. We will not put back the connection in the buffer.
-                if !commands::keep_connection(&err) {
-                    conn.invalidate();
-                }
-                return Err(err);
-            }
+    // Stream the records to the recordset as they arrive instead of storing them in the batch
+    // reads. Records that were not found are skipped. The command does not keep the recordset
+    // alive, so that it is cancelled once the consumer drops the recordset.
+    pub fn set_recordset(&mut self, recordset: Weak<Recordset>) {
+        self.recordset = Some(recordset);
+    }
 
-            // command has completed successfully.  Exit method.
-            return Ok(());
+    async fn stream_record(recordset: &Weak<Recordset>, record: Option<Record>) -> Result<()> {
+        let record = match record {
+            Some(record) => record,
+            None => return Ok(()),
+        };
+        if !Recordset::push_wait(recordset, Ok(record)).await {
+            bail!("Batch stream has been closed");
         }
-
-        bail!(ErrorKind::Connection("Timeout".to_string()))
+        Ok(())
     }
 
-    fn parse_group(&mut self, conn: &mut Connection, size: usize) -> Result<bool> {
+    async fn parse_group(&mut self, conn: &mut Connection, size: usize) -> Result<bool> {
         while conn.bytes_read() < size {
-            conn.read_buffer(commands::buffer::MSG_REMAINING_HEADER_SIZE as usize)?;
-            match self.parse_record(conn)? {
+            conn.read_buffer(commands::buffer::MSG_REMAINING_HEADER_SIZE as usize)
+                .await?;
+            match self.parse_record(conn).await? {
                 None => return Ok(false),
                 Some(batch_record) => {
+                    if let Some(ref recordset) = self.recordset {
+                        BatchReadCommand::stream_record(recordset, batch_record.record).await?;
+                        continue;
+                    }
                     let batch_read = self
                         .batch_reads
                         .get_mut(batch_record.batch_index)
                         .expect("Invalid batch index");
+                    batch_read.result_code = Some(batch_record.result_code);
                     batch_read.record = batch_record.record;
                 }
             }
@@ -151,17 +97,16 @@ impl<'a, 'b> BatchReadCommand<'a, 'b> {
         Ok(true)
     }
 
-    fn parse_record(&mut self, conn: &mut Connection) -> Result<Option<BatchRecord>> {
-        let found_key = match ResultCode::from(conn.buffer.read_u8(Some(5))?) {
-            ResultCode::Ok => true,
-            ResultCode::KeyNotFoundError => false,
-            rc => bail!(ErrorKind::ServerError(rc)),
-        };
+    async fn parse_record(&mut self, conn: &mut Connection) -> Result<Option<BatchRecord>> {
+        let result_code = ResultCode::from(conn.buffer.read_u8(Some(5))?);
 
         // if cmd is the end marker of the response, do not proceed further
         let info3 = conn.buffer.read_u8(Some(3))?;
         if info3 & commands::buffer::INFO3_LAST == commands::buffer::INFO3_LAST {
-            return Ok(None);
+            match result_code {
+                ResultCode::Ok | ResultCode::KeyNotFoundError => return Ok(None),
+                rc => bail!(ErrorKind::ServerError(rc)),
+            }
         }
 
         conn.buffer.skip(6)?;
@@ -171,46 +116,159 @@ impl<'a, 'b> BatchReadCommand<'a, 'b> {
         let field_count = conn.buffer.read_u16(None)? as usize; // almost certainly 0
         let op_count = conn.buffer.read_u16(None)? as usize;
 
-        let key = commands::StreamCommand::parse_key(conn, field_count)?;
-
-        let record = if found_key {
-            let mut bins: HashMap<String, Value> = HashMap::with_capacity(op_count);
-
-            for _ in 0..op_count {
-                conn.read_buffer(8)?;
-                let op_size = conn.buffer.read_u32(None)? as usize;
-                conn.buffer.skip(1)?;
-                let particle_type = conn.buffer.read_u8(None)?;
-                conn.buffer.skip(1)?;
-                let name_size = conn.buffer.read_u8(None)? as usize;
-                conn.read_buffer(name_size)?;
-                let name = conn.buffer.read_str(name_size)?;
-                let particle_bytes_size = op_size - (4 + name_size);
-                conn.read_buffer(particle_bytes_size)?;
-                let value =
-                    value::bytes_to_particle(particle_type, &mut conn.buffer, particle_bytes_size)?;
-                bins.insert(name, value);
-            }
+        let key = commands::StreamCommand::parse_key(conn, field_count).await?;
+
+        let mut bins: HashMap<String, Value> = HashMap::with_capacity(op_count);
+        for _ in 0..op_count {
+            conn.read_buffer(8).await?;
+            let op_size = conn.buffer.read_u32(None)? as usize;
+            conn.buffer.skip(1)?;
+            let particle_type = conn.buffer.read_u8(None)?;
+            conn.buffer.skip(1)?;
+            let name_size = conn.buffer.read_u8(None)? as usize;
+            conn.read_buffer(name_size).await?;
+            let name = conn.buffer.read_str(name_size)?;
+            let particle_bytes_size = op_size - (4 + name_size);
+            conn.read_buffer(particle_bytes_size).await?;
+            let value =
+                value::bytes_to_particle(particle_type, &mut conn.buffer, particle_bytes_size)?;
+            bins.insert(name, value);
+        }
 
+        // records that were not found or failed are reported by their result code only
+        let record = if result_code == ResultCode::Ok {
             Some(Record::new(Some(key), bins, generation, expiration))
         } else {
             None
         };
         Ok(Some(BatchRecord {
             batch_index: batch_index as usize,
+            result_code,
             record,
         }))
     }
 }
 
+// Execute a batch command against its node until successful, timed out or the maximum number of
+// retries has been reached.
+pub async fn execute_batch_command<C: Command>(policy: &BatchPolicy, cmd: &mut C) -> Result<()> {
+    let mut iterations = 0;
+    // whether the last attempt was rejected due to the error rate of the node
+    let mut rejected = false;
+    let base_policy = policy.base();
+
+    // set timeout outside the loop
+    let deadline = base_policy.deadline();
+
+    // Execute command until successful, timed out or maximum iterations have been reached.
+    loop {
+        iterations += 1;
+
+        // too many retries
+        if let Some(max_retries) = base_policy.max_retries() {
+            if iterations > max_retries + 1 {
+                if rejected {
+                    bail!(ErrorKind::MaxErrorRate);
+                }
+                bail!(ErrorKind::Connection(format!(
+                    "Timeout after {} tries",
+                    iterations
+                )));
+            }
+        }
+
+        // Sleep before trying again, after the first iteration
+        if iterations > 1 {
+            let sleep = base_policy.sleep_before_retry(iterations - 1);
+            if let Some(sleep) = commands::retry_sleep(sleep, deadline) {
+                cmd.runtime().sleep(sleep).await;
+            }
+        }
+
+        // check for command timeout
+        if let Some(deadline) = deadline {
+            if Instant::now() >= deadline {
+                break;
+            }
+        }
+
+        // set command node, so when you return a record it has the node
+        let node = match cmd.get_node() {
+            Ok(node) => node,
+            Err(_) => continue, // Node is currently inactive. Retry.
+        };
+        rejected = false;
+
+        let timeout = commands::attempt_timeout(base_policy.socket_timeout(), deadline);
+        let mut conn = match node.get_connection(timeout).await {
+            Ok(conn) => conn,
+            Err(err) => {
+                warn!("Node {}: {}", node, err);
+                rejected = matches!(err.kind(), ErrorKind::MaxErrorRate);
+                continue;
+            }
+        };
+
+        cmd.prepare_buffer(&mut conn)
+            .chain_err(|| "Failed to prepare send buffer")?;
+        cmd.write_timeout(&mut conn, deadline.map(commands::time_left))
+            .chain_err(|| "Failed to set timeout for send buffer")?;
+
+        // Send command.
+        if let Err(err) = cmd.write_buffer(&mut conn).await {
+            // IO errors are considered temporary anomalies. Retry.
+            // Close socket to flush out possible garbage. Do not put back in pool.
+            conn.invalidate();
+            node.add_error();
+            warn!("Node {}: {}", node, err);
+            continue;
+        }
+
+        // Parse results.
+        if let Err(err) = cmd.parse_result(&mut conn).await {
+            // Socket timeouts are retried like other network errors, unless records have been
+            // delivered to the consumer already.
+            if commands::is_timeout(&err) && cmd.retry_on_timeout() {
+                conn.invalidate();
+                node.add_error();
+                warn!("Node {}: {}", node, err);
+                continue;
+            }
+
+            // close the connection
+            // cancelling/closing the batch/multi commands will return an error, which will
+            // close the connection to throw away its data and signal the server about the
+            // situation. We will not put back the connection in the buffer.
+            if commands::keep_connection(&err) {
+                conn.mark_reusable();
+            } else {
+                conn.invalidate();
+                node.add_error();
+            }
+            return Err(err);
+        }
+
+        // command has completed successfully.  Exit method.
+        conn.mark_reusable();
+        return Ok(());
+    }
+
+    if rejected {
+        bail!(ErrorKind::MaxErrorRate);
+    }
+    bail!(ErrorKind::Connection("Timeout".to_string()))
+}
+
+impl<'a, 'b> commands::BatchCommand for BatchReadCommand<'a, 'b> {}
+
 impl<'a, 'b> commands::Command for BatchReadCommand<'a, 'b> {
     fn write_timeout(&mut self, conn: &mut Connection, timeout: Option<Duration>) -> Result<()> {
         conn.buffer.write_timeout(timeout);
         Ok(())
     }
 
-    fn write_buffer(&mut self, conn: &mut Connection) -> Result<()> {
-        conn.flush()
+    async fn write_buffer(&mut self, conn: &mut Connection) -> Result<()> {
+        conn.flush().await
     }
 
     fn prepare_buffer(&mut self, conn: &mut Connection) -> Result<()> {
@@ -225,15 +283,22 @@ impl<'a, 'b> commands::Command for BatchReadCommand<'a, 'b> {
         Ok(self.node.clone())
     }
 
-    fn parse_result(&mut self, conn: &mut Connection) -> Result<()> {
+    fn runtime(&self) -> Runtime {
+        self.node.runtime()
+    }
+
+    async fn parse_result(&mut self, conn: &mut Connection) -> Result<()> {
         loop {
-            conn.read_buffer(8)?;
+            conn.read_buffer(8).await?;
             let size = conn.buffer.read_msg_size(None)?;
             conn.bookmark();
-            if size > 0 && !self.parse_group(conn, size as usize)? {
+            if size > 0 && !self.parse_group(conn, size as usize).await? {
                 break;
             }
         }
         Ok(())
     }
+    fn retry_on_timeout(&self) -> bool {
+        self.recordset.is_none()
+    }
 }
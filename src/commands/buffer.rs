@@ -12,76 +12,86 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::convert::TryFrom;
 use std::str;
 use std::time::Duration;
 
 use byteorder::{ByteOrder, LittleEndian, NetworkEndian};
 
+use crate::batch::batch_attr::BatchAttr;
 use crate::batch::batch_executor::SharedSlice;
+use crate::batch::BatchEntry;
+use crate::cluster::partition::NodePartitions;
 use crate::commands::field_type::FieldType;
 use crate::errors::Result;
 use crate::expressions::FilterExpression;
 use crate::msgpack::encoder;
 use crate::operations::{Operation, OperationBin, OperationData, OperationType};
 use crate::policy::{
-    BatchPolicy, CommitLevel, ConsistencyLevel, GenerationPolicy, QueryPolicy, ReadPolicy,
-    RecordExistsAction, ScanPolicy, WritePolicy,
+    BatchPolicy, CommitLevel, ConsistencyLevel, GenerationPolicy, QueryDuration, QueryPolicy,
+    ReadPolicy, RecordExistsAction, ScanPolicy, WritePolicy,
 };
 use crate::{BatchRead, Bin, Bins, CollectionIndexType, Key, Statement, Value};
 
 // Contains a read operation.
-const INFO1_READ: u8 = 1;
+pub const INFO1_READ: u8 = 1;
 
 // Get all bins.
-const INFO1_GET_ALL: u8 = 1 << 1;
+pub const INFO1_GET_ALL: u8 = 1 << 1;
+
+// Short query.
+const INFO1_SHORT_QUERY: u8 = 1 << 2;
 
 // Batch read or exists.
 const INFO1_BATCH: u8 = 1 << 3;
 
 // Do not read the bins
-const INFO1_NOBINDATA: u8 = 1 << 5;
+pub const INFO1_NOBINDATA: u8 = 1 << 5;
 
 // Involve all replicas in read operation.
 const INFO1_CONSISTENCY_ALL: u8 = 1 << 6;
 
 // Create or update record
-const INFO2_WRITE: u8 = 1;
+pub const INFO2_WRITE: u8 = 1;
 
 // Fling a record into the belly of Moloch.
-const INFO2_DELETE: u8 = 1 << 1;
+pub const INFO2_DELETE: u8 = 1 << 1;
 
 // Update if expected generation == old.
-const INFO2_GENERATION: u8 = 1 << 2;
+pub const INFO2_GENERATION: u8 = 1 << 2;
 
 // Update if new generation >= old, good for restore.
-const INFO2_GENERATION_GT: u8 = 1 << 3;
+pub const INFO2_GENERATION_GT: u8 = 1 << 3;
 
 // Transaction resulting in record deletion leaves tombstone (Enterprise only).
-const INFO2_DURABLE_DELETE: u8 = 1 << 4;
+pub const INFO2_DURABLE_DELETE: u8 = 1 << 4;
 
 // Create only. Fail if record already exists.
-const INFO2_CREATE_ONLY: u8 = 1 << 5;
+pub const INFO2_CREATE_ONLY: u8 = 1 << 5;
+
+// Treat as long query, but relax read consistency.
+const INFO2_RELAX_AP_LONG_QUERY: u8 = 1 << 6;
 
 // Return a result for every operation.
-const INFO2_RESPOND_ALL_OPS: u8 = 1 << 7;
+pub const INFO2_RESPOND_ALL_OPS: u8 = 1 << 7;
 
 // This is the last of a multi-part message.
 pub const INFO3_LAST: u8 = 1;
 
 // Commit to master only before declaring success.
-const INFO3_COMMIT_MASTER: u8 = 1 << 1;
+pub const INFO3_COMMIT_MASTER: u8 = 1 << 1;
 
 // Partition is complete response in scan.
 pub const _INFO3_PARTITION_DONE: u8 = 1 << 2;
 
 // Update only. Merge bins.
-const INFO3_UPDATE_ONLY: u8 = 1 << 3;
+pub const INFO3_UPDATE_ONLY: u8 = 1 << 3;
 
 // Create or completely replace record.
-const INFO3_CREATE_OR_REPLACE: u8 = 1 << 4;
+pub const INFO3_CREATE_OR_REPLACE: u8 = 1 << 4;
 
 // Completely replace existing record only.
-const INFO3_REPLACE_ONLY: u8 = 1 << 5;
+pub const INFO3_REPLACE_ONLY: u8 = 1 << 5;
 
 pub const MSG_TOTAL_HEADER_SIZE: u8 = 30;
 const FIELD_HEADER_SIZE: u8 = 5;
@@ -91,6 +101,30 @@ const DIGEST_SIZE: u8 = 20;
 const CL_MSG_VERSION: u8 = 2;
 const AS_MSG_TYPE: u8 = 3;
 
+// Allow batch to be processed in the server's receiving thread.
+const BATCH_FLAG_INLINE: u8 = 1;
+
+// Allow batch to be processed in the server's receiving thread on SSD namespaces.
+const BATCH_FLAG_INLINE_SSD: u8 = 1 << 1;
+
+// Attempt all keys in the batch, regardless of errors on individual keys.
+const BATCH_FLAG_RESPOND_ALL_KEYS: u8 = 1 << 2;
+
+// Batch records carry their own attributes (batch operate protocol, server 6.0+).
+const BATCH_FLAG_OPERATE: u8 = 1 << 3;
+
+// Batch record repeats the namespace, set and operations of the previous record.
+const BATCH_MSG_REPEAT: u8 = 1;
+
+// Batch record contains read/write/info attributes.
+const BATCH_MSG_INFO: u8 = 1 << 1;
+
+// Batch record contains the expected generation.
+const BATCH_MSG_GEN: u8 = 1 << 2;
+
+// Batch record contains the record expiration.
+const BATCH_MSG_TTL: u8 = 1 << 3;
+
 // MAX_BUFFER_SIZE protects against allocating massive memory blocks
 // for buffers. Tweak this number if you are returning a lot of
 // LDT elements in your queries.
@@ -408,7 +442,7 @@ impl Buffer {
         };
         self.write_field_header(0, field_type)?;
         self.write_u32(offsets.len() as u32)?;
-        self.write_u8(if policy.allow_inline { 1 } else { 0 })?;
+        self.write_u8(Self::batch_flags(policy))?;
 
         prev = None;
         for idx in offsets {
@@ -469,6 +503,74 @@ impl Buffer {
     }
 
     // Writes the command for getting metadata operations
+    pub fn set_batch_operate<T: BatchEntry>(
+        &mut self,
+        policy: &BatchPolicy,
+        records: SharedSlice<T>,
+        offsets: &[usize],
+    ) -> Result<()> {
+        self.begin()?;
+        let mut field_count = 1;
+        self.data_offset += FIELD_HEADER_SIZE as usize + 5;
+
+        let filter_size = self.estimate_filter_size(policy.filter_expression())?;
+        if filter_size > 0 {
+            field_count += 1;
+        }
+
+        let mut prev: Option<&T> = None;
+        for idx in offsets {
+            let record: &T = records.get(*idx).unwrap();
+            self.data_offset += record.key().digest.len() + 4;
+            match prev {
+                Some(prev) if record.match_header(prev) => {
+                    self.data_offset += 1;
+                }
+                _ => {
+                    self.estimate_batch_record_size(record.key(), &record.batch_attr()?)?;
+                }
+            }
+            prev = Some(record);
+        }
+
+        self.size_buffer()?;
+        self.write_header(&policy.base_policy, INFO1_BATCH, 0, field_count, 0)?;
+
+        if let Some(filter) = policy.filter_expression() {
+            self.write_filter_expression(filter, filter_size)?;
+        }
+
+        let field_size_offset = self.data_offset;
+        self.write_field_header(0, FieldType::BatchIndex)?;
+        self.write_u32(offsets.len() as u32)?;
+        self.write_u8(BATCH_FLAG_OPERATE | Self::batch_flags(policy))?;
+
+        prev = None;
+        for idx in offsets {
+            let record = records.get(*idx).unwrap();
+            let key = record.key();
+            self.write_u32(*idx as u32)?;
+            self.write_bytes(&key.digest)?;
+            match prev {
+                Some(prev) if record.match_header(prev) => {
+                    self.write_u8(BATCH_MSG_REPEAT)?;
+                }
+                _ => {
+                    self.write_batch_record(key, &record.batch_attr()?)?;
+                }
+            }
+            prev = Some(record);
+        }
+
+        let field_size = self.data_offset - MSG_TOTAL_HEADER_SIZE as usize - 4;
+        NetworkEndian::write_u32(
+            &mut self.data_buffer[field_size_offset..field_size_offset + 4],
+            field_size as u32,
+        );
+
+        self.end()
+    }
+
     pub fn set_operate<'a>(
         &mut self,
         policy: &WritePolicy,
@@ -479,6 +581,8 @@ impl Buffer {
 
         let mut read_attr = 0;
         let mut write_attr = 0;
+        let mut read_header = false;
+        let mut read_bin = false;
 
         for operation in operations {
             match *operation {
@@ -486,12 +590,18 @@ impl Buffer {
                     op: OperationType::Read,
                     bin: OperationBin::None,
                     ..
-                } => read_attr |= INFO1_READ | INFO1_NOBINDATA,
+                } => {
+                    read_attr |= INFO1_READ;
+                    read_header = true;
+                }
                 Operation {
                     op: OperationType::Read,
                     bin: OperationBin::All,
                     ..
-                } => read_attr |= INFO1_READ | INFO1_GET_ALL,
+                } => {
+                    read_attr |= INFO1_READ | INFO1_GET_ALL;
+                    read_bin = true;
+                }
                 Operation {
                     op: OperationType::Read,
                     ..
@@ -511,7 +621,10 @@ impl Buffer {
                 | Operation {
                     op: OperationType::ExpRead,
                     ..
-                } => read_attr |= INFO1_READ,
+                } => {
+                    read_attr |= INFO1_READ;
+                    read_bin = true;
+                }
                 _ => write_attr |= INFO2_WRITE,
             }
 
@@ -530,6 +643,11 @@ impl Buffer {
             self.data_offset += operation.estimate_size()? + OPERATION_HEADER_SIZE as usize;
         }
 
+        // Only suppress bin data if the header is the only thing being read.
+        if read_header && !read_bin {
+            read_attr |= INFO1_NOBINDATA;
+        }
+
         let mut field_count = self.estimate_key_size(key, policy.send_key && write_attr != 0)?;
         let filter_size = self.estimate_filter_size(policy.filter_expression())?;
         if filter_size > 0 {
@@ -604,7 +722,7 @@ impl Buffer {
         set_name: &str,
         bins: &Bins,
         task_id: u64,
-        partitions: &Vec<u16>,
+        partitions: &NodePartitions,
     ) -> Result<()> {
         self.begin()?;
 
@@ -628,14 +746,17 @@ impl Buffer {
         // self.data_offset += 2 + FIELD_HEADER_SIZE as usize;
         // field_count += 1;
 
-        // Estimate pid size
-        self.data_offset += partitions.len() * 2 + FIELD_HEADER_SIZE as usize;
-        field_count += 1;
+        field_count += self.estimate_partitions_size(partitions);
 
         // Estimate scan timeout size.
         self.data_offset += 4 + FIELD_HEADER_SIZE as usize;
         field_count += 1;
 
+        if policy.records_per_second > 0 {
+            self.data_offset += 4 + FIELD_HEADER_SIZE as usize;
+            field_count += 1;
+        }
+
         // Allocate space for task_id field.
         self.data_offset += 8 + FIELD_HEADER_SIZE as usize;
         field_count += 1;
@@ -653,7 +774,7 @@ impl Buffer {
         self.size_buffer()?;
 
         let mut read_attr = INFO1_READ;
-        if bins.is_none() {
+        if bins.is_none() || !policy.include_bin_data {
             read_attr |= INFO1_NOBINDATA;
         }
 
@@ -673,10 +794,7 @@ impl Buffer {
             self.write_field_string(set_name, FieldType::Table)?;
         }
 
-        self.write_field_header(partitions.len() * 2, FieldType::PIDArray)?;
-        for pid in partitions {
-            self.write_u16_little_endian(*pid)?;
-        }
+        self.write_partitions(partitions)?;
 
         if let Some(filter) = policy.filter_expression() {
             self.write_filter_expression(filter, filter_size)?;
@@ -695,8 +813,16 @@ impl Buffer {
         // self.write_u8(policy.scan_percent)?;
 
         // Write scan timeout
+        let scan_timeout = policy.base_policy.socket_timeout.map_or(0, |timeout| {
+            u32::try_from(timeout.as_millis()).unwrap_or(u32::MAX)
+        });
         self.write_field_header(4, FieldType::ScanTimeout)?;
-        self.write_u32(policy.socket_timeout)?;
+        self.write_u32(scan_timeout)?;
+
+        if policy.records_per_second > 0 {
+            self.write_field_header(4, FieldType::RecordsPerSecond)?;
+            self.write_u32(policy.records_per_second)?;
+        }
 
         self.write_field_header(8, FieldType::TranId)?;
         self.write_u64(task_id)?;
@@ -717,7 +843,7 @@ impl Buffer {
         statement: &Statement,
         write: bool,
         task_id: u64,
-        partitions: &Vec<u16>,
+        partitions: &NodePartitions,
     ) -> Result<()> {
         let filter = match statement.filters {
             Some(ref filters) => Some(&filters[0]),
@@ -751,6 +877,11 @@ impl Buffer {
         self.data_offset += 8 + FIELD_HEADER_SIZE as usize;
         field_count += 1;
 
+        if policy.records_per_second > 0 {
+            self.data_offset += 4 + FIELD_HEADER_SIZE as usize;
+            field_count += 1;
+        }
+
         if let Some(filter) = filter {
             let idx_type = filter.collection_index_type();
             if idx_type != CollectionIndexType::Default {
@@ -773,14 +904,17 @@ impl Buffer {
                 self.data_offset += bin_name_size;
                 field_count += 1;
             }
+
+            if partitions.max_records > 0 {
+                self.data_offset += 8 + FIELD_HEADER_SIZE as usize;
+                field_count += 1;
+            }
         } else {
             // Calling query with no filters is more efficiently handled by a primary index scan.
             // Estimate scan options size.
             // self.data_offset += 2 + FIELD_HEADER_SIZE as usize;
             // field_count += 1;
-            // Estimate pid size
-            self.data_offset += partitions.len() * 2 + FIELD_HEADER_SIZE as usize;
-            field_count += 1;
+            field_count += self.estimate_partitions_size(partitions);
         }
         let filter_exp_size = self.estimate_filter_size(policy.filter_expression())?;
         if filter_exp_size > 0 {
@@ -816,12 +950,19 @@ impl Buffer {
             }
         }
 
-        let info1 = if statement.bins.is_none() {
+        let mut info1 = if statement.bins.is_none() || !policy.include_bin_data {
             INFO1_READ | INFO1_NOBINDATA
         } else {
             INFO1_READ
         };
-        let info2 = if write { INFO2_WRITE } else { 0 };
+        let mut info2 = if write { INFO2_WRITE } else { 0 };
+        if !write && statement.aggregation.is_none() {
+            match policy.expected_duration {
+                QueryDuration::Long => (),
+                QueryDuration::Short => info1 |= INFO1_SHORT_QUERY,
+                QueryDuration::LongRelaxAP => info2 |= INFO2_RELAX_AP_LONG_QUERY,
+            }
+        }
 
         self.write_header(
             &policy.base_policy,
@@ -848,6 +989,11 @@ impl Buffer {
         self.write_field_header(8, FieldType::TranId)?;
         self.write_u64(task_id)?;
 
+        if policy.records_per_second > 0 {
+            self.write_field_header(4, FieldType::RecordsPerSecond)?;
+            self.write_u32(policy.records_per_second)?;
+        }
+
         if let Some(filter) = filter {
             let idx_type = filter.collection_index_type();
 
@@ -872,16 +1018,18 @@ impl Buffer {
                     }
                 }
             }
+
+            if partitions.max_records > 0 {
+                self.write_field_header(8, FieldType::MaxRecords)?;
+                self.write_u64(partitions.max_records)?;
+            }
         } else {
             // // Calling query with no filters is more efficiently handled by a primary index scan.
             // self.write_field_header(2, FieldType::ScanOptions)?;
             // let priority: u8 = (policy.base_policy.priority.clone() as u8) << 4;
             // self.write_u8(priority)?;
             // self.write_u8(100)?;
-            self.write_field_header(partitions.len() * 2, FieldType::PIDArray)?;
-            for pid in partitions {
-                self.write_u16_little_endian(*pid)?;
-            }
+            self.write_partitions(partitions)?;
         }
 
         if let Some(filter_exp) = policy.filter_expression() {
@@ -890,11 +1038,7 @@ impl Buffer {
 
         if let Some(ref aggregation) = statement.aggregation {
             self.write_field_header(1, FieldType::UdfOp)?;
-            if statement.bins.is_none() {
-                self.write_u8(2)?;
-            } else {
-                self.write_u8(1)?;
-            }
+            self.write_u8(1)?; // aggregate
 
             self.write_field_string(&aggregation.package_name, FieldType::UdfPackageName)?;
             self.write_field_string(&aggregation.function_name, FieldType::UdfFunction)?;
@@ -917,6 +1061,163 @@ impl Buffer {
         self.end()
     }
 
+    // Background query or scan, that applies the operations to all matching records on the server.
+    pub fn set_background_query(
+        &mut self,
+        policy: &WritePolicy,
+        statement: &Statement,
+        operations: &[Operation],
+        task_id: u64,
+        partitions: &NodePartitions,
+    ) -> Result<()> {
+        let filter = match statement.filters {
+            Some(ref filters) => filters.first(),
+            None => None,
+        };
+
+        self.begin()?;
+
+        let mut field_count = 0;
+        let mut filter_size = 0;
+
+        if !statement.namespace.is_empty() {
+            self.data_offset += statement.namespace.len() + FIELD_HEADER_SIZE as usize;
+            field_count += 1;
+        }
+
+        if !statement.set_name.is_empty() {
+            self.data_offset += statement.set_name.len() + FIELD_HEADER_SIZE as usize;
+            field_count += 1;
+        }
+
+        if let Some(ref index_name) = statement.index_name {
+            if !index_name.is_empty() {
+                self.data_offset += index_name.len() + FIELD_HEADER_SIZE as usize;
+                field_count += 1;
+            }
+        }
+
+        // Allocate space for TaskId field.
+        self.data_offset += 8 + FIELD_HEADER_SIZE as usize;
+        field_count += 1;
+
+        if let Some(filter) = filter {
+            if filter.collection_index_type() != CollectionIndexType::Default {
+                self.data_offset += 1 + FIELD_HEADER_SIZE as usize;
+                field_count += 1;
+            }
+
+            filter_size = 1 + filter.estimate_size()?;
+            self.data_offset += filter_size + FIELD_HEADER_SIZE as usize;
+            field_count += 1;
+        } else {
+            field_count += self.estimate_partitions_size(partitions);
+        }
+
+        let filter_exp_size = self.estimate_filter_size(policy.filter_expression())?;
+        if filter_exp_size > 0 {
+            field_count += 1;
+        }
+
+        for operation in operations {
+            self.data_offset += operation.estimate_size()? + OPERATION_HEADER_SIZE as usize;
+        }
+
+        self.size_buffer()?;
+        self.write_header_with_policy(
+            policy,
+            0,
+            INFO2_WRITE,
+            field_count,
+            operations.len() as u16,
+        )?;
+
+        if !statement.namespace.is_empty() {
+            self.write_field_string(&statement.namespace, FieldType::Namespace)?;
+        }
+
+        if let Some(ref index_name) = statement.index_name {
+            if !index_name.is_empty() {
+                self.write_field_string(index_name, FieldType::IndexName)?;
+            }
+        }
+
+        if !statement.set_name.is_empty() {
+            self.write_field_string(&statement.set_name, FieldType::Table)?;
+        }
+
+        self.write_field_header(8, FieldType::TranId)?;
+        self.write_u64(task_id)?;
+
+        if let Some(filter) = filter {
+            let idx_type = filter.collection_index_type();
+            if idx_type != CollectionIndexType::Default {
+                self.write_field_header(1, FieldType::IndexType)?;
+                self.write_u8(idx_type as u8)?;
+            }
+
+            self.write_field_header(filter_size, FieldType::IndexRange)?;
+            self.write_u8(1)?;
+            filter.write(self)?;
+        } else {
+            self.write_partitions(partitions)?;
+        }
+
+        if let Some(filter_exp) = policy.filter_expression() {
+            self.write_filter_expression(filter_exp, filter_exp_size)?;
+        }
+
+        for operation in operations {
+            operation.write_to(self)?;
+        }
+
+        self.end()
+    }
+
+    // Partitions that are scanned from the start are sent by id; partitions that are resumed are
+    // sent by the digest of the last record returned, from which the server derives the
+    // partition.
+    fn estimate_partitions_size(&mut self, node_partitions: &NodePartitions) -> u16 {
+        let mut field_count = 0;
+        let partitions = &node_partitions.partitions;
+        let digests = &node_partitions.digests;
+        if !partitions.is_empty() || digests.is_empty() {
+            self.data_offset += partitions.len() * 2 + FIELD_HEADER_SIZE as usize;
+            field_count += 1;
+        }
+        if !digests.is_empty() {
+            self.data_offset += digests.len() * DIGEST_SIZE as usize + FIELD_HEADER_SIZE as usize;
+            field_count += 1;
+        }
+        if node_partitions.max_records > 0 {
+            self.data_offset += 8 + FIELD_HEADER_SIZE as usize;
+            field_count += 1;
+        }
+        field_count
+    }
+
+    fn write_partitions(&mut self, node_partitions: &NodePartitions) -> Result<()> {
+        let partitions = &node_partitions.partitions;
+        let digests = &node_partitions.digests;
+        if !partitions.is_empty() || digests.is_empty() {
+            self.write_field_header(partitions.len() * 2, FieldType::PIDArray)?;
+            for pid in partitions {
+                self.write_u16_little_endian(*pid)?;
+            }
+        }
+        if !digests.is_empty() {
+            self.write_field_header(digests.len() * DIGEST_SIZE as usize, FieldType::DigestArray)?;
+            for digest in digests {
+                self.write_bytes(digest)?;
+            }
+        }
+        if node_partitions.max_records > 0 {
+            self.write_field_header(8, FieldType::MaxRecords)?;
+            self.write_u64(node_partitions.max_records)?;
+        }
+        Ok(())
+    }
+
     fn estimate_filter_size(&mut self, filter: &Option<FilterExpression>) -> Result<usize> {
         if let Some(filter) = filter {
             let filter_size = filter.pack(&mut None)?;
@@ -954,6 +1255,56 @@ impl Buffer {
         Ok(field_count)
     }
 
+    fn batch_flags(policy: &BatchPolicy) -> u8 {
+        let mut flags = 0;
+        if policy.allow_inline {
+            flags |= BATCH_FLAG_INLINE;
+        }
+        if policy.allow_inline_ssd {
+            flags |= BATCH_FLAG_INLINE_SSD;
+        }
+        if policy.respond_all_keys {
+            flags |= BATCH_FLAG_RESPOND_ALL_KEYS;
+        }
+        flags
+    }
+
+    fn estimate_batch_record_size(&mut self, key: &Key, attr: &BatchAttr) -> Result<()> {
+        // info flags(1) + attributes(3) + field count(2) + operation count(2) = 8
+        self.data_offset += 8;
+        if attr.has_write {
+            // generation(2) + expiration(4) = 6
+            self.data_offset += 6;
+        }
+
+        self.data_offset += key.namespace.len() + FIELD_HEADER_SIZE as usize;
+        self.data_offset += key.set_name.len() + FIELD_HEADER_SIZE as usize;
+
+        if let Some(filter) = attr.filter_expression {
+            self.data_offset += filter.pack(&mut None)? + FIELD_HEADER_SIZE as usize;
+        }
+
+        if attr.send_key {
+            if let Some(ref user_key) = key.user_key {
+                self.data_offset += user_key.estimate_size()? + FIELD_HEADER_SIZE as usize + 1;
+            }
+        }
+
+        if let Some(ref udf) = attr.udf {
+            self.estimate_udf_size(udf.package_name, udf.function_name, udf.args)?;
+        }
+
+        for operation in attr.operations {
+            self.data_offset += operation.estimate_size()? + OPERATION_HEADER_SIZE as usize;
+        }
+
+        for bin_name in attr.bin_names {
+            self.estimate_operation_size_for_bin_name(bin_name)?;
+        }
+
+        Ok(())
+    }
+
     fn estimate_args_size(&mut self, args: Option<&[Value]>) -> Result<()> {
         if let Some(args) = args {
             self.data_offset += encoder::pack_array(&mut None, args)? + FIELD_HEADER_SIZE as usize;
@@ -1117,6 +1468,69 @@ impl Buffer {
         Ok(())
     }
 
+    fn write_batch_record(&mut self, key: &Key, attr: &BatchAttr) -> Result<()> {
+        let mut field_count: u16 = 2;
+
+        let filter_size = match attr.filter_expression {
+            Some(filter) => {
+                field_count += 1;
+                filter.pack(&mut None)?
+            }
+            None => 0,
+        };
+
+        let user_key = if attr.send_key {
+            key.user_key.as_ref()
+        } else {
+            None
+        };
+        if user_key.is_some() {
+            field_count += 1;
+        }
+        if attr.udf.is_some() {
+            field_count += 3;
+        }
+
+        if attr.has_write {
+            self.write_u8(BATCH_MSG_INFO | BATCH_MSG_GEN | BATCH_MSG_TTL)?;
+        } else {
+            self.write_u8(BATCH_MSG_INFO)?;
+        }
+        self.write_u8(attr.read_attr)?;
+        self.write_u8(attr.write_attr)?;
+        self.write_u8(attr.info_attr)?;
+        if attr.has_write {
+            self.write_u16(attr.generation)?;
+            self.write_u32(attr.expiration)?;
+        }
+        self.write_u16(field_count)?;
+        self.write_u16((attr.operations.len() + attr.bin_names.len()) as u16)?;
+
+        if let Some(filter) = attr.filter_expression {
+            self.write_filter_expression(filter, filter_size)?;
+        }
+        self.write_field_string(&key.namespace, FieldType::Namespace)?;
+        self.write_field_string(&key.set_name, FieldType::Table)?;
+        if let Some(user_key) = user_key {
+            self.write_field_value(user_key, FieldType::Key)?;
+        }
+        if let Some(ref udf) = attr.udf {
+            self.write_field_string(udf.package_name, FieldType::UdfPackageName)?;
+            self.write_field_string(udf.function_name, FieldType::UdfFunction)?;
+            self.write_args(udf.args, FieldType::UdfArgList)?;
+        }
+
+        for operation in attr.operations {
+            operation.write_to(self)?;
+        }
+
+        for bin_name in attr.bin_names {
+            self.write_operation_for_bin_name(bin_name, OperationType::Read)?;
+        }
+
+        Ok(())
+    }
+
     fn write_filter_expression(&mut self, filter: &FilterExpression, size: usize) -> Result<()> {
         self.write_field_header(size, FieldType::FilterExp)?;
         filter.pack(&mut Some(self))?;
@@ -20,6 +20,7 @@ use crate::commands::{buffer, Command, SingleCommand};
 use crate::errors::{ErrorKind, Result};
 use crate::net::Connection;
 use crate::policy::WritePolicy;
+use crate::runtime::Runtime;
 use crate::{Key, ResultCode};
 
 pub struct DeleteCommand<'a> {
@@ -37,8 +38,8 @@ impl<'a> DeleteCommand<'a> {
         }
     }
 
-    pub fn execute(&mut self) -> Result<()> {
-        SingleCommand::execute(self.policy, self)
+    pub async fn execute(&mut self) -> Result<()> {
+        SingleCommand::execute(self.policy, self).await
     }
 }
 
@@ -48,8 +49,8 @@ impl<'a> Command for DeleteCommand<'a> {
         Ok(())
     }
 
-    fn write_buffer(&mut self, conn: &mut Connection) -> Result<()> {
-        conn.flush()
+    async fn write_buffer(&mut self, conn: &mut Connection) -> Result<()> {
+        conn.flush().await
     }
 
     fn prepare_buffer(&mut self, conn: &mut Connection) -> Result<()> {
@@ -60,9 +61,16 @@ impl<'a> Command for DeleteCommand<'a> {
         self.single_command.get_node()
     }
 
-    fn parse_result(&mut self, conn: &mut Connection) -> Result<()> {
+    fn runtime(&self) -> Runtime {
+        self.single_command.runtime()
+    }
+
+    async fn parse_result(&mut self, conn: &mut Connection) -> Result<()> {
         // Read header.
-        if let Err(err) = conn.read_buffer(buffer::MSG_TOTAL_HEADER_SIZE as usize) {
+        if let Err(err) = conn
+            .read_buffer(buffer::MSG_TOTAL_HEADER_SIZE as usize)
+            .await
+        {
             warn!("Parse result error: {}", err);
             return Err(err);
         }
@@ -79,6 +87,6 @@ impl<'a> Command for DeleteCommand<'a> {
 
         self.existed = result_code == ResultCode::Ok;
 
-        SingleCommand::empty_socket(conn)
+        SingleCommand::empty_socket(conn).await
     }
 }
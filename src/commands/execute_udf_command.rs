@@ -21,6 +21,7 @@ use crate::commands::{Command, ReadCommand, SingleCommand};
 use crate::errors::Result;
 use crate::net::Connection;
 use crate::policy::WritePolicy;
+use crate::runtime::Runtime;
 use crate::{Bins, Key, Value};
 
 pub struct ExecuteUDFCommand<'a> {
@@ -49,8 +50,8 @@ impl<'a> ExecuteUDFCommand<'a> {
         }
     }
 
-    pub fn execute(&mut self) -> Result<()> {
-        SingleCommand::execute(self.policy, self)
+    pub async fn execute(&mut self) -> Result<()> {
+        SingleCommand::execute(self.policy, self).await
     }
 }
 
@@ -60,8 +61,8 @@ impl<'a> Command for ExecuteUDFCommand<'a> {
         Ok(())
     }
 
-    fn write_buffer(&mut self, conn: &mut Connection) -> Result<()> {
-        conn.flush()
+    async fn write_buffer(&mut self, conn: &mut Connection) -> Result<()> {
+        conn.flush().await
     }
 
     fn prepare_buffer(&mut self, conn: &mut Connection) -> Result<()> {
@@ -78,7 +79,11 @@ impl<'a> Command for ExecuteUDFCommand<'a> {
         self.read_command.get_node()
     }
 
-    fn parse_result(&mut self, conn: &mut Connection) -> Result<()> {
-        self.read_command.parse_result(conn)
+    fn runtime(&self) -> Runtime {
+        self.read_command.runtime()
+    }
+
+    async fn parse_result(&mut self, conn: &mut Connection) -> Result<()> {
+        self.read_command.parse_result(conn).await
     }
 }
@@ -28,6 +28,9 @@ pub enum FieldType {
     // ScanOptions = 8,
     ScanTimeout = 9,
     PIDArray = 11,
+    DigestArray = 12,
+    MaxRecords = 13,
+    RecordsPerSecond = 14,
     IndexName = 21,
     IndexRange = 22,
     // IndexFilter = 23,
@@ -31,11 +31,11 @@ pub struct Message {
 }
 
 impl Message {
-    pub fn info(conn: &mut Connection, commands: &[&str]) -> Result<HashMap<String, String>> {
+    pub async fn info(conn: &mut Connection, commands: &[&str]) -> Result<HashMap<String, String>> {
         let cmd = commands.join("\n") + "\n";
         let mut msg = Message::new(&cmd.into_bytes())?;
 
-        msg.send(conn)?;
+        msg.send(conn).await?;
         Ok(msg.parse_response()?)
     }
 
@@ -59,11 +59,11 @@ impl Message {
         rdr.read_u64::<NetworkEndian>().unwrap()
     }
 
-    fn send(&mut self, conn: &mut Connection) -> Result<()> {
-        conn.write(&self.buf)?;
+    async fn send(&mut self, conn: &mut Connection) -> Result<()> {
+        conn.write(&self.buf).await?;
 
         // read the header
-        conn.read(self.buf[..8].as_mut())?;
+        conn.read(self.buf[..8].as_mut()).await?;
 
         // figure our message size and grow the buffer if necessary
         let data_len = self.data_len() as usize;
@@ -76,7 +76,7 @@ impl Message {
         self.buf.resize(data_len, 0);
 
         // read the message content
-        conn.read(self.buf.as_mut())?;
+        conn.read(self.buf.as_mut()).await?;
 
         Ok(())
     }
@@ -103,3 +103,74 @@ impl Message {
         Ok(result)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Message;
+    use crate::net::{Connection, Host};
+    use crate::policy::ClientPolicy;
+    use crate::runtime::{self, Runtime};
+    use parking_lot::RwLock;
+    use std::collections::HashMap;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    // Answers a single info request with the given response.
+    fn serve_info(response: &'static str) -> Host {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        thread::spawn(move || {
+            let (mut socket, _) = listener.accept().unwrap();
+            let mut header = [0u8; 8];
+            socket.read_exact(&mut header).unwrap();
+            let mut request = vec![0u8; u64::from_be_bytes(header) as usize & 0xFFFF_FFFF_FFFF];
+            socket.read_exact(&mut request).unwrap();
+
+            let mut message = ((2u64 << 56) | (1 << 48) | response.len() as u64)
+                .to_be_bytes()
+                .to_vec();
+            message.extend_from_slice(response.as_bytes());
+            socket.write_all(&message).unwrap();
+        });
+        Host::new("127.0.0.1", port)
+    }
+
+    async fn info(host: Host, runtime: Runtime) -> HashMap<String, String> {
+        let policy = ClientPolicy::default();
+        let mut conn = Connection::new(&host, &policy, &None, &RwLock::new(None), runtime)
+            .await
+            .unwrap();
+        Message::info(&mut conn, &["node", "build"]).await.unwrap()
+    }
+
+    #[test]
+    fn info_blocking() {
+        let host = serve_info("node\tBB9\nbuild\t6.0.0\n");
+        let response = runtime::block_on(info(host, Runtime::Blocking));
+        assert_eq!(response["node"], "BB9");
+        assert_eq!(response["build"], "6.0.0");
+    }
+
+    #[cfg(feature = "rt-tokio")]
+    #[test]
+    fn info_tokio() {
+        let host = serve_info("node\tBB9\nbuild\t6.0.0\n");
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let response = rt.block_on(info(host, Runtime::Tokio));
+        assert_eq!(response["node"], "BB9");
+        assert_eq!(response["build"], "6.0.0");
+    }
+
+    #[cfg(feature = "rt-async-std")]
+    #[test]
+    fn info_async_std() {
+        let host = serve_info("node\tBB9\nbuild\t6.0.0\n");
+        let response = async_std::task::block_on(info(host, Runtime::AsyncStd));
+        assert_eq!(response["node"], "BB9");
+        assert_eq!(response["build"], "6.0.0");
+    }
+}
@@ -13,6 +13,8 @@
 // limitations under the License.
 
 pub mod admin_command;
+pub mod background_query_command;
+pub mod batch_operate_command;
 pub mod batch_read_command;
 pub mod buffer;
 pub mod delete_command;
@@ -31,9 +33,13 @@ pub mod write_command;
 
 mod field_type;
 
+use std::cmp;
+use std::io;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
+pub use self::background_query_command::BackgroundQueryCommand;
+pub use self::batch_operate_command::BatchOperateCommand;
 pub use self::batch_read_command::BatchReadCommand;
 pub use self::delete_command::DeleteCommand;
 pub use self::execute_udf_command::ExecuteUDFCommand;
@@ -52,6 +58,7 @@ pub use self::write_command::WriteCommand;
 use crate::cluster::Node;
 use crate::errors::{Error, ErrorKind, Result};
 use crate::net::Connection;
+use crate::runtime::Runtime;
 use crate::ResultCode;
 
 // Command interface describes all commands available
@@ -59,8 +66,27 @@ pub trait Command {
     fn write_timeout(&mut self, conn: &mut Connection, timeout: Option<Duration>) -> Result<()>;
     fn prepare_buffer(&mut self, conn: &mut Connection) -> Result<()>;
     fn get_node(&self) -> Result<Arc<Node>>;
-    fn parse_result(&mut self, conn: &mut Connection) -> Result<()>;
-    fn write_buffer(&mut self, conn: &mut Connection) -> Result<()>;
+    fn runtime(&self) -> Runtime;
+    async fn parse_result(&mut self, conn: &mut Connection) -> Result<()>;
+    async fn write_buffer(&mut self, conn: &mut Connection) -> Result<()>;
+
+    // Called before each retry, e.g. to move on to the next replica of the partition.
+    fn prepare_retry(&mut self) {}
+
+    // Whether the command may be sent again if reading its response times out. Commands that
+    // stream their records to the consumer must not be retried, since the records received
+    // before the timeout would be delivered twice.
+    fn retry_on_timeout(&self) -> bool {
+        true
+    }
+}
+
+// Batch commands can record a failure of their node on each of their records instead of failing
+// the whole batch. Returns true if the error has been recorded.
+pub trait BatchCommand: Command {
+    fn set_node_error(&mut self, _err: &Error) -> bool {
+        false
+    }
 }
 
 pub const fn keep_connection(err: &Error) -> bool {
@@ -71,3 +97,96 @@ pub const fn keep_connection(err: &Error) -> bool {
         _ => false,
     }
 }
+
+// Whether the error is a socket read or write timeout. Depending on the platform, a blocking
+// socket reports a timeout as either `TimedOut` or `WouldBlock`.
+pub fn is_timeout(err: &Error) -> bool {
+    match *err.kind() {
+        ErrorKind::Io(ref err) => matches!(
+            err.kind(),
+            io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock
+        ),
+        _ => false,
+    }
+}
+
+// Time left until the deadline of a command. Never zero, since the server treats a zero timeout
+// as no timeout.
+pub fn time_left(deadline: Instant) -> Duration {
+    cmp::max(
+        deadline.saturating_duration_since(Instant::now()),
+        Duration::from_millis(1),
+    )
+}
+
+// Socket timeout of the next attempt of a command: the socket timeout of the policy, capped by
+// the time left until the deadline of the command.
+pub fn attempt_timeout(
+    socket_timeout: Option<Duration>,
+    deadline: Option<Instant>,
+) -> Option<Duration> {
+    match (socket_timeout, deadline.map(time_left)) {
+        (Some(socket_timeout), Some(time_left)) => Some(cmp::min(socket_timeout, time_left)),
+        (socket_timeout, time_left) => socket_timeout.or(time_left),
+    }
+}
+
+// Time to sleep before the retry of a command: the sleep of the policy, capped by the time left
+// until the deadline of the command. No sleep once the deadline has passed.
+pub fn retry_sleep(sleep: Option<Duration>, deadline: Option<Instant>) -> Option<Duration> {
+    let sleep = sleep?;
+    match deadline.map(|deadline| deadline.saturating_duration_since(Instant::now())) {
+        Some(time_left) if time_left.is_zero() => None,
+        Some(time_left) => Some(cmp::min(sleep, time_left)),
+        None => Some(sleep),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::time::{Duration, Instant};
+
+    use super::{attempt_timeout, is_timeout, retry_sleep, time_left};
+    use crate::errors::{Error, ErrorKind};
+
+    #[test]
+    fn timeouts() {
+        let second = Duration::from_secs(1);
+        let deadline = Some(Instant::now() + 10 * second);
+        assert_eq!(attempt_timeout(None, None), None);
+        assert_eq!(attempt_timeout(Some(second), None), Some(second));
+        assert_eq!(attempt_timeout(Some(second), deadline), Some(second));
+        assert!(attempt_timeout(None, deadline).unwrap() > 9 * second);
+        assert!(attempt_timeout(Some(20 * second), deadline).unwrap() <= 10 * second);
+
+        let expired = Instant::now() - second;
+        assert_eq!(time_left(expired), Duration::from_millis(1));
+    }
+
+    #[test]
+    fn retry_sleeps() {
+        let second = Duration::from_secs(1);
+        let deadline = Some(Instant::now() + 10 * second);
+        assert_eq!(retry_sleep(None, deadline), None);
+        assert_eq!(retry_sleep(Some(second), None), Some(second));
+        assert_eq!(retry_sleep(Some(second), deadline), Some(second));
+        assert!(retry_sleep(Some(60 * second), deadline).unwrap() <= 10 * second);
+
+        let expired = Some(Instant::now() - second);
+        assert_eq!(retry_sleep(Some(second), expired), None);
+    }
+
+    #[test]
+    fn timeout_errors() {
+        let timed_out: Error = io::Error::from(io::ErrorKind::TimedOut).into();
+        assert!(is_timeout(&timed_out));
+        let would_block: Error = io::Error::from(io::ErrorKind::WouldBlock).into();
+        assert!(is_timeout(&would_block));
+        let reset: Error = io::Error::from(io::ErrorKind::ConnectionReset).into();
+        assert!(!is_timeout(&reset));
+        assert!(!is_timeout(
+            &ErrorKind::Connection("Timeout".to_string()).into()
+        ));
+    }
+}
@@ -21,6 +21,7 @@ use crate::errors::Result;
 use crate::net::Connection;
 use crate::operations::Operation;
 use crate::policy::WritePolicy;
+use crate::runtime::Runtime;
 use crate::{Bins, Key};
 
 pub struct OperateCommand<'a> {
@@ -36,15 +37,17 @@ impl<'a> OperateCommand<'a> {
         key: &'a Key,
         operations: &'a [Operation<'a>],
     ) -> Self {
+        let mut read_command = ReadCommand::new(&policy.base_policy, cluster, key, Bins::All);
+        read_command.set_respond_all_ops(policy.respond_per_each_op);
         OperateCommand {
-            read_command: ReadCommand::new(&policy.base_policy, cluster, key, Bins::All),
+            read_command,
             policy,
             operations,
         }
     }
 
-    pub fn execute(&mut self) -> Result<()> {
-        SingleCommand::execute(self.policy, self)
+    pub async fn execute(&mut self) -> Result<()> {
+        SingleCommand::execute(self.policy, self).await
     }
 }
 
@@ -54,8 +57,8 @@ impl<'a> Command for OperateCommand<'a> {
         Ok(())
     }
 
-    fn write_buffer(&mut self, conn: &mut Connection) -> Result<()> {
-        conn.flush()
+    async fn write_buffer(&mut self, conn: &mut Connection) -> Result<()> {
+        conn.flush().await
     }
 
     fn prepare_buffer(&mut self, conn: &mut Connection) -> Result<()> {
@@ -67,10 +70,23 @@ impl<'a> Command for OperateCommand<'a> {
     }
 
     fn get_node(&self) -> Result<Arc<Node>> {
-        self.read_command.get_node()
+        // only reads may go to a replica
+        if self.operations.iter().any(Operation::is_write) {
+            self.read_command.single_command.get_node()
+        } else {
+            self.read_command.get_node()
+        }
+    }
+
+    fn runtime(&self) -> Runtime {
+        self.read_command.runtime()
+    }
+
+    fn prepare_retry(&mut self) {
+        self.read_command.prepare_retry();
     }
 
-    fn parse_result(&mut self, conn: &mut Connection) -> Result<()> {
-        self.read_command.parse_result(conn)
+    async fn parse_result(&mut self, conn: &mut Connection) -> Result<()> {
+        self.read_command.parse_result(conn).await
     }
 }
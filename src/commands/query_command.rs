@@ -15,18 +15,20 @@
 use std::sync::Arc;
 use std::time::Duration;
 
+use crate::cluster::partition::NodePartitions;
 use crate::cluster::Node;
 use crate::commands::{Command, SingleCommand, StreamCommand};
 use crate::errors::Result;
 use crate::net::Connection;
 use crate::policy::QueryPolicy;
+use crate::runtime::Runtime;
 use crate::{Recordset, Statement};
 
 pub struct QueryCommand<'a> {
     stream_command: StreamCommand,
     policy: &'a QueryPolicy,
     statement: Arc<Statement>,
-    partitions: Vec<u16>,
+    partitions: NodePartitions,
 }
 
 impl<'a> QueryCommand<'a> {
@@ -35,18 +37,20 @@ impl<'a> QueryCommand<'a> {
         node: Arc<Node>,
         statement: Arc<Statement>,
         recordset: Arc<Recordset>,
-        partitions: Vec<u16>,
+        partitions: NodePartitions,
     ) -> Self {
+        let mut stream_command = StreamCommand::new(node, recordset);
+        stream_command.aggregate = statement.aggregation.is_some();
         QueryCommand {
-            stream_command: StreamCommand::new(node, recordset),
+            stream_command,
             policy,
             statement,
             partitions,
         }
     }
 
-    pub fn execute(&mut self) -> Result<()> {
-        SingleCommand::execute(self.policy, self)
+    pub async fn execute(&mut self) -> Result<()> {
+        SingleCommand::execute(self.policy, self).await
     }
 }
 
@@ -56,8 +60,8 @@ impl<'a> Command for QueryCommand<'a> {
         Ok(())
     }
 
-    fn write_buffer(&mut self, conn: &mut Connection) -> Result<()> {
-        conn.flush()
+    async fn write_buffer(&mut self, conn: &mut Connection) -> Result<()> {
+        conn.flush().await
     }
 
     fn prepare_buffer(&mut self, conn: &mut Connection) -> Result<()> {
@@ -65,7 +69,7 @@ impl<'a> Command for QueryCommand<'a> {
             self.policy,
             &self.statement,
             false,
-            self.stream_command.recordset.task_id(),
+            self.stream_command.task_id,
             &self.partitions,
         )
     }
@@ -74,7 +78,15 @@ impl<'a> Command for QueryCommand<'a> {
         self.stream_command.get_node()
     }
 
-    fn parse_result(&mut self, conn: &mut Connection) -> Result<()> {
-        StreamCommand::parse_result(&mut self.stream_command, conn)
+    fn runtime(&self) -> Runtime {
+        self.stream_command.runtime()
+    }
+
+    async fn parse_result(&mut self, conn: &mut Connection) -> Result<()> {
+        StreamCommand::parse_result(&mut self.stream_command, conn).await
+    }
+
+    fn retry_on_timeout(&self) -> bool {
+        false
     }
 }
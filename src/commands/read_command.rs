@@ -23,6 +23,7 @@ use crate::commands::{Command, SingleCommand};
 use crate::errors::{ErrorKind, Result};
 use crate::net::Connection;
 use crate::policy::ReadPolicy;
+use crate::runtime::Runtime;
 use crate::value::bytes_to_particle;
 use crate::{Bins, Key, Record, ResultCode, Value};
 
@@ -31,6 +32,7 @@ pub struct ReadCommand<'a> {
     pub record: Option<Record>,
     policy: &'a ReadPolicy,
     bins: Bins,
+    respond_all_ops: bool,
 }
 
 impl<'a> ReadCommand<'a> {
@@ -40,11 +42,17 @@ impl<'a> ReadCommand<'a> {
             bins,
             policy,
             record: None,
+            respond_all_ops: false,
         }
     }
 
-    pub fn execute(&mut self) -> Result<()> {
-        SingleCommand::execute(self.policy, self)
+    // Return one result per operation; repeated bins collect their results in operation order.
+    pub fn set_respond_all_ops(&mut self, respond_all_ops: bool) {
+        self.respond_all_ops = respond_all_ops;
+    }
+
+    pub async fn execute(&mut self) -> Result<()> {
+        SingleCommand::execute(self.policy, self).await
     }
 
     fn parse_record(
@@ -64,6 +72,10 @@ impl<'a> ReadCommand<'a> {
             conn.buffer.skip(4 + field_size)?;
         }
 
+        if self.respond_all_ops {
+            return Self::parse_op_results(conn, op_count, generation, expiration);
+        }
+
         for _ in 0..op_count {
             let op_size = conn.buffer.read_u32(None)? as usize;
             conn.buffer.skip(1)?;
@@ -93,6 +105,44 @@ impl<'a> ReadCommand<'a> {
 
         Ok(Record::new(None, bins, generation, expiration))
     }
+
+    fn parse_op_results(
+        conn: &mut Connection,
+        op_count: usize,
+        generation: u32,
+        expiration: u32,
+    ) -> Result<Record> {
+        let mut results: HashMap<String, Vec<Value>> = HashMap::with_capacity(op_count);
+
+        for _ in 0..op_count {
+            let op_size = conn.buffer.read_u32(None)? as usize;
+            conn.buffer.skip(1)?;
+            let particle_type = conn.buffer.read_u8(None)?;
+            conn.buffer.skip(1)?;
+            let name_size = conn.buffer.read_u8(None)? as usize;
+            let name: String = conn.buffer.read_str(name_size)?;
+
+            let particle_bytes_size = op_size - (4 + name_size);
+            let value = bytes_to_particle(particle_type, &mut conn.buffer, particle_bytes_size)?;
+
+            // keep nil results so every value lines up with its operation
+            results.entry(name).or_insert_with(Vec::new).push(value);
+        }
+
+        let bins = results
+            .into_iter()
+            .map(|(name, mut values)| {
+                let value = if values.len() == 1 {
+                    values.pop().unwrap()
+                } else {
+                    Value::List(values)
+                };
+                (name, value)
+            })
+            .collect();
+
+        Ok(Record::new(None, bins, generation, expiration))
+    }
 }
 
 impl<'a> Command for ReadCommand<'a> {
@@ -101,8 +151,8 @@ impl<'a> Command for ReadCommand<'a> {
         Ok(())
     }
 
-    fn write_buffer(&mut self, conn: &mut Connection) -> Result<()> {
-        conn.flush()
+    async fn write_buffer(&mut self, conn: &mut Connection) -> Result<()> {
+        conn.flush().await
     }
 
     fn prepare_buffer(&mut self, conn: &mut Connection) -> Result<()> {
@@ -111,11 +161,22 @@ impl<'a> Command for ReadCommand<'a> {
     }
 
     fn get_node(&self) -> Result<Arc<Node>> {
-        self.single_command.get_node()
+        self.single_command.get_read_node(self.policy.replica)
+    }
+
+    fn runtime(&self) -> Runtime {
+        self.single_command.runtime()
+    }
+
+    fn prepare_retry(&mut self) {
+        self.single_command.prepare_retry();
     }
 
-    fn parse_result(&mut self, conn: &mut Connection) -> Result<()> {
-        if let Err(err) = conn.read_buffer(buffer::MSG_TOTAL_HEADER_SIZE as usize) {
+    async fn parse_result(&mut self, conn: &mut Connection) -> Result<()> {
+        if let Err(err) = conn
+            .read_buffer(buffer::MSG_TOTAL_HEADER_SIZE as usize)
+            .await
+        {
             warn!("Parse result error: {}", err);
             bail!(err);
         }
@@ -132,7 +193,7 @@ impl<'a> Command for ReadCommand<'a> {
 
         // Read remaining message bytes
         if receive_size > 0 {
-            if let Err(err) = conn.read_buffer(receive_size) {
+            if let Err(err) = conn.read_buffer(receive_size).await {
                 warn!("Parse result error: {}", err);
                 bail!(err);
             }
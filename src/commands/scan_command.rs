@@ -16,11 +16,13 @@ use std::str;
 use std::sync::Arc;
 use std::time::Duration;
 
+use crate::cluster::partition::NodePartitions;
 use crate::cluster::Node;
 use crate::commands::{Command, SingleCommand, StreamCommand};
 use crate::errors::Result;
 use crate::net::Connection;
 use crate::policy::ScanPolicy;
+use crate::runtime::Runtime;
 use crate::{Bins, Recordset};
 
 pub struct ScanCommand<'a> {
@@ -29,7 +31,7 @@ pub struct ScanCommand<'a> {
     namespace: &'a str,
     set_name: &'a str,
     bins: Bins,
-    partitions: Vec<u16>,
+    partitions: NodePartitions,
 }
 
 impl<'a> ScanCommand<'a> {
@@ -40,7 +42,7 @@ impl<'a> ScanCommand<'a> {
         set_name: &'a str,
         bins: Bins,
         recordset: Arc<Recordset>,
-        partitions: Vec<u16>,
+        partitions: NodePartitions,
     ) -> Self {
         ScanCommand {
             stream_command: StreamCommand::new(node, recordset),
@@ -52,8 +54,8 @@ impl<'a> ScanCommand<'a> {
         }
     }
 
-    pub fn execute(&mut self) -> Result<()> {
-        SingleCommand::execute(self.policy, self)
+    pub async fn execute(&mut self) -> Result<()> {
+        SingleCommand::execute(self.policy, self).await
     }
 }
 
@@ -63,8 +65,8 @@ impl<'a> Command for ScanCommand<'a> {
         Ok(())
     }
 
-    fn write_buffer(&mut self, conn: &mut Connection) -> Result<()> {
-        conn.flush()
+    async fn write_buffer(&mut self, conn: &mut Connection) -> Result<()> {
+        conn.flush().await
     }
 
     fn prepare_buffer(&mut self, conn: &mut Connection) -> Result<()> {
@@ -73,7 +75,7 @@ impl<'a> Command for ScanCommand<'a> {
             self.namespace,
             self.set_name,
             &self.bins,
-            self.stream_command.recordset.task_id(),
+            self.stream_command.task_id,
             &self.partitions,
         )
     }
@@ -82,7 +84,15 @@ impl<'a> Command for ScanCommand<'a> {
         self.stream_command.get_node()
     }
 
-    fn parse_result(&mut self, conn: &mut Connection) -> Result<()> {
-        StreamCommand::parse_result(&mut self.stream_command, conn)
+    fn runtime(&self) -> Runtime {
+        self.stream_command.runtime()
+    }
+
+    async fn parse_result(&mut self, conn: &mut Connection) -> Result<()> {
+        StreamCommand::parse_result(&mut self.stream_command, conn).await
+    }
+
+    fn retry_on_timeout(&self) -> bool {
+        false
     }
 }
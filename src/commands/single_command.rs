@@ -13,7 +13,6 @@
 // limitations under the License.
 
 use std::sync::Arc;
-use std::thread;
 use std::time::Instant;
 
 use crate::cluster::partition::Partition;
@@ -21,13 +20,16 @@ use crate::cluster::{Cluster, Node};
 use crate::commands::{self};
 use crate::errors::{ErrorKind, Result, ResultExt};
 use crate::net::Connection;
-use crate::policy::Policy;
+use crate::policy::{Policy, Replica};
+use crate::runtime::Runtime;
 use crate::Key;
 
 pub struct SingleCommand<'a> {
     cluster: Arc<Cluster>,
     pub key: &'a Key,
     partition: Partition<'a>,
+    // Number of retries, to select the replica to read from.
+    sequence: usize,
 }
 
 impl<'a> SingleCommand<'a> {
@@ -37,6 +39,7 @@ impl<'a> SingleCommand<'a> {
             cluster,
             key,
             partition,
+            sequence: 0,
         }
     }
 
@@ -44,7 +47,20 @@ impl<'a> SingleCommand<'a> {
         self.cluster.get_node(&self.partition)
     }
 
-    pub fn empty_socket(conn: &mut Connection) -> Result<()> {
+    pub fn get_read_node(&self, replica: Replica) -> Result<Arc<Node>> {
+        self.cluster
+            .get_read_node(&self.partition, replica, self.sequence)
+    }
+
+    pub fn prepare_retry(&mut self) {
+        self.sequence += 1;
+    }
+
+    pub fn runtime(&self) -> Runtime {
+        self.cluster.runtime()
+    }
+
+    pub async fn empty_socket(conn: &mut Connection) -> Result<()> {
         // There should not be any more bytes.
         // Empty the socket to be safe.
         let sz = conn.buffer.read_i64(None)?;
@@ -54,7 +70,7 @@ impl<'a> SingleCommand<'a> {
         // Read remaining message bytes.
         if receive_size > 0 {
             conn.buffer.resize_buffer(receive_size)?;
-            conn.read_buffer(receive_size)?;
+            conn.read_buffer(receive_size).await?;
         }
 
         Ok(())
@@ -63,8 +79,14 @@ impl<'a> SingleCommand<'a> {
     // EXECUTE
     //
 
-    pub fn execute(policy: &dyn Policy, cmd: &'a mut dyn commands::Command) -> Result<()> {
+    pub async fn execute<P, C>(policy: &P, cmd: &mut C) -> Result<()>
+    where
+        P: Policy,
+        C: commands::Command,
+    {
         let mut iterations = 0;
+        // whether the last attempt was rejected due to the error rate of the node
+        let mut rejected = false;
 
         // set timeout outside the loop
         let deadline = policy.deadline();
@@ -76,6 +98,9 @@ impl<'a> SingleCommand<'a> {
             // too many retries
             if let Some(max_retries) = policy.max_retries() {
                 if iterations > max_retries + 1 {
+                    if rejected {
+                        bail!(ErrorKind::MaxErrorRate);
+                    }
                     bail!(ErrorKind::Connection(format!(
                         "Timeout after {} tries",
                         iterations
@@ -85,14 +110,16 @@ impl<'a> SingleCommand<'a> {
 
             // Sleep before trying again, after the first iteration
             if iterations > 1 {
-                if let Some(sleep_between_retries) = policy.sleep_between_retries() {
-                    thread::sleep(sleep_between_retries);
+                cmd.prepare_retry();
+                let sleep = policy.sleep_before_retry(iterations - 1);
+                if let Some(sleep) = commands::retry_sleep(sleep, deadline) {
+                    cmd.runtime().sleep(sleep).await;
                 }
             }
 
             // check for command timeout
             if let Some(deadline) = deadline {
-                if Instant::now() > deadline {
+                if Instant::now() >= deadline {
                     break;
                 }
             }
@@ -102,45 +129,65 @@ impl<'a> SingleCommand<'a> {
                 Ok(node) => node,
                 Err(_) => continue, // Node is currently inactive. Retry.
             };
+            rejected = false;
 
-            let mut conn = match node.get_connection(policy.timeout()) {
+            let timeout = commands::attempt_timeout(policy.socket_timeout(), deadline);
+            let mut conn = match node.get_connection(timeout).await {
                 Ok(conn) => conn,
                 Err(err) => {
                     warn!("Node {}: {}", node, err);
+                    rejected = matches!(err.kind(), ErrorKind::MaxErrorRate);
                     continue;
                 }
             };
 
             cmd.prepare_buffer(&mut conn)
                 .chain_err(|| "Failed to prepare send buffer")?;
-            cmd.write_timeout(&mut conn, policy.timeout())
+            cmd.write_timeout(&mut conn, deadline.map(commands::time_left))
                 .chain_err(|| "Failed to set timeout for send buffer")?;
 
             // Send command.
-            if let Err(err) = cmd.write_buffer(&mut conn) {
+            if let Err(err) = cmd.write_buffer(&mut conn).await {
                 // IO errors are considered temporary anomalies. Retry.
                 // Close socket to flush out possible garbage. Do not put back in pool.
                 conn.invalidate();
+                node.add_error();
                 warn!("Node {}: {}", node, err);
                 continue;
             }
 
             // Parse results.
-            if let Err(err) = cmd.parse_result(&mut conn) {
+            if let Err(err) = cmd.parse_result(&mut conn).await {
+                // Socket timeouts are retried like other network errors, unless records have been
+                // delivered to the consumer already.
+                if commands::is_timeout(&err) && cmd.retry_on_timeout() {
+                    conn.invalidate();
+                    node.add_error();
+                    warn!("Node {}: {}", node, err);
+                    continue;
+                }
+
                 // close the connection
                 // cancelling/closing the batch/multi commands will return an error, which will
                 // close the connection to throw away its data and signal the server about the
                 // situation. We will not put back the connection in the buffer.
-                if !commands::keep_connection(&err) {
+                if commands::keep_connection(&err) {
+                    conn.mark_reusable();
+                } else {
                     conn.invalidate();
+                    node.add_error();
                 }
                 return Err(err);
             }
 
             // command has completed successfully.  Exit method.
+            conn.mark_reusable();
             return Ok(());
         }
 
+        if rejected {
+            bail!(ErrorKind::MaxErrorRate);
+        }
         bail!(ErrorKind::Connection("Timeout".to_string()))
     }
 }
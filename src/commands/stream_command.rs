@@ -13,55 +13,103 @@
 // limitations under the License.
 
 use std::collections::HashMap;
-use std::sync::Arc;
-use std::thread;
+use std::sync::{Arc, Weak};
 use std::time::Duration;
 
 use crate::cluster::Node;
 use crate::commands::buffer;
 use crate::commands::field_type::FieldType;
 use crate::commands::Command;
-use crate::errors::{ErrorKind, Result};
+use crate::errors::{Error, ErrorKind, Result};
 use crate::net::Connection;
 use crate::query::Recordset;
+use crate::runtime::Runtime;
 use crate::value::bytes_to_particle;
 use crate::{Key, Record, ResultCode, Value};
 
+enum StreamItem {
+    Record(Record),
+    PartitionDone(u16),
+    Skip,
+    End,
+}
+
 pub struct StreamCommand {
     node: Arc<Node>,
-    pub recordset: Arc<Recordset>,
+    // The command does not keep the recordset alive, so that the command is cancelled once the
+    // consumer drops the recordset.
+    recordset: Weak<Recordset>,
+    pub task_id: u64,
+    // Records hold the results of a stream UDF aggregation.
+    pub aggregate: bool,
 }
 
 impl Drop for StreamCommand {
     fn drop(&mut self) {
-        // signal_end
-        self.recordset.signal_end();
+        if let Some(recordset) = self.recordset.upgrade() {
+            recordset.signal_end();
+        }
     }
 }
 
 impl StreamCommand {
     pub fn new(node: Arc<Node>, recordset: Arc<Recordset>) -> Self {
-        StreamCommand { node, recordset }
+        StreamCommand {
+            node,
+            recordset: Arc::downgrade(&recordset),
+            task_id: recordset.task_id(),
+            aggregate: false,
+        }
+    }
+
+    // Returns the recordset, unless it has been closed or dropped by the consumer.
+    fn active_recordset(&self) -> Option<Arc<Recordset>> {
+        self.recordset
+            .upgrade()
+            .filter(|recordset| recordset.is_active())
+    }
+
+    fn push_error(&self, err: Error) {
+        if let Some(recordset) = self.recordset.upgrade() {
+            recordset.push(Err(err));
+        }
     }
 
-    fn parse_record(conn: &mut Connection, size: usize) -> Result<(Option<Record>, bool)> {
+    async fn parse_record(conn: &mut Connection, size: usize) -> Result<StreamItem> {
         let result_code = ResultCode::from(conn.buffer.read_u8(Some(5))?);
+        let info3 = conn.buffer.read_u8(Some(3))?;
+
+        // Partition is done, don't go further; the generation holds the partition id
+        if info3 & buffer::_INFO3_PARTITION_DONE != 0 {
+            conn.buffer.skip(6)?;
+            let partition_id = conn.buffer.read_u32(None)? as u16;
+            conn.buffer.skip(8)?;
+            let field_count = conn.buffer.read_u16(None)? as usize;
+            conn.buffer.skip(2)?;
+            StreamCommand::parse_key(conn, field_count).await?;
+
+            // an unavailable partition is not done and will be retried when the scan is resumed
+            return match result_code {
+                ResultCode::Ok => Ok(StreamItem::PartitionDone(partition_id)),
+                _ => Ok(StreamItem::Skip),
+            };
+        }
+
         if result_code != ResultCode::Ok {
             if conn.bytes_read() < size {
                 let remaining = size - conn.bytes_read();
-                conn.read_buffer(remaining)?;
+                conn.read_buffer(remaining).await?;
             }
 
             match result_code {
-                ResultCode::KeyNotFoundError => return Ok((None, false)),
+                ResultCode::KeyNotFoundError => return Ok(StreamItem::End),
                 _ => bail!(ErrorKind::ServerError(result_code)),
             }
         }
 
         // if cmd is the end marker of the response, do not proceed further
-        let info3 = conn.buffer.read_u8(Some(3))?;
         if info3 & buffer::INFO3_LAST == buffer::INFO3_LAST {
-            return Ok((None, false));
+            return Ok(StreamItem::End);
         }
 
         conn.buffer.skip(6)?;
@@ -71,78 +119,89 @@ impl StreamCommand {
         let field_count = conn.buffer.read_u16(None)? as usize; // almost certainly 0
         let op_count = conn.buffer.read_u16(None)? as usize;
 
-        let key = StreamCommand::parse_key(conn, field_count)?;
-
-        // Partition is done, don't go further
-        if info3 & buffer::_INFO3_PARTITION_DONE != 0 {
-            return Ok((None, true));
-        }
+        let key = StreamCommand::parse_key(conn, field_count).await?;
 
         let mut bins: HashMap<String, Value> = HashMap::with_capacity(op_count);
 
         for _ in 0..op_count {
-            conn.read_buffer(8)?;
+            conn.read_buffer(8).await?;
             let op_size = conn.buffer.read_u32(None)? as usize;
             conn.buffer.skip(1)?;
             let particle_type = conn.buffer.read_u8(None)?;
             conn.buffer.skip(1)?;
             let name_size = conn.buffer.read_u8(None)? as usize;
-            conn.read_buffer(name_size)?;
+            conn.read_buffer(name_size).await?;
             let name: String = conn.buffer.read_str(name_size)?;
 
             let particle_bytes_size = op_size - (4 + name_size);
-            conn.read_buffer(particle_bytes_size)?;
+            conn.read_buffer(particle_bytes_size).await?;
             let value = bytes_to_particle(particle_type, &mut conn.buffer, particle_bytes_size)?;
 
             bins.insert(name, value);
         }
 
         let record = Record::new(Some(key), bins, generation, expiration);
-        Ok((Some(record), true))
+        Ok(StreamItem::Record(record))
     }
 
-    fn parse_stream(&mut self, conn: &mut Connection, size: usize) -> Result<bool> {
-        while self.recordset.is_active() && conn.bytes_read() < size {
+    async fn parse_stream(&mut self, conn: &mut Connection, size: usize) -> Result<bool> {
+        while self.active_recordset().is_some() && conn.bytes_read() < size {
             // Read header.
-            if let Err(err) = conn.read_buffer(buffer::MSG_REMAINING_HEADER_SIZE as usize) {
+            if let Err(err) = conn
+                .read_buffer(buffer::MSG_REMAINING_HEADER_SIZE as usize)
+                .await
+            {
                 warn!("Parse result error: {}", err);
                 return Err(err);
             }
 
-            let res = StreamCommand::parse_record(conn, size);
+            let res = StreamCommand::parse_record(conn, size).await;
             match res {
-                Ok((Some(mut rec), _)) => loop {
-                    let result = self.recordset.push(Ok(rec));
-                    match result {
-                        None => break,
-                        Some(returned) => {
-                            rec = returned?;
-                            thread::yield_now();
-                        }
+                Ok(StreamItem::Record(rec))
+                    if self.aggregate && rec.bins.contains_key("FAILURE") =>
+                {
+                    // bin "FAILURE" contains details about the UDF error
+                    let reason = rec.bins["FAILURE"].to_string();
+                    self.push_error(ErrorKind::UdfBadResponse(reason).into());
+                    return Ok(false);
+                }
+                Ok(StreamItem::Record(rec)) => {
+                    // a closed recordset ends the loop below
+                    Recordset::push_wait(&self.recordset, Ok(rec)).await;
+                }
+                Ok(StreamItem::PartitionDone(partition_id)) => {
+                    if let Some(recordset) = self.recordset.upgrade() {
+                        recordset.push_partition_done(partition_id);
                     }
-                },
-                Ok((None, false)) => return Ok(false),
-                Ok((None, true)) => continue, // handle partition done
+                }
+                Ok(StreamItem::Skip) => continue,
+                Ok(StreamItem::End) => return Ok(false),
                 Err(err) => {
-                    self.recordset.push(Err(err));
+                    self.push_error(err);
                     return Ok(false);
                 }
             };
         }
 
+        // the recordset has been closed or dropped by the consumer; abort the command to discard
+        // the rest of the response
+        if self.active_recordset().is_none() {
+            bail!(ErrorKind::Connection("Recordset closed".to_string()));
+        }
+
         Ok(true)
     }
 
-    pub fn parse_key(conn: &mut Connection, field_count: usize) -> Result<Key> {
+    pub async fn parse_key(conn: &mut Connection, field_count: usize) -> Result<Key> {
         let mut digest: [u8; 20] = [0; 20];
         let mut namespace: String = "".to_string();
         let mut set_name: String = "".to_string();
         let mut orig_key: Option<Value> = None;
 
         for _ in 0..field_count {
-            conn.read_buffer(4)?;
+            conn.read_buffer(4).await?;
             let field_len = conn.buffer.read_u32(None)? as usize;
-            conn.read_buffer(field_len)?;
+            conn.read_buffer(field_len).await?;
             let field_type = conn.buffer.read_u8(None)?;
 
             match field_type {
@@ -183,8 +242,8 @@ impl Command for StreamCommand {
         Ok(())
     }
 
-    fn write_buffer(&mut self, conn: &mut Connection) -> Result<()> {
-        conn.flush()
+    async fn write_buffer(&mut self, conn: &mut Connection) -> Result<()> {
+        conn.flush().await
     }
 
     #[allow(unused_variables)]
@@ -197,17 +256,21 @@ impl Command for StreamCommand {
         Ok(self.node.clone())
     }
 
-    fn parse_result(&mut self, conn: &mut Connection) -> Result<()> {
+    fn runtime(&self) -> Runtime {
+        self.node.runtime()
+    }
+
+    async fn parse_result(&mut self, conn: &mut Connection) -> Result<()> {
         let mut status = true;
 
         while status {
-            conn.read_buffer(8)?;
+            conn.read_buffer(8).await?;
             let size = conn.buffer.read_msg_size(None)?;
             conn.bookmark();
 
             status = false;
             if size > 0 {
-                status = self.parse_stream(conn, size as usize)?;
+                status = self.parse_stream(conn, size as usize).await?;
             }
         }
 
@@ -21,6 +21,7 @@ use crate::commands::{Command, SingleCommand};
 use crate::errors::{ErrorKind, Result};
 use crate::net::Connection;
 use crate::policy::WritePolicy;
+use crate::runtime::Runtime;
 use crate::{Key, ResultCode};
 
 pub struct TouchCommand<'a> {
@@ -36,8 +37,8 @@ impl<'a> TouchCommand<'a> {
         }
     }
 
-    pub fn execute(&mut self) -> Result<()> {
-        SingleCommand::execute(self.policy, self)
+    pub async fn execute(&mut self) -> Result<()> {
+        SingleCommand::execute(self.policy, self).await
     }
 }
 
@@ -47,8 +48,8 @@ impl<'a> Command for TouchCommand<'a> {
         Ok(())
     }
 
-    fn write_buffer(&mut self, conn: &mut Connection) -> Result<()> {
-        conn.flush()
+    async fn write_buffer(&mut self, conn: &mut Connection) -> Result<()> {
+        conn.flush().await
     }
 
     fn prepare_buffer(&mut self, conn: &mut Connection) -> Result<()> {
@@ -59,9 +60,16 @@ impl<'a> Command for TouchCommand<'a> {
         self.single_command.get_node()
     }
 
-    fn parse_result(&mut self, conn: &mut Connection) -> Result<()> {
+    fn runtime(&self) -> Runtime {
+        self.single_command.runtime()
+    }
+
+    async fn parse_result(&mut self, conn: &mut Connection) -> Result<()> {
         // Read header.
-        if let Err(err) = conn.read_buffer(buffer::MSG_TOTAL_HEADER_SIZE as usize) {
+        if let Err(err) = conn
+            .read_buffer(buffer::MSG_TOTAL_HEADER_SIZE as usize)
+            .await
+        {
             warn!("Parse result error: {}", err);
             return Err(err);
         }
@@ -73,6 +81,6 @@ impl<'a> Command for TouchCommand<'a> {
             bail!(ErrorKind::ServerError(result_code));
         }
 
-        SingleCommand::empty_socket(conn)
+        SingleCommand::empty_socket(conn).await
     }
 }
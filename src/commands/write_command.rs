@@ -22,17 +22,18 @@ use crate::errors::{ErrorKind, Result};
 use crate::net::Connection;
 use crate::operations::OperationType;
 use crate::policy::WritePolicy;
+use crate::runtime::Runtime;
 use crate::{Bin, Key, ResultCode};
 
-pub struct WriteCommand<'a, A: 'a> {
+pub struct WriteCommand<'a, 'b> {
     single_command: SingleCommand<'a>,
     policy: &'a WritePolicy,
-    bins: &'a [A],
+    bins: Vec<&'a Bin<'b>>,
     operation: OperationType,
 }
 
-impl<'a, 'b, A: AsRef<Bin<'b>>> WriteCommand<'a, A> {
-    pub fn new(
+impl<'a, 'b> WriteCommand<'a, 'b> {
+    pub fn new<A: AsRef<Bin<'b>>>(
         policy: &'a WritePolicy,
         cluster: Arc<Cluster>,
         key: &'a Key,
@@ -41,25 +42,25 @@ impl<'a, 'b, A: AsRef<Bin<'b>>> WriteCommand<'a, A> {
     ) -> Self {
         WriteCommand {
             single_command: SingleCommand::new(cluster, key),
-            bins,
+            bins: bins.iter().map(AsRef::as_ref).collect(),
             policy,
             operation,
         }
     }
 
-    pub fn execute(&mut self) -> Result<()> {
-        SingleCommand::execute(self.policy, self)
+    pub async fn execute(&mut self) -> Result<()> {
+        SingleCommand::execute(self.policy, self).await
     }
 }
 
-impl<'a, 'b, A: AsRef<Bin<'b>>> Command for WriteCommand<'a, A> {
+impl<'a, 'b> Command for WriteCommand<'a, 'b> {
     fn write_timeout(&mut self, conn: &mut Connection, timeout: Option<Duration>) -> Result<()> {
         conn.buffer.write_timeout(timeout);
         Ok(())
     }
 
-    fn write_buffer(&mut self, conn: &mut Connection) -> Result<()> {
-        conn.flush()
+    async fn write_buffer(&mut self, conn: &mut Connection) -> Result<()> {
+        conn.flush().await
     }
 
     fn prepare_buffer(&mut self, conn: &mut Connection) -> Result<()> {
@@ -67,7 +68,7 @@ impl<'a, 'b, A: AsRef<Bin<'b>>> Command for WriteCommand<'a, A> {
             self.policy,
             self.operation,
             self.single_command.key,
-            self.bins,
+            &self.bins,
         )
     }
 
@@ -75,9 +76,16 @@ impl<'a, 'b, A: AsRef<Bin<'b>>> Command for WriteCommand<'a, A> {
         self.single_command.get_node()
     }
 
-    fn parse_result(&mut self, conn: &mut Connection) -> Result<()> {
+    fn runtime(&self) -> Runtime {
+        self.single_command.runtime()
+    }
+
+    async fn parse_result(&mut self, conn: &mut Connection) -> Result<()> {
         // Read header.
-        if let Err(err) = conn.read_buffer(buffer::MSG_TOTAL_HEADER_SIZE as usize) {
+        if let Err(err) = conn
+            .read_buffer(buffer::MSG_TOTAL_HEADER_SIZE as usize)
+            .await
+        {
             warn!("Parse result error: {}", err);
             return Err(err);
         }
@@ -89,6 +97,6 @@ impl<'a, 'b, A: AsRef<Bin<'b>>> Command for WriteCommand<'a, A> {
             bail!(ErrorKind::ServerError(result_code));
         }
 
-        SingleCommand::empty_socket(conn)
+        SingleCommand::empty_socket(conn).await
     }
 }
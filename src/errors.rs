@@ -103,6 +103,13 @@ error_chain! {
             display("Invalid cluster node: {}", details)
         }
 
+/// The node exceeded the maximum error rate of the client policy; commands to the node are
+/// rejected until the current error rate window ends.
+        MaxErrorRate {
+            description("Max error rate exceeded")
+            display("Max error rate exceeded")
+        }
+
 /// Exceeded max. number of connections per node.
         NoMoreConnections {
             description("Too many connections")
@@ -57,7 +57,7 @@ pub enum BitExpOp {
 /// use aerospike::expressions::bitwise::{count, resize};
 /// eq(
 ///   count(int_val(0), int_val(3),
-///     resize(&BitPolicy::default(), int_val(4), BitwiseResizeFlags::Default, blob_bin("a".to_string()))),
+///     resize(&BitPolicy::default(), int_val(4), BitwiseResizeFlags::Default, blob_bin("a"))),
 ///   int_val(2));
 /// ```
 #[allow(clippy::trivially_copy_pass_by_ref)]
@@ -91,7 +91,7 @@ pub fn resize(
 /// let bytes: Vec<u8> = vec![];
 /// eq(
 ///   count(int_val(0), int_val(3),
-///     insert(&BitPolicy::default(), int_val(1), blob_val(bytes), blob_bin("a".to_string()))),
+///     insert(&BitPolicy::default(), int_val(1), blob_val(bytes), blob_bin("a"))),
 ///   int_val(2));
 /// ```
 #[allow(clippy::trivially_copy_pass_by_ref)]
@@ -123,7 +123,7 @@ pub fn insert(
 /// use aerospike::expressions::bitwise::{count, remove};
 /// eq(
 ///   count(int_val(0), int_val(3),
-///     remove(&BitPolicy::default(), int_val(2), int_val(3), blob_bin("a".to_string()))),
+///     remove(&BitPolicy::default(), int_val(2), int_val(3), blob_bin("a"))),
 ///   int_val(2));
 /// ```
 #[allow(clippy::trivially_copy_pass_by_ref)]
@@ -157,7 +157,7 @@ pub fn remove(
 /// let bytes: Vec<u8> = vec![];
 /// eq(
 ///   count(int_val(0), int_val(3),
-///     set(&BitPolicy::default(), int_val(13), int_val(3), blob_val(bytes), blob_bin("a".to_string()))),
+///     set(&BitPolicy::default(), int_val(13), int_val(3), blob_val(bytes), blob_bin("a"))),
 ///   int_val(2));
 /// ```
 #[allow(clippy::trivially_copy_pass_by_ref)]
@@ -379,7 +379,7 @@ pub fn add(
 
 /// Create expression that subtracts value from byte[] bin starting at bitOffset for bitSize and returns byte[].
 /// `BitSize` must be <= 64. Signed indicates if bits should be treated as a signed number.
-/// If add overflows/underflows, `BitwiseOverflowActions` is used.
+/// If subtract overflows/underflows, `BitwiseOverflowActions` is used.
 ///
 /// ```text
 /// bin = [0b00000001, 0b01000010, 0b00000011, 0b00000100, 0b00000101]
@@ -454,7 +454,7 @@ pub fn set_int(
 /// use aerospike::expressions::{eq, int_val, blob_bin, blob_val};
 /// use aerospike::expressions::bitwise::get;
 /// eq(
-///   get(int_val(9), int_val(5), blob_bin("a".to_string())),
+///   get(int_val(9), int_val(5), blob_bin("a")),
 ///   blob_val(vec![0b10000000]));
 /// ```
 pub fn get(
@@ -482,7 +482,7 @@ pub fn get(
 ///
 /// use aerospike::expressions::{le, int_val, blob_bin};
 /// use aerospike::expressions::bitwise::count;
-/// le(count(int_val(0), int_val(5), blob_bin("a".to_string())), int_val(2));
+/// le(count(int_val(0), int_val(5), blob_bin("a")), int_val(2));
 /// ```
 pub fn count(
     bit_offset: FilterExpression,
@@ -509,7 +509,7 @@ pub fn count(
 /// // returns 5
 /// use aerospike::expressions::{eq, int_val, blob_bin};
 /// use aerospike::expressions::bitwise::lscan;
-/// eq(lscan(int_val(24), int_val(8), int_val(1), blob_bin("a".to_string())), int_val(5));
+/// eq(lscan(int_val(24), int_val(8), int_val(1), blob_bin("a")), int_val(5));
 /// ```
 ///
 pub fn lscan(
@@ -541,7 +541,7 @@ pub fn lscan(
 ///
 /// use aerospike::expressions::{eq, int_val, blob_bin};
 /// use aerospike::expressions::bitwise::rscan;
-/// eq(rscan(int_val(32), int_val(8), int_val(1), blob_bin("a".to_string())), int_val(7));
+/// eq(rscan(int_val(32), int_val(8), int_val(1), blob_bin("a")), int_val(7));
 /// ```
 ///
 pub fn rscan(
@@ -571,7 +571,7 @@ pub fn rscan(
 /// // returns 16899
 /// use aerospike::expressions::{eq, int_val, blob_bin};
 /// use aerospike::expressions::bitwise::get_int;
-/// eq(get_int(int_val(8), int_val(16), false, blob_bin("a".to_string())), int_val(16899));
+/// eq(get_int(int_val(8), int_val(16), false, blob_bin("a")), int_val(16899));
 /// ```
 pub fn get_int(
     bit_offset: FilterExpression,
@@ -600,6 +600,7 @@ fn add_write(bin: FilterExpression, arguments: Vec<ExpressionArgument>) -> Filte
         module: Some(ExpType::BLOB),
         exps: None,
         arguments: Some(arguments),
+        packed: None,
     }
 }
 
@@ -617,5 +618,6 @@ fn add_read(
         module: Some(return_type),
         exps: None,
         arguments: Some(arguments),
+        packed: None,
     }
 }
@@ -56,7 +56,7 @@ pub fn init_with_min_hash(
             ExpressionArgument::Value(Value::from(HllExpOp::Init as i64)),
             ExpressionArgument::FilterExpression(index_bit_count),
             ExpressionArgument::FilterExpression(min_hash_count),
-            ExpressionArgument::Value(Value::from(policy.flags as i64)),
+            ExpressionArgument::Value(Value::from(i64::from(policy.flags.bits()))),
         ],
     )
 }
@@ -71,7 +71,7 @@ pub fn init_with_min_hash(
 ///
 /// // Add values to HLL bin "a" and check count > 7
 /// let list = vec![Value::from(1)];
-/// gt(add(HLLPolicy::default(), list_val(list), hll_bin("a".to_string())), int_val(7));
+/// gt(add(HLLPolicy::default(), list_val(list), hll_bin("a")), int_val(7));
 /// ```
 pub fn add(policy: HLLPolicy, list: FilterExpression, bin: FilterExpression) -> FilterExpression {
     add_with_index_and_min_hash(policy, list, int_val(-1), int_val(-1), bin)
@@ -87,7 +87,7 @@ pub fn add(policy: HLLPolicy, list: FilterExpression, bin: FilterExpression) ->
 ///
 /// // Add values to HLL bin "a" and check count > 7
 /// let list = vec![Value::from(1)];
-/// gt(add_with_index(HLLPolicy::default(), list_val(list), int_val(10), hll_bin("a".to_string())), int_val(7));
+/// gt(add_with_index(HLLPolicy::default(), list_val(list), int_val(10), hll_bin("a")), int_val(7));
 /// ```
 pub fn add_with_index(
     policy: HLLPolicy,
@@ -108,7 +108,7 @@ pub fn add_with_index(
 ///
 /// // Add values to HLL bin "a" and check count > 7
 /// let list = vec![Value::from(1)];
-/// gt(add_with_index_and_min_hash(HLLPolicy::default(), list_val(list), int_val(10), int_val(20), hll_bin("a".to_string())), int_val(7));
+/// gt(add_with_index_and_min_hash(HLLPolicy::default(), list_val(list), int_val(10), int_val(20), hll_bin("a")), int_val(7));
 /// ```
 pub fn add_with_index_and_min_hash(
     policy: HLLPolicy,
@@ -124,7 +124,7 @@ pub fn add_with_index_and_min_hash(
             ExpressionArgument::FilterExpression(list),
             ExpressionArgument::FilterExpression(index_bit_count),
             ExpressionArgument::FilterExpression(min_hash_count),
-            ExpressionArgument::Value(Value::from(policy.flags as i64)),
+            ExpressionArgument::Value(Value::from(i64::from(policy.flags.bits()))),
         ],
     )
 }
@@ -135,7 +135,7 @@ pub fn add_with_index_and_min_hash(
 /// // HLL bin "a" count > 7
 /// use aerospike::expressions::{gt, hll_bin, int_val};
 /// use aerospike::expressions::hll::get_count;
-/// gt(get_count(hll_bin("a".to_string())), int_val(7));
+/// gt(get_count(hll_bin("a")), int_val(7));
 /// ```
 pub fn get_count(bin: FilterExpression) -> FilterExpression {
     add_read(
@@ -151,15 +151,16 @@ pub fn get_count(bin: FilterExpression) -> FilterExpression {
 /// in the list with the HLL bin.
 ///
 /// ```
+/// use aerospike::Value;
 /// use aerospike::expressions::hll::get_union;
-/// use aerospike::expressions::{hll_bin, blob_val};
+/// use aerospike::expressions::{hll_bin, list_val};
 ///
 /// // Union of HLL bins "a" and "b"
-/// get_union(hll_bin("a".to_string()), hll_bin("b".to_string()));
+/// get_union(hll_bin("a"), hll_bin("b"));
 ///
 /// // Union of local HLL list with bin "b"
-/// let blob: Vec<u8> = vec![];
-/// get_union(hll_bin("b".to_string()), blob_val(blob));
+/// let hll: Vec<u8> = vec![];
+/// get_union(list_val(vec![Value::HLL(hll)]), hll_bin("b"));
 /// ```
 pub fn get_union(list: FilterExpression, bin: FilterExpression) -> FilterExpression {
     add_read(
@@ -176,15 +177,16 @@ pub fn get_union(list: FilterExpression, bin: FilterExpression) -> FilterExpress
 /// the union of these HLL objects.
 ///
 /// ```
+/// use aerospike::Value;
 /// use aerospike::expressions::hll::get_union_count;
-/// use aerospike::expressions::{hll_bin, blob_val};
+/// use aerospike::expressions::{hll_bin, list_val};
 ///
 /// // Union count of HLL bins "a" and "b"
-/// get_union_count(hll_bin("a".to_string()), hll_bin("b".to_string()));
+/// get_union_count(hll_bin("a"), hll_bin("b"));
 ///
 /// // Union count of local HLL list with bin "b"
-/// let blob: Vec<u8> = vec![];
-/// get_union_count(hll_bin("b".to_string()), blob_val(blob));
+/// let hll: Vec<u8> = vec![];
+/// get_union_count(list_val(vec![Value::HLL(hll)]), hll_bin("b"));
 /// ```
 pub fn get_union_count(list: FilterExpression, bin: FilterExpression) -> FilterExpression {
     add_read(
@@ -201,15 +203,16 @@ pub fn get_union_count(list: FilterExpression, bin: FilterExpression) -> FilterE
 /// the intersection of these HLL objects.
 ///
 /// ```
-/// use aerospike::expressions::{hll_bin, blob_val};
-/// use aerospike::expressions::hll::get_union_count;
+/// use aerospike::Value;
+/// use aerospike::expressions::{hll_bin, list_val};
+/// use aerospike::expressions::hll::get_intersect_count;
 ///
 /// // Intersect count of HLL bins "a" and "b"
-/// get_union_count(hll_bin("a".to_string()), hll_bin("b".to_string()));
+/// get_intersect_count(hll_bin("a"), hll_bin("b"));
 ///
 /// // Intersect count of local HLL list with bin "b"
-/// let blob: Vec<u8> = vec![];
-/// get_union_count(hll_bin("b".to_string()), blob_val(blob));
+/// let hll: Vec<u8> = vec![];
+/// get_intersect_count(list_val(vec![Value::HLL(hll)]), hll_bin("b"));
 /// ```
 pub fn get_intersect_count(list: FilterExpression, bin: FilterExpression) -> FilterExpression {
     add_read(
@@ -229,7 +232,7 @@ pub fn get_intersect_count(list: FilterExpression, bin: FilterExpression) -> Fil
 /// use aerospike::expressions::hll::get_similarity;
 ///
 /// // Similarity of HLL bins "a" and "b" >= 0.75
-/// ge(get_similarity(hll_bin("a".to_string()), hll_bin("b".to_string())), float_val(0.75));
+/// ge(get_similarity(hll_bin("a"), hll_bin("b")), float_val(0.75));
 /// ```
 pub fn get_similarity(list: FilterExpression, bin: FilterExpression) -> FilterExpression {
     add_read(
@@ -252,7 +255,7 @@ pub fn get_similarity(list: FilterExpression, bin: FilterExpression) -> FilterEx
 /// use aerospike::expressions::hll::describe;
 ///
 /// // Bin "a" `indexBitCount` < 10
-/// lt(get_by_index(ListReturnType::Values, ExpType::INT, int_val(0), describe(hll_bin("a".to_string())), &[]), int_val(10));
+/// lt(get_by_index(ListReturnType::Values, ExpType::INT, int_val(0), describe(hll_bin("a")), &[]), int_val(10));
 /// ```
 pub fn describe(bin: FilterExpression) -> FilterExpression {
     add_read(
@@ -273,7 +276,7 @@ pub fn describe(bin: FilterExpression) -> FilterExpression {
 /// let list: Vec<Value> = vec![Value::from("x")];
 ///
 /// // Bin "a" may contain value "x"
-/// eq(may_contain(list_val(list), hll_bin("a".to_string())), int_val(1));
+/// eq(may_contain(list_val(list), hll_bin("a")), int_val(1));
 /// ```
 pub fn may_contain(list: FilterExpression, bin: FilterExpression) -> FilterExpression {
     add_read(
@@ -300,6 +303,7 @@ fn add_read(
         module: Some(return_type),
         exps: None,
         arguments: Some(arguments),
+        packed: None,
     }
 }
 
@@ -313,5 +317,6 @@ fn add_write(bin: FilterExpression, arguments: Vec<ExpressionArgument>) -> Filte
         module: Some(ExpType::HLL),
         exps: None,
         arguments: Some(arguments),
+        packed: None,
     }
 }
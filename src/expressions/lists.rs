@@ -32,7 +32,7 @@ pub fn append(
         ExpressionArgument::Value(Value::from(CdtListOpType::Append as i64)),
         ExpressionArgument::FilterExpression(value),
         ExpressionArgument::Value(Value::from(policy.attributes as u8)),
-        ExpressionArgument::Value(Value::from(policy.flags as u8)),
+        ExpressionArgument::Value(Value::from(policy.flags.bits())),
         ExpressionArgument::Context(ctx.to_vec()),
     ];
     add_write(bin, ctx, args)
@@ -49,7 +49,7 @@ pub fn append_items(
         ExpressionArgument::Value(Value::from(CdtListOpType::AppendItems as i64)),
         ExpressionArgument::FilterExpression(list),
         ExpressionArgument::Value(Value::from(policy.attributes as u8)),
-        ExpressionArgument::Value(Value::from(policy.flags as u8)),
+        ExpressionArgument::Value(Value::from(policy.flags.bits())),
         ExpressionArgument::Context(ctx.to_vec()),
     ];
     add_write(bin, ctx, args)
@@ -67,7 +67,7 @@ pub fn insert(
         ExpressionArgument::Value(Value::from(CdtListOpType::Insert as i64)),
         ExpressionArgument::FilterExpression(index),
         ExpressionArgument::FilterExpression(value),
-        ExpressionArgument::Value(Value::from(policy.flags as u8)),
+        ExpressionArgument::Value(Value::from(policy.flags.bits())),
         ExpressionArgument::Context(ctx.to_vec()),
     ];
     add_write(bin, ctx, args)
@@ -85,7 +85,7 @@ pub fn insert_items(
         ExpressionArgument::Value(Value::from(CdtListOpType::InsertItems as i64)),
         ExpressionArgument::FilterExpression(index),
         ExpressionArgument::FilterExpression(list),
-        ExpressionArgument::Value(Value::from(policy.flags as u8)),
+        ExpressionArgument::Value(Value::from(policy.flags.bits())),
         ExpressionArgument::Context(ctx.to_vec()),
     ];
     add_write(bin, ctx, args)
@@ -105,7 +105,7 @@ pub fn increment(
         ExpressionArgument::FilterExpression(index),
         ExpressionArgument::FilterExpression(value),
         ExpressionArgument::Value(Value::from(policy.attributes as u8)),
-        ExpressionArgument::Value(Value::from(policy.flags as u8)),
+        ExpressionArgument::Value(Value::from(policy.flags.bits())),
         ExpressionArgument::Context(ctx.to_vec()),
     ];
     add_write(bin, ctx, args)
@@ -123,7 +123,7 @@ pub fn set(
         ExpressionArgument::Value(Value::from(CdtListOpType::Set as i64)),
         ExpressionArgument::FilterExpression(index),
         ExpressionArgument::FilterExpression(value),
-        ExpressionArgument::Value(Value::from(policy.flags as u8)),
+        ExpressionArgument::Value(Value::from(policy.flags.bits())),
         ExpressionArgument::Context(ctx.to_vec()),
     ];
     add_write(bin, ctx, args)
@@ -154,28 +154,50 @@ pub fn sort(
 
 /// Create expression that removes list items identified by value.
 pub fn remove_by_value(
+    return_type: ListReturnType,
     value: FilterExpression,
     bin: FilterExpression,
     ctx: &[CdtContext],
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtListOpType::RemoveByValue as i64)),
-        ExpressionArgument::Value(Value::from(ListReturnType::None as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(value),
         ExpressionArgument::Context(ctx.to_vec()),
     ];
     add_write(bin, ctx, args)
 }
 
-/// Create expression that removes list items identified by values.
+/// Create expression that removes list items identified by values. Use
+/// `ListReturnType::Inverted` to remove all items except those identified by values.
+/// ```
+/// use aerospike::expressions::{eq, int_val, list_bin, list_val};
+/// use aerospike::expressions::lists::{remove_by_value_list, size};
+/// use aerospike::operations::lists::ListReturnType;
+/// use aerospike::Value;
+/// // List bin "a" contains neither the value 1 nor the value 2
+/// eq(
+///     size(
+///         remove_by_value_list(
+///             ListReturnType::Inverted,
+///             list_val(vec![Value::from(1), Value::from(2)]),
+///             list_bin("a"),
+///             &[],
+///         ),
+///         &[],
+///     ),
+///     int_val(0),
+/// );
+/// ```
 pub fn remove_by_value_list(
+    return_type: ListReturnType,
     values: FilterExpression,
     bin: FilterExpression,
     ctx: &[CdtContext],
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtListOpType::RemoveByValueList as i64)),
-        ExpressionArgument::Value(Value::from(ListReturnType::None as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(values),
         ExpressionArgument::Context(ctx.to_vec()),
     ];
@@ -186,6 +208,7 @@ pub fn remove_by_value_list(
 /// If valueBegin is null, the range is less than valueEnd. If valueEnd is null, the range is
 /// greater than equal to valueBegin.
 pub fn remove_by_value_range(
+    return_type: ListReturnType,
     value_begin: Option<FilterExpression>,
     value_end: Option<FilterExpression>,
     bin: FilterExpression,
@@ -194,7 +217,7 @@ pub fn remove_by_value_range(
     let mut args = vec![
         ExpressionArgument::Context(ctx.to_vec()),
         ExpressionArgument::Value(Value::from(CdtListOpType::RemoveByValueInterval as i64)),
-        ExpressionArgument::Value(Value::from(ListReturnType::None as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
     ];
     if let Some(val_beg) = value_begin {
         args.push(ExpressionArgument::FilterExpression(val_beg));
@@ -220,6 +243,7 @@ pub fn remove_by_value_range(
 /// (3,-3) = [0,4,5,9,11,15]
 /// ```
 pub fn remove_by_value_relative_rank_range(
+    return_type: ListReturnType,
     value: FilterExpression,
     rank: FilterExpression,
     bin: FilterExpression,
@@ -227,7 +251,7 @@ pub fn remove_by_value_relative_rank_range(
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtListOpType::RemoveByValueRelRankRange as i64)),
-        ExpressionArgument::Value(Value::from(ListReturnType::None as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(value),
         ExpressionArgument::FilterExpression(rank),
         ExpressionArgument::Context(ctx.to_vec()),
@@ -248,6 +272,7 @@ pub fn remove_by_value_relative_rank_range(
 /// (3,-3,2) = []
 /// ```
 pub fn remove_by_value_relative_rank_range_count(
+    return_type: ListReturnType,
     value: FilterExpression,
     rank: FilterExpression,
     count: FilterExpression,
@@ -256,7 +281,7 @@ pub fn remove_by_value_relative_rank_range_count(
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtListOpType::RemoveByValueRelRankRange as i64)),
-        ExpressionArgument::Value(Value::from(ListReturnType::None as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(value),
         ExpressionArgument::FilterExpression(rank),
         ExpressionArgument::FilterExpression(count),
@@ -267,13 +292,14 @@ pub fn remove_by_value_relative_rank_range_count(
 
 /// Create expression that removes list item identified by index.
 pub fn remove_by_index(
+    return_type: ListReturnType,
     index: FilterExpression,
     bin: FilterExpression,
     ctx: &[CdtContext],
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtListOpType::RemoveByIndex as i64)),
-        ExpressionArgument::Value(Value::from(ListReturnType::None as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(index),
         ExpressionArgument::Context(ctx.to_vec()),
     ];
@@ -282,13 +308,14 @@ pub fn remove_by_index(
 
 /// Create expression that removes list items starting at specified index to the end of list.
 pub fn remove_by_index_range(
+    return_type: ListReturnType,
     index: FilterExpression,
     bin: FilterExpression,
     ctx: &[CdtContext],
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtListOpType::RemoveByIndexRange as i64)),
-        ExpressionArgument::Value(Value::from(ListReturnType::None as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(index),
         ExpressionArgument::Context(ctx.to_vec()),
     ];
@@ -297,6 +324,7 @@ pub fn remove_by_index_range(
 
 /// Create expression that removes "count" list items starting at specified index.
 pub fn remove_by_index_range_count(
+    return_type: ListReturnType,
     index: FilterExpression,
     count: FilterExpression,
     bin: FilterExpression,
@@ -304,7 +332,7 @@ pub fn remove_by_index_range_count(
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtListOpType::RemoveByIndexRange as i64)),
-        ExpressionArgument::Value(Value::from(ListReturnType::None as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(index),
         ExpressionArgument::FilterExpression(count),
         ExpressionArgument::Context(ctx.to_vec()),
@@ -314,13 +342,14 @@ pub fn remove_by_index_range_count(
 
 /// Create expression that removes list item identified by rank.
 pub fn remove_by_rank(
+    return_type: ListReturnType,
     rank: FilterExpression,
     bin: FilterExpression,
     ctx: &[CdtContext],
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtListOpType::RemoveByRank as i64)),
-        ExpressionArgument::Value(Value::from(ListReturnType::None as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(rank),
         ExpressionArgument::Context(ctx.to_vec()),
     ];
@@ -329,13 +358,14 @@ pub fn remove_by_rank(
 
 /// Create expression that removes list items starting at specified rank to the last ranked item.
 pub fn remove_by_rank_range(
+    return_type: ListReturnType,
     rank: FilterExpression,
     bin: FilterExpression,
     ctx: &[CdtContext],
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtListOpType::RemoveByRankRange as i64)),
-        ExpressionArgument::Value(Value::from(ListReturnType::None as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(rank),
         ExpressionArgument::Context(ctx.to_vec()),
     ];
@@ -344,6 +374,7 @@ pub fn remove_by_rank_range(
 
 /// Create expression that removes "count" list items starting at specified rank.
 pub fn remove_by_rank_range_count(
+    return_type: ListReturnType,
     rank: FilterExpression,
     count: FilterExpression,
     bin: FilterExpression,
@@ -351,7 +382,7 @@ pub fn remove_by_rank_range_count(
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtListOpType::RemoveByRankRange as i64)),
-        ExpressionArgument::Value(Value::from(ListReturnType::None as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(rank),
         ExpressionArgument::FilterExpression(count),
         ExpressionArgument::Context(ctx.to_vec()),
@@ -365,7 +396,7 @@ pub fn remove_by_rank_range_count(
 /// // List bin "a" size > 7
 /// use aerospike::expressions::{gt, list_bin, int_val};
 /// use aerospike::expressions::lists::size;
-/// gt(size(list_bin("a".to_string()), &[]), int_val(7));
+/// gt(size(list_bin("a"), &[]), int_val(7));
 /// ```
 pub fn size(bin: FilterExpression, ctx: &[CdtContext]) -> FilterExpression {
     let args = vec![
@@ -384,7 +415,7 @@ pub fn size(bin: FilterExpression, ctx: &[CdtContext]) -> FilterExpression {
 /// use aerospike::operations::lists::ListReturnType;
 /// use aerospike::expressions::lists::get_by_value;
 /// gt(
-///   get_by_value(ListReturnType::Count, string_val("abc".to_string()), list_bin("a".to_string()), &[]),
+///   get_by_value(ListReturnType::Count, string_val("abc"), list_bin("a"), &[]),
 ///   int_val(0));
 /// ```
 ///
@@ -396,7 +427,7 @@ pub fn get_by_value(
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtListOpType::GetByValue as i64)),
-        ExpressionArgument::Value(Value::from(return_type as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(value),
         ExpressionArgument::Context(ctx.to_vec()),
     ];
@@ -412,7 +443,7 @@ pub fn get_by_value(
 /// use aerospike::expressions::lists::get_by_value_range;
 /// use aerospike::expressions::{int_val, list_bin};
 ///
-/// get_by_value_range(ListReturnType::Values, Some(int_val(10)), Some(int_val(20)), list_bin("a".to_string()), &[]);
+/// get_by_value_range(ListReturnType::Values, Some(int_val(10)), Some(int_val(20)), list_bin("a"), &[]);
 /// ```
 pub fn get_by_value_range(
     return_type: ListReturnType,
@@ -424,7 +455,7 @@ pub fn get_by_value_range(
     let mut args = vec![
         ExpressionArgument::Context(ctx.to_vec()),
         ExpressionArgument::Value(Value::from(CdtListOpType::GetByValueInterval as i64)),
-        ExpressionArgument::Value(Value::from(return_type as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
     ];
     if let Some(val_beg) = value_begin {
         args.push(ExpressionArgument::FilterExpression(val_beg));
@@ -447,7 +478,7 @@ pub fn get_by_value_list(
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtListOpType::GetByValueList as i64)),
-        ExpressionArgument::Value(Value::from(return_type as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(values),
         ExpressionArgument::Context(ctx.to_vec()),
     ];
@@ -476,7 +507,7 @@ pub fn get_by_value_relative_rank_range(
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtListOpType::GetByValueRelRankRange as i64)),
-        ExpressionArgument::Value(Value::from(return_type as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(value),
         ExpressionArgument::FilterExpression(rank),
         ExpressionArgument::Context(ctx.to_vec()),
@@ -497,6 +528,24 @@ pub fn get_by_value_relative_rank_range(
 /// (3,3,7) = [11,15]
 /// (3,-3,2) = []
 /// ```
+///
+/// ```
+/// // List bin "scores" has at least one of the three values ranked right above 100
+/// use aerospike::expressions::{gt, int_val, list_bin};
+/// use aerospike::expressions::lists::get_by_value_relative_rank_range_count;
+/// use aerospike::operations::lists::ListReturnType;
+/// gt(
+///     get_by_value_relative_rank_range_count(
+///         ListReturnType::Count,
+///         int_val(100),
+///         int_val(1),
+///         int_val(3),
+///         list_bin("scores"),
+///         &[],
+///     ),
+///     int_val(0),
+/// );
+/// ```
 pub fn get_by_value_relative_rank_range_count(
     return_type: ListReturnType,
     value: FilterExpression,
@@ -507,7 +556,7 @@ pub fn get_by_value_relative_rank_range_count(
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtListOpType::GetByValueRelRankRange as i64)),
-        ExpressionArgument::Value(Value::from(return_type as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(value),
         ExpressionArgument::FilterExpression(rank),
         ExpressionArgument::FilterExpression(count),
@@ -525,7 +574,7 @@ pub fn get_by_value_relative_rank_range_count(
 /// use aerospike::operations::lists::ListReturnType;
 /// use aerospike::expressions::lists::get_by_index;
 /// eq(
-///   get_by_index(ListReturnType::Values, ExpType::INT, int_val(3), list_bin("a".to_string()), &[]),
+///   get_by_index(ListReturnType::Values, ExpType::INT, int_val(3), list_bin("a"), &[]),
 ///   int_val(5));
 /// ```
 ///
@@ -538,7 +587,7 @@ pub fn get_by_index(
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtListOpType::GetByIndex as i64)),
-        ExpressionArgument::Value(Value::from(return_type as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(index),
         ExpressionArgument::Context(ctx.to_vec()),
     ];
@@ -555,7 +604,7 @@ pub fn get_by_index_range(
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtListOpType::GetByIndexRange as i64)),
-        ExpressionArgument::Value(Value::from(return_type as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(index),
         ExpressionArgument::Context(ctx.to_vec()),
     ];
@@ -573,7 +622,7 @@ pub fn get_by_index_range_count(
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtListOpType::GetByIndexRange as i64)),
-        ExpressionArgument::Value(Value::from(return_type as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(index),
         ExpressionArgument::FilterExpression(count),
         ExpressionArgument::Context(ctx.to_vec()),
@@ -589,7 +638,7 @@ pub fn get_by_index_range_count(
 /// use aerospike::operations::lists::ListReturnType;
 /// use aerospike::expressions::{ExpType, int_val, list_bin};
 /// use aerospike::expressions::lists::get_by_rank;
-/// get_by_rank(ListReturnType::Values, ExpType::STRING, int_val(0), list_bin("a".to_string()), &[]);
+/// get_by_rank(ListReturnType::Values, ExpType::STRING, int_val(0), list_bin("a"), &[]);
 /// ```
 pub fn get_by_rank(
     return_type: ListReturnType,
@@ -600,7 +649,7 @@ pub fn get_by_rank(
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtListOpType::GetByRank as i64)),
-        ExpressionArgument::Value(Value::from(return_type as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(rank),
         ExpressionArgument::Context(ctx.to_vec()),
     ];
@@ -617,7 +666,7 @@ pub fn get_by_rank_range(
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtListOpType::GetByRankRange as i64)),
-        ExpressionArgument::Value(Value::from(return_type as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(rank),
         ExpressionArgument::Context(ctx.to_vec()),
     ];
@@ -635,7 +684,7 @@ pub fn get_by_rank_range_count(
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtListOpType::GetByRankRange as i64)),
-        ExpressionArgument::Value(Value::from(return_type as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(rank),
         ExpressionArgument::FilterExpression(count),
         ExpressionArgument::Context(ctx.to_vec()),
@@ -657,6 +706,7 @@ fn add_read(
         module: Some(return_type),
         exps: None,
         arguments: Some(arguments),
+        packed: None,
     }
 }
 
@@ -683,14 +733,54 @@ fn add_write(
         module: Some(return_type),
         exps: None,
         arguments: Some(arguments),
+        packed: None,
     }
 }
 
 #[doc(hidden)]
 const fn get_value_type(return_type: ListReturnType) -> ExpType {
-    if (return_type as u8 & !(ListReturnType::Inverted as u8)) == ListReturnType::Values as u8 {
+    if (return_type as i64 & !(ListReturnType::Inverted as i64)) == ListReturnType::Values as i64 {
         ExpType::LIST
     } else {
         ExpType::INT
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{get_by_value_relative_rank_range, remove_by_value_list};
+    use crate::expressions::tests::pack;
+    use crate::expressions::{int_val, list_bin, list_val};
+    use crate::operations::lists::ListReturnType;
+    use crate::Value;
+
+    #[test]
+    fn get_by_value_relative_rank_range_inverted() {
+        let exp = get_by_value_relative_rank_range(
+            ListReturnType::Inverted,
+            int_val(5),
+            int_val(-1),
+            list_bin("l"),
+            &[],
+        );
+        // call int read, [get by value relative rank range, inverted, 5, -1], bin "l"; the
+        // inverted flag does not fit into a byte
+        let expected = vec![
+            0x95, 0x7f, 0x02, 0x00, 0x94, 0x1b, 0xce, 0x00, 0x01, 0x00, 0x00, 0x05, 0xff, 0x93,
+            0x51, 0x04, 0xa1, b'l',
+        ];
+        assert_eq!(pack(&exp), expected);
+    }
+
+    #[test]
+    fn remove_by_value_list_inverted() {
+        let values = list_val(vec![Value::from(1), Value::from(2)]);
+        let exp = remove_by_value_list(ListReturnType::Inverted, values, list_bin("l"), &[]);
+        // call list modify, [remove by value list, inverted, quoted [1, 2]], bin "l"
+        let expected = vec![
+            0x95, 0x7f, 0x04, 0x40, 0x93, 0x24, 0xce, 0x00, 0x01, 0x00, 0x00, 0x92, 0x7e, 0x92,
+            0x01, 0x02, 0x93, 0x51, 0x04, 0xa1, b'l',
+        ];
+        assert_eq!(pack(&exp), expected);
+    }
+}
@@ -31,23 +31,18 @@ pub fn put(
     bin: FilterExpression,
     ctx: &[CdtContext],
 ) -> FilterExpression {
-    let args: Vec<ExpressionArgument>;
     let op = map_write_op(policy, false);
-    if op as u8 == CdtMapOpType::Replace as u8 {
-        args = vec![
-            ExpressionArgument::Context(ctx.to_vec()),
-            ExpressionArgument::Value(Value::from(op as u8)),
-            ExpressionArgument::FilterExpression(key),
-            ExpressionArgument::FilterExpression(value),
-        ]
-    } else {
-        args = vec![
-            ExpressionArgument::Context(ctx.to_vec()),
-            ExpressionArgument::Value(Value::from(op as u8)),
-            ExpressionArgument::FilterExpression(key),
-            ExpressionArgument::FilterExpression(value),
-            ExpressionArgument::Value(Value::from(policy.order as u8)),
-        ]
+    let mut args = vec![
+        ExpressionArgument::Context(ctx.to_vec()),
+        ExpressionArgument::Value(Value::from(op as u8)),
+        ExpressionArgument::FilterExpression(key),
+        ExpressionArgument::FilterExpression(value),
+    ];
+    if op as u8 != CdtMapOpType::Replace as u8 {
+        args.push(ExpressionArgument::Value(Value::from(policy.order as u8)));
+    }
+    if policy.flags.bits() != 0 {
+        args.push(ExpressionArgument::Value(Value::from(policy.flags.bits())));
     }
     add_write(bin, ctx, args)
 }
@@ -60,21 +55,17 @@ pub fn put_items(
     bin: FilterExpression,
     ctx: &[CdtContext],
 ) -> FilterExpression {
-    let args: Vec<ExpressionArgument>;
     let op = map_write_op(policy, true);
-    if op as u8 == CdtMapOpType::Replace as u8 {
-        args = vec![
-            ExpressionArgument::Context(ctx.to_vec()),
-            ExpressionArgument::Value(Value::from(op as u8)),
-            ExpressionArgument::FilterExpression(map),
-        ]
-    } else {
-        args = vec![
-            ExpressionArgument::Context(ctx.to_vec()),
-            ExpressionArgument::Value(Value::from(op as u8)),
-            ExpressionArgument::FilterExpression(map),
-            ExpressionArgument::Value(Value::from(policy.order as u8)),
-        ]
+    let mut args = vec![
+        ExpressionArgument::Context(ctx.to_vec()),
+        ExpressionArgument::Value(Value::from(op as u8)),
+        ExpressionArgument::FilterExpression(map),
+    ];
+    if op as u8 != CdtMapOpType::ReplaceItems as u8 {
+        args.push(ExpressionArgument::Value(Value::from(policy.order as u8)));
+    }
+    if policy.flags.bits() != 0 {
+        args.push(ExpressionArgument::Value(Value::from(policy.flags.bits())));
     }
     add_write(bin, ctx, args)
 }
@@ -89,13 +80,16 @@ pub fn increment(
     bin: FilterExpression,
     ctx: &[CdtContext],
 ) -> FilterExpression {
-    let args = vec![
+    let mut args = vec![
         ExpressionArgument::Value(Value::from(CdtMapOpType::Increment as u8)),
         ExpressionArgument::FilterExpression(key),
         ExpressionArgument::FilterExpression(incr),
         ExpressionArgument::Context(ctx.to_vec()),
         ExpressionArgument::Value(Value::from(policy.order as u8)),
     ];
+    if policy.flags.bits() != 0 {
+        args.push(ExpressionArgument::Value(Value::from(policy.flags.bits())));
+    }
     add_write(bin, ctx, args)
 }
 
@@ -110,28 +104,49 @@ pub fn clear(bin: FilterExpression, ctx: &[CdtContext]) -> FilterExpression {
 
 /// Create expression that removes map item identified by key.
 pub fn remove_by_key(
+    return_type: MapReturnType,
     key: FilterExpression,
     bin: FilterExpression,
     ctx: &[CdtContext],
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtMapOpType::RemoveByKey as u8)),
-        ExpressionArgument::Value(Value::from(MapReturnType::None as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(key),
         ExpressionArgument::Context(ctx.to_vec()),
     ];
     add_write(bin, ctx, args)
 }
 
-/// Create expression that removes map items identified by keys.
+/// Create expression that removes map items identified by keys. Use `MapReturnType::Inverted`
+/// to remove all items except those identified by keys.
+/// ```
+/// use aerospike::expressions::{eq, int_val, list_val, map_bin};
+/// use aerospike::expressions::maps::{remove_by_key_list, size};
+/// use aerospike::{MapReturnType, Value};
+/// // Map bin "a" has exactly one item besides the keys "x" and "y"
+/// eq(
+///     size(
+///         remove_by_key_list(
+///             MapReturnType::None,
+///             list_val(vec![Value::from("x"), Value::from("y")]),
+///             map_bin("a"),
+///             &[],
+///         ),
+///         &[],
+///     ),
+///     int_val(1),
+/// );
+/// ```
 pub fn remove_by_key_list(
+    return_type: MapReturnType,
     keys: FilterExpression,
     bin: FilterExpression,
     ctx: &[CdtContext],
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtMapOpType::RemoveKeyList as u8)),
-        ExpressionArgument::Value(Value::from(MapReturnType::None as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(keys),
         ExpressionArgument::Context(ctx.to_vec()),
     ];
@@ -142,6 +157,7 @@ pub fn remove_by_key_list(
 /// If keyBegin is null, the range is less than keyEnd.
 /// If keyEnd is null, the range is greater than equal to keyBegin.
 pub fn remove_by_key_range(
+    return_type: MapReturnType,
     key_begin: Option<FilterExpression>,
     key_end: Option<FilterExpression>,
     bin: FilterExpression,
@@ -150,7 +166,7 @@ pub fn remove_by_key_range(
     let mut args = vec![
         ExpressionArgument::Context(ctx.to_vec()),
         ExpressionArgument::Value(Value::from(CdtMapOpType::RemoveByKeyInterval as u8)),
-        ExpressionArgument::Value(Value::from(MapReturnType::None as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
     ];
     if let Some(val_beg) = key_begin {
         args.push(ExpressionArgument::FilterExpression(val_beg));
@@ -174,6 +190,7 @@ pub fn remove_by_key_range(
 /// * (3,2) = [{9=10}]
 /// * (3,-2) = [{0=17},{4=2},{5=15},{9=10}]
 pub fn remove_by_key_relative_index_range(
+    return_type: MapReturnType,
     key: FilterExpression,
     index: FilterExpression,
     bin: FilterExpression,
@@ -181,7 +198,7 @@ pub fn remove_by_key_relative_index_range(
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtMapOpType::RemoveByKeyRelIndexRange as u8)),
-        ExpressionArgument::Value(Value::from(MapReturnType::None as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(key),
         ExpressionArgument::FilterExpression(index),
         ExpressionArgument::Context(ctx.to_vec()),
@@ -200,6 +217,7 @@ pub fn remove_by_key_relative_index_range(
 /// * (3,2,1) = [{9=10}]
 /// * (3,-2,2) = [{0=17}]
 pub fn remove_by_key_relative_index_range_count(
+    return_type: MapReturnType,
     key: FilterExpression,
     index: FilterExpression,
     count: FilterExpression,
@@ -208,7 +226,7 @@ pub fn remove_by_key_relative_index_range_count(
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtMapOpType::RemoveByKeyRelIndexRange as u8)),
-        ExpressionArgument::Value(Value::from(MapReturnType::None as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(key),
         ExpressionArgument::FilterExpression(index),
         ExpressionArgument::FilterExpression(count),
@@ -219,13 +237,14 @@ pub fn remove_by_key_relative_index_range_count(
 
 /// Create expression that removes map items identified by value.
 pub fn remove_by_value(
+    return_type: MapReturnType,
     value: FilterExpression,
     bin: FilterExpression,
     ctx: &[CdtContext],
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtMapOpType::RemoveByValue as u8)),
-        ExpressionArgument::Value(Value::from(MapReturnType::None as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(value),
         ExpressionArgument::Context(ctx.to_vec()),
     ];
@@ -234,13 +253,14 @@ pub fn remove_by_value(
 
 /// Create expression that removes map items identified by values.
 pub fn remove_by_value_list(
+    return_type: MapReturnType,
     values: FilterExpression,
     bin: FilterExpression,
     ctx: &[CdtContext],
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtMapOpType::RemoveValueList as u8)),
-        ExpressionArgument::Value(Value::from(MapReturnType::None as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(values),
         ExpressionArgument::Context(ctx.to_vec()),
     ];
@@ -251,6 +271,7 @@ pub fn remove_by_value_list(
 /// If valueBegin is null, the range is less than valueEnd.
 /// If valueEnd is null, the range is greater than equal to valueBegin.
 pub fn remove_by_value_range(
+    return_type: MapReturnType,
     value_begin: Option<FilterExpression>,
     value_end: Option<FilterExpression>,
     bin: FilterExpression,
@@ -259,7 +280,7 @@ pub fn remove_by_value_range(
     let mut args = vec![
         ExpressionArgument::Context(ctx.to_vec()),
         ExpressionArgument::Value(Value::from(CdtMapOpType::RemoveByValueInterval as u8)),
-        ExpressionArgument::Value(Value::from(MapReturnType::None as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
     ];
     if let Some(val_beg) = value_begin {
         args.push(ExpressionArgument::FilterExpression(val_beg));
@@ -280,6 +301,7 @@ pub fn remove_by_value_range(
 /// * (11,1) = [{0=17}]
 /// * (11,-1) = [{9=10},{5=15},{0=17}]
 pub fn remove_by_value_relative_rank_range(
+    return_type: MapReturnType,
     value: FilterExpression,
     rank: FilterExpression,
     bin: FilterExpression,
@@ -287,7 +309,7 @@ pub fn remove_by_value_relative_rank_range(
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtMapOpType::RemoveByValueRelRankRange as u8)),
-        ExpressionArgument::Value(Value::from(MapReturnType::None as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(value),
         ExpressionArgument::FilterExpression(rank),
         ExpressionArgument::Context(ctx.to_vec()),
@@ -303,6 +325,7 @@ pub fn remove_by_value_relative_rank_range(
 /// * (11,1,1) = [{0=17}]
 /// * (11,-1,1) = [{9=10}]
 pub fn remove_by_value_relative_rank_range_count(
+    return_type: MapReturnType,
     value: FilterExpression,
     rank: FilterExpression,
     count: FilterExpression,
@@ -311,7 +334,7 @@ pub fn remove_by_value_relative_rank_range_count(
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtMapOpType::RemoveByValueRelRankRange as u8)),
-        ExpressionArgument::Value(Value::from(MapReturnType::None as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(value),
         ExpressionArgument::FilterExpression(rank),
         ExpressionArgument::FilterExpression(count),
@@ -322,13 +345,14 @@ pub fn remove_by_value_relative_rank_range_count(
 
 /// Create expression that removes map item identified by index.
 pub fn remove_by_index(
+    return_type: MapReturnType,
     index: FilterExpression,
     bin: FilterExpression,
     ctx: &[CdtContext],
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtMapOpType::RemoveByIndex as u8)),
-        ExpressionArgument::Value(Value::from(MapReturnType::None as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(index),
         ExpressionArgument::Context(ctx.to_vec()),
     ];
@@ -337,13 +361,14 @@ pub fn remove_by_index(
 
 /// Create expression that removes map items starting at specified index to the end of map.
 pub fn remove_by_index_range(
+    return_type: MapReturnType,
     index: FilterExpression,
     bin: FilterExpression,
     ctx: &[CdtContext],
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtMapOpType::RemoveByIndexRange as u8)),
-        ExpressionArgument::Value(Value::from(MapReturnType::None as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(index),
         ExpressionArgument::Context(ctx.to_vec()),
     ];
@@ -352,6 +377,7 @@ pub fn remove_by_index_range(
 
 /// Create expression that removes "count" map items starting at specified index.
 pub fn remove_by_index_range_count(
+    return_type: MapReturnType,
     index: FilterExpression,
     count: FilterExpression,
     bin: FilterExpression,
@@ -359,7 +385,7 @@ pub fn remove_by_index_range_count(
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtMapOpType::RemoveByIndexRange as u8)),
-        ExpressionArgument::Value(Value::from(MapReturnType::None as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(index),
         ExpressionArgument::FilterExpression(count),
         ExpressionArgument::Context(ctx.to_vec()),
@@ -369,13 +395,14 @@ pub fn remove_by_index_range_count(
 
 /// Create expression that removes map item identified by rank.
 pub fn remove_by_rank(
+    return_type: MapReturnType,
     rank: FilterExpression,
     bin: FilterExpression,
     ctx: &[CdtContext],
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtMapOpType::RemoveByRank as u8)),
-        ExpressionArgument::Value(Value::from(MapReturnType::None as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(rank),
         ExpressionArgument::Context(ctx.to_vec()),
     ];
@@ -384,13 +411,14 @@ pub fn remove_by_rank(
 
 /// Create expression that removes map items starting at specified rank to the last ranked item.
 pub fn remove_by_rank_range(
+    return_type: MapReturnType,
     rank: FilterExpression,
     bin: FilterExpression,
     ctx: &[CdtContext],
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtMapOpType::RemoveByRankRange as u8)),
-        ExpressionArgument::Value(Value::from(MapReturnType::None as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(rank),
         ExpressionArgument::Context(ctx.to_vec()),
     ];
@@ -399,6 +427,7 @@ pub fn remove_by_rank_range(
 
 /// Create expression that removes "count" map items starting at specified rank.
 pub fn remove_by_rank_range_count(
+    return_type: MapReturnType,
     rank: FilterExpression,
     count: FilterExpression,
     bin: FilterExpression,
@@ -406,7 +435,7 @@ pub fn remove_by_rank_range_count(
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtMapOpType::RemoveByRankRange as u8)),
-        ExpressionArgument::Value(Value::from(MapReturnType::None as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(rank),
         ExpressionArgument::FilterExpression(count),
         ExpressionArgument::Context(ctx.to_vec()),
@@ -421,7 +450,7 @@ pub fn remove_by_rank_range_count(
 /// use aerospike::expressions::{gt, map_bin, int_val};
 /// use aerospike::expressions::maps::size;
 ///
-/// gt(size(map_bin("a".to_string()), &[]), int_val(7));
+/// gt(size(map_bin("a"), &[]), int_val(7));
 ///
 /// ```
 pub fn size(bin: FilterExpression, ctx: &[CdtContext]) -> FilterExpression {
@@ -441,7 +470,7 @@ pub fn size(bin: FilterExpression, ctx: &[CdtContext]) -> FilterExpression {
 /// use aerospike::MapReturnType;
 /// use aerospike::expressions::maps::get_by_key;
 ///
-/// gt(get_by_key(MapReturnType::Count, ExpType::INT, string_val("B".to_string()), map_bin("a".to_string()), &[]), int_val(0));
+/// gt(get_by_key(MapReturnType::Count, ExpType::INT, string_val("B"), map_bin("a"), &[]), int_val(0));
 /// ```
 ///
 pub fn get_by_key(
@@ -453,7 +482,7 @@ pub fn get_by_key(
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtMapOpType::GetByKey as u8)),
-        ExpressionArgument::Value(Value::from(return_type as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(key),
         ExpressionArgument::Context(ctx.to_vec()),
     ];
@@ -474,7 +503,7 @@ pub fn get_by_key_range(
     let mut args = vec![
         ExpressionArgument::Context(ctx.to_vec()),
         ExpressionArgument::Value(Value::from(CdtMapOpType::GetByKeyInterval as u8)),
-        ExpressionArgument::Value(Value::from(return_type as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
     ];
     if let Some(val_beg) = key_begin {
         args.push(ExpressionArgument::FilterExpression(val_beg));
@@ -488,6 +517,21 @@ pub fn get_by_key_range(
 }
 
 /// Create expression that selects map items identified by keys and returns selected data specified by returnType
+/// ```
+/// use aerospike::expressions::{gt, int_val, list_val, map_bin};
+/// use aerospike::expressions::maps::get_by_key_list;
+/// use aerospike::{MapReturnType, Value};
+/// // Map bin "a" contains any of the keys "x" or "y"
+/// gt(
+///     get_by_key_list(
+///         MapReturnType::Count,
+///         list_val(vec![Value::from("x"), Value::from("y")]),
+///         map_bin("a"),
+///         &[],
+///     ),
+///     int_val(0),
+/// );
+/// ```
 pub fn get_by_key_list(
     return_type: MapReturnType,
     keys: FilterExpression,
@@ -496,7 +540,7 @@ pub fn get_by_key_list(
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtMapOpType::GetByKeyList as u8)),
-        ExpressionArgument::Value(Value::from(return_type as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(keys),
         ExpressionArgument::Context(ctx.to_vec()),
     ];
@@ -523,7 +567,7 @@ pub fn get_by_key_relative_index_range(
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtMapOpType::GetByKeyRelIndexRange as u8)),
-        ExpressionArgument::Value(Value::from(return_type as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(key),
         ExpressionArgument::FilterExpression(index),
         ExpressionArgument::Context(ctx.to_vec()),
@@ -552,7 +596,7 @@ pub fn get_by_key_relative_index_range_count(
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtMapOpType::GetByKeyRelIndexRange as u8)),
-        ExpressionArgument::Value(Value::from(return_type as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(key),
         ExpressionArgument::FilterExpression(index),
         ExpressionArgument::FilterExpression(count),
@@ -570,7 +614,7 @@ pub fn get_by_key_relative_index_range_count(
 /// use aerospike::MapReturnType;
 /// use aerospike::expressions::maps::get_by_value;
 ///
-/// gt(get_by_value(MapReturnType::Count, string_val("BBB".to_string()), map_bin("a".to_string()), &[]), int_val(0));
+/// gt(get_by_value(MapReturnType::Count, string_val("BBB"), map_bin("a"), &[]), int_val(0));
 /// ```
 pub fn get_by_value(
     return_type: MapReturnType,
@@ -580,7 +624,7 @@ pub fn get_by_value(
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtMapOpType::GetByValue as u8)),
-        ExpressionArgument::Value(Value::from(return_type as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(value),
         ExpressionArgument::Context(ctx.to_vec()),
     ];
@@ -602,7 +646,7 @@ pub fn get_by_value_range(
     let mut args = vec![
         ExpressionArgument::Context(ctx.to_vec()),
         ExpressionArgument::Value(Value::from(CdtMapOpType::GetByValueInterval as u8)),
-        ExpressionArgument::Value(Value::from(return_type as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
     ];
     if let Some(val_beg) = value_begin {
         args.push(ExpressionArgument::FilterExpression(val_beg));
@@ -616,6 +660,21 @@ pub fn get_by_value_range(
 }
 
 /// Create expression that selects map items identified by values and returns selected data specified by returnType.
+/// ```
+/// use aerospike::expressions::{eq, int_val, list_val, map_bin};
+/// use aerospike::expressions::maps::get_by_value_list;
+/// use aerospike::{MapReturnType, Value};
+/// // Map bin "a" contains both values 1 and 2
+/// eq(
+///     get_by_value_list(
+///         MapReturnType::Count,
+///         list_val(vec![Value::from(1), Value::from(2)]),
+///         map_bin("a"),
+///         &[],
+///     ),
+///     int_val(2),
+/// );
+/// ```
 pub fn get_by_value_list(
     return_type: MapReturnType,
     values: FilterExpression,
@@ -624,7 +683,7 @@ pub fn get_by_value_list(
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtMapOpType::GetByValueList as u8)),
-        ExpressionArgument::Value(Value::from(return_type as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(values),
         ExpressionArgument::Context(ctx.to_vec()),
     ];
@@ -648,7 +707,7 @@ pub fn get_by_value_relative_rank_range(
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtMapOpType::GetByValueRelRankRange as u8)),
-        ExpressionArgument::Value(Value::from(return_type as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(value),
         ExpressionArgument::FilterExpression(rank),
         ExpressionArgument::Context(ctx.to_vec()),
@@ -674,7 +733,7 @@ pub fn get_by_value_relative_rank_range_count(
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtMapOpType::GetByValueRelRankRange as u8)),
-        ExpressionArgument::Value(Value::from(return_type as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(value),
         ExpressionArgument::FilterExpression(rank),
         ExpressionArgument::FilterExpression(count),
@@ -693,7 +752,7 @@ pub fn get_by_index(
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtMapOpType::GetByIndex as u8)),
-        ExpressionArgument::Value(Value::from(return_type as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(index),
         ExpressionArgument::Context(ctx.to_vec()),
     ];
@@ -710,7 +769,7 @@ pub fn get_by_index_range(
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtMapOpType::GetByIndexRange as u8)),
-        ExpressionArgument::Value(Value::from(return_type as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(index),
         ExpressionArgument::Context(ctx.to_vec()),
     ];
@@ -728,7 +787,7 @@ pub fn get_by_index_range_count(
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtMapOpType::GetByIndexRange as u8)),
-        ExpressionArgument::Value(Value::from(return_type as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(index),
         ExpressionArgument::FilterExpression(count),
         ExpressionArgument::Context(ctx.to_vec()),
@@ -746,7 +805,7 @@ pub fn get_by_rank(
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtMapOpType::GetByRank as u8)),
-        ExpressionArgument::Value(Value::from(return_type as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(rank),
         ExpressionArgument::Context(ctx.to_vec()),
     ];
@@ -763,7 +822,7 @@ pub fn get_by_rank_range(
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtMapOpType::GetByRankRange as u8)),
-        ExpressionArgument::Value(Value::from(return_type as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(rank),
         ExpressionArgument::Context(ctx.to_vec()),
     ];
@@ -781,7 +840,7 @@ pub fn get_by_rank_range_count(
 ) -> FilterExpression {
     let args = vec![
         ExpressionArgument::Value(Value::from(CdtMapOpType::GetByRankRange as u8)),
-        ExpressionArgument::Value(Value::from(return_type as u8)),
+        ExpressionArgument::Value(Value::from(return_type as i64)),
         ExpressionArgument::FilterExpression(rank),
         ExpressionArgument::FilterExpression(count),
         ExpressionArgument::Context(ctx.to_vec()),
@@ -803,6 +862,7 @@ fn add_read(
         module: Some(return_type),
         exps: None,
         arguments: Some(arguments),
+        packed: None,
     }
 }
 
@@ -829,17 +889,67 @@ fn add_write(
         module: Some(return_type),
         exps: None,
         arguments: Some(arguments),
+        packed: None,
     }
 }
 
 #[doc(hidden)]
 const fn get_value_type(return_type: MapReturnType) -> ExpType {
-    let t = return_type as u8 & !(MapReturnType::Inverted as u8);
-    if t == MapReturnType::Key as u8 || t == MapReturnType::Value as u8 {
+    let t = return_type as i64 & !(MapReturnType::Inverted as i64);
+    if t == MapReturnType::Key as i64 || t == MapReturnType::Value as i64 {
         ExpType::LIST
-    } else if t == MapReturnType::KeyValue as u8 {
+    } else if t == MapReturnType::KeyValue as i64 {
         ExpType::MAP
     } else {
         ExpType::INT
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{get_by_key_list, increment, remove_by_key_list};
+    use crate::expressions::tests::pack;
+    use crate::expressions::{int_val, list_val, map_bin};
+    use crate::operations::{MapOrder, MapWriteFlags};
+    use crate::{MapPolicy, MapReturnType, Value};
+
+    #[test]
+    fn increment_with_flags() {
+        let policy = MapPolicy::new_with_flags(
+            MapOrder::KeyOrdered,
+            MapWriteFlags::UpdateOnly | MapWriteFlags::NoFail,
+        );
+        let exp = increment(&policy, int_val(1), int_val(2), map_bin("m"), &[]);
+        // call map modify, [increment, 1, 2, key ordered, update only | no fail], bin "m"
+        let expected = vec![
+            0x95, 0x7f, 0x05, 0x40, 0x95, 0x49, 0x01, 0x02, 0x01, 0x06, 0x93, 0x51, 0x05, 0xa1,
+            b'm',
+        ];
+        assert_eq!(pack(&exp), expected);
+    }
+
+    #[test]
+    fn get_by_key_list_inverted() {
+        let keys = list_val(vec![Value::from(1), Value::from(2)]);
+        let exp = get_by_key_list(MapReturnType::Inverted, keys, map_bin("m"), &[]);
+        // call int read, [get by key list, inverted, quoted [1, 2]], bin "m"; the inverted flag
+        // does not fit into a byte
+        let expected = vec![
+            0x95, 0x7f, 0x02, 0x00, 0x93, 0x6b, 0xce, 0x00, 0x01, 0x00, 0x00, 0x92, 0x7e, 0x92,
+            0x01, 0x02, 0x93, 0x51, 0x05, 0xa1, b'm',
+        ];
+        assert_eq!(pack(&exp), expected);
+    }
+
+    #[test]
+    fn remove_by_key_list_inverted() {
+        let keys = list_val(vec![Value::from(1), Value::from(2)]);
+        let exp = remove_by_key_list(MapReturnType::Inverted, keys, map_bin("m"), &[]);
+        // call map modify, [remove by key list, inverted, quoted [1, 2]], bin "m"
+        let expected = vec![
+            0x95, 0x7f, 0x05, 0x40, 0x93, 0x51, 0xce, 0x00, 0x01, 0x00, 0x00, 0x92, 0x7e, 0x92,
+            0x01, 0x02, 0x93, 0x51, 0x05, 0xa1, b'm',
+        ];
+        assert_eq!(pack(&exp), expected);
+    }
+}
@@ -19,17 +19,23 @@ pub mod bitwise;
 pub mod hll;
 pub mod lists;
 pub mod maps;
+pub mod parser;
 pub mod regex_flag;
 use crate::commands::buffer::Buffer;
-use crate::errors::Result;
+use crate::errors::{Error, ErrorKind, Result};
+use crate::expressions::regex_flag::RegexFlags;
 use crate::msgpack::encoder::{pack_array_begin, pack_integer, pack_raw_string, pack_value};
 use crate::operations::cdt_context::CdtContext;
 use crate::{ParticleType, Value};
 use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
 use std::fmt::Debug;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// Expression Data Types for usage in some `FilterExpressions` on for example Map and List
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ExpType {
     /// NIL Expression Type
     NIL = 0,
@@ -53,7 +59,7 @@ pub enum ExpType {
     HLL = 9,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[doc(hidden)]
 pub enum ExpOp {
     Unknown = 0,
@@ -102,6 +108,8 @@ pub enum ExpOp {
     SetName = 70,
     KeyExists = 71,
     IsTombstone = 72,
+    MemorySize = 73,
+    RecordSize = 74,
     Key = 80,
     Bin = 81,
     BinType = 82,
@@ -115,7 +123,7 @@ pub enum ExpOp {
 #[doc(hidden)]
 pub const MODIFY: i64 = 0x40;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 #[doc(hidden)]
 pub enum ExpressionArgument {
     Value(Value),
@@ -126,7 +134,7 @@ pub enum ExpressionArgument {
 /// Filter expression, which can be applied to most commands, to control which records are
 /// affected by the command. Filter expression are created using the functions in the
 /// [expressions](crate::expressions) module and its submodules.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct FilterExpression {
     /// The Operation code
     cmd: Option<ExpOp>,
@@ -142,6 +150,8 @@ pub struct FilterExpression {
     exps: Option<Vec<FilterExpression>>,
     /// Optional Arguments (CDT)
     arguments: Option<Vec<ExpressionArgument>>,
+    /// Pre-packed wire format of the expression (decoded from base64)
+    packed: Option<Vec<u8>>,
 }
 
 #[doc(hidden)]
@@ -163,6 +173,7 @@ impl FilterExpression {
             module,
             exps,
             arguments: None,
+            packed: None,
         }
     }
 
@@ -174,7 +185,7 @@ impl FilterExpression {
         let mut size = 0;
         if let Some(val) = &self.val {
             // DEF expression
-            size += pack_raw_string(buf, &val.to_string())?;
+            size += Self::pack_raw_value(buf, val)?;
             size += exps[0].pack(buf)?;
         } else {
             // Normal Expressions
@@ -207,9 +218,9 @@ impl FilterExpression {
                 // Regex Flags
                 size += pack_integer(buf, self.flags.unwrap())?;
                 // Raw String is needed instead of the msgpack String that the pack_value method would use.
-                size += pack_raw_string(buf, &self.val.clone().unwrap().to_string())?;
+                size += Self::pack_raw_value(buf, self.val.as_ref().unwrap())?;
                 // The Bin
-                size += self.bin.clone().unwrap().pack(buf)?;
+                size += self.bin.as_ref().unwrap().pack(buf)?;
             }
             ExpOp::Call => {
                 // Packing logic for Module
@@ -235,7 +246,7 @@ impl FilterExpression {
                                     size += pack_array_begin(buf, ctx.len() * 2)?;
 
                                     for c in ctx {
-                                        size += pack_integer(buf, i64::from(c.id))?;
+                                        size += pack_integer(buf, i64::from(c.id | c.flags))?;
                                         size += pack_value(buf, &c.value)?;
                                     }
                                 }
@@ -257,10 +268,10 @@ impl FilterExpression {
                     }
                 } else {
                     // No Arguments
-                    size += pack_value(buf, &self.val.clone().unwrap())?;
+                    size += pack_value(buf, self.val.as_ref().unwrap())?;
                 }
                 // Write the Bin
-                size += self.bin.clone().unwrap().pack(buf)?;
+                size += self.bin.as_ref().unwrap().pack(buf)?;
             }
             ExpOp::Bin => {
                 // Bin Encoder
@@ -270,7 +281,7 @@ impl FilterExpression {
                 // The Bin Type (INT/String etc.)
                 size += pack_integer(buf, self.module.unwrap() as i64)?;
                 // The name - Raw String is needed instead of the msgpack String that the pack_value method would use.
-                size += pack_raw_string(buf, &self.val.clone().unwrap().to_string())?;
+                size += Self::pack_raw_value(buf, self.val.as_ref().unwrap())?;
             }
             ExpOp::BinType | ExpOp::Var => {
                 // BinType/Var encoder
@@ -278,7 +289,7 @@ impl FilterExpression {
                 // BinType/Var Operation
                 size += pack_integer(buf, cmd as i64)?;
                 // The name - Raw String is needed instead of the msgpack String that the pack_value method would use.
-                size += pack_raw_string(buf, &self.val.clone().unwrap().to_string())?;
+                size += Self::pack_raw_value(buf, self.val.as_ref().unwrap())?;
             }
             _ => {
                 // Packing logic for all other Ops
@@ -301,14 +312,27 @@ impl FilterExpression {
         Ok(size)
     }
 
+    // Packs bin/variable names and regex patterns as raw strings, without copying the value.
+    fn pack_raw_value(buf: &mut Option<&mut Buffer>, val: &Value) -> Result<usize> {
+        match val {
+            Value::String(name) => pack_raw_string(buf, name),
+            _ => pack_raw_string(buf, &val.to_string()),
+        }
+    }
+
     fn pack_value(&self, buf: &mut Option<&mut Buffer>) -> Result<usize> {
         // Packing logic for Value based Ops
-        pack_value(buf, &self.val.clone().unwrap())
+        pack_value(buf, self.val.as_ref().unwrap())
     }
 
     pub fn pack(&self, buf: &mut Option<&mut Buffer>) -> Result<usize> {
         let mut size = 0;
-        if let Some(exps) = &self.exps {
+        if let Some(packed) = &self.packed {
+            if let Some(ref mut buf) = *buf {
+                buf.write_bytes(packed)?;
+            }
+            size += packed.len();
+        } else if let Some(exps) = &self.exps {
             size += self.pack_expression(exps, buf)?;
         } else if let Some(cmd) = self.cmd {
             size += self.pack_command(cmd, buf)?;
@@ -320,6 +344,293 @@ impl FilterExpression {
     }
 }
 
+impl FilterExpression {
+    /// Pack the expression into its wire format and return it as a base64 encoded string, the
+    /// same representation used by the server and other Aerospike tools.
+    /// ```
+    /// use aerospike::expressions::{eq, int_bin, int_val};
+    /// let exp = eq(int_bin("a"), int_val(1));
+    /// assert_eq!(exp.base64().unwrap(), "kwGTUQKhYQE=");
+    /// ```
+    pub fn base64(&self) -> Result<String> {
+        let size = self.pack(&mut None)?;
+        let mut buf = Buffer::new(size);
+        buf.resize_buffer(size)?;
+        self.pack(&mut Some(&mut buf))?;
+        Ok(base64::encode(&buf.data_buffer))
+    }
+
+    /// Create a filter expression from its base64 encoded wire format, e.g. as produced by
+    /// [`base64`](FilterExpression::base64), another Aerospike client or aql. The expression is
+    /// not parsed; it is sent to the server as is.
+    /// ```
+    /// use aerospike::expressions::FilterExpression;
+    /// let exp = FilterExpression::from_base64("kwGTUQKhYQE=").unwrap();
+    /// assert_eq!(exp.base64().unwrap(), "kwGTUQKhYQE=");
+    /// ```
+    pub fn from_base64(encoded: &str) -> Result<FilterExpression> {
+        let packed = base64::decode(encoded)?;
+        if packed.is_empty() {
+            bail!(ErrorKind::InvalidArgument(
+                "Empty filter expression".to_string()
+            ));
+        }
+        Ok(FilterExpression {
+            cmd: None,
+            val: None,
+            bin: None,
+            flags: None,
+            module: None,
+            exps: None,
+            arguments: None,
+            packed: Some(packed),
+        })
+    }
+}
+
+impl FromStr for FilterExpression {
+    type Err = Error;
+
+    /// Parse a textual filter expression. See [`parser`](crate::expressions::parser) for the
+    /// syntax.
+    /// ```
+    /// use aerospike::expressions::FilterExpression;
+    /// let exp: FilterExpression = "intBin('a') > 5 && setName() == 'users'".parse().unwrap();
+    /// ```
+    fn from_str(s: &str) -> Result<Self> {
+        parser::parse(s)
+    }
+}
+
+impl fmt::Display for FilterExpression {
+    /// Render the expression in a human-readable infix form.
+    /// ```
+    /// use aerospike::expressions::{and, eq, gt, int_bin, int_val, set_name, string_val};
+    /// let exp = and(vec![
+    ///     gt(int_bin("a"), int_val(5)),
+    ///     eq(set_name(), string_val("users")),
+    /// ]);
+    /// assert_eq!(exp.to_string(), r#"((int_bin("a") > 5) && (set_name() == "users"))"#);
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(packed) = &self.packed {
+            return write!(f, "packed({})", base64::encode(packed));
+        }
+
+        if let Some(exps) = &self.exps {
+            if let Some(val) = &self.val {
+                // DEF expression
+                return write!(f, "def({:?}, {})", val.to_string(), exps[0]);
+            }
+            let cmd = match self.cmd {
+                Some(cmd) => cmd,
+                None => return write!(f, "[{}]", Joined(exps, ", ")),
+            };
+            return match cmd.infix() {
+                Some(op) if exps.len() == 1 => write!(f, "{}{}", op, exps[0]),
+                Some(op) => write!(f, "({})", Joined(exps, &format!(" {} ", op))),
+                None => write!(f, "{}({})", cmd.name(), Joined(exps, ", ")),
+            };
+        }
+
+        match self.cmd {
+            None => fmt_value(f, self.val.as_ref().unwrap_or(&Value::Nil)),
+            Some(ExpOp::Bin) => {
+                let exp_type = match self.module {
+                    Some(ExpType::NIL) | None => "",
+                    Some(ExpType::BOOL) => "bool_",
+                    Some(ExpType::INT) => "int_",
+                    Some(ExpType::STRING) => "string_",
+                    Some(ExpType::LIST) => "list_",
+                    Some(ExpType::MAP) => "map_",
+                    Some(ExpType::BLOB) => "blob_",
+                    Some(ExpType::FLOAT) => "float_",
+                    Some(ExpType::GEO) => "geo_",
+                    Some(ExpType::HLL) => "hll_",
+                };
+                write!(f, "{}bin({:?})", exp_type, self.string_val())
+            }
+            Some(ExpOp::Regex) => write!(
+                f,
+                "regex_compare({:?}, {}, {})",
+                self.string_val(),
+                self.flags.unwrap_or_default(),
+                Joined(self.bin.iter().map(AsRef::as_ref), "")
+            ),
+            Some(ExpOp::Call) => {
+                let module = match self.flags.map(|flags| flags & !MODIFY) {
+                    Some(0) => "cdt",
+                    Some(1) => "bit",
+                    _ => "hll",
+                };
+                write!(f, "{}(", module)?;
+                for arg in self.arguments.iter().flatten() {
+                    match arg {
+                        ExpressionArgument::Value(val) => fmt_value(f, val)?,
+                        ExpressionArgument::FilterExpression(exp) => write!(f, "{}", exp)?,
+                        ExpressionArgument::Context(ctx) if ctx.is_empty() => continue,
+                        ExpressionArgument::Context(ctx) => write!(f, "ctx{:?}", ctx)?,
+                    }
+                    write!(f, ", ")?;
+                }
+                write!(f, "{})", Joined(self.bin.iter().map(AsRef::as_ref), ""))
+            }
+            Some(ExpOp::Quoted) => fmt_value(f, self.val.as_ref().unwrap_or(&Value::Nil)),
+            Some(ExpOp::BinType) | Some(ExpOp::Var) => {
+                write!(f, "{}({:?})", self.cmd.unwrap().name(), self.string_val())
+            }
+            Some(cmd) => match &self.val {
+                Some(val) => {
+                    write!(f, "{}(", cmd.name())?;
+                    fmt_value(f, val)?;
+                    write!(f, ")")
+                }
+                None => write!(f, "{}()", cmd.name()),
+            },
+        }
+    }
+}
+
+impl FilterExpression {
+    fn string_val(&self) -> String {
+        self.val.as_ref().map(Value::to_string).unwrap_or_default()
+    }
+}
+
+impl ExpOp {
+    const fn infix(self) -> Option<&'static str> {
+        match self {
+            ExpOp::EQ => Some("=="),
+            ExpOp::NE => Some("!="),
+            ExpOp::GT => Some(">"),
+            ExpOp::GE => Some(">="),
+            ExpOp::LT => Some("<"),
+            ExpOp::LE => Some("<="),
+            ExpOp::And => Some("&&"),
+            ExpOp::Or => Some("||"),
+            ExpOp::Not => Some("!"),
+            ExpOp::Add => Some("+"),
+            ExpOp::Sub => Some("-"),
+            ExpOp::Mul => Some("*"),
+            ExpOp::Div => Some("/"),
+            ExpOp::Mod => Some("%"),
+            _ => None,
+        }
+    }
+
+    const fn name(self) -> &'static str {
+        match self {
+            ExpOp::Unknown => "unknown",
+            ExpOp::EQ => "eq",
+            ExpOp::NE => "ne",
+            ExpOp::GT => "gt",
+            ExpOp::GE => "ge",
+            ExpOp::LT => "lt",
+            ExpOp::LE => "le",
+            ExpOp::Regex => "regex_compare",
+            ExpOp::Geo => "geo_compare",
+            ExpOp::And => "and",
+            ExpOp::Or => "or",
+            ExpOp::Not => "not",
+            ExpOp::Xor => "xor",
+            ExpOp::Add => "num_add",
+            ExpOp::Sub => "num_sub",
+            ExpOp::Mul => "num_mul",
+            ExpOp::Div => "num_div",
+            ExpOp::Pow => "num_pow",
+            ExpOp::Log => "num_log",
+            ExpOp::Mod => "num_mod",
+            ExpOp::Abs => "num_abs",
+            ExpOp::Floor => "num_floor",
+            ExpOp::Ceil => "num_ceil",
+            ExpOp::ToInt => "to_int",
+            ExpOp::ToFloat => "to_float",
+            ExpOp::IntAnd => "int_and",
+            ExpOp::IntOr => "int_or",
+            ExpOp::IntXor => "int_xor",
+            ExpOp::IntNot => "int_not",
+            ExpOp::IntLshift => "int_lshift",
+            ExpOp::IntRshift => "int_rshift",
+            ExpOp::IntARshift => "int_arshift",
+            ExpOp::IntCount => "int_count",
+            ExpOp::IntLscan => "int_lscan",
+            ExpOp::IntRscan => "int_rscan",
+            ExpOp::Min => "min",
+            ExpOp::Max => "max",
+            ExpOp::DigestModulo => "digest_modulo",
+            ExpOp::DeviceSize => "device_size",
+            ExpOp::LastUpdate => "last_update",
+            ExpOp::SinceUpdate => "since_update",
+            ExpOp::VoidTime => "void_time",
+            ExpOp::TTL => "ttl",
+            ExpOp::SetName => "set_name",
+            ExpOp::KeyExists => "key_exists",
+            ExpOp::IsTombstone => "is_tombstone",
+            ExpOp::MemorySize => "memory_size",
+            ExpOp::RecordSize => "record_size",
+            ExpOp::Key => "key",
+            ExpOp::Bin => "bin",
+            ExpOp::BinType => "bin_type",
+            ExpOp::Cond => "cond",
+            ExpOp::Var => "var",
+            ExpOp::Let => "let",
+            ExpOp::Quoted => "quoted",
+            ExpOp::Call => "call",
+        }
+    }
+}
+
+// Helper to render a list of expressions separated by `sep`.
+struct Joined<'a, I>(I, &'a str);
+
+impl<'a, I, T> fmt::Display for Joined<'a, I>
+where
+    I: IntoIterator<Item = T> + Clone,
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, item) in self.0.clone().into_iter().enumerate() {
+            if i > 0 {
+                write!(f, "{}", self.1)?;
+            }
+            write!(f, "{}", item)?;
+        }
+        Ok(())
+    }
+}
+
+fn fmt_value(f: &mut fmt::Formatter, val: &Value) -> fmt::Result {
+    match val {
+        Value::String(s) => write!(f, "{:?}", s),
+        Value::GeoJSON(s) => write!(f, "geo({:?})", s),
+        Value::List(list) => {
+            write!(f, "[")?;
+            for (i, v) in list.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                fmt_value(f, v)?;
+            }
+            write!(f, "]")
+        }
+        Value::HashMap(map) => {
+            write!(f, "{{")?;
+            for (i, (k, v)) in map.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                fmt_value(f, k)?;
+                write!(f, ": ")?;
+                fmt_value(f, v)?;
+            }
+            write!(f, "}}")
+        }
+        Value::Float(fv) => write!(f, "{:?}", f64::from(fv)),
+        Value::Nil => write!(f, "nil"),
+        _ => write!(f, "{}", val),
+    }
+}
+
 /// Create a record key expression of specified type.
 /// ```
 /// use aerospike::expressions::{ExpType, ge, int_val, key};
@@ -352,12 +663,15 @@ pub fn key_exists() -> FilterExpression {
 /// ```
 /// // Integer bin "a" == 500
 /// use aerospike::expressions::{int_bin, int_val, eq};
-/// eq(int_bin("a".to_string()), int_val(500));
+/// eq(int_bin("a"), int_val(500));
 /// ```
-pub fn int_bin(name: String) -> FilterExpression {
+pub fn int_bin<S>(name: S) -> FilterExpression
+where
+    S: Into<String>,
+{
     FilterExpression::new(
         Some(ExpOp::Bin),
-        Some(Value::from(name)),
+        Some(Value::from(name.into())),
         None,
         None,
         Some(ExpType::INT),
@@ -365,16 +679,39 @@ pub fn int_bin(name: String) -> FilterExpression {
     )
 }
 
+/// Create boolean bin expression.
+/// ```
+/// // Boolean bin "a" == true
+/// use aerospike::expressions::{bool_bin, bool_val, eq};
+/// eq(bool_bin("a"), bool_val(true));
+/// ```
+pub fn bool_bin<S>(name: S) -> FilterExpression
+where
+    S: Into<String>,
+{
+    FilterExpression::new(
+        Some(ExpOp::Bin),
+        Some(Value::from(name.into())),
+        None,
+        None,
+        Some(ExpType::BOOL),
+        None,
+    )
+}
+
 /// Create string bin expression.
 /// ```
 /// // String bin "a" == "views"
 /// use aerospike::expressions::{eq, string_bin, string_val};
-/// eq(string_bin("a".to_string()), string_val("views".to_string()));
+/// eq(string_bin("a"), string_val("views"));
 /// ```
-pub fn string_bin(name: String) -> FilterExpression {
+pub fn string_bin<S>(name: S) -> FilterExpression
+where
+    S: Into<String>,
+{
     FilterExpression::new(
         Some(ExpOp::Bin),
-        Some(Value::from(name)),
+        Some(Value::from(name.into())),
         None,
         None,
         Some(ExpType::STRING),
@@ -387,12 +724,15 @@ pub fn string_bin(name: String) -> FilterExpression {
 /// // String bin "a" == [1,2,3]
 /// use aerospike::expressions::{eq, blob_bin, blob_val};
 /// let blob: Vec<u8> = vec![1,2,3];
-/// eq(blob_bin("a".to_string()), blob_val(blob));
+/// eq(blob_bin("a"), blob_val(blob));
 /// ```
-pub fn blob_bin(name: String) -> FilterExpression {
+pub fn blob_bin<S>(name: S) -> FilterExpression
+where
+    S: Into<String>,
+{
     FilterExpression::new(
         Some(ExpOp::Bin),
-        Some(Value::from(name)),
+        Some(Value::from(name.into())),
         None,
         None,
         Some(ExpType::BLOB),
@@ -404,12 +744,15 @@ pub fn blob_bin(name: String) -> FilterExpression {
 /// ```
 /// use aerospike::expressions::{float_val, float_bin, eq};
 /// // Integer bin "a" == 500.5
-/// eq(float_bin("a".to_string()), float_val(500.5));
+/// eq(float_bin("a"), float_val(500.5));
 /// ```
-pub fn float_bin(name: String) -> FilterExpression {
+pub fn float_bin<S>(name: S) -> FilterExpression
+where
+    S: Into<String>,
+{
     FilterExpression::new(
         Some(ExpOp::Bin),
-        Some(Value::from(name)),
+        Some(Value::from(name.into())),
         None,
         None,
         Some(ExpType::FLOAT),
@@ -419,15 +762,19 @@ pub fn float_bin(name: String) -> FilterExpression {
 
 /// Create geo bin expression.
 /// ```
-/// // String bin "a" == region
-/// use aerospike::expressions::{eq, geo_bin, string_val};
-/// let region = "{ \"type\": \"AeroCircle\", \"coordinates\": [[-122.0, 37.5], 50000.0] }";
-/// eq(geo_bin("a".to_string()), string_val(region.to_string()));
+/// // Geo bin "a" == region
+/// use aerospike::{AeroCircle, GeoPoint};
+/// use aerospike::expressions::{eq, geo_bin, geo_val};
+/// let region = AeroCircle::new(GeoPoint::new(-122.0, 37.5), 50000.0);
+/// eq(geo_bin("a"), geo_val(region));
 /// ```
-pub fn geo_bin(name: String) -> FilterExpression {
+pub fn geo_bin<S>(name: S) -> FilterExpression
+where
+    S: Into<String>,
+{
     FilterExpression::new(
         Some(ExpOp::Bin),
-        Some(Value::from(name)),
+        Some(Value::from(name.into())),
         None,
         None,
         Some(ExpType::GEO),
@@ -441,12 +788,15 @@ pub fn geo_bin(name: String) -> FilterExpression {
 /// use aerospike::operations::lists::ListReturnType;
 /// use aerospike::expressions::lists::get_by_index;
 /// // String bin a[2] == 3
-/// eq(get_by_index(ListReturnType::Values, ExpType::INT, int_val(2), list_bin("a".to_string()), &[]), int_val(3));
+/// eq(get_by_index(ListReturnType::Values, ExpType::INT, int_val(2), list_bin("a"), &[]), int_val(3));
 /// ```
-pub fn list_bin(name: String) -> FilterExpression {
+pub fn list_bin<S>(name: S) -> FilterExpression
+where
+    S: Into<String>,
+{
     FilterExpression::new(
         Some(ExpOp::Bin),
-        Some(Value::from(name)),
+        Some(Value::from(name.into())),
         None,
         None,
         Some(ExpType::LIST),
@@ -463,13 +813,16 @@ pub fn list_bin(name: String) -> FilterExpression {
 /// use aerospike::expressions::maps::get_by_key;
 ///
 /// eq(
-///     get_by_key(MapReturnType::Value, ExpType::STRING, string_val("key".to_string()), map_bin("a".to_string()), &[]),
-///     string_val("value".to_string()));
+///     get_by_key(MapReturnType::Value, ExpType::STRING, string_val("key"), map_bin("a"), &[]),
+///     string_val("value"));
 /// ```
-pub fn map_bin(name: String) -> FilterExpression {
+pub fn map_bin<S>(name: S) -> FilterExpression
+where
+    S: Into<String>,
+{
     FilterExpression::new(
         Some(ExpOp::Bin),
-        Some(Value::from(name)),
+        Some(Value::from(name.into())),
         None,
         None,
         Some(ExpType::MAP),
@@ -487,12 +840,15 @@ pub fn map_bin(name: String) -> FilterExpression {
 ///
 /// // Add values to HLL bin "a" and check count > 7
 /// let list = vec![Value::from(1)];
-/// gt(add(HLLPolicy::default(), list_val(list), hll_bin("a".to_string())), int_val(7));
+/// gt(add(HLLPolicy::default(), list_val(list), hll_bin("a")), int_val(7));
 /// ```
-pub fn hll_bin(name: String) -> FilterExpression {
+pub fn hll_bin<S>(name: S) -> FilterExpression
+where
+    S: Into<String>,
+{
     FilterExpression::new(
         Some(ExpOp::Bin),
-        Some(Value::from(name)),
+        Some(Value::from(name.into())),
         None,
         None,
         Some(ExpType::HLL),
@@ -504,9 +860,12 @@ pub fn hll_bin(name: String) -> FilterExpression {
 /// ```
 /// // Bin "a" exists in record
 /// use aerospike::expressions::bin_exists;
-/// bin_exists("a".to_string());
+/// bin_exists("a");
 /// ```
-pub fn bin_exists(name: String) -> FilterExpression {
+pub fn bin_exists<S>(name: S) -> FilterExpression
+where
+    S: Into<String>,
+{
     ne(bin_type(name), int_val(ParticleType::NULL as i64))
 }
 
@@ -515,12 +874,15 @@ pub fn bin_exists(name: String) -> FilterExpression {
 /// use aerospike::ParticleType;
 /// use aerospike::expressions::{eq, bin_type, int_val};
 /// // Bin "a" particle type is a list
-/// eq(bin_type("a".to_string()), int_val(ParticleType::LIST as i64));
+/// eq(bin_type("a"), int_val(ParticleType::LIST as i64));
 /// ```
-pub fn bin_type(name: String) -> FilterExpression {
+pub fn bin_type<S>(name: S) -> FilterExpression
+where
+    S: Into<String>,
+{
     FilterExpression::new(
         Some(ExpOp::BinType),
-        Some(Value::from(name)),
+        Some(Value::from(name.into())),
         None,
         None,
         None,
@@ -532,7 +894,7 @@ pub fn bin_type(name: String) -> FilterExpression {
 /// ```
 /// use aerospike::expressions::{eq, set_name, string_val};
 /// // Record set name == "myset
-/// eq(set_name(), string_val("myset".to_string()));
+/// eq(set_name(), string_val("myset"));
 /// ```
 pub fn set_name() -> FilterExpression {
     FilterExpression::new(Some(ExpOp::SetName), None, None, None, None, None)
@@ -549,12 +911,36 @@ pub fn device_size() -> FilterExpression {
     FilterExpression::new(Some(ExpOp::DeviceSize), None, None, None, None, None)
 }
 
+/// Create function that returns record size in memory.
+/// If server storage-engine is not memory nor data-in-memory, then zero is returned.
+/// ```
+/// use aerospike::expressions::{ge, memory_size, int_val};
+/// // Record memory size >= 100 KB
+/// ge(memory_size(), int_val(100*1024));
+/// ```
+pub fn memory_size() -> FilterExpression {
+    FilterExpression::new(Some(ExpOp::MemorySize), None, None, None, None, None)
+}
+
+/// Create function that returns the record size. This is the size of the record in the
+/// namespace's storage, regardless of whether the storage-engine is memory or device.
+/// Requires server version 7.0+.
+/// ```
+/// use aerospike::expressions::{ge, record_size, int_val};
+/// // Record size >= 100 KB
+/// ge(record_size(), int_val(100*1024));
+/// ```
+pub fn record_size() -> FilterExpression {
+    FilterExpression::new(Some(ExpOp::RecordSize), None, None, None, None, None)
+}
+
 /// Create function that returns record last update time expressed as 64 bit integer
 /// nanoseconds since 1970-01-01 epoch.
 /// ```
 /// // Record last update time >=2020-08-01
-/// use aerospike::expressions::{ge, last_update, float_val};
-/// ge(last_update(), float_val(1.5962E+18));
+/// use aerospike::expressions::{ge, last_update, last_update_val};
+/// use std::time::{Duration, UNIX_EPOCH};
+/// ge(last_update(), last_update_val(UNIX_EPOCH + Duration::from_secs(1_596_240_000)));
 /// ```
 pub fn last_update() -> FilterExpression {
     FilterExpression::new(Some(ExpOp::LastUpdate), None, None, None, None, None)
@@ -565,8 +951,9 @@ pub fn last_update() -> FilterExpression {
 ///
 /// ```
 /// // Record last updated more than 2 hours ago
-/// use aerospike::expressions::{gt, int_val, since_update};
-/// gt(since_update(), int_val(2 * 60 * 60 * 1000));
+/// use aerospike::expressions::{gt, since_update, since_update_val};
+/// use std::time::Duration;
+/// gt(since_update(), since_update_val(Duration::from_secs(2 * 60 * 60)));
 /// ```
 pub fn since_update() -> FilterExpression {
     FilterExpression::new(Some(ExpOp::SinceUpdate), None, None, None, None, None)
@@ -576,8 +963,13 @@ pub fn since_update() -> FilterExpression {
 /// nanoseconds since 1970-01-01 epoch.
 /// ```
 /// // Expires on 2020-08-01
-/// use aerospike::expressions::{and, ge, last_update, float_val, lt};
-/// and(vec![ge(last_update(), float_val(1.5962E+18)), lt(last_update(), float_val(1.5963E+18))]);
+/// use aerospike::expressions::{and, ge, lt, void_time, void_time_val};
+/// use std::time::{Duration, UNIX_EPOCH};
+/// let day = UNIX_EPOCH + Duration::from_secs(1_596_240_000);
+/// and(vec![
+///     ge(void_time(), void_time_val(day)),
+///     lt(void_time(), void_time_val(day + Duration::from_secs(24 * 60 * 60))),
+/// ]);
 /// ```
 pub fn void_time() -> FilterExpression {
     FilterExpression::new(Some(ExpOp::VoidTime), None, None, None, None, None)
@@ -586,13 +978,45 @@ pub fn void_time() -> FilterExpression {
 /// Create function that returns record expiration time (time to live) in integer seconds.
 /// ```
 /// // Record expires in less than 1 hour
-/// use aerospike::expressions::{lt, ttl, int_val};
-/// lt(ttl(), int_val(60*60));
+/// use aerospike::expressions::{lt, ttl, ttl_val};
+/// use std::time::Duration;
+/// lt(ttl(), ttl_val(Duration::from_secs(60 * 60)));
 /// ```
 pub fn ttl() -> FilterExpression {
     FilterExpression::new(Some(ExpOp::TTL), None, None, None, None, None)
 }
 
+/// Creates an integer value in nanoseconds since 1970-01-01 epoch, for comparison with
+/// `last_update()`.
+pub fn last_update_val(time: SystemTime) -> FilterExpression {
+    int_val(epoch_nanos(time))
+}
+
+/// Creates an integer value in milliseconds, for comparison with `since_update()`.
+pub fn since_update_val(duration: Duration) -> FilterExpression {
+    int_val(i64::try_from(duration.as_millis()).unwrap_or(i64::MAX))
+}
+
+/// Creates an integer value in nanoseconds since 1970-01-01 epoch, for comparison with
+/// `void_time()`.
+pub fn void_time_val(time: SystemTime) -> FilterExpression {
+    int_val(epoch_nanos(time))
+}
+
+/// Creates an integer value in seconds, for comparison with `ttl()`.
+pub fn ttl_val(duration: Duration) -> FilterExpression {
+    int_val(i64::try_from(duration.as_secs()).unwrap_or(i64::MAX))
+}
+
+// Times before the Unix epoch are returned as negative offsets. Times too far from the epoch to
+// fit into an i64 saturate.
+fn epoch_nanos(time: SystemTime) -> i64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(since) => i64::try_from(since.as_nanos()).unwrap_or(i64::MAX),
+        Err(err) => i64::try_from(err.duration().as_nanos()).map_or(i64::MIN, |nanos| -nanos),
+    }
+}
+
 /// Create expression that returns if record has been deleted and is still in tombstone state.
 /// This expression usually evaluates quickly because record meta data is cached in memory.
 ///
@@ -622,30 +1046,69 @@ pub fn digest_modulo(modulo: i64) -> FilterExpression {
 }
 
 /// Create function like regular expression string operation.
+/// The pattern is not checked by the client; invalid patterns are rejected by the server. With
+/// the `regex` feature enabled, use `regex_compare_checked` to check the pattern locally instead.
 /// ```
-/// use aerospike::RegexFlag;
+/// use aerospike::RegexFlags;
 /// use aerospike::expressions::{regex_compare, string_bin};
 /// // Select string bin "a" that starts with "prefix" and ends with "suffix".
 /// // Ignore case and do not match newline.
-/// regex_compare("prefix.*suffix".to_string(), RegexFlag::ICASE as i64 | RegexFlag::NEWLINE as i64, string_bin("a".to_string()));
+/// regex_compare("prefix.*suffix", RegexFlags::ICASE | RegexFlags::NEWLINE, string_bin("a"));
 /// ```
-pub fn regex_compare(regex: String, flags: i64, bin: FilterExpression) -> FilterExpression {
+pub fn regex_compare<S, F>(regex: S, flags: F, bin: FilterExpression) -> FilterExpression
+where
+    S: Into<String>,
+    F: Into<RegexFlags>,
+{
     FilterExpression::new(
         Some(ExpOp::Regex),
-        Some(Value::from(regex)),
+        Some(Value::from(regex.into())),
         Some(bin),
-        Some(flags),
+        Some(flags.into().bits()),
         None,
         None,
     )
 }
 
+/// Create function like regular expression string operation, after checking the pattern locally
+/// with `regex_flag::validate`. Invalid patterns are rejected with an `InvalidArgument` error
+/// instead of a server error once the command is sent. See `regex_flag::validate` for the
+/// patterns the local check can not handle.
+/// ```
+/// use aerospike::RegexFlags;
+/// use aerospike::expressions::{regex_compare_checked, string_bin};
+/// let flags = RegexFlags::EXTENDED | RegexFlags::ICASE;
+/// assert!(regex_compare_checked("prefix.*(a|b)$", flags, string_bin("a")).is_ok());
+/// assert!(regex_compare_checked("prefix.*(a|b$", flags, string_bin("a")).is_err());
+/// ```
+#[cfg(feature = "regex")]
+pub fn regex_compare_checked<S, F>(
+    regex: S,
+    flags: F,
+    bin: FilterExpression,
+) -> Result<FilterExpression>
+where
+    S: Into<String>,
+    F: Into<RegexFlags>,
+{
+    let regex = regex.into();
+    let flags = flags.into();
+    regex_flag::validate(&regex, flags)?;
+    Ok(regex_compare(regex, flags, bin))
+}
+
 /// Create compare geospatial operation.
 /// ```
+/// use aerospike::{GeoPoint, GeoPolygon};
 /// use aerospike::expressions::{geo_compare, geo_bin, geo_val};
 /// // Query region within coordinates.
-/// let region = "{\"type\": \"Polygon\", \"coordinates\": [ [[-122.500000, 37.000000],[-121.000000, 37.000000], [-121.000000, 38.080000],[-122.500000, 38.080000], [-122.500000, 37.000000]] ] }";
-/// geo_compare(geo_bin("a".to_string()), geo_val(region.to_string()));
+/// let region = GeoPolygon::new(vec![
+///     GeoPoint::new(-122.5, 37.0),
+///     GeoPoint::new(-121.0, 37.0),
+///     GeoPoint::new(-121.0, 38.08),
+///     GeoPoint::new(-122.5, 38.08),
+/// ]);
+/// geo_compare(geo_bin("a"), geo_val(region));
 /// ```
 pub fn geo_compare(left: FilterExpression, right: FilterExpression) -> FilterExpression {
     FilterExpression::new(
@@ -669,8 +1132,11 @@ pub fn bool_val(val: bool) -> FilterExpression {
 }
 
 /// Creates String bin value
-pub fn string_val(val: String) -> FilterExpression {
-    FilterExpression::new(None, Some(Value::from(val)), None, None, None, None)
+pub fn string_val<S>(val: S) -> FilterExpression
+where
+    S: Into<String>,
+{
+    FilterExpression::new(None, Some(Value::from(val.into())), None, None, None, None)
 }
 
 /// Creates 64 bit float bin value
@@ -683,7 +1149,8 @@ pub fn blob_val(val: Vec<u8>) -> FilterExpression {
     FilterExpression::new(None, Some(Value::from(val)), None, None, None, None)
 }
 
-/// Create List bin Value
+/// Create List bin Value. The elements are constants; the server does not evaluate
+/// expressions inside of a list, so lists can not be built from bins at evaluation time.
 pub fn list_val(val: Vec<Value>) -> FilterExpression {
     FilterExpression::new(
         Some(ExpOp::Quoted),
@@ -701,20 +1168,75 @@ pub fn map_val(val: HashMap<Value, Value>) -> FilterExpression {
     FilterExpression::new(None, Some(Value::from(val)), None, None, None, None)
 }
 
-/// Create geospatial json string value.
-pub fn geo_val(val: String) -> FilterExpression {
-    FilterExpression::new(None, Some(Value::from(val)), None, None, None, None)
+/// Create geospatial json string value. Accepts GeoJSON strings as well as the typed `GeoPoint`,
+/// `GeoPolygon` and `AeroCircle` shapes.
+/// ```
+/// use aerospike::{AeroCircle, GeoPoint};
+/// use aerospike::expressions::geo_val;
+/// geo_val(AeroCircle::new(GeoPoint::new(-122.0, 37.5), 1000.0));
+/// geo_val("{\"type\": \"Point\", \"coordinates\": [-122.0, 37.5]}");
+/// ```
+pub fn geo_val<S>(val: S) -> FilterExpression
+where
+    S: ToString,
+{
+    FilterExpression::new(
+        None,
+        Some(Value::GeoJSON(val.to_string())),
+        None,
+        None,
+        None,
+        None,
+    )
 }
 
 /// Create a Nil Value
 pub fn nil() -> FilterExpression {
     FilterExpression::new(None, Some(Value::Nil), None, None, None, None)
 }
+
+/// Create a Wildcard Value, matching any value when used in CDT expressions. Inside list or map
+/// values, use `Value::Wildcard` instead.
+/// ```
+/// use aerospike::expressions::{list_val, lists, list_bin, wildcard};
+/// use aerospike::operations::lists::ListReturnType;
+/// use aerospike::Value;
+/// // Count all items of list bin "a"
+/// lists::get_by_value(ListReturnType::Count, wildcard(), list_bin("a"), &[]);
+/// // Get all list items of the form [1, *] from list bin "a"
+/// lists::get_by_value(
+///     ListReturnType::Values,
+///     list_val(vec![Value::from(1), Value::Wildcard]),
+///     list_bin("a"),
+///     &[],
+/// );
+/// ```
+pub fn wildcard() -> FilterExpression {
+    FilterExpression::new(None, Some(Value::Wildcard), None, None, None, None)
+}
+
+/// Create an Infinity Value, greater than any other value, used for open-ended ranges in CDT
+/// expressions.
+/// ```
+/// use aerospike::expressions::{maps, map_bin, int_val, infinity};
+/// use aerospike::MapReturnType;
+/// // Get all map items with keys >= 10 from map bin "a"
+/// maps::get_by_key_range(
+///     MapReturnType::Value,
+///     Some(int_val(10)),
+///     Some(infinity()),
+///     map_bin("a"),
+///     &[],
+/// );
+/// ```
+pub fn infinity() -> FilterExpression {
+    FilterExpression::new(None, Some(Value::Infinity), None, None, None, None)
+}
 /// Create "not" operator expression.
 /// ```
 /// // ! (a == 0 || a == 10)
 /// use aerospike::expressions::{not, or, eq, int_bin, int_val};
-/// not(or(vec![eq(int_bin("a".to_string()), int_val(0)), eq(int_bin("a".to_string()), int_val(10))]));
+/// not(or(vec![eq(int_bin("a"), int_val(0)), eq(int_bin("a"), int_val(10))]));
 /// ```
 pub fn not(exp: FilterExpression) -> FilterExpression {
     FilterExpression {
@@ -725,6 +1247,7 @@ pub fn not(exp: FilterExpression) -> FilterExpression {
         module: None,
         exps: Some(vec![exp]),
         arguments: None,
+        packed: None,
     }
 }
 
@@ -732,7 +1255,7 @@ pub fn not(exp: FilterExpression) -> FilterExpression {
 /// ```
 /// // (a > 5 || a == 0) && b < 3
 /// use aerospike::expressions::{and, or, gt, int_bin, int_val, eq, lt};
-/// and(vec![or(vec![gt(int_bin("a".to_string()), int_val(5)), eq(int_bin("a".to_string()), int_val(0))]), lt(int_bin("b".to_string()), int_val(3))]);
+/// and(vec![or(vec![gt(int_bin("a"), int_val(5)), eq(int_bin("a"), int_val(0))]), lt(int_bin("b"), int_val(3))]);
 /// ```
 pub const fn and(exps: Vec<FilterExpression>) -> FilterExpression {
     FilterExpression {
@@ -743,6 +1266,7 @@ pub const fn and(exps: Vec<FilterExpression>) -> FilterExpression {
         module: None,
         exps: Some(exps),
         arguments: None,
+        packed: None,
     }
 }
 
@@ -750,7 +1274,7 @@ pub const fn and(exps: Vec<FilterExpression>) -> FilterExpression {
 /// ```
 /// // a == 0 || b == 0
 /// use aerospike::expressions::{or, eq, int_bin, int_val};
-/// or(vec![eq(int_bin("a".to_string()), int_val(0)), eq(int_bin("b".to_string()), int_val(0))]);
+/// or(vec![eq(int_bin("a"), int_val(0)), eq(int_bin("b"), int_val(0))]);
 /// ```
 pub const fn or(exps: Vec<FilterExpression>) -> FilterExpression {
     FilterExpression {
@@ -761,6 +1285,7 @@ pub const fn or(exps: Vec<FilterExpression>) -> FilterExpression {
         module: None,
         exps: Some(exps),
         arguments: None,
+        packed: None,
     }
 }
 
@@ -768,7 +1293,7 @@ pub const fn or(exps: Vec<FilterExpression>) -> FilterExpression {
 /// ```
 /// // a == 11
 /// use aerospike::expressions::{eq, int_bin, int_val};
-/// eq(int_bin("a".to_string()), int_val(11));
+/// eq(int_bin("a"), int_val(11));
 /// ```
 pub fn eq(left: FilterExpression, right: FilterExpression) -> FilterExpression {
     FilterExpression {
@@ -779,6 +1304,7 @@ pub fn eq(left: FilterExpression, right: FilterExpression) -> FilterExpression {
         module: None,
         exps: Some(vec![left, right]),
         arguments: None,
+        packed: None,
     }
 }
 
@@ -786,7 +1312,7 @@ pub fn eq(left: FilterExpression, right: FilterExpression) -> FilterExpression {
 /// ```
 /// // a != 13
 /// use aerospike::expressions::{ne, int_bin, int_val};
-/// ne(int_bin("a".to_string()), int_val(13));
+/// ne(int_bin("a"), int_val(13));
 /// ```
 pub fn ne(left: FilterExpression, right: FilterExpression) -> FilterExpression {
     FilterExpression {
@@ -797,6 +1323,7 @@ pub fn ne(left: FilterExpression, right: FilterExpression) -> FilterExpression {
         module: None,
         exps: Some(vec![left, right]),
         arguments: None,
+        packed: None,
     }
 }
 
@@ -804,7 +1331,7 @@ pub fn ne(left: FilterExpression, right: FilterExpression) -> FilterExpression {
 /// ```
 /// // a > 8
 /// use aerospike::expressions::{gt, int_bin, int_val};
-/// gt(int_bin("a".to_string()), int_val(8));
+/// gt(int_bin("a"), int_val(8));
 /// ```
 pub fn gt(left: FilterExpression, right: FilterExpression) -> FilterExpression {
     FilterExpression {
@@ -815,6 +1342,7 @@ pub fn gt(left: FilterExpression, right: FilterExpression) -> FilterExpression {
         module: None,
         exps: Some(vec![left, right]),
         arguments: None,
+        packed: None,
     }
 }
 
@@ -822,7 +1350,7 @@ pub fn gt(left: FilterExpression, right: FilterExpression) -> FilterExpression {
 /// ```
 /// use aerospike::expressions::{ge, int_bin, int_val};
 /// // a >= 88
-/// ge(int_bin("a".to_string()), int_val(88));
+/// ge(int_bin("a"), int_val(88));
 /// ```
 pub fn ge(left: FilterExpression, right: FilterExpression) -> FilterExpression {
     FilterExpression {
@@ -833,6 +1361,7 @@ pub fn ge(left: FilterExpression, right: FilterExpression) -> FilterExpression {
         module: None,
         exps: Some(vec![left, right]),
         arguments: None,
+        packed: None,
     }
 }
 
@@ -840,7 +1369,7 @@ pub fn ge(left: FilterExpression, right: FilterExpression) -> FilterExpression {
 /// ```
 /// // a < 1000
 /// use aerospike::expressions::{lt, int_bin, int_val};
-/// lt(int_bin("a".to_string()), int_val(1000));
+/// lt(int_bin("a"), int_val(1000));
 /// ```
 pub fn lt(left: FilterExpression, right: FilterExpression) -> FilterExpression {
     FilterExpression {
@@ -851,6 +1380,7 @@ pub fn lt(left: FilterExpression, right: FilterExpression) -> FilterExpression {
         module: None,
         exps: Some(vec![left, right]),
         arguments: None,
+        packed: None,
     }
 }
 
@@ -858,7 +1388,7 @@ pub fn lt(left: FilterExpression, right: FilterExpression) -> FilterExpression {
 /// ```
 /// use aerospike::expressions::{le, int_bin, int_val};
 /// // a <= 1
-/// le(int_bin("a".to_string()), int_val(1));
+/// le(int_bin("a"), int_val(1));
 /// ```
 pub fn le(left: FilterExpression, right: FilterExpression) -> FilterExpression {
     FilterExpression {
@@ -869,6 +1399,7 @@ pub fn le(left: FilterExpression, right: FilterExpression) -> FilterExpression {
         module: None,
         exps: Some(vec![left, right]),
         arguments: None,
+        packed: None,
     }
 }
 
@@ -878,7 +1409,7 @@ pub fn le(left: FilterExpression, right: FilterExpression) -> FilterExpression {
 /// ```
 /// use aerospike::expressions::{eq, num_add, int_bin, int_val};
 /// // a + b + c == 10
-/// eq(num_add(vec![int_bin("a".to_string()), int_bin("b".to_string()), int_bin("c".to_string())]), int_val(10));
+/// eq(num_add(vec![int_bin("a"), int_bin("b"), int_bin("c")]), int_val(10));
 /// ```
 pub const fn num_add(exps: Vec<FilterExpression>) -> FilterExpression {
     FilterExpression {
@@ -889,6 +1420,7 @@ pub const fn num_add(exps: Vec<FilterExpression>) -> FilterExpression {
         module: None,
         exps: Some(exps),
         arguments: None,
+        packed: None,
     }
 }
 
@@ -900,7 +1432,7 @@ pub const fn num_add(exps: Vec<FilterExpression>) -> FilterExpression {
 /// ```
 /// use aerospike::expressions::{gt, num_sub, int_bin, int_val};
 /// // a - b - c > 10
-/// gt(num_sub(vec![int_bin("a".to_string()), int_bin("b".to_string()), int_bin("c".to_string())]), int_val(10));
+/// gt(num_sub(vec![int_bin("a"), int_bin("b"), int_bin("c")]), int_val(10));
 /// ```
 pub const fn num_sub(exps: Vec<FilterExpression>) -> FilterExpression {
     FilterExpression {
@@ -911,6 +1443,7 @@ pub const fn num_sub(exps: Vec<FilterExpression>) -> FilterExpression {
         module: None,
         exps: Some(exps),
         arguments: None,
+        packed: None,
     }
 }
 
@@ -921,7 +1454,7 @@ pub const fn num_sub(exps: Vec<FilterExpression>) -> FilterExpression {
 /// ```
 /// use aerospike::expressions::{lt, num_mul, int_val, int_bin};
 /// // a * b * c < 100
-/// lt(num_mul(vec![int_bin("a".to_string()), int_bin("b".to_string()), int_bin("c".to_string())]), int_val(100));
+/// lt(num_mul(vec![int_bin("a"), int_bin("b"), int_bin("c")]), int_val(100));
 /// ```
 pub const fn num_mul(exps: Vec<FilterExpression>) -> FilterExpression {
     FilterExpression {
@@ -932,6 +1465,7 @@ pub const fn num_mul(exps: Vec<FilterExpression>) -> FilterExpression {
         module: None,
         exps: Some(exps),
         arguments: None,
+        packed: None,
     }
 }
 
@@ -943,7 +1477,7 @@ pub const fn num_mul(exps: Vec<FilterExpression>) -> FilterExpression {
 /// ```
 /// use aerospike::expressions::{lt, int_val, int_bin, num_div};
 /// // a / b / c > 1
-/// lt(num_div(vec![int_bin("a".to_string()), int_bin("b".to_string()), int_bin("c".to_string())]), int_val(1));
+/// lt(num_div(vec![int_bin("a"), int_bin("b"), int_bin("c")]), int_val(1));
 /// ```
 pub const fn num_div(exps: Vec<FilterExpression>) -> FilterExpression {
     FilterExpression {
@@ -954,6 +1488,7 @@ pub const fn num_div(exps: Vec<FilterExpression>) -> FilterExpression {
         module: None,
         exps: Some(exps),
         arguments: None,
+        packed: None,
     }
 }
 
@@ -963,7 +1498,7 @@ pub const fn num_div(exps: Vec<FilterExpression>) -> FilterExpression {
 /// ```
 /// // pow(a, 2.0) == 4.0
 /// use aerospike::expressions::{eq, num_pow, float_bin, float_val};
-/// eq(num_pow(float_bin("a".to_string()), float_val(2.0)), float_val(4.0));
+/// eq(num_pow(float_bin("a"), float_val(2.0)), float_val(4.0));
 /// ```
 pub fn num_pow(base: FilterExpression, exponent: FilterExpression) -> FilterExpression {
     FilterExpression {
@@ -974,6 +1509,7 @@ pub fn num_pow(base: FilterExpression, exponent: FilterExpression) -> FilterExpr
         module: None,
         exps: Some(vec![base, exponent]),
         arguments: None,
+        packed: None,
     }
 }
 
@@ -983,7 +1519,7 @@ pub fn num_pow(base: FilterExpression, exponent: FilterExpression) -> FilterExpr
 /// ```
 /// // log(a, 2.0) == 4.0
 /// use aerospike::expressions::{eq, float_bin, float_val, num_log};
-/// eq(num_log(float_bin("a".to_string()), float_val(2.0)), float_val(4.0));
+/// eq(num_log(float_bin("a"), float_val(2.0)), float_val(4.0));
 /// ```
 pub fn num_log(num: FilterExpression, base: FilterExpression) -> FilterExpression {
     FilterExpression {
@@ -994,6 +1530,7 @@ pub fn num_log(num: FilterExpression, base: FilterExpression) -> FilterExpressio
         module: None,
         exps: Some(vec![num, base]),
         arguments: None,
+        packed: None,
     }
 }
 
@@ -1003,7 +1540,7 @@ pub fn num_log(num: FilterExpression, base: FilterExpression) -> FilterExpressio
 /// ```
 /// // a % 10 == 0
 /// use aerospike::expressions::{eq, num_mod, int_val, int_bin};
-/// eq(num_mod(int_bin("a".to_string()), int_val(10)), int_val(0));
+/// eq(num_mod(int_bin("a"), int_val(10)), int_val(0));
 /// ```
 pub fn num_mod(numerator: FilterExpression, denominator: FilterExpression) -> FilterExpression {
     FilterExpression {
@@ -1014,6 +1551,7 @@ pub fn num_mod(numerator: FilterExpression, denominator: FilterExpression) -> Fi
         module: None,
         exps: Some(vec![numerator, denominator]),
         arguments: None,
+        packed: None,
     }
 }
 
@@ -1023,7 +1561,7 @@ pub fn num_mod(numerator: FilterExpression, denominator: FilterExpression) -> Fi
 /// ```
 /// // abs(a) == 1
 /// use aerospike::expressions::{eq, int_val, int_bin, num_abs};
-/// eq(num_abs(int_bin("a".to_string())), int_val(1));
+/// eq(num_abs(int_bin("a")), int_val(1));
 /// ```
 pub fn num_abs(value: FilterExpression) -> FilterExpression {
     FilterExpression {
@@ -1034,6 +1572,7 @@ pub fn num_abs(value: FilterExpression) -> FilterExpression {
         module: None,
         exps: Some(vec![value]),
         arguments: None,
+        packed: None,
     }
 }
 
@@ -1054,6 +1593,7 @@ pub fn num_floor(num: FilterExpression) -> FilterExpression {
         module: None,
         exps: Some(vec![num]),
         arguments: None,
+        packed: None,
     }
 }
 
@@ -1074,6 +1614,7 @@ pub fn num_ceil(num: FilterExpression) -> FilterExpression {
         module: None,
         exps: Some(vec![num]),
         arguments: None,
+        packed: None,
     }
 }
 
@@ -1093,6 +1634,7 @@ pub fn to_int(num: FilterExpression) -> FilterExpression {
         module: None,
         exps: Some(vec![num]),
         arguments: None,
+        packed: None,
     }
 }
 
@@ -1112,6 +1654,7 @@ pub fn to_float(num: FilterExpression) -> FilterExpression {
         module: None,
         exps: Some(vec![num]),
         arguments: None,
+        packed: None,
     }
 }
 
@@ -1121,7 +1664,7 @@ pub fn to_float(num: FilterExpression) -> FilterExpression {
 /// ```
 /// // a & 0xff == 0x11
 /// use aerospike::expressions::{eq, int_val, int_and, int_bin};
-/// eq(int_and(vec![int_bin("a".to_string()), int_val(0xff)]), int_val(0x11));
+/// eq(int_and(vec![int_bin("a"), int_val(0xff)]), int_val(0x11));
 /// ```
 pub const fn int_and(exps: Vec<FilterExpression>) -> FilterExpression {
     FilterExpression {
@@ -1132,6 +1675,7 @@ pub const fn int_and(exps: Vec<FilterExpression>) -> FilterExpression {
         module: None,
         exps: Some(exps),
         arguments: None,
+        packed: None,
     }
 }
 
@@ -1141,7 +1685,7 @@ pub const fn int_and(exps: Vec<FilterExpression>) -> FilterExpression {
 /// ```
 /// // a ^ b == 16
 /// use aerospike::expressions::{eq, int_val, int_xor, int_bin};
-/// eq(int_xor(vec![int_bin("a".to_string()), int_bin("b".to_string())]), int_val(16));
+/// eq(int_xor(vec![int_bin("a"), int_bin("b")]), int_val(16));
 /// ```
 pub const fn int_xor(exps: Vec<FilterExpression>) -> FilterExpression {
     FilterExpression {
@@ -1152,6 +1696,7 @@ pub const fn int_xor(exps: Vec<FilterExpression>) -> FilterExpression {
         module: None,
         exps: Some(exps),
         arguments: None,
+        packed: None,
     }
 }
 
@@ -1160,7 +1705,7 @@ pub const fn int_xor(exps: Vec<FilterExpression>) -> FilterExpression {
 /// ```
 /// // ~a == 7
 /// use aerospike::expressions::{eq, int_val, int_not, int_bin};
-/// eq(int_not(int_bin("a".to_string())), int_val(7));
+/// eq(int_not(int_bin("a")), int_val(7));
 /// ```
 pub fn int_not(exp: FilterExpression) -> FilterExpression {
     FilterExpression {
@@ -1171,6 +1716,7 @@ pub fn int_not(exp: FilterExpression) -> FilterExpression {
         module: None,
         exps: Some(vec![exp]),
         arguments: None,
+        packed: None,
     }
 }
 
@@ -1179,7 +1725,7 @@ pub fn int_not(exp: FilterExpression) -> FilterExpression {
 /// ```
 /// // a << 8 > 0xff
 /// use aerospike::expressions::{int_val, int_bin, gt, int_lshift};
-/// gt(int_lshift(int_bin("a".to_string()), int_val(8)), int_val(0xff));
+/// gt(int_lshift(int_bin("a"), int_val(8)), int_val(0xff));
 /// ```
 pub fn int_lshift(value: FilterExpression, shift: FilterExpression) -> FilterExpression {
     FilterExpression {
@@ -1190,6 +1736,7 @@ pub fn int_lshift(value: FilterExpression, shift: FilterExpression) -> FilterExp
         module: None,
         exps: Some(vec![value, shift]),
         arguments: None,
+        packed: None,
     }
 }
 
@@ -1198,7 +1745,7 @@ pub fn int_lshift(value: FilterExpression, shift: FilterExpression) -> FilterExp
 /// ```
 /// // a >> 8 > 0xff
 /// use aerospike::expressions::{int_val, int_bin, gt, int_rshift};
-/// gt(int_rshift(int_bin("a".to_string()), int_val(8)), int_val(0xff));
+/// gt(int_rshift(int_bin("a"), int_val(8)), int_val(0xff));
 /// ```
 pub fn int_rshift(value: FilterExpression, shift: FilterExpression) -> FilterExpression {
     FilterExpression {
@@ -1209,6 +1756,7 @@ pub fn int_rshift(value: FilterExpression, shift: FilterExpression) -> FilterExp
         module: None,
         exps: Some(vec![value, shift]),
         arguments: None,
+        packed: None,
     }
 }
 
@@ -1218,7 +1766,7 @@ pub fn int_rshift(value: FilterExpression, shift: FilterExpression) -> FilterExp
 /// ```
 /// // a >>> 8 > 0xff
 /// use aerospike::expressions::{int_val, int_bin, gt, int_arshift};
-/// gt(int_arshift(int_bin("a".to_string()), int_val(8)), int_val(0xff));
+/// gt(int_arshift(int_bin("a"), int_val(8)), int_val(0xff));
 /// ```
 pub fn int_arshift(value: FilterExpression, shift: FilterExpression) -> FilterExpression {
     FilterExpression {
@@ -1229,6 +1777,7 @@ pub fn int_arshift(value: FilterExpression, shift: FilterExpression) -> FilterEx
         module: None,
         exps: Some(vec![value, shift]),
         arguments: None,
+        packed: None,
     }
 }
 
@@ -1237,7 +1786,7 @@ pub fn int_arshift(value: FilterExpression, shift: FilterExpression) -> FilterEx
 /// ```
 /// // count(a) == 4
 /// use aerospike::expressions::{int_val, int_bin, int_count, eq};
-/// eq(int_count(int_bin("a".to_string())), int_val(4));
+/// eq(int_count(int_bin("a")), int_val(4));
 /// ```
 pub fn int_count(exp: FilterExpression) -> FilterExpression {
     FilterExpression {
@@ -1248,6 +1797,7 @@ pub fn int_count(exp: FilterExpression) -> FilterExpression {
         module: None,
         exps: Some(vec![exp]),
         arguments: None,
+        packed: None,
     }
 }
 
@@ -1260,7 +1810,7 @@ pub fn int_count(exp: FilterExpression) -> FilterExpression {
 /// ```
 /// // lscan(a, true) == 4
 /// use aerospike::expressions::{int_val, int_bin, eq, int_lscan, bool_val};
-/// eq(int_lscan(int_bin("a".to_string()), bool_val(true)), int_val(4));
+/// eq(int_lscan(int_bin("a"), bool_val(true)), int_val(4));
 /// ```
 pub fn int_lscan(value: FilterExpression, search: FilterExpression) -> FilterExpression {
     FilterExpression {
@@ -1271,6 +1821,7 @@ pub fn int_lscan(value: FilterExpression, search: FilterExpression) -> FilterExp
         module: None,
         exps: Some(vec![value, search]),
         arguments: None,
+        packed: None,
     }
 }
 
@@ -1283,7 +1834,7 @@ pub fn int_lscan(value: FilterExpression, search: FilterExpression) -> FilterExp
 /// ```
 /// // rscan(a, true) == 4
 /// use aerospike::expressions::{int_val, int_bin, eq, int_rscan, bool_val};
-/// eq(int_rscan(int_bin("a".to_string()), bool_val(true)), int_val(4));
+/// eq(int_rscan(int_bin("a"), bool_val(true)), int_val(4));
 /// ```
 pub fn int_rscan(value: FilterExpression, search: FilterExpression) -> FilterExpression {
     FilterExpression {
@@ -1294,6 +1845,7 @@ pub fn int_rscan(value: FilterExpression, search: FilterExpression) -> FilterExp
         module: None,
         exps: Some(vec![value, search]),
         arguments: None,
+        packed: None,
     }
 }
 
@@ -1303,7 +1855,7 @@ pub fn int_rscan(value: FilterExpression, search: FilterExpression) -> FilterExp
 /// ```
 /// // min(a, b, c) > 0
 /// use aerospike::expressions::{int_val, int_bin, gt, min};
-/// gt(min(vec![int_bin("a".to_string()),int_bin("b".to_string()),int_bin("c".to_string())]), int_val(0));
+/// gt(min(vec![int_bin("a"),int_bin("b"),int_bin("c")]), int_val(0));
 /// ```
 pub const fn min(exps: Vec<FilterExpression>) -> FilterExpression {
     FilterExpression {
@@ -1314,6 +1866,7 @@ pub const fn min(exps: Vec<FilterExpression>) -> FilterExpression {
         module: None,
         exps: Some(exps),
         arguments: None,
+        packed: None,
     }
 }
 
@@ -1323,7 +1876,7 @@ pub const fn min(exps: Vec<FilterExpression>) -> FilterExpression {
 /// ```
 /// // max(a, b, c) > 100
 /// use aerospike::expressions::{int_val, int_bin, gt, max};
-/// gt(max(vec![int_bin("a".to_string()),int_bin("b".to_string()),int_bin("c".to_string())]), int_val(100));
+/// gt(max(vec![int_bin("a"),int_bin("b"),int_bin("c")]), int_val(100));
 /// ```
 pub const fn max(exps: Vec<FilterExpression>) -> FilterExpression {
     FilterExpression {
@@ -1334,6 +1887,7 @@ pub const fn max(exps: Vec<FilterExpression>) -> FilterExpression {
         module: None,
         exps: Some(exps),
         arguments: None,
+        packed: None,
     }
 }
 
@@ -1351,9 +1905,9 @@ pub const fn max(exps: Vec<FilterExpression>) -> FilterExpression {
 /// use aerospike::expressions::{cond, int_bin, eq, int_val, num_add, num_sub, num_mul};
 /// cond(
 ///   vec![
-///     eq(int_bin("type".to_string()), int_val(0)), num_add(vec![int_bin("val1".to_string()), int_bin("val2".to_string())]),
-///     eq(int_bin("type".to_string()), int_val(1)), num_sub(vec![int_bin("val1".to_string()), int_bin("val2".to_string())]),
-///     eq(int_bin("type".to_string()), int_val(2)), num_mul(vec![int_bin("val1".to_string()), int_bin("val2".to_string())]),
+///     eq(int_bin("type"), int_val(0)), num_add(vec![int_bin("val1"), int_bin("val2")]),
+///     eq(int_bin("type"), int_val(1)), num_sub(vec![int_bin("val1"), int_bin("val2")]),
+///     eq(int_bin("type"), int_val(2)), num_mul(vec![int_bin("val1"), int_bin("val2")]),
 ///     int_val(-1)
 ///   ]
 /// );
@@ -1367,6 +1921,7 @@ pub const fn cond(exps: Vec<FilterExpression>) -> FilterExpression {
         module: None,
         exps: Some(exps),
         arguments: None,
+        packed: None,
     }
 }
 
@@ -1377,10 +1932,10 @@ pub const fn cond(exps: Vec<FilterExpression>) -> FilterExpression {
 /// use aerospike::expressions::{exp_let, def, int_bin, and, lt, int_val, var};
 /// exp_let(
 ///   vec![
-///     def("x".to_string(), int_bin("a".to_string())),
+///     def("x", int_bin("a")),
 ///     and(vec![
-///       lt(int_val(5), var("x".to_string()),),
-///       lt(var("x".to_string()), int_val(10))
+///       lt(int_val(5), var("x"),),
+///       lt(var("x"), int_val(10))
 ///     ])
 ///   ]
 /// );
@@ -1394,6 +1949,7 @@ pub const fn exp_let(exps: Vec<FilterExpression>) -> FilterExpression {
         module: None,
         exps: Some(exps),
         arguments: None,
+        packed: None,
     }
 }
 
@@ -1404,37 +1960,45 @@ pub const fn exp_let(exps: Vec<FilterExpression>) -> FilterExpression {
 /// use aerospike::expressions::{exp_let, def, int_bin, and, lt, int_val, var};
 /// exp_let(
 ///   vec![
-///     def("x".to_string(), int_bin("a".to_string())),
+///     def("x", int_bin("a")),
 ///     and(vec![
-///       lt(int_val(5), var("x".to_string()),),
-///       lt(var("x".to_string()), int_val(10))
+///       lt(int_val(5), var("x"),),
+///       lt(var("x"), int_val(10))
 ///     ])
 ///   ]
 /// );
 /// ```
-pub fn def(name: String, value: FilterExpression) -> FilterExpression {
+pub fn def<S>(name: S, value: FilterExpression) -> FilterExpression
+where
+    S: Into<String>,
+{
     FilterExpression {
         cmd: None,
-        val: Some(Value::from(name)),
+        val: Some(Value::from(name.into())),
         bin: None,
         flags: None,
         module: None,
         exps: Some(vec![value]),
         arguments: None,
+        packed: None,
     }
 }
 
 /// Retrieve expression value from a variable.
 /// Requires server version 5.6.0+.
-pub fn var(name: String) -> FilterExpression {
+pub fn var<S>(name: S) -> FilterExpression
+where
+    S: Into<String>,
+{
     FilterExpression {
         cmd: Some(ExpOp::Var),
-        val: Some(Value::from(name)),
+        val: Some(Value::from(name.into())),
         bin: None,
         flags: None,
         module: None,
         exps: None,
         arguments: None,
+        packed: None,
     }
 }
 
@@ -1449,8 +2013,8 @@ pub fn var(name: String) -> FilterExpression {
 /// use aerospike::expressions::{exp_let, def, num_sub, float_bin, float_val, cond, ge, var, unknown};
 /// exp_let(
 ///     vec![
-///         def("v".to_string(), num_sub(vec![float_bin("balance".to_string()), float_val(100.0)])),
-///         cond(vec![ge(var("v".to_string()), float_val(0.0)), var("v".to_string())]),
+///         def("v", num_sub(vec![float_bin("balance"), float_val(100.0)])),
+///         cond(vec![ge(var("v"), float_val(0.0)), var("v")]),
 ///         unknown()
 ///     ]
 /// );
@@ -1464,5 +2028,54 @@ pub const fn unknown() -> FilterExpression {
         module: None,
         exps: None,
         arguments: None,
+        packed: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        int_val, last_update_val, since_update_val, ttl_val, void_time_val, FilterExpression,
+    };
+    use std::time::{Duration, UNIX_EPOCH};
+
+    // Wire format of an expression, for the packing tests of the expression modules.
+    pub(super) fn pack(exp: &FilterExpression) -> Vec<u8> {
+        base64::decode(&exp.base64().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn since_update_val_millis() {
+        assert_eq!(
+            since_update_val(Duration::from_millis(1_500)),
+            int_val(1_500)
+        );
+        assert_eq!(since_update_val(Duration::from_micros(2_999)), int_val(2));
+        assert_eq!(since_update_val(Duration::MAX), int_val(i64::MAX));
+    }
+
+    #[test]
+    fn ttl_val_secs() {
+        assert_eq!(ttl_val(Duration::from_millis(61_999)), int_val(61));
+        assert_eq!(ttl_val(Duration::from_secs(u64::MAX)), int_val(i64::MAX));
+    }
+
+    #[test]
+    fn epoch_nanos() {
+        let day = Duration::from_secs(86_400);
+        assert_eq!(last_update_val(UNIX_EPOCH), int_val(0));
+        assert_eq!(
+            last_update_val(UNIX_EPOCH + day),
+            int_val(86_400_000_000_000)
+        );
+        assert_eq!(
+            void_time_val(UNIX_EPOCH - day),
+            int_val(-86_400_000_000_000)
+        );
+
+        // about 292 years is the range of an i64 in nanoseconds
+        let far = Duration::from_secs(300 * 365 * 86_400);
+        assert_eq!(last_update_val(UNIX_EPOCH + far), int_val(i64::MAX));
+        assert_eq!(last_update_val(UNIX_EPOCH - far), int_val(i64::MIN));
     }
 }
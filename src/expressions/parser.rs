@@ -0,0 +1,640 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+//! Parser for filter expressions written in a textual form.
+//!
+//! The syntax follows the expression builder functions, with infix operators for boolean logic,
+//! comparisons and arithmetic. Function names may be written in camel case (`intBin`) or snake
+//! case (`int_bin`).
+//!
+//! ```text
+//! expr       := or
+//! or         := and ( "||" and )*
+//! and        := comparison ( "&&" comparison )*
+//! comparison := sum ( ( "==" | "!=" | ">" | ">=" | "<" | "<=" ) sum )?
+//! sum        := product ( ( "+" | "-" ) product )*
+//! product    := unary ( ( "*" | "/" | "%" ) unary )*
+//! unary      := ( "!" | "-" ) unary | primary
+//! primary    := literal | function "(" [ expr ( "," expr )* ] ")" | "(" expr ")"
+//! literal    := integer | float | 'string' | "string" | true | false | nil
+//! ```
+//!
+//! A `-` directly followed by a digit is read as a negative literal, any other `-` in front of an
+//! operand negates it, e.g. `-intBin('a')` => `num_sub([int_bin("a")])`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use aerospike::expressions::{and, eq, gt, int_bin, int_val, set_name, string_val};
+//! use aerospike::expressions::parser::parse;
+//!
+//! let exp = parse("intBin('a') > 5 && setName() == 'users'").unwrap();
+//! let expected = and(vec![
+//!     gt(int_bin("a"), int_val(5)),
+//!     eq(set_name(), string_val("users")),
+//! ]);
+//! assert_eq!(exp, expected);
+//! ```
+
+use std::iter::Peekable;
+use std::str::Chars;
+
+use crate::errors::{ErrorKind, Result};
+use crate::expressions::{
+    and, bin_exists, bin_type, blob_bin, bool_bin, bool_val, device_size, digest_modulo, eq,
+    float_bin, float_val, ge, geo_bin, geo_compare, geo_val, gt, hll_bin, int_bin, int_count,
+    int_not, int_val, is_tombstone, key_exists, last_update, le, list_bin, lt, map_bin, max,
+    memory_size, min, ne, nil, not, num_abs, num_add, num_ceil, num_div, num_floor, num_log,
+    num_mod, num_mul, num_pow, num_sub, or, record_size, regex_compare, set_name, since_update,
+    string_bin, string_val, to_float, to_int, ttl, unknown, void_time, FilterExpression,
+};
+
+/// Parse a textual filter expression into a `FilterExpression`.
+pub fn parse(input: &str) -> Result<FilterExpression> {
+    let mut parser = Parser::new(input);
+    let exp = parser.read_or()?;
+    parser.skip_whitespace();
+    if parser.peek().is_some() {
+        return Err(parser.error("unexpected trailing input"));
+    }
+    Ok(exp)
+}
+
+enum Literal {
+    Int(i64),
+    Float(f64),
+    String(String),
+}
+
+struct Parser<'a> {
+    s: Peekable<Chars<'a>>,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(s: &'a str) -> Self {
+        Parser {
+            s: s.chars().peekable(),
+            pos: 0,
+        }
+    }
+
+    fn read_or(&mut self) -> Result<FilterExpression> {
+        let mut exps = vec![self.read_and()?];
+        while self.consume("||") {
+            exps.push(self.read_and()?);
+        }
+        Ok(if exps.len() == 1 {
+            exps.remove(0)
+        } else {
+            or(exps)
+        })
+    }
+
+    fn read_and(&mut self) -> Result<FilterExpression> {
+        let mut exps = vec![self.read_comparison()?];
+        while self.consume("&&") {
+            exps.push(self.read_comparison()?);
+        }
+        Ok(if exps.len() == 1 {
+            exps.remove(0)
+        } else {
+            and(exps)
+        })
+    }
+
+    fn read_comparison(&mut self) -> Result<FilterExpression> {
+        let left = self.read_sum()?;
+        let op: fn(FilterExpression, FilterExpression) -> FilterExpression = if self.consume("==") {
+            eq
+        } else if self.consume("!=") {
+            ne
+        } else if self.consume(">=") {
+            ge
+        } else if self.consume("<=") {
+            le
+        } else if self.consume(">") {
+            gt
+        } else if self.consume("<") {
+            lt
+        } else {
+            return Ok(left);
+        };
+        let right = self.read_sum()?;
+        Ok(op(left, right))
+    }
+
+    fn read_sum(&mut self) -> Result<FilterExpression> {
+        let mut chain = Chain::new(self.read_product()?);
+        loop {
+            if self.consume("+") {
+                chain.push('+', self.read_product()?);
+            } else if self.consume("-") {
+                chain.push('-', self.read_product()?);
+            } else {
+                return Ok(chain.finish());
+            }
+        }
+    }
+
+    fn read_product(&mut self) -> Result<FilterExpression> {
+        let mut chain = Chain::new(self.read_unary()?);
+        loop {
+            if self.consume("*") {
+                chain.push('*', self.read_unary()?);
+            } else if self.consume("/") {
+                chain.push('/', self.read_unary()?);
+            } else if self.consume("%") {
+                let left = chain.finish();
+                chain = Chain::new(num_mod(left, self.read_unary()?));
+            } else {
+                return Ok(chain.finish());
+            }
+        }
+    }
+
+    fn read_unary(&mut self) -> Result<FilterExpression> {
+        if self.consume("!") {
+            return Ok(not(self.read_unary()?));
+        }
+        if self.consume_negation() {
+            return Ok(num_sub(vec![self.read_unary()?]));
+        }
+        self.read_primary()
+    }
+
+    // Consumes a "-" that negates the following operand, but not the sign of a number literal.
+    fn consume_negation(&mut self) -> bool {
+        self.skip_whitespace();
+        let mut lookahead = self.s.clone();
+        if lookahead.next() != Some('-') {
+            return false;
+        }
+        match lookahead.peek() {
+            Some(&c) if c.is_ascii_digit() || c == '.' => false,
+            _ => {
+                self.next_char();
+                true
+            }
+        }
+    }
+
+    fn read_primary(&mut self) -> Result<FilterExpression> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(&'(') => {
+                self.next_char();
+                let exp = self.read_or()?;
+                self.expect(')')?;
+                Ok(exp)
+            }
+            Some(&c) if c == '\'' || c == '"' => Ok(string_val(self.read_string()?)),
+            Some(&c) if c.is_ascii_digit() || c == '-' => match self.read_number()? {
+                Literal::Int(val) => Ok(int_val(val)),
+                Literal::Float(val) => Ok(float_val(val)),
+                Literal::String(_) => unreachable!(),
+            },
+            Some(&c) if c.is_alphabetic() || c == '_' => {
+                let name = self.read_identifier();
+                match name.as_str() {
+                    "true" => return Ok(bool_val(true)),
+                    "false" => return Ok(bool_val(false)),
+                    "nil" | "null" => return Ok(nil()),
+                    _ => (),
+                }
+                self.read_function(&name)
+            }
+            Some(_) => Err(self.error("unexpected character")),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    fn read_function(&mut self, name: &str) -> Result<FilterExpression> {
+        self.expect('(')?;
+        let mut args = vec![];
+        self.skip_whitespace();
+        if self.peek() == Some(&')') {
+            self.next_char();
+        } else {
+            loop {
+                args.push(self.read_argument()?);
+                self.skip_whitespace();
+                match self.next_char() {
+                    Some(',') => continue,
+                    Some(')') => break,
+                    _ => return Err(self.error("expected ',' or ')'")),
+                }
+            }
+        }
+
+        let func = name.replace('_', "").to_lowercase();
+        let exp = match (func.as_str(), args.len()) {
+            ("intbin", 1) => int_bin(self.string_arg(&mut args)?),
+            ("boolbin", 1) => bool_bin(self.string_arg(&mut args)?),
+            ("stringbin", 1) => string_bin(self.string_arg(&mut args)?),
+            ("floatbin", 1) => float_bin(self.string_arg(&mut args)?),
+            ("blobbin", 1) => blob_bin(self.string_arg(&mut args)?),
+            ("geobin", 1) => geo_bin(self.string_arg(&mut args)?),
+            ("listbin", 1) => list_bin(self.string_arg(&mut args)?),
+            ("mapbin", 1) => map_bin(self.string_arg(&mut args)?),
+            ("hllbin", 1) => hll_bin(self.string_arg(&mut args)?),
+            ("binexists", 1) => bin_exists(self.string_arg(&mut args)?),
+            ("bintype", 1) => bin_type(self.string_arg(&mut args)?),
+            ("geo", 1) => geo_val(self.string_arg(&mut args)?),
+            ("setname", 0) => set_name(),
+            ("devicesize", 0) => device_size(),
+            ("memorysize", 0) => memory_size(),
+            ("recordsize", 0) => record_size(),
+            ("lastupdate", 0) => last_update(),
+            ("sinceupdate", 0) => since_update(),
+            ("voidtime", 0) => void_time(),
+            ("ttl", 0) => ttl(),
+            ("istombstone", 0) => is_tombstone(),
+            ("keyexists", 0) => key_exists(),
+            ("unknown", 0) => unknown(),
+            ("digestmodulo", 1) => digest_modulo(self.int_arg(&mut args)?),
+            ("regexcompare", 3) => {
+                let bin = self.exp_arg(&mut args)?;
+                let flags = self.int_arg(&mut args)?;
+                let regex = self.string_arg(&mut args)?;
+                regex_compare(regex, flags, bin)
+            }
+            ("geocompare", 2) => {
+                let right = self.exp_arg(&mut args)?;
+                geo_compare(self.exp_arg(&mut args)?, right)
+            }
+            ("toint", 1) => to_int(self.exp_arg(&mut args)?),
+            ("tofloat", 1) => to_float(self.exp_arg(&mut args)?),
+            ("abs", 1) | ("numabs", 1) => num_abs(self.exp_arg(&mut args)?),
+            ("floor", 1) | ("numfloor", 1) => num_floor(self.exp_arg(&mut args)?),
+            ("ceil", 1) | ("numceil", 1) => num_ceil(self.exp_arg(&mut args)?),
+            ("intnot", 1) => int_not(self.exp_arg(&mut args)?),
+            ("intcount", 1) => int_count(self.exp_arg(&mut args)?),
+            ("pow", 2) | ("numpow", 2) => {
+                let exponent = self.exp_arg(&mut args)?;
+                num_pow(self.exp_arg(&mut args)?, exponent)
+            }
+            ("log", 2) | ("numlog", 2) => {
+                let base = self.exp_arg(&mut args)?;
+                num_log(self.exp_arg(&mut args)?, base)
+            }
+            ("min", n) if n > 0 => min(self.exp_args(args)?),
+            ("max", n) if n > 0 => max(self.exp_args(args)?),
+            _ => {
+                return Err(self.error(&format!(
+                    "unknown function '{}' with {} argument(s)",
+                    name,
+                    args.len()
+                )))
+            }
+        };
+        Ok(exp)
+    }
+
+    // Literal arguments are kept as is, since some functions require a plain string or integer
+    // instead of an expression.
+    fn read_argument(&mut self) -> Result<Argument> {
+        self.skip_whitespace();
+        let (s, pos) = (self.s.clone(), self.pos);
+        let literal = match self.peek() {
+            Some(&c) if c == '\'' || c == '"' => Some(Literal::String(self.read_string()?)),
+            Some(&c) if c.is_ascii_digit() || c == '-' => self.read_number().ok(),
+            _ => None,
+        };
+        if let Some(literal) = literal {
+            self.skip_whitespace();
+            if let Some(&',') | Some(&')') = self.peek() {
+                return Ok(Argument::Literal(literal));
+            }
+        }
+
+        // Not a single literal; rewind and parse a full expression.
+        self.s = s;
+        self.pos = pos;
+        Ok(Argument::Expression(self.read_or()?))
+    }
+
+    fn string_arg(&self, args: &mut Vec<Argument>) -> Result<String> {
+        match args.pop() {
+            Some(Argument::Literal(Literal::String(val))) => Ok(val),
+            _ => Err(self.error("expected string argument")),
+        }
+    }
+
+    fn int_arg(&self, args: &mut Vec<Argument>) -> Result<i64> {
+        match args.pop() {
+            Some(Argument::Literal(Literal::Int(val))) => Ok(val),
+            _ => Err(self.error("expected integer argument")),
+        }
+    }
+
+    fn exp_arg(&self, args: &mut Vec<Argument>) -> Result<FilterExpression> {
+        match args.pop() {
+            Some(Argument::Expression(exp)) => Ok(exp),
+            Some(Argument::Literal(Literal::String(val))) => Ok(string_val(val)),
+            Some(Argument::Literal(Literal::Int(val))) => Ok(int_val(val)),
+            Some(Argument::Literal(Literal::Float(val))) => Ok(float_val(val)),
+            None => Err(self.error("missing argument")),
+        }
+    }
+
+    fn exp_args(&self, args: Vec<Argument>) -> Result<Vec<FilterExpression>> {
+        let mut exps = Vec::with_capacity(args.len());
+        for arg in args {
+            exps.push(self.exp_arg(&mut vec![arg])?);
+        }
+        Ok(exps)
+    }
+
+    fn read_string(&mut self) -> Result<String> {
+        let quote = self.next_char();
+        let mut val = String::new();
+        loop {
+            match self.next_char() {
+                Some('\\') => match self.next_char() {
+                    Some(c) => val.push(c),
+                    None => return Err(self.error("unterminated string")),
+                },
+                Some(c) if Some(c) == quote => return Ok(val),
+                Some(c) => val.push(c),
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+
+    fn read_number(&mut self) -> Result<Literal> {
+        let mut num = String::new();
+        if self.peek() == Some(&'-') {
+            num.push('-');
+            self.next_char();
+        }
+        while let Some(&c) = self.peek() {
+            let exponent_sign = (c == '-' || c == '+') && num.ends_with(['e', 'E']);
+            if c.is_ascii_digit() || c == '.' || c == 'e' || c == 'E' || exponent_sign {
+                num.push(c);
+                self.next_char();
+            } else {
+                break;
+            }
+        }
+        if num.contains(|c| c == '.' || c == 'e' || c == 'E') {
+            num.parse()
+                .map(Literal::Float)
+                .map_err(|_| self.error("invalid float literal"))
+        } else {
+            num.parse()
+                .map(Literal::Int)
+                .map_err(|_| self.error("invalid integer literal"))
+        }
+    }
+
+    fn read_identifier(&mut self) -> String {
+        let mut name = String::new();
+        while let Some(&c) = self.peek() {
+            if c.is_alphanumeric() || c == '_' {
+                name.push(c);
+                self.next_char();
+            } else {
+                break;
+            }
+        }
+        name
+    }
+
+    fn expect(&mut self, expected: char) -> Result<()> {
+        self.skip_whitespace();
+        match self.next_char() {
+            Some(c) if c == expected => Ok(()),
+            _ => Err(self.error(&format!("expected '{}'", expected))),
+        }
+    }
+
+    // Consumes the given operator if it is next in the input. Single character operators do not
+    // match the first character of a longer operator, e.g. "!" does not match "!=".
+    fn consume(&mut self, op: &str) -> bool {
+        self.skip_whitespace();
+        let mut lookahead = self.s.clone();
+        for expected in op.chars() {
+            if lookahead.next() != Some(expected) {
+                return false;
+            }
+        }
+        if op.len() == 1 && "<>!".contains(op) && lookahead.peek() == Some(&'=') {
+            return false;
+        }
+        for _ in op.chars() {
+            self.next_char();
+        }
+        true
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(&c) = self.peek() {
+            if !c.is_whitespace() {
+                break;
+            }
+            self.next_char();
+        }
+    }
+
+    fn error(&self, msg: &str) -> crate::errors::Error {
+        ErrorKind::InvalidArgument(format!(
+            "Invalid filter expression at position {}: {}",
+            self.pos, msg
+        ))
+        .into()
+    }
+
+    fn peek(&mut self) -> Option<&char> {
+        self.s.peek()
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        let c = self.s.next();
+        if c.is_some() {
+            self.pos += 1;
+        }
+        c
+    }
+}
+
+enum Argument {
+    Literal(Literal),
+    Expression(FilterExpression),
+}
+
+// Left-associative arithmetic; consecutive uses of the same operator are collected into a single
+// n-ary expression, e.g. `a + b + c` => num_add([a, b, c]).
+struct Chain {
+    op: Option<char>,
+    exps: Vec<FilterExpression>,
+}
+
+impl Chain {
+    fn new(exp: FilterExpression) -> Self {
+        Chain {
+            op: None,
+            exps: vec![exp],
+        }
+    }
+
+    fn push(&mut self, op: char, exp: FilterExpression) {
+        if let Some(current) = self.op {
+            if current != op {
+                let exps = std::mem::replace(&mut self.exps, vec![]);
+                self.exps.push(Chain::build(current, exps));
+            }
+        }
+        self.op = Some(op);
+        self.exps.push(exp);
+    }
+
+    fn finish(mut self) -> FilterExpression {
+        match self.op {
+            Some(op) => Chain::build(op, self.exps),
+            None => self.exps.remove(0),
+        }
+    }
+
+    fn build(op: char, exps: Vec<FilterExpression>) -> FilterExpression {
+        match op {
+            '+' => num_add(exps),
+            '-' => num_sub(exps),
+            '*' => num_mul(exps),
+            _ => num_div(exps),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+    use crate::expressions::*;
+
+    fn assert_parse(input: &str, expected: FilterExpression) {
+        assert_eq!(parse(input).unwrap(), expected, "{}", input);
+        // The rendered expression parses back into the same expression.
+        assert_eq!(
+            parse(&expected.to_string()).unwrap(),
+            expected,
+            "{}",
+            expected
+        );
+    }
+
+    #[test]
+    fn parse_literals() {
+        assert_parse("5", int_val(5));
+        assert_parse("-5", int_val(-5));
+        assert_parse("1.5", float_val(1.5));
+        assert_parse("1e-5", float_val(1e-5));
+        assert_parse("2E+3", float_val(2000.0));
+        assert_parse("-2.5e3", float_val(-2500.0));
+        assert_parse("'abc'", string_val("abc".to_string()));
+        assert_parse("\"a\\\"b\"", string_val("a\"b".to_string()));
+        assert_parse("true", bool_val(true));
+        assert_parse("nil", nil());
+    }
+
+    #[test]
+    fn parse_comparisons() {
+        assert_parse(
+            "intBin('a') > 5 && setName() == 'users'",
+            and(vec![
+                gt(int_bin("a".to_string()), int_val(5)),
+                eq(set_name(), string_val("users".to_string())),
+            ]),
+        );
+        assert_parse(
+            "int_bin('a') >= 1 || int_bin('a') <= -1 || !bin_exists('b')",
+            or(vec![
+                ge(int_bin("a".to_string()), int_val(1)),
+                le(int_bin("a".to_string()), int_val(-1)),
+                not(bin_exists("b".to_string())),
+            ]),
+        );
+        assert_parse(
+            "!(stringBin('s') != 'x')",
+            not(ne(string_bin("s".to_string()), string_val("x".to_string()))),
+        );
+    }
+
+    #[test]
+    fn parse_arithmetic() {
+        assert_parse(
+            "intBin('a') + 1 + 2 == 10",
+            eq(
+                num_add(vec![int_bin("a".to_string()), int_val(1), int_val(2)]),
+                int_val(10),
+            ),
+        );
+        assert_parse(
+            "intBin('a') - 1 * 2 < 10 % 3",
+            lt(
+                num_sub(vec![
+                    int_bin("a".to_string()),
+                    num_mul(vec![int_val(1), int_val(2)]),
+                ]),
+                num_mod(int_val(10), int_val(3)),
+            ),
+        );
+        assert_parse(
+            "max(intBin('a'), 5, ttl()) > pow(2, 3)",
+            gt(
+                max(vec![int_bin("a".to_string()), int_val(5), ttl()]),
+                num_pow(int_val(2), int_val(3)),
+            ),
+        );
+    }
+
+    #[test]
+    fn parse_negation() {
+        assert_parse("-intBin('a')", num_sub(vec![int_bin("a".to_string())]));
+        assert_parse(
+            "-(1 + 2) < 0",
+            lt(
+                num_sub(vec![num_add(vec![int_val(1), int_val(2)])]),
+                int_val(0),
+            ),
+        );
+        assert_parse(
+            "5 - -intBin('a') * 2",
+            num_sub(vec![
+                int_val(5),
+                num_mul(vec![num_sub(vec![int_bin("a".to_string())]), int_val(2)]),
+            ]),
+        );
+        assert_parse("--1", num_sub(vec![int_val(-1)]));
+    }
+
+    #[test]
+    fn parse_functions() {
+        assert_parse("digestModulo(3) == 1", eq(digest_modulo(3), int_val(1)));
+        assert_parse(
+            "regexCompare('^pre', 2, stringBin('a'))",
+            regex_compare("^pre".to_string(), 2, string_bin("a".to_string())),
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(parse("").is_err());
+        assert!(parse("intBin(").is_err());
+        assert!(parse("intBin(5)").is_err());
+        assert!(parse("fooBin('a')").is_err());
+        assert!(parse("5 5").is_err());
+        assert!(parse("'abc").is_err());
+    }
+}
@@ -1,5 +1,12 @@
 //! Regex Bit Flags
+#[cfg(feature = "regex")]
+use crate::errors::{ErrorKind, Result};
+#[cfg(feature = "regex")]
+use regex::RegexBuilder;
+use std::ops::{BitOr, BitOrAssign};
+
 /// Used to change the Regex Mode in Filters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RegexFlag {
     /// Use regex defaults.
     NONE = 0,
@@ -8,7 +15,115 @@ pub enum RegexFlag {
     /// Do not differentiate case.
     ICASE = 2,
     /// Do not report position of matches.
-    NOSUB = 3,
+    NOSUB = 4,
     /// Match-any-character operators don't match a newline.
     NEWLINE = 8,
 }
+
+/// Set of `RegexFlag` values, combined using the `|` operator.
+/// ```
+/// use aerospike::{RegexFlag, RegexFlags};
+/// let flags = RegexFlags::ICASE | RegexFlags::NEWLINE;
+/// assert_eq!(flags, RegexFlag::ICASE | RegexFlag::NEWLINE);
+/// assert_eq!(flags.bits(), 10);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RegexFlags(i64);
+
+impl RegexFlags {
+    /// Use regex defaults.
+    pub const NONE: RegexFlags = RegexFlags(RegexFlag::NONE as i64);
+    /// Use POSIX Extended Regular Expression syntax when interpreting regex.
+    pub const EXTENDED: RegexFlags = RegexFlags(RegexFlag::EXTENDED as i64);
+    /// Do not differentiate case.
+    pub const ICASE: RegexFlags = RegexFlags(RegexFlag::ICASE as i64);
+    /// Do not report position of matches.
+    pub const NOSUB: RegexFlags = RegexFlags(RegexFlag::NOSUB as i64);
+    /// Match-any-character operators don't match a newline.
+    pub const NEWLINE: RegexFlags = RegexFlags(RegexFlag::NEWLINE as i64);
+
+    /// Returns the raw flag bits as sent to the server.
+    pub const fn bits(self) -> i64 {
+        self.0
+    }
+
+    /// Returns true if all flags in `other` are set.
+    pub const fn contains(self, other: RegexFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl BitOr for RegexFlags {
+    type Output = RegexFlags;
+
+    fn bitor(self, rhs: RegexFlags) -> RegexFlags {
+        RegexFlags(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for RegexFlags {
+    fn bitor_assign(&mut self, rhs: RegexFlags) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl BitOr for RegexFlag {
+    type Output = RegexFlags;
+
+    fn bitor(self, rhs: RegexFlag) -> RegexFlags {
+        RegexFlags::from(self) | RegexFlags::from(rhs)
+    }
+}
+
+impl From<RegexFlag> for RegexFlags {
+    fn from(flag: RegexFlag) -> RegexFlags {
+        RegexFlags(flag as i64)
+    }
+}
+
+impl From<i64> for RegexFlags {
+    fn from(bits: i64) -> RegexFlags {
+        RegexFlags(bits)
+    }
+}
+
+impl From<RegexFlags> for i64 {
+    fn from(flags: RegexFlags) -> i64 {
+        flags.0
+    }
+}
+
+/// Compiles the regular expression locally to catch syntax errors before the expression is sent
+/// to the server. Only patterns using the POSIX Extended syntax (`RegexFlags::EXTENDED`) are
+/// checked; basic POSIX patterns are always accepted. This is an opt-in check, run by
+/// `regex_compare_checked` but never by `regex_compare`.
+///
+/// The pattern is compiled with the syntax of the `regex` crate, which only approximates POSIX
+/// ERE. A backslash inside a bracket expression is a literal in POSIX but an escape here, so
+/// valid patterns like `[\]` are rejected. Equivalence classes and collating elements in
+/// brackets (`[[=a=]]`, `[[.a.]]`) are not understood either, and older versions of the `regex`
+/// crate reject the word boundaries `\<` and `\>`. Only use this check for patterns that avoid
+/// these constructs.
+/// ```
+/// use aerospike::RegexFlags;
+/// use aerospike::expressions::regex_flag::validate;
+/// assert!(validate("^prefix.*(a|b)$", RegexFlags::EXTENDED).is_ok());
+/// assert!(validate("^prefix.*(a|b$", RegexFlags::EXTENDED).is_err());
+/// // Valid POSIX ERE, but rejected by the local check.
+/// assert!(validate(r"[\]", RegexFlags::EXTENDED).is_err());
+/// ```
+#[cfg(feature = "regex")]
+pub fn validate(pattern: &str, flags: RegexFlags) -> Result<()> {
+    if !flags.contains(RegexFlags::EXTENDED) {
+        return Ok(());
+    }
+
+    RegexBuilder::new(pattern)
+        .case_insensitive(flags.contains(RegexFlags::ICASE))
+        .dot_matches_new_line(!flags.contains(RegexFlags::NEWLINE))
+        .build()
+        .map_err(|err| {
+            ErrorKind::InvalidArgument(format!("Invalid regular expression: {}", err)).into()
+        })
+        .map(|_| ())
+}
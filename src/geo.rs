@@ -0,0 +1,207 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+//! Typed GeoJSON shapes for geospatial bins, query filters and filter expressions.
+
+use crate::value::Value;
+use std::fmt;
+
+/// GeoJSON point, given as longitude and latitude in degrees.
+///
+/// ```
+/// use aerospike::GeoPoint;
+/// let point = GeoPoint::new(-122.0, 37.5);
+/// assert_eq!(point.to_string(), r#"{"type": "Point", "coordinates": [-122.0, 37.5]}"#);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeoPoint {
+    /// Longitude in degrees.
+    pub lng: f64,
+
+    /// Latitude in degrees.
+    pub lat: f64,
+}
+
+impl GeoPoint {
+    /// Creates a new point from longitude and latitude.
+    pub const fn new(lng: f64, lat: f64) -> Self {
+        GeoPoint { lng, lat }
+    }
+
+    fn fmt_coordinates(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{:?}, {:?}]", self.lng, self.lat)
+    }
+}
+
+impl fmt::Display for GeoPoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{\"type\": \"Point\", \"coordinates\": ")?;
+        self.fmt_coordinates(f)?;
+        write!(f, "}}")
+    }
+}
+
+/// GeoJSON polygon, given as a single outer ring of points. The ring is closed automatically if
+/// the last point differs from the first one.
+///
+/// ```
+/// use aerospike::{GeoPoint, GeoPolygon};
+/// let region = GeoPolygon::new(vec![
+///     GeoPoint::new(-122.5, 37.0),
+///     GeoPoint::new(-121.0, 37.0),
+///     GeoPoint::new(-121.0, 38.08),
+/// ]);
+/// assert_eq!(region.points().len(), 4);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeoPolygon {
+    points: Vec<GeoPoint>,
+}
+
+impl GeoPolygon {
+    /// Creates a new polygon from the points of its outer ring.
+    pub fn new(mut points: Vec<GeoPoint>) -> Self {
+        if let (Some(first), Some(last)) = (points.first(), points.last()) {
+            if first != last {
+                points.push(*first);
+            }
+        }
+        GeoPolygon { points }
+    }
+
+    /// Points of the closed outer ring.
+    pub fn points(&self) -> &[GeoPoint] {
+        &self.points
+    }
+}
+
+impl fmt::Display for GeoPolygon {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{\"type\": \"Polygon\", \"coordinates\": [[")?;
+        for (i, point) in self.points.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            point.fmt_coordinates(f)?;
+        }
+        write!(f, "]]}}")
+    }
+}
+
+/// Aerospike specific circle region, given as a center point and a radius in meters.
+///
+/// ```
+/// use aerospike::{AeroCircle, GeoPoint};
+/// let circle = AeroCircle::new(GeoPoint::new(-122.0, 37.5), 1000.0);
+/// assert_eq!(
+///     circle.to_string(),
+///     r#"{"type": "AeroCircle", "coordinates": [[-122.0, 37.5], 1000.0]}"#
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AeroCircle {
+    /// Center of the circle.
+    pub center: GeoPoint,
+
+    /// Radius in meters.
+    pub radius: f64,
+}
+
+impl AeroCircle {
+    /// Creates a new circle from its center and radius in meters.
+    pub const fn new(center: GeoPoint, radius: f64) -> Self {
+        AeroCircle { center, radius }
+    }
+}
+
+impl fmt::Display for AeroCircle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{\"type\": \"AeroCircle\", \"coordinates\": [")?;
+        self.center.fmt_coordinates(f)?;
+        write!(f, ", {:?}]}}", self.radius)
+    }
+}
+
+impl From<GeoPoint> for Value {
+    fn from(val: GeoPoint) -> Value {
+        Value::GeoJSON(val.to_string())
+    }
+}
+
+impl From<GeoPolygon> for Value {
+    fn from(val: GeoPolygon) -> Value {
+        Value::GeoJSON(val.to_string())
+    }
+}
+
+impl From<AeroCircle> for Value {
+    fn from(val: AeroCircle) -> Value {
+        Value::GeoJSON(val.to_string())
+    }
+}
+
+#[cfg(feature = "geojson")]
+impl From<GeoPoint> for geojson::Geometry {
+    fn from(val: GeoPoint) -> geojson::Geometry {
+        geojson::Geometry::new(geojson::Value::Point(vec![val.lng, val.lat]))
+    }
+}
+
+#[cfg(feature = "geojson")]
+impl From<GeoPolygon> for geojson::Geometry {
+    fn from(val: GeoPolygon) -> geojson::Geometry {
+        let ring = val.points.iter().map(|p| vec![p.lng, p.lat]).collect();
+        geojson::Geometry::new(geojson::Value::Polygon(vec![ring]))
+    }
+}
+
+#[cfg(feature = "geojson")]
+impl From<geojson::Geometry> for Value {
+    fn from(val: geojson::Geometry) -> Value {
+        Value::GeoJSON(val.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AeroCircle, GeoPoint, GeoPolygon};
+    use crate::Value;
+
+    #[test]
+    fn polygon_to_geojson() {
+        let polygon = GeoPolygon::new(vec![
+            GeoPoint::new(0.0, 0.0),
+            GeoPoint::new(1.0, 0.0),
+            GeoPoint::new(1.0, 1.0),
+            GeoPoint::new(0.0, 0.0),
+        ]);
+        assert_eq!(polygon.points().len(), 4);
+        assert_eq!(
+            polygon.to_string(),
+            r#"{"type": "Polygon", "coordinates": [[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0]]]}"#
+        );
+    }
+
+    #[test]
+    fn into_geojson_value() {
+        let circle = AeroCircle::new(GeoPoint::new(-122.25, 37.5), 250.5);
+        assert_eq!(
+            Value::from(circle),
+            Value::GeoJSON(
+                r#"{"type": "AeroCircle", "coordinates": [[-122.25, 37.5], 250.5]}"#.to_string()
+            )
+        );
+    }
+}
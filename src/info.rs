@@ -0,0 +1,101 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+//! Helpers to parse the responses of info commands. See
+//! <https://www.aerospike.com/docs/reference/info> for the available commands and their
+//! response formats.
+
+use std::collections::HashMap;
+
+/// Parse a list response, e.g. the response of the `namespaces` command `test;bar`. Empty
+/// entries are skipped.
+///
+/// # Examples
+///
+/// ```rust
+/// use aerospike::info;
+///
+/// assert_eq!(info::parse_list("test;bar;"), vec!["test", "bar"]);
+/// ```
+pub fn parse_list(response: &str) -> Vec<&str> {
+    parse_list_with(response, ';')
+}
+
+/// Parse a list response whose entries are separated by `separator`.
+pub fn parse_list_with(response: &str, separator: char) -> Vec<&str> {
+    response
+        .split(separator)
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .collect()
+}
+
+/// Parse a key/value response, e.g. the response of the `namespace/<ns>` command
+/// `objects=10;tombstones=0`. Entries without a `=` are skipped.
+///
+/// # Examples
+///
+/// ```rust
+/// use aerospike::info;
+///
+/// let stats = info::parse_map("objects=10;tombstones=0");
+/// assert_eq!(stats["objects"], "10");
+/// ```
+pub fn parse_map(response: &str) -> HashMap<String, String> {
+    parse_map_with(response, ';')
+}
+
+/// Parse a key/value response whose entries are separated by `separator`, e.g. the entries of
+/// a `sindex-list` response, which use `:`.
+pub fn parse_map_with(response: &str, separator: char) -> HashMap<String, String> {
+    parse_list_with(response, separator)
+        .into_iter()
+        .filter_map(|entry| {
+            let mut kv = entry.splitn(2, '=');
+            match (kv.next(), kv.next()) {
+                (Some(key), Some(value)) => {
+                    Some((key.trim().to_string(), value.trim().to_string()))
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_list, parse_list_with, parse_map, parse_map_with};
+
+    #[test]
+    fn list() {
+        assert_eq!(parse_list("test;bar"), vec!["test", "bar"]);
+        assert_eq!(parse_list(" test ;;bar;\n"), vec!["test", "bar"]);
+        assert!(parse_list("").is_empty());
+        assert_eq!(parse_list_with("a,b", ','), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn map() {
+        let map = parse_map("objects=10;tombstones=0;invalid;filter=a=b");
+        assert_eq!(map.len(), 3);
+        assert_eq!(map["objects"], "10");
+        assert_eq!(map["tombstones"], "0");
+        assert_eq!(map["filter"], "a=b");
+
+        let map = parse_map_with("ns=test:indexname=idx", ':');
+        assert_eq!(map["ns"], "test");
+        assert_eq!(map["indexname"], "idx");
+    }
+}
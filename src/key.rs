@@ -14,11 +14,14 @@
 // the License.
 
 use std::fmt;
+use std::io::Cursor;
 use std::result::Result as StdResult;
 
+use crate::cluster::node;
 use crate::errors::Result;
 use crate::Value;
 
+use byteorder::{LittleEndian, ReadBytesExt};
 use ripemd160::digest::Digest;
 use ripemd160::Ripemd160;
 #[cfg(feature = "serialization")]
@@ -64,6 +67,17 @@ impl Key {
         Ok(key)
     }
 
+    /// Returns the ID of the partition that stores the record, which is derived from the digest
+    /// of the key. Use it to look up the nodes of the record in `Client::partition_map`.
+    pub fn partition_id(&self) -> usize {
+        let mut rdr = Cursor::new(&self.digest[0..4]);
+
+        // CAN'T USE MOD directly - mod will give negative numbers.
+        // First AND makes positive and negative correctly, then mod.
+        // For any x, y : x % 2^y = x & (2^y - 1); the second method is twice as fast
+        rdr.read_u32::<LittleEndian>().unwrap() as usize & (node::PARTITIONS - 1)
+    }
+
     fn compute_digest(&mut self) -> Result<()> {
         let mut hash = Ripemd160::new();
         hash.input(self.set_name.as_bytes());
@@ -265,6 +279,12 @@ mod tests {
         );
     }
 
+    #[test]
+    fn partition_id() {
+        assert_eq!(as_key!("namespace", "set", 0).partition_id(), 0x993);
+        assert_eq!(as_key!("namespace", "set", -1).partition_id(), 0x122);
+    }
+
     #[test]
     #[should_panic(expected = "Data type is not supported as Key value.")]
     fn unsupported_float_key() {
@@ -134,6 +134,11 @@
 
 // `error_chain` can recurse deeply
 #![recursion_limit = "1024"]
+// without a client, only the data types of the crate are in use
+#![cfg_attr(
+    not(any(feature = "sync", feature = "rt-tokio", feature = "rt-async-std")),
+    allow(dead_code, unused_imports)
+)]
 
 extern crate base64;
 extern crate byteorder;
@@ -145,29 +150,43 @@ extern crate error_chain;
 extern crate lazy_static;
 #[macro_use]
 extern crate log;
+#[cfg(feature = "native-tls")]
+extern crate native_tls;
 extern crate parking_lot;
 extern crate pwhash;
 extern crate rand;
 extern crate scoped_pool;
+extern crate sha1;
 
-pub use batch::BatchRead;
+#[cfg(any(feature = "rt-tokio", feature = "rt-async-std"))]
+pub use async_client::AsyncClient;
+pub use batch::{BatchDelete, BatchRead, BatchRecord, BatchUDF, BatchWrite};
 pub use bin::{Bin, Bins};
+#[cfg(feature = "sync")]
 pub use client::Client;
+pub use cluster::{ClusterEventListener, Feature, Node, NodeStats, PartitionMap};
 pub use commands::particle_type::ParticleType;
 pub use errors::{Error, ErrorKind, Result};
-pub use expressions::regex_flag::RegexFlag;
+pub use expressions::regex_flag::{RegexFlag, RegexFlags};
+pub use geo::{AeroCircle, GeoPoint, GeoPolygon};
 pub use key::Key;
-pub use net::Host;
+pub use net::{Host, Resolver};
 pub use operations::{MapPolicy, MapReturnType, MapWriteMode};
 pub use policy::{
-    BatchPolicy, ClientPolicy, CommitLevel, Concurrency, ConsistencyLevel, Expiration,
-    GenerationPolicy, Policy, Priority, QueryPolicy, ReadPolicy, RecordExistsAction, ScanPolicy,
-    WritePolicy,
+    AdminPolicy, AuthMode, BatchDeletePolicy, BatchPolicy, BatchUDFPolicy, BatchWritePolicy,
+    ClientPolicy, CommitLevel, Concurrency, ConsistencyLevel, Expiration, GenerationPolicy, Policy,
+    Priority, QueryDuration, QueryPolicy, ReadPolicy, RecordExistsAction, Replica, ScanPolicy,
+    TlsPolicy, WritePolicy,
+};
+pub use privilege::{Privilege, PrivilegeCode};
+pub use query::{
+    CollectionIndexType, IndexInfo, IndexType, PartitionFilter, PartitionStatus, Recordset,
+    Statement, UDFLang, UDFMeta,
 };
-pub use query::{CollectionIndexType, IndexType, Recordset, Statement, UDFLang};
 pub use record::Record;
 pub use result_code::ResultCode;
-pub use task::{IndexTask, RegisterTask, Task};
+pub use role::Role;
+pub use task::{ExecuteTask, IndexTask, RegisterTask, Task};
 pub use user::User;
 pub use value::{FloatValue, Value};
 
@@ -179,18 +198,26 @@ mod value;
 mod bin;
 #[macro_use]
 mod key;
+#[cfg(any(feature = "sync", feature = "rt-tokio", feature = "rt-async-std"))]
+mod async_client;
 mod batch;
+#[cfg(feature = "sync")]
 mod client;
 mod cluster;
 mod commands;
 pub mod expressions;
+mod geo;
+pub mod info;
 mod msgpack;
 mod net;
 pub mod operations;
 pub mod policy;
+mod privilege;
 pub mod query;
 mod record;
 mod result_code;
+mod role;
+mod runtime;
 pub mod task;
 mod user;
 
@@ -36,11 +36,14 @@ pub fn pack_value(buf: &mut Option<&mut Buffer>, val: &Value) -> Result<usize> {
             FloatValue::F64(_) => pack_f64(buf, f64::from(val)),
             FloatValue::F32(_) => pack_f32(buf, f32::from(val)),
         },
-        Value::Blob(ref val) | Value::HLL(ref val) => pack_blob(buf, val),
+        Value::Blob(ref val) => pack_blob(buf, val),
+        Value::HLL(ref val) => pack_particle_bytes(buf, val, ParticleType::HLL),
         Value::List(ref val) => pack_array(buf, val),
         Value::HashMap(ref val) => pack_map(buf, val),
         Value::OrderedMap(_) => panic!("Ordered maps are not supported in this encoder."),
         Value::GeoJSON(ref val) => pack_geo_json(buf, val),
+        Value::Wildcard => pack_ext_marker(buf, MSGPACK_EXT_WILDCARD),
+        Value::Infinity => pack_ext_marker(buf, MSGPACK_EXT_INFINITY),
     }
 }
 
@@ -57,41 +60,87 @@ pub fn pack_cdt_op(
     buf: &mut Option<&mut Buffer>,
     cdt_op: &CdtOperation,
     ctx: &[CdtContext],
+) -> Result<usize> {
+    pack_cdt_op_args(buf, cdt_op.op, &cdt_op.args, ctx, 0)
+}
+
+// The first argument of a create operation is not sent as an argument, but holds the flag for
+// creating the list/map at the last context level.
+#[doc(hidden)]
+pub fn pack_cdt_create_op(
+    buf: &mut Option<&mut Buffer>,
+    cdt_op: &CdtOperation,
+    ctx: &[CdtContext],
+) -> Result<usize> {
+    match cdt_op.args.split_first() {
+        Some((CdtArgument::Byte(flag), args)) => pack_cdt_op_args(buf, cdt_op.op, args, ctx, *flag),
+        _ => pack_cdt_op_args(buf, cdt_op.op, &cdt_op.args, ctx, 0),
+    }
+}
+
+// The arguments of a map create operation are the flag for creating the map at the last context
+// level, the map order of a nested map, and the map attributes of a top-level map, which may
+// include the persisted index flag that the server rejects for nested maps.
+#[doc(hidden)]
+pub fn pack_map_create_op(
+    buf: &mut Option<&mut Buffer>,
+    cdt_op: &CdtOperation,
+    ctx: &[CdtContext],
+) -> Result<usize> {
+    match cdt_op.args.as_slice() {
+        [CdtArgument::Byte(flag), CdtArgument::Byte(order), CdtArgument::Byte(attributes)] => {
+            if ctx.is_empty() {
+                pack_cdt_op_args(buf, cdt_op.op, &[CdtArgument::Byte(*attributes)], ctx, 0)
+            } else {
+                pack_cdt_op_args(buf, cdt_op.op, &[CdtArgument::Byte(*order)], ctx, *flag)
+            }
+        }
+        _ => pack_cdt_op_args(buf, cdt_op.op, &cdt_op.args, ctx, 0),
+    }
+}
+
+fn pack_cdt_op_args(
+    buf: &mut Option<&mut Buffer>,
+    op: u8,
+    args: &[CdtArgument],
+    ctx: &[CdtContext],
+    create_flag: u8,
 ) -> Result<usize> {
     let mut size: usize = 0;
     if ctx.is_empty() {
-        size += pack_raw_u16(buf, u16::from(cdt_op.op))?;
-        if !cdt_op.args.is_empty() {
-            size += pack_array_begin(buf, cdt_op.args.len())?;
+        size += pack_raw_u16(buf, u16::from(op))?;
+        if !args.is_empty() {
+            size += pack_array_begin(buf, args.len())?;
         }
     } else {
         size += pack_array_begin(buf, 3)?;
         size += pack_integer(buf, 0xff)?;
         size += pack_array_begin(buf, ctx.len() * 2)?;
 
-        for c in ctx {
-            if c.id == 0 {
-                size += pack_integer(buf, i64::from(c.id))?;
-            } else {
-                size += pack_integer(buf, i64::from(c.id | c.flags))?;
+        for (i, c) in ctx.iter().enumerate() {
+            let mut id = c.id;
+            if id != 0 {
+                id |= c.flags;
+            }
+            if i == ctx.len() - 1 {
+                id |= create_flag;
             }
+            size += pack_integer(buf, i64::from(id))?;
             size += pack_value(buf, &c.value)?;
         }
 
-        size += pack_array_begin(buf, cdt_op.args.len() + 1)?;
-        size += pack_integer(buf, i64::from(cdt_op.op))?;
+        size += pack_array_begin(buf, args.len() + 1)?;
+        size += pack_integer(buf, i64::from(op))?;
     }
 
-    if !cdt_op.args.is_empty() {
-        for arg in &cdt_op.args {
-            size += match *arg {
-                CdtArgument::Byte(byte) => pack_value(buf, &Value::from(byte))?,
-                CdtArgument::Int(int) => pack_value(buf, &Value::from(int))?,
-                CdtArgument::Value(value) => pack_value(buf, value)?,
-                CdtArgument::List(list) => pack_array(buf, list)?,
-                CdtArgument::Map(map) => pack_map(buf, map)?,
-                CdtArgument::Bool(bool_val) => pack_value(buf, &Value::from(bool_val))?,
-            }
+    for arg in args {
+        size += match *arg {
+            CdtArgument::Byte(byte) => pack_value(buf, &Value::from(byte))?,
+            CdtArgument::Int(int) => pack_value(buf, &Value::from(int))?,
+            CdtArgument::Value(value) => pack_value(buf, value)?,
+            CdtArgument::List(list) => pack_array(buf, list)?,
+            CdtArgument::Map(map) => pack_map(buf, map)?,
+            CdtArgument::Bool(bool_val) => pack_value(buf, &Value::from(bool_val))?,
         }
     }
 
@@ -203,6 +252,11 @@ const MSGPACK_MARKER_NI16: u8 = 0xd1;
 const MSGPACK_MARKER_NI32: u8 = 0xd2;
 const MSGPACK_MARKER_NI64: u8 = 0xd3;
 
+const MSGPACK_MARKER_FIXEXT1: u8 = 0xd4;
+const MSGPACK_EXT_TYPE_AEROSPIKE: u8 = 0xff;
+const MSGPACK_EXT_WILDCARD: u8 = 0x00;
+const MSGPACK_EXT_INFINITY: u8 = 0x01;
+
 // This method is not compatible with MsgPack specs and is only used by aerospike client<->server
 // for wire transfer only
 #[doc(hidden)]
@@ -238,6 +292,15 @@ pub fn pack_nil(buf: &mut Option<&mut Buffer>) -> Result<usize> {
     Ok(1)
 }
 
+fn pack_ext_marker(buf: &mut Option<&mut Buffer>, marker: u8) -> Result<usize> {
+    if let Some(ref mut buf) = *buf {
+        buf.write_u8(MSGPACK_MARKER_FIXEXT1)?;
+        buf.write_u8(MSGPACK_EXT_TYPE_AEROSPIKE)?;
+        buf.write_u8(marker)?;
+    }
+    Ok(3)
+}
+
 #[doc(hidden)]
 pub fn pack_bool(buf: &mut Option<&mut Buffer>, value: bool) -> Result<usize> {
     if let Some(ref mut buf) = *buf {
@@ -279,11 +342,19 @@ pub fn pack_byte_array_begin(buf: &mut Option<&mut Buffer>, length: usize) -> Re
 
 #[doc(hidden)]
 pub fn pack_blob(buf: &mut Option<&mut Buffer>, value: &[u8]) -> Result<usize> {
+    pack_particle_bytes(buf, value, ParticleType::BLOB)
+}
+
+fn pack_particle_bytes(
+    buf: &mut Option<&mut Buffer>,
+    value: &[u8],
+    particle_type: ParticleType,
+) -> Result<usize> {
     let mut size = value.len() + 1;
 
     size += pack_byte_array_begin(buf, size)?;
     if let Some(ref mut buf) = *buf {
-        buf.write_u8(ParticleType::BLOB as u8)?;
+        buf.write_u8(particle_type as u8)?;
         buf.write_bytes(value)?;
     }
 
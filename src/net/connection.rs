@@ -13,15 +13,20 @@
 // License for the specific language governing permissions and limitations under
 // the License.
 
-use std::io::prelude::*;
-use std::net::{Shutdown, TcpStream, ToSocketAddrs};
+use std::net::Shutdown;
 use std::ops::Add;
 use std::time::{Duration, Instant};
 
-use crate::commands::admin_command::AdminCommand;
+use parking_lot::RwLock;
+
+use crate::commands::admin_command::{AdminCommand, Session};
 use crate::commands::buffer::Buffer;
-use crate::errors::Result;
-use crate::policy::ClientPolicy;
+use crate::errors::{Error, ErrorKind, Result};
+use crate::net::stream::Stream;
+use crate::net::Host;
+use crate::policy::{AuthMode, ClientPolicy};
+use crate::runtime::Runtime;
+use crate::ResultCode;
 
 #[derive(Debug)]
 pub struct Connection {
@@ -30,7 +35,7 @@ pub struct Connection {
     idle_deadline: Option<Instant>,
 
     // connection object
-    conn: TcpStream,
+    conn: Stream,
 
     bytes_read: usize,
 
@@ -38,9 +43,41 @@ pub struct Connection {
 }
 
 impl Connection {
-    pub fn new<T: ToSocketAddrs>(addr: T, policy: &ClientPolicy) -> Result<Self> {
-        let stream = TcpStream::connect(addr)?;
-        let mut conn = Connection {
+    pub async fn new(
+        host: &Host,
+        policy: &ClientPolicy,
+        user_password: &Option<(String, String)>,
+        session: &RwLock<Option<Session>>,
+        runtime: Runtime,
+    ) -> Result<Self> {
+        let mut conn = Connection::connect(host, policy, runtime).await?;
+        if let Err(err) = conn.authenticate(policy, user_password, session).await {
+            conn.close();
+            match err {
+                // The server no longer accepts the session, e.g. because the password of the
+                // user was changed; log in again on a new connection.
+                Error(
+                    ErrorKind::ServerError(
+                        ResultCode::InvalidCredential | ResultCode::ExpiredSession,
+                    ),
+                    _,
+                ) if session.write().take().is_some() => {
+                    conn = Connection::connect(host, policy, runtime).await?;
+                    if let Err(err) = conn.authenticate(policy, user_password, session).await {
+                        conn.close();
+                        return Err(err);
+                    }
+                }
+                err => return Err(err),
+            }
+        }
+        conn.refresh();
+        Ok(conn)
+    }
+
+    async fn connect(host: &Host, policy: &ClientPolicy, runtime: Runtime) -> Result<Self> {
+        let stream = Stream::connect(host, policy, runtime).await?;
+        Ok(Connection {
             buffer: Buffer::new(policy.buffer_reclaim_threshold),
             bytes_read: 0,
             conn: stream,
@@ -49,47 +86,43 @@ impl Connection {
                 None => None,
                 Some(timeout) => Some(Instant::now() + timeout),
             },
-        };
-        conn.authenticate(&policy.user_password)?;
-        conn.refresh();
-        Ok(conn)
+        })
     }
 
     pub fn close(&mut self) {
         let _ = self.conn.shutdown(Shutdown::Both);
     }
 
-    pub fn flush(&mut self) -> Result<()> {
-        self.conn.write_all(&self.buffer.data_buffer)?;
+    pub async fn flush(&mut self) -> Result<()> {
+        self.conn.write_all(&self.buffer.data_buffer).await?;
         self.refresh();
         Ok(())
     }
 
-    pub fn read_buffer(&mut self, size: usize) -> Result<()> {
+    pub async fn read_buffer(&mut self, size: usize) -> Result<()> {
         self.buffer.resize_buffer(size)?;
-        self.conn.read_exact(&mut self.buffer.data_buffer)?;
+        self.conn.read_exact(&mut self.buffer.data_buffer).await?;
         self.bytes_read += size;
         self.buffer.reset_offset()?;
         self.refresh();
         Ok(())
     }
 
-    pub fn write(&mut self, buf: &[u8]) -> Result<()> {
-        self.conn.write_all(buf)?;
+    pub async fn write(&mut self, buf: &[u8]) -> Result<()> {
+        self.conn.write_all(buf).await?;
         self.refresh();
         Ok(())
     }
 
-    pub fn read(&mut self, buf: &mut [u8]) -> Result<()> {
-        self.conn.read_exact(buf)?;
+    pub async fn read(&mut self, buf: &mut [u8]) -> Result<()> {
+        self.conn.read_exact(buf).await?;
         self.bytes_read += buf.len();
         self.refresh();
         Ok(())
     }
 
-    pub fn set_timeout(&self, timeout: Option<Duration>) -> Result<()> {
-        self.conn.set_read_timeout(timeout)?;
-        self.conn.set_write_timeout(timeout)?;
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) -> Result<()> {
+        self.conn.set_timeout(timeout)?;
         Ok(())
     }
 
@@ -105,19 +138,45 @@ impl Connection {
         };
     }
 
-    fn authenticate(&mut self, user_password: &Option<(String, String)>) -> Result<()> {
-        if let Some((ref user, ref password)) = *user_password {
-            match AdminCommand::authenticate(self, user, password) {
-                Ok(()) => {
-                    return Ok(());
-                }
-                Err(err) => {
-                    self.close();
-                    return Err(err);
-                }
-            }
+    // Authenticates the connection with the session of the node while it is valid; otherwise
+    // logs in with the credentials of the user and keeps the new session for later connections.
+    async fn authenticate(
+        &mut self,
+        policy: &ClientPolicy,
+        user_password: &Option<(String, String)>,
+        session: &RwLock<Option<Session>>,
+    ) -> Result<()> {
+        let user = match *user_password {
+            _ if policy.auth_mode == AuthMode::PKI => None,
+            Some((ref user, _)) => Some(user.as_str()),
+            None => return Ok(()),
+        };
+
+        let current = session.read().clone();
+        if let Some(current) = current.filter(Session::is_valid) {
+            return AdminCommand::authenticate_session(self, user, &current).await;
         }
 
+        let new_session = match (policy.auth_mode, user_password) {
+            (AuthMode::Internal, Some((user, password))) => {
+                match AdminCommand::authenticate(self, user, password, None).await {
+                    // servers without support for the login command can only authenticate
+                    // internal users, and do not issue sessions
+                    Err(Error(ErrorKind::ServerError(ResultCode::InvalidCommand), _)) => {
+                        AdminCommand::authenticate_legacy(self, user, password).await?;
+                        None
+                    }
+                    res => res?,
+                }
+            }
+            (AuthMode::External, Some((user, password)))
+            | (AuthMode::ExternalInsecure, Some((user, password))) => {
+                let clear_password = policy.clear_password.as_ref().map(String::as_str);
+                AdminCommand::authenticate(self, user, password, clear_password).await?
+            }
+            _ => AdminCommand::authenticate_pki(self).await?,
+        };
+        *session.write() = new_session;
         Ok(())
     }
 
@@ -14,16 +14,19 @@
 // the License.
 
 use std::collections::VecDeque;
+use std::mem;
 use std::ops::{Deref, DerefMut, Drop};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
-use parking_lot::Mutex;
+use parking_lot::{Mutex, RwLock};
 
+use crate::commands::admin_command::Session;
 use crate::errors::{Error, ErrorKind, Result};
 use crate::net::{Connection, Host};
 use crate::policy::ClientPolicy;
+use crate::runtime::Runtime;
 
 #[derive(Debug)]
 struct IdleConnection(Connection);
@@ -38,15 +41,28 @@ struct QueueInternals {
 struct SharedQueue {
     internals: Mutex<QueueInternals>,
     capacity: usize,
+    // Number of connections kept open, even if idle.
+    min_size: usize,
     host: Host,
     policy: ClientPolicy,
+    user_password: Arc<RwLock<Option<(String, String)>>>,
+    session: Arc<RwLock<Option<Session>>>,
+    runtime: Runtime,
 }
 
 #[derive(Debug)]
 struct Queue(Arc<SharedQueue>);
 
 impl Queue {
-    pub fn with_capacity(capacity: usize, host: Host, policy: ClientPolicy) -> Self {
+    pub fn with_capacity(
+        capacity: usize,
+        min_size: usize,
+        host: Host,
+        policy: ClientPolicy,
+        user_password: Arc<RwLock<Option<(String, String)>>>,
+        session: Arc<RwLock<Option<Session>>>,
+        runtime: Runtime,
+    ) -> Self {
         let internals = QueueInternals {
             connections: VecDeque::with_capacity(capacity),
             num_conns: 0,
@@ -54,48 +70,110 @@ impl Queue {
         let shared = SharedQueue {
             internals: Mutex::new(internals),
             capacity,
+            min_size,
             host,
             policy,
+            user_password,
+            session,
+            runtime,
         };
         Queue(Arc::new(shared))
     }
 
-    pub fn get(&self, timeout: Option<Duration>) -> Result<PooledConnection> {
+    pub async fn get(&self, timeout: Option<Duration>) -> Result<PooledConnection> {
+        let mut connection = match self.pop_idle()? {
+            Some(conn) => conn,
+            None => self.open().await?,
+        };
+
+        if let Err(err) = connection.set_timeout(timeout) {
+            self.drop_conn(connection);
+            return Err(err);
+        }
+
+        Ok(PooledConnection {
+            queue: self.clone(),
+            conn: Some(connection),
+            reusable: false,
+        })
+    }
+
+    // Opens a new connection, which has been counted already while it is being opened, without
+    // holding the lock of the queue. The room reserved for the connection is released if opening
+    // the connection fails or the future is dropped before it completes.
+    async fn open(&self) -> Result<Connection> {
+        let reserved = Reserved(self);
+        let user_password = self.0.user_password.read().clone();
+        let conn = Connection::new(
+            &self.0.host,
+            &self.0.policy,
+            &user_password,
+            &self.0.session,
+            self.0.runtime,
+        )
+        .await?;
+        mem::forget(reserved);
+        Ok(conn)
+    }
+
+    // Returns an idle connection of the queue, or reserves room for a new connection. The
+    // minimum number of connections are kept open even if they have been idle for too long.
+    fn pop_idle(&self) -> Result<Option<Connection>> {
         let mut internals = self.0.internals.lock();
-        let connection;
-        loop {
-            if let Some(IdleConnection(mut conn)) = internals.connections.pop_front() {
-                if conn.is_idle() {
+        while let Some(IdleConnection(mut conn)) = internals.connections.pop_front() {
+            if !conn.is_idle() || internals.num_conns <= self.0.min_size {
+                return Ok(Some(conn));
+            }
+            internals.num_conns -= 1;
+            conn.close();
+        }
+        if internals.num_conns >= self.0.capacity {
+            bail!(ErrorKind::NoMoreConnections);
+        }
+        internals.num_conns += 1;
+        Ok(None)
+    }
+
+    // Closes the connections that have been idle for too long, beyond the minimum number of
+    // connections, and opens connections until the minimum number of connections is reached.
+    pub async fn balance(&self) -> Result<()> {
+        let missing = {
+            let mut internals = self.0.internals.lock();
+            let mut i = 0;
+            while i < internals.connections.len() && internals.num_conns > self.0.min_size {
+                if internals.connections[i].0.is_idle() {
+                    if let Some(IdleConnection(mut conn)) = internals.connections.remove(i) {
+                        conn.close();
+                    }
                     internals.num_conns -= 1;
-                    conn.close();
-                    continue;
+                } else {
+                    i += 1;
                 }
-                connection = conn;
-                break;
-            } else {
-                if internals.num_conns >= self.0.capacity {
-                    bail!(ErrorKind::NoMoreConnections);
+            }
+
+            self.0.min_size.saturating_sub(internals.num_conns)
+        };
+
+        // Reserve the room for one connection at a time, so that no room is kept reserved if
+        // balancing is cancelled.
+        for _ in 0..missing {
+            {
+                let mut internals = self.0.internals.lock();
+                if internals.num_conns >= self.0.min_size {
+                    break;
                 }
-                let conn = Connection::new(&self.0.host, &self.0.policy)?;
                 internals.num_conns += 1;
-                connection = conn;
-                break;
             }
+            let conn = self.open().await?;
+            self.put_back(conn);
         }
-        connection.set_timeout(timeout).map_err(|err| {
-            internals.num_conns -= 1;
-            err
-        })?;
-
-        Ok(PooledConnection {
-            queue: self.clone(),
-            conn: Some(connection),
-        })
+        Ok(())
     }
 
+    // Returns a connection to the queue. The connection is still counted by the queue.
     pub fn put_back(&self, mut conn: Connection) {
         let mut internals = self.0.internals.lock();
-        if internals.num_conns < self.0.capacity {
+        if internals.num_conns <= self.0.capacity {
             internals.connections.push_back(IdleConnection(conn));
         } else {
             conn.close();
@@ -111,12 +189,24 @@ impl Queue {
         conn.close();
     }
 
-    pub fn clear(&mut self) {
+    // Closes the idle connections. Connections in use are still counted until they are handed
+    // back to the queue.
+    pub fn clear(&self) {
         let mut internals = self.0.internals.lock();
-        for mut conn in internals.connections.drain(..) {
+        let idle: Vec<_> = internals.connections.drain(..).collect();
+        internals.num_conns -= idle.len();
+        for mut conn in idle {
             conn.0.close();
         }
-        internals.num_conns = 0;
+    }
+}
+
+// Room reserved in a queue for a connection that is being opened.
+struct Reserved<'a>(&'a Queue);
+
+impl<'a> Drop for Reserved<'a> {
+    fn drop(&mut self) {
+        self.0 .0.internals.lock().num_conns -= 1;
     }
 }
 
@@ -134,10 +224,24 @@ pub struct ConnectionPool {
 }
 
 impl ConnectionPool {
-    pub fn new(host: Host, policy: ClientPolicy) -> Self {
+    pub fn new(
+        host: Host,
+        policy: ClientPolicy,
+        user_password: Arc<RwLock<Option<(String, String)>>>,
+        runtime: Runtime,
+    ) -> Self {
         let num_conns = policy.max_conns_per_node;
+        let min_conns = policy.min_conns_per_node;
         let num_queues = policy.conn_pools_per_node;
-        let queues = ConnectionPool::initialize_queues(num_conns, num_queues, host, policy);
+        let queues = ConnectionPool::initialize_queues(
+            num_conns,
+            min_conns,
+            num_queues,
+            host,
+            policy,
+            user_password,
+            runtime,
+        );
         ConnectionPool {
             num_queues,
             queues,
@@ -147,12 +251,19 @@ impl ConnectionPool {
 
     fn initialize_queues(
         num_conns: usize,
+        min_conns: usize,
         num_queues: usize,
         host: Host,
         policy: ClientPolicy,
+        user_password: Arc<RwLock<Option<(String, String)>>>,
+        runtime: Runtime,
     ) -> Vec<Queue> {
+        // The login session is shared by all connections to the node.
+        let session = Arc::new(RwLock::new(None));
         let max = num_conns / num_queues;
         let mut rem = num_conns % num_queues;
+        let min = min_conns / num_queues;
+        let mut min_rem = min_conns % num_queues;
         let mut queues = Vec::with_capacity(num_queues);
         for _ in 0..num_queues {
             let mut capacity = max;
@@ -160,19 +271,32 @@ impl ConnectionPool {
                 capacity += 1;
                 rem -= 1;
             }
-            queues.push(Queue::with_capacity(capacity, host.clone(), policy.clone()));
+            let mut min_size = min;
+            if min_rem > 0 {
+                min_size += 1;
+                min_rem -= 1;
+            }
+            queues.push(Queue::with_capacity(
+                capacity,
+                min_size,
+                host.clone(),
+                policy.clone(),
+                Arc::clone(&user_password),
+                Arc::clone(&session),
+                runtime,
+            ));
         }
         queues
     }
 
-    pub fn get(&self, timeout: Option<Duration>) -> Result<PooledConnection> {
+    pub async fn get(&self, timeout: Option<Duration>) -> Result<PooledConnection> {
         if self.num_queues == 1 {
-            self.queues[0].get(timeout)
+            self.queues[0].get(timeout).await
         } else {
             let mut attempts = self.num_queues;
             loop {
                 let i = self.queue_counter.fetch_add(1, Ordering::Relaxed);
-                let connection = self.queues[i % self.num_queues].get(timeout);
+                let connection = self.queues[i % self.num_queues].get(timeout).await;
                 if let Err(Error(ErrorKind::NoMoreConnections, _)) = connection {
                     attempts -= 1;
                     if attempts > 0 {
@@ -184,17 +308,29 @@ impl ConnectionPool {
         }
     }
 
-    pub fn close(&mut self) {
-        for mut queue in self.queues.drain(..) {
+    // Closes idle connections and opens the minimum number of connections of each queue.
+    pub async fn balance(&self) -> Result<()> {
+        for queue in &self.queues {
+            queue.balance().await?;
+        }
+        Ok(())
+    }
+
+    pub fn close(&self) {
+        for queue in &self.queues {
             queue.clear();
         }
     }
 }
 
+// A connection taken from the pool. The connection is closed when it is dropped, unless it has
+// been marked as reusable, so that a connection is never returned to the pool with a response
+// that has not been read completely, e.g. because a command future was dropped.
 #[derive(Debug)]
 pub struct PooledConnection {
     queue: Queue,
     pub conn: Option<Connection>,
+    reusable: bool,
 }
 
 impl PooledConnection {
@@ -202,12 +338,22 @@ impl PooledConnection {
         let conn = self.conn.take().unwrap();
         self.queue.drop_conn(conn);
     }
+
+    // Returns the connection to the pool when it is dropped. Must only be called once the
+    // response of the last command has been read completely.
+    pub fn mark_reusable(&mut self) {
+        self.reusable = true;
+    }
 }
 
 impl Drop for PooledConnection {
     fn drop(&mut self) {
         if let Some(conn) = self.conn.take() {
-            self.queue.put_back(conn);
+            if self.reusable {
+                self.queue.put_back(conn);
+            } else {
+                self.queue.drop_conn(conn);
+            }
         }
     }
 }
@@ -225,3 +371,162 @@ impl DerefMut for PooledConnection {
         self.conn.as_mut().unwrap()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ConnectionPool;
+    use crate::net::Host;
+    use crate::policy::ClientPolicy;
+    use crate::runtime::{self, Runtime};
+    use parking_lot::RwLock;
+    use std::net::TcpListener;
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    // Accepts connections and keeps them open until the listener is dropped.
+    fn listen() -> Host {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        thread::spawn(move || {
+            let mut sockets = vec![];
+            for socket in listener.incoming() {
+                sockets.push(socket);
+            }
+        });
+        Host::new("127.0.0.1", port)
+    }
+
+    fn num_conns(pool: &ConnectionPool) -> usize {
+        pool.queues
+            .iter()
+            .map(|queue| queue.0.internals.lock().num_conns)
+            .sum()
+    }
+
+    #[test]
+    fn balance() {
+        let mut policy = ClientPolicy::default();
+        policy.min_conns_per_node = 3;
+        policy.conn_pools_per_node = 2;
+        policy.idle_timeout = Some(Duration::from_millis(50));
+        let pool = ConnectionPool::new(
+            listen(),
+            policy,
+            Arc::new(RwLock::new(None)),
+            Runtime::Blocking,
+        );
+
+        // opens the minimum connections
+        runtime::block_on(pool.balance()).unwrap();
+        assert_eq!(num_conns(&pool), 3);
+
+        // closes idle connections beyond the minimum only
+        let conns: Vec<_> = (0..5)
+            .map(|_| {
+                let mut conn = runtime::block_on(pool.get(None)).unwrap();
+                conn.mark_reusable();
+                conn
+            })
+            .collect();
+        assert_eq!(num_conns(&pool), 5);
+        drop(conns);
+        thread::sleep(Duration::from_millis(100));
+        runtime::block_on(pool.balance()).unwrap();
+        assert_eq!(num_conns(&pool), 3);
+
+        // keeps the minimum connections even if idle
+        let mut conn = runtime::block_on(pool.get(None)).unwrap();
+        assert_eq!(num_conns(&pool), 3);
+        conn.mark_reusable();
+        drop(conn);
+        assert_eq!(num_conns(&pool), 3);
+
+        // closes connections that have not been marked as reusable
+        drop(runtime::block_on(pool.get(None)).unwrap());
+        assert_eq!(num_conns(&pool), 2);
+    }
+
+    #[test]
+    fn balance_full_pool() {
+        let mut policy = ClientPolicy::default();
+        policy.min_conns_per_node = 2;
+        policy.max_conns_per_node = 2;
+        let pool = ConnectionPool::new(
+            listen(),
+            policy,
+            Arc::new(RwLock::new(None)),
+            Runtime::Blocking,
+        );
+        runtime::block_on(pool.balance()).unwrap();
+        assert_eq!(num_conns(&pool), 2);
+
+        // connections handed back to a full pool are kept, so balancing opens none
+        let mut conn = runtime::block_on(pool.get(None)).unwrap();
+        conn.mark_reusable();
+        drop(conn);
+        assert_eq!(num_conns(&pool), 2);
+        assert_eq!(pool.queues[0].0.internals.lock().connections.len(), 2);
+        runtime::block_on(pool.balance()).unwrap();
+        assert_eq!(num_conns(&pool), 2);
+        assert_eq!(pool.queues[0].0.internals.lock().connections.len(), 2);
+    }
+
+    #[test]
+    fn close() {
+        let mut policy = ClientPolicy::default();
+        policy.min_conns_per_node = 2;
+        let pool = ConnectionPool::new(
+            listen(),
+            policy,
+            Arc::new(RwLock::new(None)),
+            Runtime::Blocking,
+        );
+        runtime::block_on(pool.balance()).unwrap();
+        let mut conn = runtime::block_on(pool.get(None)).unwrap();
+        assert_eq!(num_conns(&pool), 2);
+
+        // closes the idle connections, but keeps counting the connection in use
+        pool.close();
+        assert_eq!(num_conns(&pool), 1);
+        assert!(pool.queues[0].0.internals.lock().connections.is_empty());
+        conn.mark_reusable();
+        drop(conn);
+        assert_eq!(num_conns(&pool), 1);
+    }
+
+    #[cfg(feature = "rt-tokio")]
+    #[test]
+    fn cancel_pending_command() {
+        use tokio::time::timeout;
+
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            // the listener never answers, so logging in is pending until the future is dropped
+            let user_password = Arc::new(RwLock::new(Some(("user".into(), "pass".into()))));
+            let pool = ConnectionPool::new(
+                listen(),
+                ClientPolicy::default(),
+                Arc::clone(&user_password),
+                Runtime::Tokio,
+            );
+            let cancelled = timeout(Duration::from_millis(50), pool.get(None)).await;
+            assert!(cancelled.is_err());
+            assert_eq!(num_conns(&pool), 0);
+
+            // a connection waiting for a response is closed instead of returned to the pool
+            *user_password.write() = None;
+            let command = async {
+                let mut conn = pool.get(None).await.unwrap();
+                conn.read_buffer(8).await.unwrap();
+                conn.mark_reusable();
+            };
+            assert!(timeout(Duration::from_millis(50), command).await.is_err());
+            assert_eq!(num_conns(&pool), 0);
+            assert!(pool.queues[0].0.internals.lock().connections.is_empty());
+        });
+    }
+}
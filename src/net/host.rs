@@ -14,6 +14,7 @@
 // the License.
 
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::io;
 use std::net::{SocketAddr, ToSocketAddrs};
 use std::vec::IntoIter;
@@ -22,27 +23,76 @@ use crate::errors::{ErrorKind, Result, ResultExt};
 use crate::net::parser::Parser;
 
 /// Host name/port of database server.
-#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[derive(Debug, Clone)]
 pub struct Host {
     /// Host name or IP address of database server.
     pub name: String,
 
     /// Port of database server.
     pub port: u16,
+
+    /// Name the TLS certificate of the server is verified against; the host name is used if
+    /// `None`. Only used with TLS connections. Hosts are identified by their name and port only.
+    pub tls_name: Option<String>,
 }
 
 impl Host {
-    /// Create a new host instance given a hostname/IP and a port number.
+    /// Create a new host instance given a hostname/IP and a port number. IPv6 addresses may be
+    /// enclosed in brackets.
     pub fn new(name: &str, port: u16) -> Self {
         Host {
-            name: name.to_string(),
+            name: unbracket(name).to_string(),
+            port,
+            tls_name: None,
+        }
+    }
+
+    /// Create a new host instance given a hostname/IP, the name to verify the TLS certificate of
+    /// the server against and a port number.
+    pub fn with_tls_name(name: &str, tls_name: &str, port: u16) -> Self {
+        Host {
+            name: unbracket(name).to_string(),
             port,
+            tls_name: Some(tls_name.to_string()),
         }
     }
 
-    /// Returns a string representation of the host's address.
+    // Parses an address of the form `<host>:<port>`, where IPv6 addresses may be enclosed in
+    // brackets.
+    pub(crate) fn parse_address(address: &str) -> Option<Self> {
+        let (name, port) = address.rsplit_once(':')?;
+        Some(Host::new(name, port.parse().ok()?))
+    }
+
+    /// Returns a string representation of the host's address. IPv6 addresses are enclosed in
+    /// brackets.
     pub fn address(&self) -> String {
-        format!("{}:{}", self.name, self.port)
+        if self.name.contains(':') {
+            format!("[{}]:{}", self.name, self.port)
+        } else {
+            format!("{}:{}", self.name, self.port)
+        }
+    }
+}
+
+fn unbracket(name: &str) -> &str {
+    name.strip_prefix('[')
+        .and_then(|name| name.strip_suffix(']'))
+        .unwrap_or(name)
+}
+
+impl PartialEq for Host {
+    fn eq(&self, other: &Host) -> bool {
+        self.name == other.name && self.port == other.port
+    }
+}
+
+impl Eq for Host {}
+
+impl Hash for Host {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+        self.port.hash(state);
     }
 }
 
@@ -55,7 +105,7 @@ impl ToSocketAddrs for Host {
 
 impl fmt::Display for Host {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}:{}", self.name, self.port)
+        f.write_str(&self.address())
     }
 }
 
@@ -106,5 +156,32 @@ mod tests {
             vec![Host::new("foo", 1234), Host::new("bar", 1234)],
             "foo:1234,bar:1234".to_hosts().unwrap()
         );
+        assert_eq!(
+            vec![Host::new("2001:db8::1", 1234), Host::new("::1", 3000)],
+            "[2001:db8::1]:1234,[::1]".to_hosts().unwrap()
+        );
+    }
+
+    #[test]
+    fn ipv6_address() {
+        let host = Host::new("[2001:db8::1]", 3000);
+        assert_eq!(host.name, "2001:db8::1");
+        assert_eq!(host.address(), "[2001:db8::1]:3000");
+        assert_eq!(host.to_string(), "[2001:db8::1]:3000");
+        assert_eq!(Host::new("foo", 3000).address(), "foo:3000");
+    }
+
+    #[test]
+    fn parse_address() {
+        assert_eq!(
+            Host::parse_address("[2001:db8::1]:3000"),
+            Some(Host::new("2001:db8::1", 3000))
+        );
+        assert_eq!(
+            Host::parse_address("10.0.0.1:3100"),
+            Some(Host::new("10.0.0.1", 3100))
+        );
+        assert_eq!(Host::parse_address("10.0.0.1"), None);
+        assert_eq!(Host::parse_address("10.0.0.1:x"), None);
     }
 }
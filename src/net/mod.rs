@@ -18,8 +18,11 @@ pub use self::connection_pool::ConnectionPool;
 pub use self::connection_pool::PooledConnection;
 pub use self::host::Host;
 pub use self::host::ToHosts;
+pub use self::resolver::Resolver;
 
 mod connection;
 mod connection_pool;
 pub mod host;
 mod parser;
+mod resolver;
+mod stream;
@@ -35,7 +35,7 @@ impl<'a> Parser<'a> {
         let mut hosts = Vec::new();
         loop {
             let addr = self.read_addr_tuple()?;
-            let (host, _tls_name, port) = match addr.len() {
+            let (host, tls_name, port) = match addr.len() {
                 3 => (addr[0].clone(), Some(addr[1].clone()), addr[2].parse()?),
                 2 => {
                     if let Ok(port) = addr[1].parse() {
@@ -49,8 +49,10 @@ impl<'a> Parser<'a> {
                     "Invalid address string".to_string()
                 )),
             };
-            // TODO: add TLS name
-            hosts.push(Host::new(&host, port));
+            hosts.push(match tls_name {
+                Some(tls_name) => Host::with_tls_name(&host, &tls_name, port),
+                None => Host::new(&host, port),
+            });
 
             match self.peek() {
                 Some(&c) if c == ',' => self.next_char(),
@@ -76,6 +78,24 @@ impl<'a> Parser<'a> {
 
     fn read_addr_part(&mut self) -> Result<String> {
         let mut substr = String::new();
+        // IPv6 addresses are enclosed in brackets
+        if self.peek() == Some(&'[') {
+            self.next_char();
+            while let Some(c) = self.next_char() {
+                if c == ']' {
+                    return match self.peek() {
+                        None | Some(':') | Some(',') if !substr.is_empty() => Ok(substr),
+                        _ => bail!(ErrorKind::InvalidArgument(
+                            "Invalid address string".to_string()
+                        )),
+                    };
+                }
+                substr.push(c);
+            }
+            bail!(ErrorKind::InvalidArgument(
+                "Invalid address string".to_string()
+            ));
+        }
         loop {
             match self.peek() {
                 Some(&c) if c != ':' && c != ',' => {
@@ -125,6 +145,15 @@ mod tests {
         assert!(Parser::new("", 3000).read_addr_part().is_err());
         assert!(Parser::new(",", 3000).read_addr_part().is_err());
         assert!(Parser::new(":", 3000).read_addr_part().is_err());
+        assert_eq!(
+            "2001:db8::1".to_string(),
+            Parser::new("[2001:db8::1]:3000", 3000)
+                .read_addr_part()
+                .unwrap()
+        );
+        assert!(Parser::new("[2001:db8::1", 3000).read_addr_part().is_err());
+        assert!(Parser::new("[]", 3000).read_addr_part().is_err());
+        assert!(Parser::new("[::1]x", 3000).read_addr_part().is_err());
     }
 
     #[test]
@@ -165,6 +194,10 @@ mod tests {
             vec![Host::new("foo", 1234)],
             Parser::new("foo:bar:1234", 3000).read_hosts().unwrap()
         );
+        assert_eq!(
+            Some("bar".to_string()),
+            Parser::new("foo:bar:1234", 3000).read_hosts().unwrap()[0].tls_name
+        );
         assert_eq!(
             vec![Host::new("foo", 1234), Host::new("bar", 1234)],
             Parser::new("foo:1234,bar:1234", 3000).read_hosts().unwrap()
@@ -176,5 +209,15 @@ mod tests {
         assert!(Parser::new("foo:", 3000).read_hosts().is_err());
         assert!(Parser::new("foo:bar:bar", 3000).read_hosts().is_err());
         assert!(Parser::new("foo:bar:1234:1234", 3000).read_hosts().is_err());
+        assert_eq!(
+            vec![Host::new("::1", 3000)],
+            Parser::new("[::1]", 3000).read_hosts().unwrap()
+        );
+        assert_eq!(
+            vec![Host::with_tls_name("2001:db8::1", "bar", 4333)],
+            Parser::new("[2001:db8::1]:bar:4333", 3000)
+                .read_hosts()
+                .unwrap()
+        );
     }
 }
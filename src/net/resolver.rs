@@ -0,0 +1,37 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use std::fmt;
+use std::net::SocketAddr;
+
+use crate::errors::Result;
+use crate::net::Host;
+
+/// Resolves the host names of seeds and peers to socket addresses, e.g. to look them up with a
+/// custom DNS client instead of the blocking system resolver. Register a resolver with
+/// `ClientPolicy::resolver`.
+///
+/// The resolver is called from the cluster tend thread while the client discovers nodes; all
+/// returned addresses are tried in order until one of them accepts a connection.
+pub trait Resolver: Send + Sync {
+    /// Returns the addresses of the given host. An empty list is reported as a connection error.
+    fn resolve(&self, host: &Host) -> Result<Vec<SocketAddr>>;
+}
+
+impl fmt::Debug for dyn Resolver {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Resolver")
+    }
+}
@@ -0,0 +1,279 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use std::io::{self, Read, Write};
+use std::net::{Shutdown, TcpStream};
+use std::time::Duration;
+
+#[cfg(feature = "rt-async-std")]
+use async_std::io::{ReadExt as _, WriteExt as _};
+#[cfg(feature = "native-tls")]
+use native_tls::{Certificate, Identity, TlsConnector, TlsStream};
+use socket2::{SockRef, TcpKeepalive};
+#[cfg(feature = "rt-tokio")]
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use crate::errors::{ErrorKind, Result};
+use crate::net::Host;
+use crate::policy::{ClientPolicy, TlsPolicy};
+use crate::runtime::Runtime;
+
+// Transport of a connection: either a plain TCP stream, or a TLS session on top of it. Blocking
+// streams complete their reads and writes before returning.
+#[derive(Debug)]
+pub enum Stream {
+    Tcp(TcpStream),
+    #[cfg(feature = "native-tls")]
+    Tls(TlsStream<TcpStream>),
+    // Non-blocking TCP stream; the timeout bounds each read and write.
+    #[cfg(feature = "rt-tokio")]
+    Tokio(tokio::net::TcpStream, Option<Duration>),
+    #[cfg(feature = "rt-async-std")]
+    AsyncStd(async_std::net::TcpStream, Option<Duration>),
+}
+
+impl Stream {
+    pub async fn connect(host: &Host, policy: &ClientPolicy, runtime: Runtime) -> Result<Self> {
+        match runtime {
+            Runtime::Blocking => Stream::connect_blocking(host, policy),
+            #[cfg(feature = "rt-tokio")]
+            Runtime::Tokio => Stream::connect_tokio(host, policy).await,
+            #[cfg(feature = "rt-async-std")]
+            Runtime::AsyncStd => Stream::connect_async_std(host, policy).await,
+        }
+    }
+
+    fn connect_blocking(host: &Host, policy: &ClientPolicy) -> Result<Self> {
+        let stream = TcpStream::connect(host)?;
+        Stream::set_tcp_options(SockRef::from(&stream), policy)?;
+        match policy.tls_policy {
+            Some(ref tls_policy) => {
+                // bound the handshake by the timeout for opening a connection
+                stream.set_read_timeout(policy.timeout)?;
+                stream.set_write_timeout(policy.timeout)?;
+                Stream::handshake(host, tls_policy, stream)
+            }
+            None => Ok(Stream::Tcp(stream)),
+        }
+    }
+
+    #[cfg(feature = "rt-tokio")]
+    async fn connect_tokio(host: &Host, policy: &ClientPolicy) -> Result<Self> {
+        Stream::check_non_blocking(policy)?;
+        let connect = tokio::net::TcpStream::connect((host.name.as_str(), host.port));
+        let stream = Runtime::Tokio.timeout(policy.timeout, connect).await?;
+        Stream::set_tcp_options(SockRef::from(&stream), policy)?;
+        Ok(Stream::Tokio(stream, policy.timeout))
+    }
+
+    #[cfg(feature = "rt-async-std")]
+    async fn connect_async_std(host: &Host, policy: &ClientPolicy) -> Result<Self> {
+        Stream::check_non_blocking(policy)?;
+        let connect = async_std::net::TcpStream::connect((host.name.as_str(), host.port));
+        let stream = Runtime::AsyncStd.timeout(policy.timeout, connect).await?;
+        Stream::set_tcp_options(SockRef::from(&stream), policy)?;
+        Ok(Stream::AsyncStd(stream, policy.timeout))
+    }
+
+    // Applies the TCP options of the client policy to a new connection.
+    fn set_tcp_options(socket: SockRef, policy: &ClientPolicy) -> Result<()> {
+        socket.set_tcp_nodelay(policy.tcp_nodelay)?;
+        if let Some(time) = policy.tcp_keepalive {
+            #[allow(unused_mut)]
+            let mut keepalive = TcpKeepalive::new().with_time(time);
+            #[cfg(any(
+                target_os = "linux",
+                target_os = "android",
+                target_os = "macos",
+                target_os = "ios",
+                target_os = "freebsd",
+                target_os = "netbsd",
+                target_os = "windows",
+            ))]
+            {
+                if let Some(interval) = policy.tcp_keepalive_interval {
+                    keepalive = keepalive.with_interval(interval);
+                }
+            }
+            socket.set_tcp_keepalive(&keepalive)?;
+        }
+        if let Some(size) = policy.socket_send_buffer_size {
+            socket.set_send_buffer_size(size)?;
+        }
+        if let Some(size) = policy.socket_recv_buffer_size {
+            socket.set_recv_buffer_size(size)?;
+        }
+        Ok(())
+    }
+
+    #[cfg(any(feature = "rt-tokio", feature = "rt-async-std"))]
+    fn check_non_blocking(policy: &ClientPolicy) -> Result<()> {
+        if policy.tls_policy.is_some() {
+            bail!(ErrorKind::InvalidArgument(
+                "TLS connections are not supported with non-blocking sockets".to_string()
+            ));
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "native-tls")]
+    fn handshake(host: &Host, tls_policy: &TlsPolicy, stream: TcpStream) -> Result<Self> {
+        let connector = Stream::connector(tls_policy)?;
+        let tls_name = host.tls_name.as_ref().unwrap_or(&host.name);
+        match connector.connect(tls_name, stream) {
+            Ok(stream) => Ok(Stream::Tls(stream)),
+            Err(err) => bail!(ErrorKind::Connection(format!(
+                "TLS handshake with {} failed: {}",
+                host, err
+            ))),
+        }
+    }
+
+    #[cfg(not(feature = "native-tls"))]
+    fn handshake(_host: &Host, _tls_policy: &TlsPolicy, _stream: TcpStream) -> Result<Self> {
+        bail!(ErrorKind::InvalidArgument(
+            "TLS connections require the `native-tls` feature".to_string()
+        ))
+    }
+
+    #[cfg(feature = "native-tls")]
+    fn connector(tls_policy: &TlsPolicy) -> Result<TlsConnector> {
+        let tls_error = |err: native_tls::Error| ErrorKind::InvalidArgument(err.to_string());
+
+        let mut builder = TlsConnector::builder();
+        for certificate in &tls_policy.root_certificates {
+            builder.add_root_certificate(Certificate::from_pem(certificate).map_err(tls_error)?);
+        }
+        match (&tls_policy.client_certificate, &tls_policy.client_key) {
+            (Some(certificate), Some(key)) => {
+                builder.identity(Identity::from_pkcs8(certificate, key).map_err(tls_error)?);
+            }
+            (None, None) => (),
+            _ => bail!(ErrorKind::InvalidArgument(
+                "The client certificate and key must be set together".to_string()
+            )),
+        }
+        builder.danger_accept_invalid_certs(tls_policy.allow_invalid_certificates);
+        Ok(builder.build().map_err(tls_error)?)
+    }
+
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) -> io::Result<()> {
+        let stream = match *self {
+            Stream::Tcp(ref stream) => stream,
+            #[cfg(feature = "native-tls")]
+            Stream::Tls(ref stream) => stream.get_ref(),
+            #[cfg(feature = "rt-tokio")]
+            Stream::Tokio(_, ref mut stream_timeout) => {
+                *stream_timeout = timeout;
+                return Ok(());
+            }
+            #[cfg(feature = "rt-async-std")]
+            Stream::AsyncStd(_, ref mut stream_timeout) => {
+                *stream_timeout = timeout;
+                return Ok(());
+            }
+        };
+        stream.set_read_timeout(timeout)?;
+        stream.set_write_timeout(timeout)
+    }
+
+    pub async fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        match *self {
+            Stream::Tcp(ref mut stream) => stream.read_exact(buf),
+            #[cfg(feature = "native-tls")]
+            Stream::Tls(ref mut stream) => stream.read_exact(buf),
+            #[cfg(feature = "rt-tokio")]
+            Stream::Tokio(ref mut stream, timeout) => {
+                Runtime::Tokio
+                    .timeout(timeout, stream.read_exact(buf))
+                    .await?;
+                Ok(())
+            }
+            #[cfg(feature = "rt-async-std")]
+            Stream::AsyncStd(ref mut stream, timeout) => {
+                Runtime::AsyncStd
+                    .timeout(timeout, stream.read_exact(buf))
+                    .await
+            }
+        }
+    }
+
+    pub async fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        match *self {
+            Stream::Tcp(ref mut stream) => stream.write_all(buf),
+            #[cfg(feature = "native-tls")]
+            Stream::Tls(ref mut stream) => stream.write_all(buf),
+            #[cfg(feature = "rt-tokio")]
+            Stream::Tokio(ref mut stream, timeout) => {
+                Runtime::Tokio.timeout(timeout, stream.write_all(buf)).await
+            }
+            #[cfg(feature = "rt-async-std")]
+            Stream::AsyncStd(ref mut stream, timeout) => {
+                Runtime::AsyncStd
+                    .timeout(timeout, stream.write_all(buf))
+                    .await
+            }
+        }
+    }
+
+    // Tokio streams are shut down when they are dropped.
+    pub fn shutdown(&mut self, how: Shutdown) -> io::Result<()> {
+        match *self {
+            Stream::Tcp(ref stream) => stream.shutdown(how),
+            #[cfg(feature = "native-tls")]
+            Stream::Tls(ref mut stream) => {
+                let _ = stream.shutdown();
+                stream.get_ref().shutdown(how)
+            }
+            #[cfg(feature = "rt-tokio")]
+            Stream::Tokio(..) => Ok(()),
+            #[cfg(feature = "rt-async-std")]
+            Stream::AsyncStd(ref stream, _) => stream.shutdown(how),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Stream;
+    use crate::net::Host;
+    use crate::policy::ClientPolicy;
+    use crate::runtime::{self, Runtime};
+    use socket2::SockRef;
+    use std::net::TcpListener;
+    use std::time::Duration;
+
+    #[test]
+    fn tcp_options() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let host = Host::new("127.0.0.1", listener.local_addr().unwrap().port());
+        let mut policy = ClientPolicy::default();
+        policy.tcp_keepalive = Some(Duration::from_secs(60));
+        policy.tcp_keepalive_interval = Some(Duration::from_secs(10));
+        policy.socket_recv_buffer_size = Some(1 << 16);
+
+        let stream = runtime::block_on(Stream::connect(&host, &policy, Runtime::Blocking));
+        let stream = match stream.unwrap() {
+            Stream::Tcp(stream) => stream,
+            #[allow(unreachable_patterns)]
+            _ => panic!("expected a blocking TCP stream"),
+        };
+        let socket = SockRef::from(&stream);
+        assert!(socket.tcp_nodelay().unwrap());
+        assert!(socket.keepalive().unwrap());
+        // the system may round up the buffer size
+        assert!(socket.recv_buffer_size().unwrap() >= 1 << 16);
+    }
+}
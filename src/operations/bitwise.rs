@@ -57,9 +57,9 @@ pub enum CdtBitwiseOpType {
 }
 
 /// `CdtBitwiseResizeFlags` specifies the bitwise operation flags for resize.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy)]
 pub enum BitwiseResizeFlags {
-    /// Default specifies the defalt flag.
+    /// Default specifies the default flag.
     Default = 0,
     /// FromFront Adds/removes bytes from the beginning instead of the end.
     FromFront = 1,
@@ -70,7 +70,7 @@ pub enum BitwiseResizeFlags {
 }
 
 /// `CdtBitwiseWriteFlags` specify bitwise operation policy write flags.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy)]
 pub enum BitwiseWriteFlags {
     /// Default allows create or update.
     Default = 0,
@@ -90,7 +90,7 @@ pub enum BitwiseWriteFlags {
 }
 
 /// `CdtBitwiseOverflowActions` specifies the action to take when bitwise add/subtract results in overflow/underflow.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy)]
 pub enum BitwiseOverflowActions {
     /// Fail specifies to fail operation with error.
     Fail = 0,
@@ -31,8 +31,9 @@ pub enum CdtArgument<'a> {
     Map(&'a HashMap<Value, Value>),
 }
 
-pub type OperationEncoder =
-    Box<dyn Fn(&mut Option<&mut Buffer>, &CdtOperation, &[CdtContext]) -> Result<usize>>;
+pub type OperationEncoder = Box<
+    dyn Fn(&mut Option<&mut Buffer>, &CdtOperation, &[CdtContext]) -> Result<usize> + Send + Sync,
+>;
 
 #[doc(hidden)]
 pub struct CdtOperation<'a> {
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 //! Operation Context for nested Operations
+use crate::errors::{ErrorKind, Result};
 use crate::operations::lists::{list_order_flag, ListOrderType};
 use crate::operations::MapOrder;
 use crate::Value;
@@ -36,7 +37,7 @@ pub enum CtxType {
 /// for the current level.
 /// An array of CTX identifies location of the list/map on multiple
 /// levels on nesting.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct CdtContext {
     /// Context Type
     pub id: u8,
@@ -64,7 +65,9 @@ pub const fn ctx_list_index(index: i64) -> CdtContext {
     }
 }
 
-/// list with given type at index offset, given an order and pad.
+/// Defines Lookup list by index offset, creating a list with the given order at that index if it
+/// does not exist yet. If pad is true, the context is allowed to be beyond the list boundaries and
+/// nil entries are inserted to fill the gap. Only applies to write operations.
 pub const fn ctx_list_index_create(index: i64, order: ListOrderType, pad: bool) -> CdtContext {
     CdtContext {
         id: CtxType::ListIndex as u8,
@@ -130,7 +133,8 @@ pub const fn ctx_map_key(key: Value) -> CdtContext {
     }
 }
 
-/// Create map with given type at map key.
+/// Defines Lookup map by key, creating a map with the given order at that key if it does not exist
+/// yet. Only applies to write operations.
 pub const fn ctx_map_key_create(key: Value, order: MapOrder) -> CdtContext {
     CdtContext {
         id: CtxType::MapKey as u8,
@@ -147,3 +151,91 @@ pub const fn ctx_map_value(key: Value) -> CdtContext {
         value: key,
     }
 }
+
+/// Parses a path string into a list of contexts. Path segments are separated by `.` and
+/// identify map keys, while `[n]` identifies a list index, e.g. `"profile.addresses[0]"`.
+/// Negative list indexes count backwards from the end of the list. To operate on a map
+/// entry, leave its key out of the path and pass it to the map operation instead.
+///
+/// # Examples
+///
+/// ```
+/// use aerospike::Value;
+/// use aerospike::operations::cdt_context::{ctx_from_path, ctx_list_index, ctx_map_key};
+///
+/// let ctx = ctx_from_path("profile.addresses[0]").unwrap();
+/// assert_eq!(ctx, vec![
+///     ctx_map_key(Value::from("profile")),
+///     ctx_map_key(Value::from("addresses")),
+///     ctx_list_index(0),
+/// ]);
+/// ```
+pub fn ctx_from_path(path: &str) -> Result<Vec<CdtContext>> {
+    let mut ctx = vec![];
+    for (i, segment) in path.split('.').enumerate() {
+        let (key, mut indexes) = match segment.find('[') {
+            Some(pos) => segment.split_at(pos),
+            None => (segment, ""),
+        };
+        if !key.is_empty() {
+            ctx.push(ctx_map_key(Value::from(key)));
+        } else if indexes.is_empty() || i > 0 {
+            bail!(ErrorKind::InvalidArgument(format!(
+                "Invalid context path '{}': empty map key",
+                path
+            )));
+        }
+        while !indexes.is_empty() {
+            let end = match (indexes.starts_with('['), indexes.find(']')) {
+                (true, Some(end)) => end,
+                _ => bail!(ErrorKind::InvalidArgument(format!(
+                    "Invalid context path '{}': unterminated list index",
+                    path
+                ))),
+            };
+            let index = indexes[1..end].trim().parse::<i64>().map_err(|_| {
+                ErrorKind::InvalidArgument(format!(
+                    "Invalid context path '{}': invalid list index '{}'",
+                    path,
+                    &indexes[1..end]
+                ))
+            })?;
+            ctx.push(ctx_list_index(index));
+            indexes = &indexes[end + 1..];
+        }
+    }
+    Ok(ctx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ctx_from_path, ctx_list_index, ctx_map_key};
+    use crate::Value;
+
+    #[test]
+    fn parse_path() {
+        assert_eq!(
+            ctx_from_path("a[1][-2].b").unwrap(),
+            vec![
+                ctx_map_key(Value::from("a")),
+                ctx_list_index(1),
+                ctx_list_index(-2),
+                ctx_map_key(Value::from("b")),
+            ]
+        );
+        assert_eq!(
+            ctx_from_path("[3].zip").unwrap(),
+            vec![ctx_list_index(3), ctx_map_key(Value::from("zip"))]
+        );
+    }
+
+    #[test]
+    fn parse_invalid_path() {
+        assert!(ctx_from_path("").is_err());
+        assert!(ctx_from_path("a..b").is_err());
+        assert!(ctx_from_path("a.[0]").is_err());
+        assert!(ctx_from_path("a[0").is_err());
+        assert!(ctx_from_path("a[x]").is_err());
+        assert!(ctx_from_path("a[0]b").is_err());
+    }
+}
@@ -24,6 +24,7 @@ use crate::operations::{Operation, OperationBin, OperationData, OperationType};
 use crate::ParticleType;
 
 /// Expression write Flags
+#[derive(Debug, Clone, Copy)]
 pub enum ExpWriteFlags {
     /// Default. Allow create or update.
     Default = 0,
@@ -45,7 +46,8 @@ pub enum ExpWriteFlags {
 }
 
 #[doc(hidden)]
-pub type ExpressionEncoder = Box<dyn Fn(&mut Option<&mut Buffer>, &ExpOperation) -> Result<usize>>;
+pub type ExpressionEncoder =
+    Box<dyn Fn(&mut Option<&mut Buffer>, &ExpOperation) -> Result<usize> + Send + Sync>;
 
 #[doc(hidden)]
 pub struct ExpOperation<'a> {
@@ -72,6 +74,7 @@ impl<'a> ExpOperation<'a> {
 }
 
 /// Expression read Flags
+#[derive(Debug, Clone, Copy)]
 pub enum ExpReadFlags {
     /// Default
     Default = 0,
@@ -80,6 +83,23 @@ pub enum ExpReadFlags {
 }
 
 /// Create operation that performs a expression that writes to record bin.
+///
+/// # Examples
+///
+/// Store the sum of two bins in a third bin, computed on the server.
+///
+/// ```rust
+/// # use aerospike::*;
+/// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap_or("localhost".into());
+/// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+/// # let key = as_key!("test", "test", "mykey");
+/// use aerospike::expressions::{int_bin, num_add};
+/// use aerospike::operations::exp::{write_exp, ExpWriteFlags};
+///
+/// let sum = num_add(vec![int_bin("a"), int_bin("b")]);
+/// let ops = vec![write_exp("sum", &sum, ExpWriteFlags::Default)];
+/// client.operate(&WritePolicy::default(), &key, &ops).unwrap();
+/// ```
 pub fn write_exp<'a>(
     bin: &'a str,
     exp: &'a FilterExpression,
@@ -98,7 +118,24 @@ pub fn write_exp<'a>(
     }
 }
 
-/// Create operation that performs a read expression.
+/// Create operation that performs a read expression. The result is returned in the record under
+/// the given name; no bin is written.
+///
+/// # Examples
+///
+/// ```rust
+/// # use aerospike::*;
+/// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap_or("localhost".into());
+/// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+/// # let key = as_key!("test", "test", "mykey");
+/// use aerospike::expressions::{int_bin, int_val, num_mul};
+/// use aerospike::operations::exp::{read_exp, ExpReadFlags};
+///
+/// let double = num_mul(vec![int_bin("a"), int_val(2)]);
+/// let ops = vec![read_exp("double", &double, ExpReadFlags::EvalNoFail)];
+/// let rec = client.operate(&WritePolicy::default(), &key, &ops).unwrap();
+/// println!("a * 2 = {:?}", rec.bins.get("double"));
+/// ```
 pub fn read_exp<'a>(
     name: &'a str,
     exp: &'a FilterExpression,
@@ -22,9 +22,10 @@ use crate::operations::cdt_context::DEFAULT_CTX;
 use crate::operations::{Operation, OperationBin, OperationData, OperationType};
 use crate::Value;
 
-/// `HLLWriteFlags` determines write flags for HLL
-#[derive(Debug, Clone, Copy)]
-pub enum HLLWriteFlags {
+/// `HLLWriteFlag` determines write flags for HLL. Flags are combined into `HLLWriteFlags` using
+/// the `|` operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HLLWriteFlag {
     /// Default.  Allow create or update.
     Default = 0,
     /// If the bin already exists, the operation will be denied.
@@ -41,15 +42,48 @@ pub enum HLLWriteFlags {
     AllowFold = 8,
 }
 
+write_flags! {
+    /// Set of `HLLWriteFlag` values, combined using the `|` operator.
+    /// ```
+    /// use aerospike::operations::hll::{HLLWriteFlag, HLLWriteFlags};
+    /// let flags = HLLWriteFlags::CreateOnly | HLLWriteFlags::NoFail;
+    /// assert_eq!(flags, HLLWriteFlag::CreateOnly | HLLWriteFlag::NoFail);
+    /// assert_eq!(flags.bits(), 5);
+    /// ```
+    HLLWriteFlags(HLLWriteFlag) {
+        /// Default.  Allow create or update.
+        Default,
+        /// If the bin already exists, the operation will be denied.
+        /// If the bin does not exist, a new bin will be created.
+        CreateOnly,
+        /// If the bin already exists, the bin will be overwritten.
+        /// If the bin does not exist, the operation will be denied.
+        UpdateOnly,
+        /// Do not raise error if operation is denied.
+        NoFail,
+        /// Allow the resulting set to be the minimum of provided index bits.
+        /// Also, allow the usage of less precise HLL algorithms when minHash bits
+        /// of all participating sets do not match.
+        AllowFold,
+    }
+}
+
 /// `HLLPolicy` operation policy.
 #[derive(Debug, Clone, Copy)]
 pub struct HLLPolicy {
-    /// CdtListWriteFlags
+    /// HLL write flags
     pub flags: HLLWriteFlags,
 }
 
 impl HLLPolicy {
     /// Use specified `HLLWriteFlags` when performing `HLL` operations
+    ///
+    /// ```
+    /// use aerospike::operations::hll::{HLLPolicy, HLLWriteFlags};
+    ///
+    /// // Initialize the HLL bin only if it does not exist yet, without failing otherwise.
+    /// let policy = HLLPolicy::new(HLLWriteFlags::CreateOnly | HLLWriteFlags::NoFail);
+    /// ```
     pub const fn new(write_flags: HLLWriteFlags) -> Self {
         HLLPolicy { flags: write_flags }
     }
@@ -76,6 +110,7 @@ pub enum HLLOpType {
     IntersectCount = 53,
     Similarity = 54,
     Describe = 55,
+    MayContain = 56,
 }
 
 /// Create HLL init operation.
@@ -100,7 +135,7 @@ pub fn init_with_min_hash<'a>(
         args: vec![
             CdtArgument::Int(index_bit_count),
             CdtArgument::Int(min_hash_bit_count),
-            CdtArgument::Byte(policy.flags as u8),
+            CdtArgument::Byte(policy.flags.bits()),
         ],
     };
     Operation {
@@ -147,7 +182,7 @@ pub fn add_with_index_and_min_hash<'a>(
             CdtArgument::List(list),
             CdtArgument::Int(index_bit_count),
             CdtArgument::Int(min_hash_bit_count),
-            CdtArgument::Byte(policy.flags as u8),
+            CdtArgument::Byte(policy.flags.bits()),
         ],
     };
     Operation {
@@ -167,7 +202,7 @@ pub fn set_union<'a>(policy: &HLLPolicy, bin: &'a str, list: &'a [Value]) -> Ope
         encoder: Box::new(pack_hll_op),
         args: vec![
             CdtArgument::List(list),
-            CdtArgument::Byte(policy.flags as u8),
+            CdtArgument::Byte(policy.flags.bits()),
         ],
     };
     Operation {
@@ -311,3 +346,20 @@ pub fn describe(bin: &str) -> Operation {
         data: OperationData::HLLOp(cdt_op),
     }
 }
+
+/// Create HLL may contain operation.
+/// Server returns 1 if all values in the list may be contained in the HLL bin, or 0 if any of
+/// them is definitely not contained.
+pub fn may_contain<'a>(bin: &'a str, list: &'a [Value]) -> Operation<'a> {
+    let cdt_op = CdtOperation {
+        op: HLLOpType::MayContain as u8,
+        encoder: Box::new(pack_hll_op),
+        args: vec![CdtArgument::List(list)],
+    };
+    Operation {
+        op: OperationType::HllRead,
+        ctx: DEFAULT_CTX,
+        bin: OperationBin::Name(bin),
+        data: OperationData::HLLOp(cdt_op),
+    }
+}
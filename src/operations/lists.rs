@@ -31,7 +31,7 @@
 //! If an index is out of bounds, a parameter error will be returned. If a range is partially out of
 //! bounds, the valid part of the range will be returned.
 
-use crate::msgpack::encoder::pack_cdt_op;
+use crate::msgpack::encoder::{pack_cdt_create_op, pack_cdt_op};
 use crate::operations::cdt::{CdtArgument, CdtOperation};
 use crate::operations::cdt_context::{CdtContext, DEFAULT_CTX};
 use crate::operations::{Operation, OperationBin, OperationData, OperationType};
@@ -130,9 +130,10 @@ pub enum ListSortFlags {
     DropDuplicates = 2,
 }
 
-/// `CdtListWriteFlags` determines write flags for CDT lists
-#[derive(Debug, Clone, Copy)]
-pub enum ListWriteFlags {
+/// `CdtListWriteFlags` determines write flags for CDT lists. Flags are combined into
+/// `ListWriteFlags` using the `|` operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListWriteFlag {
     /// Default is the default behavior. It means:  Allow duplicate values and insertions at any index.
     Default = 0,
     /// AddUnique means: Only add unique values.
@@ -147,6 +148,30 @@ pub enum ListWriteFlags {
     Partial = 8,
 }
 
+write_flags! {
+    /// Set of `ListWriteFlag` values, combined using the `|` operator.
+    /// ```
+    /// use aerospike::operations::lists::{ListWriteFlag, ListWriteFlags};
+    /// let flags = ListWriteFlags::AddUnique | ListWriteFlags::NoFail;
+    /// assert_eq!(flags, ListWriteFlag::AddUnique | ListWriteFlag::NoFail);
+    /// assert_eq!(flags.bits(), 5);
+    /// ```
+    ListWriteFlags(ListWriteFlag) {
+        /// Default is the default behavior. It means:  Allow duplicate values and insertions at any index.
+        Default,
+        /// `AddUnique` means: Only add unique values.
+        AddUnique,
+        /// `InsertBounded` means: Enforce list boundaries when inserting.  Do not allow values to be inserted
+        /// at index outside current list boundaries.
+        InsertBounded,
+        /// `NoFail` means: do not raise error if a list item fails due to write flag constraints.
+        NoFail,
+        /// `Partial` means: allow other valid list items to be committed if a list item fails due to
+        /// write flag constraints.
+        Partial,
+    }
+}
+
 /// `ListPolicy` directives when creating a list and writing list items.
 #[derive(Debug, Clone, Copy)]
 pub struct ListPolicy {
@@ -158,7 +183,15 @@ pub struct ListPolicy {
 
 impl ListPolicy {
     /// Create unique key list with specified order when list does not exist.
-    /// Use specified write mode when writing list items.
+    /// Use specified write flags when writing list items.
+    ///
+    /// ```
+    /// use aerospike::operations::lists::{ListOrderType, ListPolicy, ListWriteFlags};
+    ///
+    /// // Append only unique values and skip duplicates without failing the whole operation.
+    /// let flags = ListWriteFlags::AddUnique | ListWriteFlags::NoFail | ListWriteFlags::Partial;
+    /// let policy = ListPolicy::new(ListOrderType::Unordered, flags);
+    /// ```
     pub const fn new(order: ListOrderType, write_flags: ListWriteFlags) -> Self {
         ListPolicy {
             attributes: order,
@@ -186,13 +219,13 @@ pub const fn list_order_flag(order: ListOrderType, pad: bool) -> u8 {
 }
 
 /// Creates list create operation.
-/// Server creates list at given context level. The context is allowed to be beyond list
-/// boundaries only if pad is set to true.  In that case, nil list entries will be inserted to
-/// satisfy the context position.
+/// Server creates list at given context level, or sets the order of the list bin if no context
+/// is given. The context is allowed to be beyond list boundaries only if pad is set to true.  In
+/// that case, nil list entries will be inserted to satisfy the context position.
 pub fn create(bin: &str, list_order: ListOrderType, pad: bool) -> Operation {
     let cdt_op = CdtOperation {
         op: CdtListOpType::SetType as u8,
-        encoder: Box::new(pack_cdt_op),
+        encoder: Box::new(pack_cdt_create_op),
         args: vec![
             CdtArgument::Byte(list_order_flag(list_order, pad)),
             CdtArgument::Byte(list_order as u8),
@@ -234,7 +267,7 @@ pub fn append<'a>(policy: &ListPolicy, bin: &'a str, value: &'a Value) -> Operat
         args: vec![
             CdtArgument::Value(value),
             CdtArgument::Byte(policy.attributes as u8),
-            CdtArgument::Byte(policy.flags as u8),
+            CdtArgument::Byte(policy.flags.bits()),
         ],
     };
     Operation {
@@ -256,7 +289,7 @@ pub fn append_items<'a>(policy: &ListPolicy, bin: &'a str, values: &'a [Value])
         args: vec![
             CdtArgument::List(values),
             CdtArgument::Byte(policy.attributes as u8),
-            CdtArgument::Byte(policy.flags as u8),
+            CdtArgument::Byte(policy.flags.bits()),
         ],
     };
     Operation {
@@ -281,7 +314,7 @@ pub fn insert<'a>(
         args: vec![
             CdtArgument::Int(index),
             CdtArgument::Value(value),
-            CdtArgument::Byte(policy.flags as u8),
+            CdtArgument::Byte(policy.flags.bits()),
         ],
     };
     Operation {
@@ -308,7 +341,7 @@ pub fn insert_items<'a>(
         args: vec![
             CdtArgument::Int(index),
             CdtArgument::List(values),
-            CdtArgument::Byte(policy.flags as u8),
+            CdtArgument::Byte(policy.flags.bits()),
         ],
     };
     Operation {
@@ -426,7 +459,7 @@ pub fn remove_by_value<'a>(
         op: CdtListOpType::RemoveByValue as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Value(value),
         ],
     };
@@ -449,7 +482,7 @@ pub fn remove_by_value_list<'a>(
         op: CdtListOpType::RemoveByValueList as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::List(values),
         ],
     };
@@ -476,7 +509,7 @@ pub fn remove_by_value_range<'a>(
         op: CdtListOpType::RemoveByValueInterval as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Value(begin),
             CdtArgument::Value(end),
         ],
@@ -513,7 +546,7 @@ pub fn remove_by_value_relative_rank_range<'a>(
         op: CdtListOpType::RemoveByValueRelRankRange as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Value(value),
             CdtArgument::Int(rank),
         ],
@@ -551,7 +584,7 @@ pub fn remove_by_value_relative_rank_range_count<'a>(
         op: CdtListOpType::RemoveByValueRelRankRange as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Value(value),
             CdtArgument::Int(rank),
             CdtArgument::Int(count),
@@ -572,7 +605,7 @@ pub fn remove_by_index(bin: &str, index: i64, return_type: ListReturnType) -> Op
         op: CdtListOpType::RemoveByIndex as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Int(index),
         ],
     };
@@ -592,7 +625,7 @@ pub fn remove_by_index_range(bin: &str, index: i64, return_type: ListReturnType)
         op: CdtListOpType::RemoveByIndexRange as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Int(index),
         ],
     };
@@ -616,7 +649,7 @@ pub fn remove_by_index_range_count(
         op: CdtListOpType::RemoveByIndexRange as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Int(index),
             CdtArgument::Int(count),
         ],
@@ -635,7 +668,7 @@ pub fn remove_by_rank(bin: &str, rank: i64, return_type: ListReturnType) -> Oper
     let cdt_op = CdtOperation {
         op: CdtListOpType::RemoveByRank as u8,
         encoder: Box::new(pack_cdt_op),
-        args: vec![CdtArgument::Byte(return_type as u8), CdtArgument::Int(rank)],
+        args: vec![CdtArgument::Int(return_type as i64), CdtArgument::Int(rank)],
     };
     Operation {
         op: OperationType::CdtWrite,
@@ -652,7 +685,7 @@ pub fn remove_by_rank_range(bin: &str, rank: i64, return_type: ListReturnType) -
     let cdt_op = CdtOperation {
         op: CdtListOpType::RemoveByRankRange as u8,
         encoder: Box::new(pack_cdt_op),
-        args: vec![CdtArgument::Byte(return_type as u8), CdtArgument::Int(rank)],
+        args: vec![CdtArgument::Int(return_type as i64), CdtArgument::Int(rank)],
     };
     Operation {
         op: OperationType::CdtWrite,
@@ -674,7 +707,7 @@ pub fn remove_by_rank_range_count(
         op: CdtListOpType::RemoveByRankRange as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Int(rank),
             CdtArgument::Int(count),
         ],
@@ -747,7 +780,7 @@ pub fn increment<'a>(policy: &ListPolicy, bin: &'a str, index: i64, value: i64)
         args: vec![
             CdtArgument::Int(index),
             CdtArgument::Int(value),
-            CdtArgument::Byte(policy.flags as u8),
+            CdtArgument::Byte(policy.flags.bits()),
         ],
     };
     Operation {
@@ -831,7 +864,7 @@ pub fn get_by_value<'a>(
         op: CdtListOpType::GetByValue as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Value(value),
         ],
     };
@@ -855,7 +888,7 @@ pub fn get_by_value_list<'a>(
         op: CdtListOpType::GetByValueList as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::List(values),
         ],
     };
@@ -882,7 +915,7 @@ pub fn get_by_value_range<'a>(
         op: CdtListOpType::GetByValueInterval as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Value(begin),
             CdtArgument::Value(end),
         ],
@@ -902,7 +935,7 @@ pub fn get_by_index(bin: &str, index: i64, return_type: ListReturnType) -> Opera
         op: CdtListOpType::GetByIndex as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Int(index),
         ],
     };
@@ -923,7 +956,7 @@ pub fn get_by_index_range(bin: &str, index: i64, return_type: ListReturnType) ->
         op: CdtListOpType::GetByIndexRange as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Int(index),
         ],
     };
@@ -949,7 +982,7 @@ pub fn get_by_index_range_count(
         op: CdtListOpType::GetByIndexRange as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Int(index),
             CdtArgument::Int(count),
         ],
@@ -969,7 +1002,7 @@ pub fn get_by_rank(bin: &str, rank: i64, return_type: ListReturnType) -> Operati
     let cdt_op = CdtOperation {
         op: CdtListOpType::GetByRank as u8,
         encoder: Box::new(pack_cdt_op),
-        args: vec![CdtArgument::Byte(return_type as u8), CdtArgument::Int(rank)],
+        args: vec![CdtArgument::Int(return_type as i64), CdtArgument::Int(rank)],
     };
     Operation {
         op: OperationType::CdtRead,
@@ -986,7 +1019,7 @@ pub fn get_by_rank_range(bin: &str, rank: i64, return_type: ListReturnType) -> O
     let cdt_op = CdtOperation {
         op: CdtListOpType::GetByRankRange as u8,
         encoder: Box::new(pack_cdt_op),
-        args: vec![CdtArgument::Byte(return_type as u8), CdtArgument::Int(rank)],
+        args: vec![CdtArgument::Int(return_type as i64), CdtArgument::Int(rank)],
     };
     Operation {
         op: OperationType::CdtRead,
@@ -1008,7 +1041,7 @@ pub fn get_by_rank_range_count(
         op: CdtListOpType::GetByRankRange as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Int(rank),
             CdtArgument::Int(count),
         ],
@@ -1045,7 +1078,7 @@ pub fn get_by_value_relative_rank_range<'a>(
         op: CdtListOpType::GetByValueRelRankRange as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Value(value),
             CdtArgument::Int(rank),
         ],
@@ -1083,7 +1116,7 @@ pub fn get_by_value_relative_rank_range_count<'a>(
         op: CdtListOpType::GetByValueRelRankRange as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Value(value),
             CdtArgument::Int(rank),
             CdtArgument::Int(count),
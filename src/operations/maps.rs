@@ -40,7 +40,7 @@
 //! * Rank 1 Count 2: Second and third lowest ranked items in map.
 //! * Rank -3 Count 3: Top three ranked items in map.
 
-use crate::msgpack::encoder::pack_cdt_op;
+use crate::msgpack::encoder::{pack_cdt_op, pack_map_create_op};
 use crate::operations::cdt::{CdtArgument, CdtOperation};
 use crate::operations::cdt_context::DEFAULT_CTX;
 use crate::operations::{Operation, OperationBin, OperationData, OperationType};
@@ -168,6 +168,50 @@ pub enum MapWriteMode {
     CreateOnly,
 }
 
+/// `MapWriteFlag` determines write flags for maps. Flags are combined into `MapWriteFlags`
+/// using the `|` operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MapWriteFlag {
+    /// Default. Allow create or update.
+    Default = 0,
+    /// If the key already exists, the item will be denied.
+    /// If the key does not exist, a new item will be created.
+    CreateOnly = 1,
+    /// If the key already exists, the item will be overwritten.
+    /// If the key does not exist, the item will be denied.
+    UpdateOnly = 2,
+    /// Do not raise error if a map item is denied due to write flag constraints.
+    NoFail = 4,
+    /// Allow other valid map items to be committed if a map item is denied due to write flag
+    /// constraints.
+    Partial = 8,
+}
+
+write_flags! {
+    /// Set of `MapWriteFlag` values, combined using the `|` operator.
+    /// ```
+    /// use aerospike::operations::{MapWriteFlag, MapWriteFlags};
+    /// let flags = MapWriteFlags::CreateOnly | MapWriteFlags::NoFail;
+    /// assert_eq!(flags, MapWriteFlag::CreateOnly | MapWriteFlag::NoFail);
+    /// assert_eq!(flags.bits(), 5);
+    /// ```
+    MapWriteFlags(MapWriteFlag) {
+        /// Default. Allow create or update.
+        Default,
+        /// If the key already exists, the item will be denied.
+        /// If the key does not exist, a new item will be created.
+        CreateOnly,
+        /// If the key already exists, the item will be overwritten.
+        /// If the key does not exist, the item will be denied.
+        UpdateOnly,
+        /// Do not raise error if a map item is denied due to write flag constraints.
+        NoFail,
+        /// Allow other valid map items to be committed if a map item is denied due to write flag
+        /// constraints.
+        Partial,
+    }
+}
+
 /// `MapPolicy` directives when creating a map and writing map items.
 #[derive(Debug, Clone, Copy)]
 pub struct MapPolicy {
@@ -175,12 +219,35 @@ pub struct MapPolicy {
     pub order: MapOrder,
     /// The Map Write Mode
     pub write_mode: MapWriteMode,
+    /// The Map Write Flags. If set, the write mode is ignored.
+    pub flags: MapWriteFlags,
 }
 
 impl MapPolicy {
     /// Create a new map policy given the ordering for the map and the write mode.
     pub const fn new(order: MapOrder, write_mode: MapWriteMode) -> Self {
-        MapPolicy { order, write_mode }
+        MapPolicy {
+            order,
+            write_mode,
+            flags: MapWriteFlags::Default,
+        }
+    }
+
+    /// Create a new map policy given the ordering for the map and the write flags. Requires
+    /// server version 4.3+.
+    ///
+    /// ```
+    /// use aerospike::operations::{MapOrder, MapPolicy, MapWriteFlags};
+    ///
+    /// let flags = MapWriteFlags::CreateOnly | MapWriteFlags::NoFail;
+    /// let policy = MapPolicy::new_with_flags(MapOrder::KeyOrdered, flags);
+    /// ```
+    pub const fn new_with_flags(order: MapOrder, flags: MapWriteFlags) -> Self {
+        MapPolicy {
+            order,
+            write_mode: MapWriteMode::Update,
+            flags,
+        }
     }
 }
 
@@ -194,6 +261,12 @@ impl Default for MapPolicy {
 /// map policy.
 #[allow(clippy::trivially_copy_pass_by_ref)]
 pub(crate) const fn map_write_op(policy: &MapPolicy, multi: bool) -> CdtMapOpType {
+    if policy.flags.bits() != 0 {
+        if multi {
+            return CdtMapOpType::PutItems;
+        }
+        return CdtMapOpType::Put;
+    }
     match policy.write_mode {
         MapWriteMode::Update => {
             if multi {
@@ -221,11 +294,20 @@ pub(crate) const fn map_write_op(policy: &MapPolicy, multi: bool) -> CdtMapOpTyp
 #[allow(clippy::trivially_copy_pass_by_ref)]
 const fn map_order_arg(policy: &MapPolicy) -> Option<CdtArgument> {
     match policy.write_mode {
-        MapWriteMode::UpdateOnly => None,
+        MapWriteMode::UpdateOnly if policy.flags.bits() == 0 => None,
         _ => Some(CdtArgument::Byte(policy.order as u8)),
     }
 }
 
+#[allow(clippy::trivially_copy_pass_by_ref)]
+const fn map_flags_arg(policy: &MapPolicy) -> Option<CdtArgument<'static>> {
+    if policy.flags.bits() == 0 {
+        None
+    } else {
+        Some(CdtArgument::Byte(policy.flags.bits()))
+    }
+}
+
 #[doc(hidden)]
 pub const fn map_order_flag(order: MapOrder) -> u8 {
     match order {
@@ -253,6 +335,35 @@ pub fn set_order(bin: &str, map_order: MapOrder) -> Operation {
     }
 }
 
+/// Create map create operation. Server creates a map with the given order at the operation's
+/// context level, or sets the order of the map bin if no context is given. Server does not return
+/// a result.
+///
+/// If `persist_index` is true, the server persists the index of an ordered map to save the cost
+/// of rebuilding it (requires server version 6.1+). The persisted index only applies to top-level
+/// maps; it is ignored if the operation has a context.
+pub fn create(bin: &str, map_order: MapOrder, persist_index: bool) -> Operation {
+    let mut attributes = map_order as u8;
+    if persist_index {
+        attributes |= 0x10;
+    }
+    let cdt_op = CdtOperation {
+        op: CdtMapOpType::SetType as u8,
+        encoder: Box::new(pack_map_create_op),
+        args: vec![
+            CdtArgument::Byte(map_order_flag(map_order)),
+            CdtArgument::Byte(map_order as u8),
+            CdtArgument::Byte(attributes),
+        ],
+    };
+    Operation {
+        op: OperationType::CdtWrite,
+        ctx: DEFAULT_CTX,
+        bin: OperationBin::Name(bin),
+        data: OperationData::CdtMapOp(cdt_op),
+    }
+}
+
 /// Create map put operation. Server writes the key/value item to the map bin and returns the
 /// map size.
 ///
@@ -271,6 +382,9 @@ pub fn put<'a>(
     if let Some(arg) = map_order_arg(policy) {
         args.push(arg);
     }
+    if let Some(arg) = map_flags_arg(policy) {
+        args.push(arg);
+    }
     let cdt_op = CdtOperation {
         op: map_write_op(policy, false) as u8,
         encoder: Box::new(pack_cdt_op),
@@ -299,6 +413,9 @@ pub fn put_items<'a>(
     if let Some(arg) = map_order_arg(policy) {
         args.push(arg);
     }
+    if let Some(arg) = map_flags_arg(policy) {
+        args.push(arg);
+    }
     let cdt_op = CdtOperation {
         op: map_write_op(policy, true) as u8,
         encoder: Box::new(pack_cdt_op),
@@ -330,6 +447,9 @@ pub fn increment_value<'a>(
     if let Some(arg) = map_order_arg(policy) {
         args.push(arg);
     }
+    if let Some(arg) = map_flags_arg(policy) {
+        args.push(arg);
+    }
     let cdt_op = CdtOperation {
         op: CdtMapOpType::Increment as u8,
         encoder: Box::new(pack_cdt_op),
@@ -401,7 +521,7 @@ pub fn remove_by_key<'a>(
         op: CdtMapOpType::RemoveByKey as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Value(key),
         ],
     };
@@ -414,7 +534,16 @@ pub fn remove_by_key<'a>(
 }
 
 /// Create map remove operation. Server removes map items identified by keys and returns
-/// removed data specified by `return_type`.
+/// removed data specified by `return_type`. With `MapReturnType::Inverted`, all items except the
+/// ones identified by keys are removed.
+///
+/// ```
+/// use aerospike::{as_val, MapReturnType};
+/// use aerospike::operations::maps;
+///
+/// let keys = vec![as_val!("a"), as_val!("b")];
+/// let op = maps::remove_by_key_list("bin", &keys, MapReturnType::Count);
+/// ```
 pub fn remove_by_key_list<'a>(
     bin: &'a str,
     keys: &'a [Value],
@@ -424,7 +553,7 @@ pub fn remove_by_key_list<'a>(
         op: CdtMapOpType::RemoveKeyList as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::List(keys),
         ],
     };
@@ -447,7 +576,7 @@ pub fn remove_by_key_range<'a>(
     return_type: MapReturnType,
 ) -> Operation<'a> {
     let mut args = vec![
-        CdtArgument::Byte(return_type as u8),
+        CdtArgument::Int(return_type as i64),
         CdtArgument::Value(begin),
     ];
     if !end.is_nil() {
@@ -477,7 +606,7 @@ pub fn remove_by_value<'a>(
         op: CdtMapOpType::RemoveByValue as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Value(value),
         ],
     };
@@ -500,7 +629,7 @@ pub fn remove_by_value_list<'a>(
         op: CdtMapOpType::RemoveValueList as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::List(values),
         ],
     };
@@ -523,7 +652,7 @@ pub fn remove_by_value_range<'a>(
     return_type: MapReturnType,
 ) -> Operation<'a> {
     let mut args = vec![
-        CdtArgument::Byte(return_type as u8),
+        CdtArgument::Int(return_type as i64),
         CdtArgument::Value(begin),
     ];
     if !end.is_nil() {
@@ -549,7 +678,7 @@ pub fn remove_by_index(bin: &str, index: i64, return_type: MapReturnType) -> Ope
         op: CdtMapOpType::RemoveByIndex as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Int(index),
         ],
     };
@@ -573,7 +702,7 @@ pub fn remove_by_index_range(
         op: CdtMapOpType::RemoveByIndexRange as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Int(index),
             CdtArgument::Int(count),
         ],
@@ -593,7 +722,7 @@ pub fn remove_by_index_range_from(bin: &str, index: i64, return_type: MapReturnT
         op: CdtMapOpType::RemoveByIndexRange as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Int(index),
         ],
     };
@@ -611,7 +740,7 @@ pub fn remove_by_rank(bin: &str, rank: i64, return_type: MapReturnType) -> Opera
     let cdt_op = CdtOperation {
         op: CdtMapOpType::RemoveByRank as u8,
         encoder: Box::new(pack_cdt_op),
-        args: vec![CdtArgument::Byte(return_type as u8), CdtArgument::Int(rank)],
+        args: vec![CdtArgument::Int(return_type as i64), CdtArgument::Int(rank)],
     };
     Operation {
         op: OperationType::CdtWrite,
@@ -633,7 +762,7 @@ pub fn remove_by_rank_range(
         op: CdtMapOpType::RemoveByRankRange as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Int(rank),
             CdtArgument::Int(count),
         ],
@@ -652,7 +781,7 @@ pub fn remove_by_rank_range_from(bin: &str, rank: i64, return_type: MapReturnTyp
     let cdt_op = CdtOperation {
         op: CdtMapOpType::RemoveByRankRange as u8,
         encoder: Box::new(pack_cdt_op),
-        args: vec![CdtArgument::Byte(return_type as u8), CdtArgument::Int(rank)],
+        args: vec![CdtArgument::Int(return_type as i64), CdtArgument::Int(rank)],
     };
     Operation {
         op: OperationType::CdtWrite,
@@ -684,7 +813,7 @@ pub fn get_by_key<'a>(bin: &'a str, key: &'a Value, return_type: MapReturnType)
         op: CdtMapOpType::GetByKey as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Value(key),
         ],
     };
@@ -707,7 +836,7 @@ pub fn get_by_key_range<'a>(
     return_type: MapReturnType,
 ) -> Operation<'a> {
     let mut args = vec![
-        CdtArgument::Byte(return_type as u8),
+        CdtArgument::Int(return_type as i64),
         CdtArgument::Value(begin),
     ];
     if !end.is_nil() {
@@ -737,7 +866,7 @@ pub fn get_by_value<'a>(
         op: CdtMapOpType::GetByValue as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Value(value),
         ],
     };
@@ -760,7 +889,7 @@ pub fn get_by_value_range<'a>(
     return_type: MapReturnType,
 ) -> Operation<'a> {
     let mut args = vec![
-        CdtArgument::Byte(return_type as u8),
+        CdtArgument::Int(return_type as i64),
         CdtArgument::Value(begin),
     ];
     if !end.is_nil() {
@@ -786,7 +915,7 @@ pub fn get_by_index(bin: &str, index: i64, return_type: MapReturnType) -> Operat
         op: CdtMapOpType::GetByIndex as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Int(index),
         ],
     };
@@ -810,7 +939,7 @@ pub fn get_by_index_range(
         op: CdtMapOpType::GetByIndexRange as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Int(index),
             CdtArgument::Int(count),
         ],
@@ -831,7 +960,7 @@ pub fn get_by_index_range_from(bin: &str, index: i64, return_type: MapReturnType
         op: CdtMapOpType::GetByIndexRange as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Int(index),
         ],
     };
@@ -849,7 +978,7 @@ pub fn get_by_rank(bin: &str, rank: i64, return_type: MapReturnType) -> Operatio
     let cdt_op = CdtOperation {
         op: CdtMapOpType::GetByRank as u8,
         encoder: Box::new(pack_cdt_op),
-        args: vec![CdtArgument::Byte(return_type as u8), CdtArgument::Int(rank)],
+        args: vec![CdtArgument::Int(return_type as i64), CdtArgument::Int(rank)],
     };
     Operation {
         op: OperationType::CdtRead,
@@ -871,7 +1000,7 @@ pub fn get_by_rank_range(
         op: CdtMapOpType::GetByRankRange as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Int(rank),
             CdtArgument::Int(count),
         ],
@@ -891,7 +1020,7 @@ pub fn get_by_rank_range_from(bin: &str, rank: i64, return_type: MapReturnType)
     let cdt_op = CdtOperation {
         op: CdtMapOpType::GetByRankRange as u8,
         encoder: Box::new(pack_cdt_op),
-        args: vec![CdtArgument::Byte(return_type as u8), CdtArgument::Int(rank)],
+        args: vec![CdtArgument::Int(return_type as i64), CdtArgument::Int(rank)],
     };
     Operation {
         op: OperationType::CdtRead,
@@ -923,7 +1052,7 @@ pub fn remove_by_key_relative_index_range<'a>(
         op: CdtMapOpType::RemoveByKeyRelIndexRange as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Value(key),
             CdtArgument::Int(index),
         ],
@@ -959,7 +1088,7 @@ pub fn remove_by_key_relative_index_range_count<'a>(
         op: CdtMapOpType::RemoveByKeyRelIndexRange as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Value(key),
             CdtArgument::Int(index),
             CdtArgument::Int(count),
@@ -992,7 +1121,7 @@ pub fn remove_by_value_relative_rank_range<'a>(
         op: CdtMapOpType::RemoveByValueRelRankRange as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Value(value),
             CdtArgument::Int(rank),
         ],
@@ -1025,7 +1154,7 @@ pub fn remove_by_value_relative_rank_range_count<'a>(
         op: CdtMapOpType::RemoveByValueRelRankRange as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Value(value),
             CdtArgument::Int(rank),
             CdtArgument::Int(count),
@@ -1041,6 +1170,15 @@ pub fn remove_by_value_relative_rank_range_count<'a>(
 
 /// Creates a map get by key list operation.
 /// Server selects map items identified by keys and returns selected data specified by returnType.
+///
+/// ```
+/// use aerospike::{as_val, MapReturnType};
+/// use aerospike::operations::maps;
+///
+/// // Fetch the values of several map entries in a single operation.
+/// let keys = vec![as_val!("a"), as_val!("b"), as_val!("c")];
+/// let op = maps::get_by_key_list("bin", &keys, MapReturnType::Value);
+/// ```
 pub fn get_by_key_list<'a>(
     bin: &'a str,
     keys: &'a [Value],
@@ -1050,7 +1188,7 @@ pub fn get_by_key_list<'a>(
         op: CdtMapOpType::GetByKeyList as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::List(keys),
         ],
     };
@@ -1073,7 +1211,7 @@ pub fn get_by_value_list<'a>(
         op: CdtMapOpType::GetByValueList as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::List(values),
         ],
     };
@@ -1107,7 +1245,7 @@ pub fn get_by_key_relative_index_range<'a>(
         op: CdtMapOpType::GetByKeyRelIndexRange as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Value(key),
             CdtArgument::Int(index),
         ],
@@ -1143,7 +1281,7 @@ pub fn get_by_key_relative_index_range_count<'a>(
         op: CdtMapOpType::GetByKeyRelIndexRange as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Value(key),
             CdtArgument::Int(index),
             CdtArgument::Int(count),
@@ -1176,7 +1314,7 @@ pub fn get_by_value_relative_rank_range<'a>(
         op: CdtMapOpType::GetByValueRelRankRange as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Value(value),
             CdtArgument::Int(rank),
         ],
@@ -1209,7 +1347,7 @@ pub fn get_by_value_relative_rank_range_count<'a>(
         op: CdtMapOpType::GetByValueRelRankRange as u8,
         encoder: Box::new(pack_cdt_op),
         args: vec![
-            CdtArgument::Byte(return_type as u8),
+            CdtArgument::Int(return_type as i64),
             CdtArgument::Value(value),
             CdtArgument::Int(rank),
             CdtArgument::Int(count),
@@ -1222,3 +1360,59 @@ pub fn get_by_value_relative_rank_range_count<'a>(
         data: OperationData::CdtMapOp(cdt_op),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{create, increment_value, MapOrder, MapPolicy, MapWriteFlags};
+    use crate::commands::buffer::Buffer;
+    use crate::operations::cdt_context::{ctx_map_key, CdtContext};
+    use crate::operations::{Operation, OperationData};
+    use crate::Value;
+
+    fn pack_create(persist_index: bool, ctx: &[CdtContext]) -> Vec<u8> {
+        pack(create("bin", MapOrder::KeyOrdered, persist_index), ctx)
+    }
+
+    fn pack(op: Operation, ctx: &[CdtContext]) -> Vec<u8> {
+        let cdt_op = match op.data {
+            OperationData::CdtMapOp(cdt_op) => cdt_op,
+            _ => unreachable!(),
+        };
+        let size = (cdt_op.encoder)(&mut None, &cdt_op, ctx).unwrap();
+        let mut buf = Buffer::new(size);
+        buf.resize_buffer(size).unwrap();
+        (cdt_op.encoder)(&mut Some(&mut buf), &cdt_op, ctx).unwrap();
+        buf.data_buffer
+    }
+
+    #[test]
+    fn create_top_level() {
+        // set type, [key ordered | persist index]
+        assert_eq!(pack_create(true, &[]), vec![0x00, 0x40, 0x91, 0x11]);
+        assert_eq!(pack_create(false, &[]), vec![0x00, 0x40, 0x91, 0x01]);
+    }
+
+    #[test]
+    fn create_nested() {
+        // the last context is flagged to create a key ordered map, without the persisted index
+        let ctx = [ctx_map_key(Value::from("a"))];
+        let expected = vec![
+            0x93, 0xcd, 0x00, 0xff, 0x92, 0xcd, 0x00, 0xa2, 0xa2, 0x03, b'a', 0x92, 0x40, 0x01,
+        ];
+        assert_eq!(pack_create(true, &ctx), expected);
+    }
+
+    #[test]
+    fn increment_with_flags() {
+        let policy = MapPolicy::new_with_flags(
+            MapOrder::KeyOrdered,
+            MapWriteFlags::UpdateOnly | MapWriteFlags::NoFail,
+        );
+        let key = Value::from("a");
+        let incr = Value::from(1);
+        let op = increment_value(&policy, "bin", &key, &incr);
+        // increment, ["a", 1, key ordered, update only | no fail]
+        let expected = vec![0x00, 0x49, 0x94, 0xa2, 0x03, b'a', 0x01, 0x01, 0x06];
+        assert_eq!(pack(op, &[]), expected);
+    }
+}
@@ -15,6 +15,74 @@
 
 //! Functions used to create database operations used in the client's `operate()` method.
 
+// Defines a set of write flags, combined using the `|` operator, for a write flag enum of a CDT
+// policy. The set has a constant for each flag, named like the variant of the enum, so that a
+// single flag can be used wherever a set is expected.
+macro_rules! write_flags {
+    (
+        $(#[$meta:meta])*
+        $set:ident($flag:ident) {
+            $($(#[$flag_meta:meta])* $name:ident,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+        pub struct $set(u8);
+
+        #[allow(non_upper_case_globals)]
+        impl $set {
+            $(
+                $(#[$flag_meta])*
+                pub const $name: $set = $set($flag::$name as u8);
+            )*
+
+            /// Returns the raw flag bits as sent to the server.
+            pub const fn bits(self) -> u8 {
+                self.0
+            }
+
+            /// Returns true if all flags in `other` are set.
+            pub const fn contains(self, other: $set) -> bool {
+                self.0 & other.0 == other.0
+            }
+        }
+
+        impl ::std::ops::BitOr for $set {
+            type Output = $set;
+
+            fn bitor(self, rhs: $set) -> $set {
+                $set(self.0 | rhs.0)
+            }
+        }
+
+        impl ::std::ops::BitOrAssign for $set {
+            fn bitor_assign(&mut self, rhs: $set) {
+                self.0 |= rhs.0;
+            }
+        }
+
+        impl ::std::ops::BitOr for $flag {
+            type Output = $set;
+
+            fn bitor(self, rhs: $flag) -> $set {
+                $set::from(self) | $set::from(rhs)
+            }
+        }
+
+        impl From<$flag> for $set {
+            fn from(flag: $flag) -> $set {
+                $set(flag as u8)
+            }
+        }
+
+        impl From<$set> for u8 {
+            fn from(flags: $set) -> u8 {
+                flags.0
+            }
+        }
+    };
+}
+
 pub mod bitwise;
 #[doc(hidden)]
 pub mod cdt;
@@ -26,7 +94,9 @@ pub mod maps;
 pub mod scalar;
 
 use self::cdt::CdtOperation;
-pub use self::maps::{MapOrder, MapPolicy, MapReturnType, MapWriteMode};
+pub use self::maps::{
+    MapOrder, MapPolicy, MapReturnType, MapWriteFlag, MapWriteFlags, MapWriteMode,
+};
 pub use self::scalar::*;
 
 use crate::commands::buffer::Buffer;
@@ -94,6 +164,18 @@ pub struct Operation<'a> {
 }
 
 impl<'a> Operation<'a> {
+    #[doc(hidden)]
+    pub const fn is_write(&self) -> bool {
+        !matches!(
+            self.op,
+            OperationType::Read
+                | OperationType::CdtRead
+                | OperationType::BitRead
+                | OperationType::HllRead
+                | OperationType::ExpRead
+        )
+    }
+
     #[doc(hidden)]
     pub fn estimate_size(&self) -> Result<usize> {
         let mut size: usize = 0;
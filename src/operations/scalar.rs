@@ -29,7 +29,27 @@ pub const fn get<'a>() -> Operation<'a> {
     }
 }
 
-/// Create read record header database operation.
+/// Create read record header database operation. The record's generation and expiration are
+/// returned without any bin data, unless other read operations in the same `operate()` call
+/// request bins.
+///
+/// # Examples
+///
+/// Increment a counter and return only the record's new generation and expiration.
+///
+/// ```rust
+/// # use aerospike::*;
+///
+/// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap_or("localhost".into());
+/// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+/// let key = as_key!("test", "test", "mykey");
+/// let bin = as_bin!("counter", 1);
+/// let ops = vec![operations::add(&bin), operations::get_header()];
+/// match client.operate(&WritePolicy::default(), &key, &ops) {
+///     Ok(record) => println!("Record generation is now {}", record.generation),
+///     Err(err) => println!("Error writing record: {}", err),
+/// }
+/// ```
 pub const fn get_header<'a>() -> Operation<'a> {
     Operation {
         op: OperationType::Read,
@@ -99,7 +119,26 @@ pub const fn touch<'a>() -> Operation<'a> {
     }
 }
 
-/// Create delete database operation
+/// Create delete record database operation. Operations placed before the delete in the same
+/// `operate()` call are executed first, so their results are returned even though the record is
+/// removed. Requires server version 4.7 or later.
+///
+/// # Examples
+///
+/// Read a record's final value and delete it in one atomic call.
+///
+/// ```rust
+/// # use aerospike::*;
+///
+/// # let hosts = std::env::var("AEROSPIKE_HOSTS").unwrap_or("localhost".into());
+/// # let client = Client::new(&ClientPolicy::default(), &hosts).unwrap();
+/// let key = as_key!("test", "test", "mykey");
+/// let ops = vec![operations::get_bin("a"), operations::delete()];
+/// match client.operate(&WritePolicy::default(), &key, &ops) {
+///     Ok(record) => println!("Removed record with value {:?}", record.bins.get("a")),
+///     Err(err) => println!("Error deleting record: {}", err),
+/// }
+/// ```
 pub const fn delete<'a>() -> Operation<'a> {
     Operation {
         op: OperationType::Delete,
@@ -20,3 +20,11 @@ pub struct AdminPolicy {
     /// Total transaction timeout for both client and server.
     pub timeout: Duration,
 }
+
+impl Default for AdminPolicy {
+    fn default() -> Self {
+        AdminPolicy {
+            timeout: Duration::new(1, 0),
+        }
+    }
+}
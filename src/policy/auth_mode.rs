@@ -0,0 +1,57 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+/// Authentication mode used when connecting to the cluster. It determines how the credentials
+/// of the user are transmitted to the server.
+///
+/// The client logs in to each node with the login command, which returns a session token that
+/// authenticates the further connections to the node until the session expires.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthMode {
+    /// Users are authenticated by the server, using the user names and passwords stored in the
+    /// cluster. Only the hash of the password is sent to the server. Servers that do not support
+    /// the login command are sent the hash with the legacy authenticate command instead.
+    Internal,
+
+    /// Users are authenticated by an external authentication service, e.g. LDAP, configured on
+    /// the server. The clear-text password is sent to the server, which requires TLS
+    /// connections, see `ClientPolicy::tls_policy`.
+    External,
+
+    /// Like `External`, but the clear-text password is sent without requiring a TLS connection.
+    /// Should only be used on trusted networks.
+    ExternalInsecure,
+
+    /// Users are authenticated by the TLS client certificate of the connection; no user name or
+    /// password is sent to the server. Requires TLS connections with a client certificate, see
+    /// `TlsPolicy::client_certificate`.
+    PKI,
+}
+
+impl AuthMode {
+    /// Returns true if the clear-text password is sent to the server.
+    pub const fn is_external(self) -> bool {
+        match self {
+            AuthMode::External | AuthMode::ExternalInsecure => true,
+            AuthMode::Internal | AuthMode::PKI => false,
+        }
+    }
+}
+
+impl Default for AuthMode {
+    fn default() -> AuthMode {
+        AuthMode::Internal
+    }
+}
@@ -23,7 +23,8 @@ pub struct BatchPolicy {
     pub base_policy: BasePolicy,
 
     /// Concurrency mode for batch requests: Sequential or Parallel (with optional max. no of
-    /// parallel threads).
+    /// parallel threads). Use `Concurrency::MaxThreads` to limit the number of concurrent node
+    /// requests.
     pub concurrency: Concurrency,
 
     /// Allow batch to be processed immediately in the server's receiving thread when the server
@@ -40,12 +41,61 @@ pub struct BatchPolicy {
     /// Default: true
     pub allow_inline: bool,
 
+    /// Allow batch to be processed immediately in the server's receiving thread for SSD
+    /// namespaces. If false, the batch will always be processed in separate service threads.
+    /// Server versions < 6.0 ignore this field.
+    ///
+    /// Inline processing can introduce the possibility of unfairness because the server can
+    /// process the entire batch before moving onto the next command.
+    ///
+    /// Default: false
+    pub allow_inline_ssd: bool,
+
+    /// Should all batch keys be attempted regardless of errors. This field is used on both the
+    /// client and server. The client handles node specific errors and the server handles key
+    /// specific errors.
+    ///
+    /// If true, every batch key is attempted regardless of previous key specific errors. Node
+    /// specific errors such as timeouts stop keys to that node, but keys directed at other nodes
+    /// will continue to be processed.
+    ///
+    /// If false, the server will stop the batch to its node on most key specific errors and the
+    /// client will not issue requests to the remaining nodes. Server versions < 6.0 do not
+    /// support this field and treat this value as false for key specific errors.
+    ///
+    /// Default: true
+    pub respond_all_keys: bool,
+
     /// Send set name field to server for every key in the batch. This is only necessary when
     /// authentication is enabled and security roles are defined on a per-set basis.
     ///
     /// Default: false
     pub send_set_name: bool,
 
+    /// Maximum number of keys sent to a node in a single batch command. Larger batches are split
+    /// into multiple commands per node, e.g. to stay below the server's `batch-max-requests`
+    /// limit or to keep a single batch from holding a connection for too long. Zero means no
+    /// limit.
+    ///
+    /// Default: 0
+    pub max_keys_per_command: usize,
+
+    /// Maximum number of batch commands in flight to the same node when a batch has been split
+    /// into multiple commands per node (see `max_keys_per_command`). The remaining commands to
+    /// that node are sent once a previous command has completed. Zero means no limit; the overall
+    /// number of concurrent commands is still limited by `concurrency`.
+    ///
+    /// Default: 0
+    pub max_commands_per_node: usize,
+
+    /// Number of records to place in queue before blocking when the batch results are streamed.
+    /// Records received from multiple server nodes will be placed in a queue. A separate thread
+    /// consumes these records in parallel. If the queue is full, the producer threads will block
+    /// until records are consumed.
+    ///
+    /// Default: 1024
+    pub record_queue_size: usize,
+
     /// Optional Filter Expression
     pub filter_expression: Option<FilterExpression>,
 }
@@ -68,7 +118,12 @@ impl Default for BatchPolicy {
             base_policy: BasePolicy::default(),
             concurrency: Concurrency::Sequential,
             allow_inline: true,
+            allow_inline_ssd: false,
+            respond_all_keys: true,
             send_set_name: false,
+            max_keys_per_command: 0,
+            max_commands_per_node: 0,
+            record_queue_size: 1024,
             filter_expression: None,
         }
     }
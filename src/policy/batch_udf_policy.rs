@@ -0,0 +1,67 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use crate::expressions::FilterExpression;
+use crate::{CommitLevel, Expiration};
+
+/// `BatchUDFPolicy` encapsulates the parameters applied to a single record of a batch UDF
+/// execution.
+#[derive(Debug, Clone)]
+pub struct BatchUDFPolicy {
+    /// Desired consistency guarantee when committing a transaction on the server. The default
+    /// (COMMIT_ALL) indicates that the server should wait for master and all replica commits to
+    /// be successful before returning success to the client.
+    pub commit_level: CommitLevel,
+
+    /// Record expiration, used if the UDF creates or updates the record. Also known as TTL
+    /// (Time-To-Live).
+    pub expiration: Expiration,
+
+    /// Send user defined key in addition to hash digest.
+    /// The default is to not send the user defined key.
+    pub send_key: bool,
+
+    /// If the transaction results in a record deletion, leave a tombstone for the record. This
+    /// prevents deleted records from reappearing after node failures. Valid for Aerospike Server
+    /// Enterprise Edition only.
+    pub durable_delete: bool,
+
+    /// Optional Filter Expression
+    pub filter_expression: Option<FilterExpression>,
+}
+
+impl BatchUDFPolicy {
+    /// Create a new batch UDF policy instance.
+    pub fn new() -> Self {
+        BatchUDFPolicy::default()
+    }
+
+    /// Get the current Filter Expression
+    pub const fn filter_expression(&self) -> &Option<FilterExpression> {
+        &self.filter_expression
+    }
+}
+
+impl Default for BatchUDFPolicy {
+    fn default() -> Self {
+        BatchUDFPolicy {
+            commit_level: CommitLevel::CommitAll,
+            expiration: Expiration::NamespaceDefault,
+            send_key: false,
+            durable_delete: false,
+            filter_expression: None,
+        }
+    }
+}
@@ -0,0 +1,78 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use crate::expressions::FilterExpression;
+use crate::{CommitLevel, Expiration, GenerationPolicy, RecordExistsAction};
+
+/// `BatchWritePolicy` encapsulates the parameters applied to a single record of a batch write.
+#[derive(Debug, Clone)]
+pub struct BatchWritePolicy {
+    /// RecordExistsAction qualifies how to handle writes where the record already exists.
+    pub record_exists_action: RecordExistsAction,
+
+    /// GenerationPolicy qualifies how to handle record writes based on record generation.
+    /// The default (NONE) indicates that the generation is not used to restrict writes.
+    pub generation_policy: GenerationPolicy,
+
+    /// Desired consistency guarantee when committing a transaction on the server. The default
+    /// (COMMIT_ALL) indicates that the server should wait for master and all replica commits to
+    /// be successful before returning success to the client.
+    pub commit_level: CommitLevel,
+
+    /// Expected generation of the record. Only used if the generation policy requires it.
+    pub generation: u32,
+
+    /// Record expiration. Also known as TTL (Time-To-Live).
+    pub expiration: Expiration,
+
+    /// Send user defined key in addition to hash digest on the write.
+    /// The default is to not send the user defined key.
+    pub send_key: bool,
+
+    /// If the transaction results in a record deletion, leave a tombstone for the record. This
+    /// prevents deleted records from reappearing after node failures. Valid for Aerospike Server
+    /// Enterprise Edition only.
+    pub durable_delete: bool,
+
+    /// Optional Filter Expression
+    pub filter_expression: Option<FilterExpression>,
+}
+
+impl BatchWritePolicy {
+    /// Create a new batch write policy instance.
+    pub fn new() -> Self {
+        BatchWritePolicy::default()
+    }
+
+    /// Get the current Filter Expression
+    pub const fn filter_expression(&self) -> &Option<FilterExpression> {
+        &self.filter_expression
+    }
+}
+
+impl Default for BatchWritePolicy {
+    fn default() -> Self {
+        BatchWritePolicy {
+            record_exists_action: RecordExistsAction::Update,
+            generation_policy: GenerationPolicy::None,
+            commit_level: CommitLevel::CommitAll,
+            generation: 0,
+            expiration: Expiration::NamespaceDefault,
+            send_key: false,
+            durable_delete: false,
+            filter_expression: None,
+        }
+    }
+}
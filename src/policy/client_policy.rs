@@ -13,21 +13,61 @@
 // limitations under the License.
 
 use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
 use std::time::Duration;
 
+use crate::cluster::ClusterEventListener;
 use crate::commands::admin_command::AdminCommand;
 use crate::errors::Result;
+use crate::net::Resolver;
+use crate::policy::{AuthMode, TlsPolicy};
 
 /// `ClientPolicy` encapsulates parameters for client policy command.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct ClientPolicy {
     /// User authentication to cluster. Leave empty for clusters running without restricted access.
+    /// The client logs in to each node once and authenticates further connections to the node
+    /// with the session token returned by the server, until the session expires.
     pub user_password: Option<(String, String)>,
 
+    /// Clear-text password of the user, sent to the server with the external authentication
+    /// modes so that it can be verified by the external authentication service. Only kept by
+    /// `set_user_password` if `auth_mode` is external.
+    pub clear_password: Option<String>,
+
+    /// Authentication mode used when connecting to the cluster. Default: `AuthMode::Internal`.
+    pub auth_mode: AuthMode,
+
+    /// Connect to the cluster nodes with TLS, using the given policy. The ports of the seed hosts
+    /// must be the TLS ports of the nodes. Requires the `native-tls` feature. Default: `None`.
+    pub tls_policy: Option<TlsPolicy>,
+
     /// Initial host connection timeout in milliseconds.  The timeout when opening a connection
     /// to the server host for the first time.
     pub timeout: Option<Duration>,
 
+    /// Disable Nagle's algorithm on the connections, so that small commands are sent without
+    /// delay. Default: true.
+    pub tcp_nodelay: bool,
+
+    /// Enable TCP keep-alive probes on the connections after they have been idle for the given
+    /// time, e.g. so that idle connections are not dropped by stateful firewalls. Default: `None`,
+    /// i.e. keep-alive is disabled.
+    pub tcp_keepalive: Option<Duration>,
+
+    /// Interval between TCP keep-alive probes, if `tcp_keepalive` is enabled. Not supported on
+    /// all platforms; the system default is used if `None`. Default: `None`.
+    pub tcp_keepalive_interval: Option<Duration>,
+
+    /// Size of the send buffer of the connections, in bytes; the system default is used if
+    /// `None`. Default: `None`.
+    pub socket_send_buffer_size: Option<usize>,
+
+    /// Size of the receive buffer of the connections, in bytes; the system default is used if
+    /// `None`. Default: `None`.
+    pub socket_recv_buffer_size: Option<usize>,
+
     /// Connection idle timeout. Every time a connection is used, its idle
     /// deadline will be extended by this duration. When this deadline is reached,
     /// the connection will be closed and discarded from the connection pool.
@@ -36,6 +76,24 @@ pub struct ClientPolicy {
     /// Maximum number of synchronous connections allowed per server node.
     pub max_conns_per_node: usize,
 
+    /// Minimum number of connections kept open per server node. The connections are opened when
+    /// the node joins the cluster and are re-opened by the cluster tend thread, so that commands
+    /// after a quiet period do not have to wait for new connections and logins. These
+    /// connections are not closed when they exceed `idle_timeout`; the idle timeout of the server
+    /// (`proto-fd-idle-ms`) should be disabled or longer than the expected quiet periods.
+    /// Default: 0.
+    pub min_conns_per_node: usize,
+
+    /// Maximum number of errors, e.g. connection failures and timeouts, of a node within an
+    /// error rate window. Once a node exceeds it, commands to the node fail with a
+    /// `MaxErrorRate` error until the window ends, instead of adding load to a node that is
+    /// already struggling. Zero disables the limit. Default: 100.
+    pub max_error_rate: usize,
+
+    /// Number of cluster tend intervals after which the error counts of the nodes are reset.
+    /// Must be greater than zero. Default: 1.
+    pub error_rate_window: usize,
+
     /// Number of connection pools used for each node. Machines with 8 CPU cores or less usually
     /// need only one connection pool per node. Machines with larger number of CPU cores may have
     /// their performance limited by contention for pooled connections. Contention for pooled
@@ -53,7 +111,9 @@ pub struct ClientPolicy {
     pub buffer_reclaim_threshold: usize,
 
     /// TendInterval determines interval for checking for cluster state changes.
-    /// Minimum possible interval is 10 Milliseconds.
+    /// Minimum possible interval is 10 Milliseconds; shorter intervals are raised to it. Large
+    /// clusters may use longer intervals to reduce the info requests to the nodes. Failed tends
+    /// are reported to `ClusterEventListener::tend_failed`.
     pub tend_interval: Duration,
 
     /// A IP translation table is used in cases where different clients
@@ -62,6 +122,8 @@ pub struct ClientPolicy {
     /// network. Default is no translation.
     /// The key is the IP address returned from friend info requests to other servers.
     /// The value is the real IP address used to connect to the server.
+    /// Keys and values may also be given as `address:port`, e.g. to map the ports of servers
+    /// running in Docker containers, which takes precedence over an entry for the address only.
     pub ip_map: Option<HashMap<String, String>>,
 
     /// UseServicesAlternate determines if the client should use "services-alternate"
@@ -71,7 +133,11 @@ pub struct ClientPolicy {
     /// providing a client "ipMap".
     /// This feature is recommended instead of using the client-side IpMap above.
     ///
-    /// "services-alternate" is available with Aerospike Server versions >= 3.7.1.
+    /// "services-alternate" is available with Aerospike Server versions >= 3.7.1. Servers that
+    /// support the peers info commands are asked for "peers-clear-alt" instead, or
+    /// "peers-tls-alt" with TLS. Enable this when the client connects to the cluster through a
+    /// NAT or from outside the network of the cluster, and the servers configure alternate access
+    /// addresses.
     pub use_services_alternate: bool,
 
     /// Size of the thread pool used in scan and query commands. These commands are often sent to
@@ -79,19 +145,52 @@ pub struct ClientPolicy {
     /// threads do not have to be created/destroyed for each command.
     pub thread_pool_size: usize,
 
-    /// Expected cluster name. It not `None`, server nodes must return this cluster name in order
-    /// to join the client's view of the cluster. Should only be set when connecting to servers
-    /// that support the "cluster-name" info command.
+    /// Expected cluster name. If not `None`, server nodes must return this cluster name in order
+    /// to join the client's view of the cluster; nodes reporting another cluster name, e.g. due to
+    /// a misconfigured seed list, are refused when they are seeded and removed when they are
+    /// tended. Should only be set when connecting to servers that support the "cluster-name" info
+    /// command.
     pub cluster_name: Option<String>,
+
+    /// Track the racks of the server nodes, so that read commands with `Replica::PreferRack` can
+    /// be sent to a node on the same rack as the client. Requires servers configured with rack
+    /// ids. Default: false.
+    pub rack_aware: bool,
+
+    /// Rack of the client, used by `Replica::PreferRack` if `rack_ids` is empty. Default: 0.
+    pub rack_id: usize,
+
+    /// Racks preferred by `Replica::PreferRack`, in order of preference. Overrides `rack_id` if
+    /// not empty.
+    pub rack_ids: Vec<usize>,
+
+    /// Listener notified about nodes joining or leaving the cluster and about the client losing
+    /// and regaining its connection to the cluster. Default: None.
+    pub event_listener: Option<Arc<dyn ClusterEventListener>>,
+
+    /// Resolver used to look up the addresses of seed and peer host names. Host names are
+    /// resolved with the system resolver if not set. Default: None.
+    pub resolver: Option<Arc<dyn Resolver>>,
 }
 
 impl Default for ClientPolicy {
     fn default() -> ClientPolicy {
         ClientPolicy {
             user_password: None,
+            clear_password: None,
+            auth_mode: AuthMode::default(),
+            tls_policy: None,
             timeout: Some(Duration::new(30, 0)),
             idle_timeout: Some(Duration::new(5, 0)),
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            tcp_keepalive_interval: None,
+            socket_send_buffer_size: None,
+            socket_recv_buffer_size: None,
             max_conns_per_node: 256,
+            min_conns_per_node: 0,
+            max_error_rate: 100,
+            error_rate_window: 1,
             conn_pools_per_node: 1,
             fail_if_not_connected: true,
             tend_interval: Duration::new(1, 0),
@@ -100,15 +199,99 @@ impl Default for ClientPolicy {
             thread_pool_size: 128,
             cluster_name: None,
             buffer_reclaim_threshold: 65536,
+            rack_aware: false,
+            rack_id: 0,
+            rack_ids: vec![],
+            event_listener: None,
+            resolver: None,
         }
     }
 }
 
+// Passwords are left out of the debug output, so that logging the policy does not leak them.
+impl fmt::Debug for ClientPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const REDACTED: &str = "<redacted>";
+        f.debug_struct("ClientPolicy")
+            .field(
+                "user_password",
+                &self
+                    .user_password
+                    .as_ref()
+                    .map(|(user, _)| (user, REDACTED)),
+            )
+            .field(
+                "clear_password",
+                &self.clear_password.as_ref().map(|_| REDACTED),
+            )
+            .field("auth_mode", &self.auth_mode)
+            .field("tls_policy", &self.tls_policy)
+            .field("timeout", &self.timeout)
+            .field("tcp_nodelay", &self.tcp_nodelay)
+            .field("tcp_keepalive", &self.tcp_keepalive)
+            .field("tcp_keepalive_interval", &self.tcp_keepalive_interval)
+            .field("socket_send_buffer_size", &self.socket_send_buffer_size)
+            .field("socket_recv_buffer_size", &self.socket_recv_buffer_size)
+            .field("idle_timeout", &self.idle_timeout)
+            .field("max_conns_per_node", &self.max_conns_per_node)
+            .field("min_conns_per_node", &self.min_conns_per_node)
+            .field("max_error_rate", &self.max_error_rate)
+            .field("error_rate_window", &self.error_rate_window)
+            .field("conn_pools_per_node", &self.conn_pools_per_node)
+            .field("fail_if_not_connected", &self.fail_if_not_connected)
+            .field("buffer_reclaim_threshold", &self.buffer_reclaim_threshold)
+            .field("tend_interval", &self.tend_interval)
+            .field("ip_map", &self.ip_map)
+            .field("use_services_alternate", &self.use_services_alternate)
+            .field("thread_pool_size", &self.thread_pool_size)
+            .field("cluster_name", &self.cluster_name)
+            .field("rack_aware", &self.rack_aware)
+            .field("rack_id", &self.rack_id)
+            .field("rack_ids", &self.rack_ids)
+            .field("event_listener", &self.event_listener)
+            .field("resolver", &self.resolver)
+            .finish()
+    }
+}
+
 impl ClientPolicy {
-    /// Set username and password to use when authenticating to the cluster.
+    /// Set username and password to use when authenticating to the cluster. The clear-text
+    /// password is only kept if the external authentication mode, which sends it to the server,
+    /// has been set as `auth_mode` before.
     pub fn set_user_password(&mut self, username: String, password: String) -> Result<()> {
-        let password = AdminCommand::hash_password(&password)?;
-        self.user_password = Some((username, password));
+        let password_hash = AdminCommand::hash_password(&password)?;
+        self.user_password = Some((username, password_hash));
+        self.clear_password = if self.auth_mode.is_external() {
+            Some(password)
+        } else {
+            None
+        };
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ClientPolicy;
+    use crate::policy::AuthMode;
+
+    #[test]
+    fn clear_password() {
+        let mut policy = ClientPolicy::default();
+        policy
+            .set_user_password("user".to_string(), "secret".to_string())
+            .unwrap();
+        assert!(policy.clear_password.is_none());
+
+        policy.auth_mode = AuthMode::External;
+        policy
+            .set_user_password("user".to_string(), "secret".to_string())
+            .unwrap();
+        assert_eq!(policy.clear_password.as_deref(), Some("secret"));
+
+        let debug = format!("{:?}", policy);
+        assert!(debug.contains("\"user\""));
+        assert!(!debug.contains("secret"));
+        assert!(!debug.contains(&policy.user_password.as_ref().unwrap().1));
+    }
+}
@@ -17,7 +17,11 @@
 #![allow(clippy::missing_errors_doc)]
 
 mod admin_policy;
+mod auth_mode;
+mod batch_delete_policy;
 mod batch_policy;
+mod batch_udf_policy;
+mod batch_write_policy;
 mod client_policy;
 mod commit_level;
 mod concurrency;
@@ -25,14 +29,21 @@ mod consistency_level;
 mod expiration;
 mod generation_policy;
 mod priority;
+mod query_duration;
 mod query_policy;
 mod read_policy;
 mod record_exists_action;
+mod replica;
 mod scan_policy;
+mod tls_policy;
 mod write_policy;
 
 pub use self::admin_policy::AdminPolicy;
+pub use self::auth_mode::AuthMode;
+pub use self::batch_delete_policy::BatchDeletePolicy;
 pub use self::batch_policy::BatchPolicy;
+pub use self::batch_udf_policy::BatchUDFPolicy;
+pub use self::batch_write_policy::BatchWritePolicy;
 pub use self::client_policy::ClientPolicy;
 pub use self::commit_level::CommitLevel;
 pub use self::concurrency::Concurrency;
@@ -40,13 +51,18 @@ pub use self::consistency_level::ConsistencyLevel;
 pub use self::expiration::Expiration;
 pub use self::generation_policy::GenerationPolicy;
 pub use self::priority::Priority;
+pub use self::query_duration::QueryDuration;
 pub use self::query_policy::QueryPolicy;
 pub use self::read_policy::ReadPolicy;
 pub use self::record_exists_action::RecordExistsAction;
+pub use self::replica::Replica;
 pub use self::scan_policy::ScanPolicy;
+pub use self::tls_policy::TlsPolicy;
 pub use self::write_policy::WritePolicy;
 
 use crate::expressions::FilterExpression;
+use rand::Rng;
+use std::convert::TryFrom;
 use std::option::Option;
 use std::time::{Duration, Instant};
 
@@ -57,16 +73,17 @@ pub trait Policy {
     fn priority(&self) -> &Priority;
 
     #[doc(hidden)]
-    /// Deadline for current transaction based on specified timeout. For internal use only.
+    /// Deadline for current transaction based on the total timeout. For internal use only.
     fn deadline(&self) -> Option<Instant>;
 
-    /// Total transaction timeout for both client and server. The timeout is tracked on the client
-    /// and also sent to the server along with the transaction in the wire protocol. The client
-    /// will most likely timeout first, but the server has the capability to timeout the
-    /// transaction as well.
-    ///
-    /// The timeout is also used as a socket timeout. Default: 0 (no timeout).
-    fn timeout(&self) -> Option<Duration>;
+    /// Socket timeout of each attempt of the transaction: it bounds getting a connection and
+    /// each read and write on its socket. Capped by the time left of the total timeout.
+    fn socket_timeout(&self) -> Option<Duration>;
+
+    /// Total transaction timeout, including all retries. The time left is also sent to the
+    /// server with each attempt, so that the server does not work on the transaction after the
+    /// client gave up on it.
+    fn total_timeout(&self) -> Option<Duration>;
 
     /// Maximum number of retries before aborting the current transaction. A retry may be attempted
     /// when there is a network error. If `max_retries` is exceeded, the abort will occur even if
@@ -76,9 +93,17 @@ pub trait Policy {
     /// Time to sleep between retries. Set to zero to skip sleep. Default: 500ms.
     fn sleep_between_retries(&self) -> Option<Duration>;
 
+    /// Time to sleep before the given retry, starting at 1: `sleep_between_retries` grows by
+    /// `sleep_multiplier` on each retry, up to `max_sleep_between_retries`, and is randomized if
+    /// `retry_jitter` is set.
+    fn sleep_before_retry(&self, retry: usize) -> Option<Duration>;
+
     /// How replicas should be consulted in read operations to provide the desired consistency
     /// guarantee.
     fn consistency_level(&self) -> &ConsistencyLevel;
+
+    /// Replica of the partition that read commands are sent to.
+    fn replica(&self) -> Replica;
 }
 
 #[doc(hidden)]
@@ -100,12 +125,20 @@ where
         self.base().consistency_level()
     }
 
+    fn replica(&self) -> Replica {
+        self.base().replica()
+    }
+
     fn deadline(&self) -> Option<Instant> {
         self.base().deadline()
     }
 
-    fn timeout(&self) -> Option<Duration> {
-        self.base().timeout()
+    fn socket_timeout(&self) -> Option<Duration> {
+        self.base().socket_timeout()
+    }
+
+    fn total_timeout(&self) -> Option<Duration> {
+        self.base().total_timeout()
     }
 
     fn max_retries(&self) -> Option<usize> {
@@ -115,6 +148,10 @@ where
     fn sleep_between_retries(&self) -> Option<Duration> {
         self.base().sleep_between_retries()
     }
+
+    fn sleep_before_retry(&self, retry: usize) -> Option<Duration> {
+        self.base().sleep_before_retry(retry)
+    }
 }
 
 /// Common parameters shared by all policy types.
@@ -130,14 +167,25 @@ pub struct BasePolicy {
     /// read operation.
     pub consistency_level: ConsistencyLevel,
 
-    /// Timeout specifies transaction timeout.
-    /// This timeout is used to set the socket timeout and is also sent to the
-    /// server along with the transaction in the wire protocol.
-    /// Default to no timeout (0).
-    pub timeout: Option<Duration>,
+    /// Replica of the partition that read commands are sent to, and the order of the replicas
+    /// on retries. Commands that write always go to the master. Batches choose the node of each
+    /// key once and are retried on the same node. Default: `Replica::Master`.
+    pub replica: Replica,
+
+    /// Socket timeout of each attempt of the transaction. A new attempt is made if a socket
+    /// read or write times out, unless the total timeout or `max_retries` is exceeded, or the
+    /// transaction is a scan, query or streamed batch read. If not set, each attempt may use the
+    /// time left of the total timeout. Default: 30s.
+    pub socket_timeout: Option<Duration>,
+
+    /// Total timeout of the transaction, including retries. The time left is sent to the server
+    /// along with each attempt in the wire protocol. No timeout if not set. Default: 30s for
+    /// reads and writes, no timeout for scans and queries.
+    pub total_timeout: Option<Duration>,
 
     /// MaxRetries determines maximum number of retries before aborting the current transaction.
-    /// A retry is attempted when there is a network error other than timeout.
+    /// A retry is attempted when there is a network error, including a socket timeout of a
+    /// command that does not stream its records.
     /// If maxRetries is exceeded, the abort will occur even if the timeout
     /// has not yet been exceeded.
     pub max_retries: Option<usize>,
@@ -146,6 +194,18 @@ pub struct BasePolicy {
     /// transaction fails and the timeout was not exceeded.  Enter zero to skip sleep.
     pub sleep_between_retries: Option<Duration>,
 
+    /// Factor by which the sleep grows on each further retry, for an exponential backoff that
+    /// does not add load on a cluster with a partial outage. Default: 1.0 (constant sleep).
+    pub sleep_multiplier: f64,
+
+    /// Longest sleep between retries when the sleep grows by `sleep_multiplier`. No limit if not
+    /// set. Default: None.
+    pub max_sleep_between_retries: Option<Duration>,
+
+    /// Sleep for a random duration of up to the backoff instead, so that the retries of many
+    /// commands that failed at the same time are spread out. Default: false.
+    pub retry_jitter: bool,
+
     /// Optional FilterExpression
     pub filter_expression: Option<FilterExpression>,
 }
@@ -156,14 +216,18 @@ impl Policy for BasePolicy {
     }
 
     fn deadline(&self) -> Option<Instant> {
-        match self.timeout {
+        match self.total_timeout {
             Some(timeout) => Some(Instant::now() + timeout),
             None => None,
         }
     }
 
-    fn timeout(&self) -> Option<Duration> {
-        self.timeout
+    fn socket_timeout(&self) -> Option<Duration> {
+        self.socket_timeout
+    }
+
+    fn total_timeout(&self) -> Option<Duration> {
+        self.total_timeout
     }
 
     fn max_retries(&self) -> Option<usize> {
@@ -174,7 +238,54 @@ impl Policy for BasePolicy {
         self.sleep_between_retries
     }
 
+    fn sleep_before_retry(&self, retry: usize) -> Option<Duration> {
+        let sleep = self.sleep_between_retries?;
+        let exponent = i32::try_from(retry.saturating_sub(1)).unwrap_or(i32::MAX);
+        let mut sleep = sleep.as_secs_f64() * self.sleep_multiplier.max(1.0).powi(exponent);
+        if let Some(max_sleep) = self.max_sleep_between_retries {
+            sleep = sleep.min(max_sleep.as_secs_f64());
+        }
+        if self.retry_jitter {
+            sleep *= rand::thread_rng().gen::<f64>();
+        }
+        // guard against overflowing durations when the sleep is not capped
+        Some(Duration::from_secs_f64(sleep.min(u32::MAX.into())))
+    }
+
     fn consistency_level(&self) -> &ConsistencyLevel {
         &self.consistency_level
     }
+
+    fn replica(&self) -> Replica {
+        self.replica
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{BasePolicy, Policy};
+
+    #[test]
+    fn sleep_before_retry() {
+        let ms = Duration::from_millis;
+        let mut policy = BasePolicy::default();
+        policy.sleep_between_retries = Some(ms(100));
+        assert_eq!(policy.sleep_before_retry(1), Some(ms(100)));
+        assert_eq!(policy.sleep_before_retry(3), Some(ms(100)));
+
+        policy.sleep_multiplier = 2.0;
+        policy.max_sleep_between_retries = Some(ms(300));
+        assert_eq!(policy.sleep_before_retry(1), Some(ms(100)));
+        assert_eq!(policy.sleep_before_retry(2), Some(ms(200)));
+        assert_eq!(policy.sleep_before_retry(3), Some(ms(300)));
+        assert_eq!(policy.sleep_before_retry(usize::MAX), Some(ms(300)));
+
+        policy.retry_jitter = true;
+        assert!(policy.sleep_before_retry(3).unwrap() <= ms(300));
+
+        policy.sleep_between_retries = None;
+        assert_eq!(policy.sleep_before_retry(1), None);
+    }
 }
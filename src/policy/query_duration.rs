@@ -0,0 +1,38 @@
+// Copyright 2015-2023 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+/// Expected duration of a query, which the server uses to choose how to execute the query.
+/// Requires Aerospike Server version >= 6.4.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QueryDuration {
+    /// The query is expected to return more than 100 records per node. The server optimizes for a
+    /// large record set. This is the default.
+    Long,
+
+    /// The query is expected to return less than 100 records per node. The server optimizes for a
+    /// small record set, e.g. a high-selectivity secondary index query, and runs it inline on the
+    /// service threads for much lower latency.
+    Short,
+
+    /// Treat the query as a long query, but relax read consistency for AP namespaces. This value is
+    /// treated exactly like `Long` for server versions < 7.1.
+    LongRelaxAP,
+}
+
+impl Default for QueryDuration {
+    fn default() -> QueryDuration {
+        QueryDuration::Long
+    }
+}
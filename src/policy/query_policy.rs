@@ -14,7 +14,7 @@
 // the License.
 
 use crate::expressions::FilterExpression;
-use crate::policy::{BasePolicy, PolicyLike};
+use crate::policy::{BasePolicy, PolicyLike, QueryDuration};
 
 /// `QueryPolicy` encapsulates parameters for query operations.
 #[derive(Debug, Clone)]
@@ -30,12 +30,35 @@ pub struct QueryPolicy {
 
     /// Number of records to place in queue before blocking. Records received from multiple server
     /// nodes will be placed in a queue. A separate thread consumes these records in parallel. If
-    /// the queue is full, the producer threads will block until records are consumed.
+    /// the queue is full, the producer threads will block until records are consumed, so that
+    /// the memory used by a slow consumer stays bounded. A size of zero is treated as one.
+    ///
+    /// Default: 1024
     pub record_queue_size: usize,
 
     /// Terminate query if cluster is in fluctuating state.
     pub fail_on_cluster_change: bool,
 
+    /// Approximate number of records to return to client. This number is divided by the number of
+    /// nodes involved in the query. The actual number of records returned may be less than
+    /// `max_records` if node record counts are small and unbalanced across nodes. Default: 0 (do
+    /// not limit record count).
+    pub max_records: u64,
+
+    /// Limit returned records per second (RPS) rate for each server node. Useful to throttle
+    /// background queries so that they do not starve latency-sensitive foreground traffic.
+    /// Default: 0 (do not limit the rate).
+    pub records_per_second: u32,
+
+    /// Expected query duration. The server treats the query in different ways depending on the
+    /// expected duration. This field is ignored for aggregation queries, background queries and
+    /// server versions < 6.4. Default: `QueryDuration::Long`.
+    pub expected_duration: QueryDuration,
+
+    /// Whether to return the bin data of the records. Set to false to only return the record
+    /// metadata (key, generation and expiration), e.g. for TTL audits. Default: true.
+    pub include_bin_data: bool,
+
     /// Optional Filter Expression
     pub filter_expression: Option<FilterExpression>,
 }
@@ -55,10 +78,17 @@ impl QueryPolicy {
 impl Default for QueryPolicy {
     fn default() -> Self {
         QueryPolicy {
-            base_policy: BasePolicy::default(),
+            base_policy: BasePolicy {
+                total_timeout: None,
+                ..BasePolicy::default()
+            },
             max_concurrent_nodes: 0,
             record_queue_size: 1024,
             fail_on_cluster_change: true,
+            max_records: 0,
+            records_per_second: 0,
+            expected_duration: QueryDuration::default(),
+            include_bin_data: true,
             filter_expression: None,
         }
     }
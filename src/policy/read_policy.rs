@@ -14,7 +14,7 @@
 // the License.
 
 use crate::expressions::FilterExpression;
-use crate::policy::BasePolicy;
+use crate::policy::{BasePolicy, Replica};
 use crate::{ConsistencyLevel, Priority};
 use std::time::Duration;
 
@@ -26,10 +26,15 @@ impl Default for ReadPolicy {
     fn default() -> ReadPolicy {
         ReadPolicy {
             priority: Priority::Default,
-            timeout: Some(Duration::new(30, 0)),
+            socket_timeout: Some(Duration::new(30, 0)),
+            total_timeout: Some(Duration::new(30, 0)),
             max_retries: Some(2),
             sleep_between_retries: Some(Duration::new(0, 500_000_000)),
+            sleep_multiplier: 1.0,
+            max_sleep_between_retries: None,
+            retry_jitter: false,
             consistency_level: ConsistencyLevel::ConsistencyOne,
+            replica: Replica::default(),
             filter_expression: None,
         }
     }
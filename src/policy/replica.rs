@@ -0,0 +1,40 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+/// `Replica` determines which replica of a partition a read command is sent to.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Replica {
+    /// Read from the node with the master replica of the partition.
+    Master,
+
+    /// Distribute the reads across the nodes of all replicas of the partition, round-robin.
+    Any,
+
+    /// Read from the master replica first, and move on to the next replica of the partition on
+    /// each retry.
+    Sequence,
+
+    /// Read from a node on one of the racks of the client, given by `ClientPolicy::rack_ids` or
+    /// `ClientPolicy::rack_id`, trying the racks in order of preference. Falls back to the
+    /// other replicas in order, starting with the master, if no replica is on those racks, and
+    /// on retries. Requires `ClientPolicy::rack_aware` and servers configured with rack ids.
+    PreferRack,
+}
+
+impl Default for Replica {
+    fn default() -> Replica {
+        Replica::Master
+    }
+}
@@ -19,7 +19,9 @@ use crate::policy::{BasePolicy, PolicyLike};
 /// `ScanPolicy` encapsulates optional parameters used in scan operations.
 #[derive(Debug, Clone)]
 pub struct ScanPolicy {
-    /// Base policy instance
+    /// Base policy instance. Its `socket_timeout` is also sent to the server as the scan's
+    /// socket timeout, i.e. the longest time the server waits for the client to read the next
+    /// records before it aborts the scan. No server timeout if not set.
     pub base_policy: BasePolicy,
 
     /// Percent of data to scan. Valid integer range is 1 to 100. Default is 100.
@@ -34,7 +36,10 @@ pub struct ScanPolicy {
 
     /// Number of records to place in queue before blocking. Records received from multiple server
     /// nodes will be placed in a queue. A separate thread consumes these records in parallel. If
-    /// the queue is full, the producer threads will block until records are consumed.
+    /// the queue is full, the producer threads will block until records are consumed, so that
+    /// the memory used by a slow consumer stays bounded. A size of zero is treated as one.
+    ///
+    /// Default: 1024
     pub record_queue_size: usize,
 
     /// Terminate scan if cluster is in fluctuating state.
@@ -42,10 +47,36 @@ pub struct ScanPolicy {
     pub fail_on_cluster_change: bool,
 
     /// Maximum time in milliseconds to wait when polling socket for availability prior to
-    /// performing an operation on the socket on the server side. Zero means there is no socket
-    /// timeout. Default: 10,000 ms.
+    /// performing an operation on the socket on the server side.
+    /// This is deprecated and won't be sent to the server; the server timeout is taken from
+    /// `base_policy.socket_timeout` instead.
+    #[deprecated(
+        since = "2.0.0",
+        note = "the server scan timeout is taken from `base_policy.socket_timeout`"
+    )]
     pub socket_timeout: u32,
 
+    /// Approximate number of records to return to client. This number is divided by the number of
+    /// nodes involved in the scan. The actual number of records returned may be less than
+    /// `max_records` if node record counts are small and unbalanced across nodes. Default: 0 (do
+    /// not limit record count).
+    pub max_records: u64,
+
+    /// Return an approximately uniform random sample of the set instead of the first records found
+    /// on each node. When set, `max_records` is divided evenly over the partitions instead of the
+    /// nodes, so that every partition contributes about the same number of records. Has no effect
+    /// if `max_records` is zero. Default: false.
+    pub sample: bool,
+
+    /// Limit returned records per second (RPS) rate for each server node. Useful to throttle
+    /// background scans so that they do not starve latency-sensitive foreground traffic.
+    /// Default: 0 (do not limit the rate).
+    pub records_per_second: u32,
+
+    /// Whether to return the bin data of the records. Set to false to only return the record
+    /// metadata (key, generation and expiration), e.g. for TTL audits. Default: true.
+    pub include_bin_data: bool,
+
     /// Optional Filter Expression
     pub filter_expression: Option<FilterExpression>,
 }
@@ -62,15 +93,23 @@ impl ScanPolicy {
     }
 }
 
+#[allow(deprecated)]
 impl Default for ScanPolicy {
     fn default() -> Self {
         ScanPolicy {
-            base_policy: BasePolicy::default(),
+            base_policy: BasePolicy {
+                total_timeout: None,
+                ..BasePolicy::default()
+            },
             scan_percent: 100,
             max_concurrent_nodes: 0,
             record_queue_size: 1024,
             fail_on_cluster_change: true,
             socket_timeout: 10000,
+            max_records: 0,
+            sample: false,
+            records_per_second: 0,
+            include_bin_data: true,
             filter_expression: None,
         }
     }
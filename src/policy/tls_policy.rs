@@ -0,0 +1,36 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+/// `TlsPolicy` encapsulates parameters for TLS connections to the cluster nodes.
+///
+/// TLS connections are provided by the TLS library of the platform, e.g. OpenSSL on Linux, and
+/// require the `native-tls` feature of the crate.
+#[derive(Debug, Clone, Default)]
+pub struct TlsPolicy {
+    /// PEM encoded certificates of the certificate authorities trusted to sign the certificates
+    /// of the servers, in addition to the ones trusted by the system.
+    pub root_certificates: Vec<Vec<u8>>,
+
+    /// PEM encoded certificate chain presented to the servers for mutual TLS authentication,
+    /// e.g. with `AuthMode::PKI`. Requires `client_key`.
+    pub client_certificate: Option<Vec<u8>>,
+
+    /// PEM encoded PKCS #8 private key of `client_certificate`.
+    pub client_key: Option<Vec<u8>>,
+
+    /// Accept server certificates that can not be verified. Only meant for testing, as it makes
+    /// the connections vulnerable to man-in-the-middle attacks. Default: false.
+    pub allow_invalid_certificates: bool,
+}
@@ -58,6 +58,10 @@ pub struct WritePolicy {
     /// (result offset equals bin's operate sequence). This only makes sense when multiple list
     /// operations are used in one operate call and some of those operations do not return results
     /// by default.
+    ///
+    /// When set, a bin targeted by more than one operation is returned as a `Value::List` holding
+    /// exactly one result per operation, in operation order. Operations without a result are
+    /// returned as `Value::Nil`.
     pub respond_per_each_op: bool,
 
     /// If the transaction results in a record deletion, leave a tombstone for the record. This
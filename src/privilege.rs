@@ -0,0 +1,137 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use std::fmt;
+
+/// Permission granted by a privilege.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrivilegeCode {
+    /// Manage users and their roles.
+    UserAdmin = 0,
+
+    /// Manage indexes, user-defined functions and server configuration.
+    SysAdmin = 1,
+
+    /// Manage indexes and user-defined functions.
+    DataAdmin = 2,
+
+    /// Manage user-defined functions.
+    UDFAdmin = 3,
+
+    /// Manage secondary indexes.
+    SIndexAdmin = 4,
+
+    /// Read records.
+    Read = 10,
+
+    /// Read and write records.
+    ReadWrite = 11,
+
+    /// Read and write records, and apply user-defined functions.
+    ReadWriteUDF = 12,
+
+    /// Write records.
+    Write = 13,
+
+    /// Truncate namespaces and sets.
+    Truncate = 14,
+}
+
+impl PrivilegeCode {
+    /// Returns true if privileges with this code can be restricted to a namespace and set. Admin
+    /// privileges always apply to the whole cluster.
+    pub const fn can_scope(self) -> bool {
+        self as u8 >= PrivilegeCode::Read as u8
+    }
+
+    #[doc(hidden)]
+    pub fn from_u8(code: u8) -> Option<Self> {
+        match code {
+            0 => Some(PrivilegeCode::UserAdmin),
+            1 => Some(PrivilegeCode::SysAdmin),
+            2 => Some(PrivilegeCode::DataAdmin),
+            3 => Some(PrivilegeCode::UDFAdmin),
+            4 => Some(PrivilegeCode::SIndexAdmin),
+            10 => Some(PrivilegeCode::Read),
+            11 => Some(PrivilegeCode::ReadWrite),
+            12 => Some(PrivilegeCode::ReadWriteUDF),
+            13 => Some(PrivilegeCode::Write),
+            14 => Some(PrivilegeCode::Truncate),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for PrivilegeCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PrivilegeCode::UserAdmin => "user-admin",
+            PrivilegeCode::SysAdmin => "sys-admin",
+            PrivilegeCode::DataAdmin => "data-admin",
+            PrivilegeCode::UDFAdmin => "udf-admin",
+            PrivilegeCode::SIndexAdmin => "sindex-admin",
+            PrivilegeCode::Read => "read",
+            PrivilegeCode::ReadWrite => "read-write",
+            PrivilegeCode::ReadWriteUDF => "read-write-udf",
+            PrivilegeCode::Write => "write",
+            PrivilegeCode::Truncate => "truncate",
+        }
+        .fmt(f)
+    }
+}
+
+/// Privilege of a role, optionally restricted to a namespace or a set within a namespace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Privilege {
+    /// Permission granted by the privilege.
+    pub code: PrivilegeCode,
+
+    /// Namespace the privilege is restricted to; empty for all namespaces.
+    pub namespace: String,
+
+    /// Set the privilege is restricted to; empty for all sets of the namespace.
+    pub set_name: String,
+}
+
+impl Privilege {
+    /// Create a privilege that applies to all namespaces.
+    pub const fn global(code: PrivilegeCode) -> Self {
+        Privilege {
+            code,
+            namespace: String::new(),
+            set_name: String::new(),
+        }
+    }
+
+    /// Create a privilege restricted to a namespace, or to a set within the namespace if
+    /// `set_name` is not empty.
+    pub fn new(code: PrivilegeCode, namespace: &str, set_name: &str) -> Self {
+        Privilege {
+            code,
+            namespace: namespace.to_string(),
+            set_name: set_name.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Privilege {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.namespace.is_empty(), self.set_name.is_empty()) {
+            (true, _) => write!(f, "{}", self.code),
+            (false, true) => write!(f, "{}.{}", self.code, self.namespace),
+            (false, false) => write!(f, "{}.{}.{}", self.code, self.namespace, self.set_name),
+        }
+    }
+}
@@ -153,7 +153,7 @@ macro_rules! as_contains_range {
 macro_rules! as_within_region {
     ($bin_name:expr, $region:expr) => {{
         let cit = $crate::CollectionIndexType::Default;
-        let region = as_geo!(String::from($region));
+        let region = as_geo!($region.to_string());
         $crate::query::Filter::new(
             $bin_name,
             cit,
@@ -163,7 +163,7 @@ macro_rules! as_within_region {
         )
     }};
     ($bin_name:expr, $region:expr, $cit:expr) => {{
-        let region = as_geo!(String::from($region));
+        let region = as_geo!($region.to_string());
         $crate::query::Filter::new(
             $bin_name,
             $cit,
@@ -180,14 +180,9 @@ macro_rules! as_within_region {
 macro_rules! as_within_radius {
     ($bin_name:expr, $lat:expr, $lng:expr, $radius:expr) => {{
         let cit = $crate::CollectionIndexType::Default;
-        let lat = as_val!($lat as f64);
-        let lng = as_val!($lng as f64);
-        let radius = as_val!($radius as f64);
-        let geo_json = format!(
-            "{{ \"type\": \"Aeroircle\", \"coordinates\": [[{:.8}, {:.8}], {}] }}",
-            lng, lat, radius
-        );
-        let geo_json = as_geo!(geo_json);
+        let center = $crate::GeoPoint::new($lng as f64, $lat as f64);
+        let circle = $crate::AeroCircle::new(center, $radius as f64);
+        let geo_json = as_geo!(circle.to_string());
         $crate::query::Filter::new(
             $bin_name,
             cit,
@@ -197,14 +192,9 @@ macro_rules! as_within_radius {
         )
     }};
     ($bin_name:expr, $lat:expr, $lng:expr, $radius:expr, $cit:expr) => {{
-        let lat = as_val!($lat as f64);
-        let lng = as_val!($lng as f64);
-        let radius = as_val!($radius as f64);
-        let geo_json = format!(
-            "{{ \"type\": \"Aeroircle\", \"coordinates\": [[{:.8}, {:.8}], {}] }}",
-            lng, lat, radius
-        );
-        let geo_json = as_geo!(geo_json);
+        let center = $crate::GeoPoint::new($lng as f64, $lat as f64);
+        let circle = $crate::AeroCircle::new(center, $radius as f64);
+        let geo_json = as_geo!(circle.to_string());
         $crate::query::Filter::new(
             $bin_name,
             $cit,
@@ -221,7 +211,7 @@ macro_rules! as_within_radius {
 macro_rules! as_regions_containing_point {
     ($bin_name:expr, $point:expr) => {{
         let cit = $crate::CollectionIndexType::Default;
-        let point = as_geo!(String::from($point));
+        let point = as_geo!($point.to_string());
         $crate::query::Filter::new(
             $bin_name,
             cit,
@@ -231,7 +221,7 @@ macro_rules! as_regions_containing_point {
         )
     }};
     ($bin_name:expr, $point:expr, $cit:expr) => {{
-        let point = as_geo!(String::from($point));
+        let point = as_geo!($point.to_string());
         $crate::query::Filter::new(
             $bin_name,
             $cit,
@@ -245,6 +235,7 @@ macro_rules! as_regions_containing_point {
 #[cfg(test)]
 mod tests {
     use super::CollectionIndexType;
+    use crate::Value;
 
     #[test]
     fn geo_filter_macros() {
@@ -262,6 +253,12 @@ mod tests {
 
         let geo_filter = as_within_radius!("bin1", 1, 3, 7);
         assert_eq!(geo_filter.bin_name, "bin1");
+        assert_eq!(
+            geo_filter.begin,
+            Value::GeoJSON(
+                r#"{"type": "AeroCircle", "coordinates": [[3.0, 1.0], 7.0]}"#.to_string()
+            )
+        );
 
         let geo_filter = as_within_radius!("bin1", 1, 3, 7, CollectionIndexType::List);
         assert_eq!(geo_filter.bin_name, "bin1");
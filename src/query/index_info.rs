@@ -0,0 +1,177 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use std::str::FromStr;
+
+use crate::errors::{Error, ErrorKind, Result};
+use crate::query::{CollectionIndexType, IndexType};
+
+/// Definition and state of a secondary index, as reported by the `sindex-list` info command.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexInfo {
+    /// Namespace of the index.
+    pub namespace: String,
+
+    /// Name of the index.
+    pub name: String,
+
+    /// Set of the index; empty if the index covers the whole namespace.
+    pub set_name: String,
+
+    /// Name of the indexed bin.
+    pub bin_name: String,
+
+    /// Data type of the indexed values.
+    pub index_type: IndexType,
+
+    /// Collection type of the index.
+    pub collection_index_type: CollectionIndexType,
+
+    /// Base64 encoded CDT context of the indexed values, if the index is on a nested element.
+    pub context: Option<String>,
+
+    /// State of the index on the node, e.g. `RW` once the index has been built, or `WO` while it
+    /// is still being populated.
+    pub state: String,
+}
+
+impl IndexInfo {
+    /// Returns true if the index has been built and can be used in queries.
+    pub fn is_ready(&self) -> bool {
+        self.state == "RW"
+    }
+}
+
+impl FromStr for IndexInfo {
+    type Err = Error;
+
+    /// Parse a single index definition of a `sindex-list` response, i.e. a `:` separated list of
+    /// `key=value` pairs such as `ns=test:indexname=idx:set=demo:bin=foo:type=NUMERIC:state=RW`.
+    fn from_str(s: &str) -> Result<Self> {
+        let mut info = IndexInfo {
+            namespace: String::new(),
+            name: String::new(),
+            set_name: String::new(),
+            bin_name: String::new(),
+            index_type: IndexType::Numeric,
+            collection_index_type: CollectionIndexType::Default,
+            context: None,
+            state: String::new(),
+        };
+
+        for pair in s.split(':') {
+            let mut kv = pair.splitn(2, '=');
+            let (key, value) = match (kv.next(), kv.next()) {
+                (Some(key), Some(value)) => (key, value),
+                _ => continue,
+            };
+            match key {
+                "ns" => info.namespace = value.to_string(),
+                "indexname" => info.name = value.to_string(),
+                "set" if value != "NULL" => info.set_name = value.to_string(),
+                // older servers report the bins of the index as `bins`
+                "bin" | "bins" => info.bin_name = value.to_string(),
+                "type" => info.index_type = parse_index_type(value)?,
+                "indextype" => info.collection_index_type = parse_collection_index_type(value)?,
+                "context" if value != "NULL" => info.context = Some(value.to_string()),
+                "state" => info.state = value.to_string(),
+                _ => (),
+            }
+        }
+
+        if info.namespace.is_empty() || info.name.is_empty() {
+            bail!(ErrorKind::BadResponse(format!(
+                "Invalid secondary index definition: {}",
+                s
+            )));
+        }
+        Ok(info)
+    }
+}
+
+fn parse_index_type(value: &str) -> Result<IndexType> {
+    match value.to_uppercase().as_str() {
+        "NUMERIC" | "INT SIGNED" => Ok(IndexType::Numeric),
+        "STRING" | "TEXT" => Ok(IndexType::String),
+        "GEO2DSPHERE" | "GEOJSON" => Ok(IndexType::Geo2DSphere),
+        _ => bail!(ErrorKind::BadResponse(format!(
+            "Unknown secondary index type: {}",
+            value
+        ))),
+    }
+}
+
+fn parse_collection_index_type(value: &str) -> Result<CollectionIndexType> {
+    match value.to_uppercase().as_str() {
+        "DEFAULT" | "NONE" => Ok(CollectionIndexType::Default),
+        "LIST" => Ok(CollectionIndexType::List),
+        "MAPKEYS" => Ok(CollectionIndexType::MapKeys),
+        "MAPVALUES" => Ok(CollectionIndexType::MapValues),
+        _ => bail!(ErrorKind::BadResponse(format!(
+            "Unknown secondary index collection type: {}",
+            value
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IndexInfo;
+    use crate::query::{CollectionIndexType, IndexType};
+
+    #[test]
+    fn parse_index_info() {
+        let info: IndexInfo = "ns=test:indexname=idx_foo:set=demo:bin=foo:type=NUMERIC:\
+                               indextype=LIST:context=kgHA:exp=null:state=RW"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            info,
+            IndexInfo {
+                namespace: "test".to_string(),
+                name: "idx_foo".to_string(),
+                set_name: "demo".to_string(),
+                bin_name: "foo".to_string(),
+                index_type: IndexType::Numeric,
+                collection_index_type: CollectionIndexType::List,
+                context: Some("kgHA".to_string()),
+                state: "RW".to_string(),
+            }
+        );
+        assert!(info.is_ready());
+    }
+
+    #[test]
+    fn parse_legacy_index_info() {
+        let info: IndexInfo = "ns=test:set=NULL:indexname=idx_bar:num_bins=1:bins=bar:\
+                               type=STRING:indextype=NONE:path=bar:sync_state=synced:state=WO"
+            .parse()
+            .unwrap();
+        assert_eq!(info.set_name, "");
+        assert_eq!(info.bin_name, "bar");
+        assert_eq!(info.index_type, IndexType::String);
+        assert_eq!(info.collection_index_type, CollectionIndexType::Default);
+        assert_eq!(info.context, None);
+        assert!(!info.is_ready());
+    }
+
+    #[test]
+    fn parse_invalid_index_info() {
+        assert!("".parse::<IndexInfo>().is_err());
+        assert!("ns=test:indexname=idx:type=BLOB"
+            .parse::<IndexInfo>()
+            .is_err());
+    }
+}
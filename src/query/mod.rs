@@ -17,13 +17,17 @@
 #![allow(clippy::missing_errors_doc)]
 
 pub use self::filter::Filter;
+pub use self::index_info::IndexInfo;
 pub use self::index_types::{CollectionIndexType, IndexType};
+pub use self::partition_filter::{PartitionFilter, PartitionStatus};
 pub use self::recordset::Recordset;
 pub use self::statement::Statement;
-pub use self::udf::UDFLang;
+pub use self::udf::{UDFLang, UDFMeta};
 
 mod filter;
+mod index_info;
 mod index_types;
+mod partition_filter;
 mod recordset;
 mod statement;
 mod udf;
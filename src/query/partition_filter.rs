@@ -0,0 +1,236 @@
+// Copyright 2015-2018 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use crate::cluster::node::PARTITIONS;
+use crate::errors::{ErrorKind, Result};
+#[cfg(feature = "serialization")]
+use serde::{Deserialize, Serialize};
+
+/// Scan progress of a single partition.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct PartitionStatus {
+    /// Partition id.
+    pub id: u16,
+
+    /// Digest of the last record consumed from this partition, if any. A resumed scan continues
+    /// after this record.
+    pub digest: Option<[u8; 20]>,
+
+    /// Whether all records of the partition have been consumed.
+    pub done: bool,
+}
+
+impl PartitionStatus {
+    /// Create the status of a partition that has not been scanned yet.
+    pub const fn new(id: u16) -> Self {
+        PartitionStatus {
+            id,
+            digest: None,
+            done: false,
+        }
+    }
+}
+
+/// Partition filter used in scans to restrict the scan to a range of partitions. The records of
+/// a namespace are distributed over 4096 partitions; scanning disjoint partition ranges in
+/// different processes allows the work of a scan to be shared.
+///
+/// A partition filter also works as a cursor: the recordset returned by a partition scan tracks
+/// the records consumed from each partition, and `Recordset::partition_filter` returns a filter
+/// that resumes the scan after the last consumed record. The filter can be serialized with the
+/// `serialization` feature to resume the scan in another process.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serialization", derive(Serialize, Deserialize))]
+pub struct PartitionFilter {
+    /// First partition id to scan.
+    pub begin: u16,
+
+    /// Number of partitions to scan.
+    pub count: u16,
+
+    /// Scan progress of each partition in the range. Empty if the filter has not been used in a
+    /// scan yet.
+    pub partitions: Vec<PartitionStatus>,
+}
+
+impl PartitionFilter {
+    /// Create a partition filter for all partitions.
+    pub const fn all() -> Self {
+        PartitionFilter {
+            begin: 0,
+            count: PARTITIONS as u16,
+            partitions: Vec::new(),
+        }
+    }
+
+    /// Create a partition filter for a single partition id (0 - 4095).
+    pub fn by_id(partition_id: u16) -> Result<Self> {
+        PartitionFilter::by_range(partition_id, 1)
+    }
+
+    /// Create a partition filter for the partition range `begin` (0 - 4095) to
+    /// `begin + count - 1`.
+    ///
+    /// # Examples
+    ///
+    /// Split a scan into four partition ranges of equal size.
+    ///
+    /// ```rust
+    /// # use aerospike::*;
+    ///
+    /// let filters: Vec<PartitionFilter> = (0..4)
+    ///     .map(|i| PartitionFilter::by_range(i * 1024, 1024).unwrap())
+    ///     .collect();
+    /// ```
+    pub fn by_range(begin: u16, count: u16) -> Result<Self> {
+        if count == 0 || begin as usize + count as usize > PARTITIONS {
+            bail!(ErrorKind::InvalidArgument(format!(
+                "Invalid partition range: begin {}, count {}",
+                begin, count
+            )));
+        }
+        Ok(PartitionFilter {
+            begin,
+            count,
+            partitions: Vec::new(),
+        })
+    }
+
+    /// Returns true if all partitions of the filter have been scanned completely.
+    pub fn is_done(&self) -> bool {
+        !self.partitions.is_empty() && self.partitions.iter().all(|partition| partition.done)
+    }
+
+    #[doc(hidden)]
+    pub const fn contains(&self, partition_id: u16) -> bool {
+        partition_id >= self.begin && partition_id - self.begin < self.count
+    }
+
+    // Initialize the status of each partition in the range, unless the filter is being resumed.
+    // A resumed filter may have been deserialized from an external source, so its partition
+    // status has to match the partition range.
+    #[doc(hidden)]
+    pub fn init_partitions(&mut self) -> Result<()> {
+        if self.count == 0 || self.begin as usize + self.count as usize > PARTITIONS {
+            bail!(ErrorKind::InvalidArgument(format!(
+                "Invalid partition range: begin {}, count {}",
+                self.begin, self.count
+            )));
+        }
+        if self.partitions.is_empty() {
+            self.partitions = (self.begin..self.begin + self.count)
+                .map(PartitionStatus::new)
+                .collect();
+        } else if self.partitions.len() != self.count as usize
+            || self
+                .partitions
+                .iter()
+                .zip(self.begin..)
+                .any(|(partition, id)| partition.id != id)
+        {
+            bail!(ErrorKind::InvalidArgument(
+                "Partition status does not match the partition range".to_string()
+            ));
+        }
+        Ok(())
+    }
+
+    #[doc(hidden)]
+    pub fn set_digest(&mut self, partition_id: u16, digest: [u8; 20]) {
+        if let Some(partition) = self.status_mut(partition_id) {
+            partition.digest = Some(digest);
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn set_done(&mut self, partition_id: u16) {
+        if let Some(partition) = self.status_mut(partition_id) {
+            partition.done = true;
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn status(&self, partition_id: u16) -> Option<&PartitionStatus> {
+        if !self.contains(partition_id) {
+            return None;
+        }
+        self.partitions
+            .get((partition_id - self.begin) as usize)
+            .filter(|partition| partition.id == partition_id)
+    }
+
+    fn status_mut(&mut self, partition_id: u16) -> Option<&mut PartitionStatus> {
+        if !self.contains(partition_id) {
+            return None;
+        }
+        self.partitions
+            .get_mut((partition_id - self.begin) as usize)
+            .filter(|partition| partition.id == partition_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PartitionFilter, PartitionStatus};
+
+    #[test]
+    fn init_partitions() {
+        let mut filter = PartitionFilter::by_range(10, 3).unwrap();
+        filter.init_partitions().unwrap();
+        let ids: Vec<u16> = filter.partitions.iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec![10, 11, 12]);
+    }
+
+    #[test]
+    fn init_partitions_resume() {
+        let mut filter = PartitionFilter::by_range(10, 3).unwrap();
+        filter.init_partitions().unwrap();
+        filter.set_digest(11, [1; 20]);
+        filter.set_done(12);
+
+        let mut resumed = filter.clone();
+        resumed.init_partitions().unwrap();
+        assert_eq!(resumed, filter);
+    }
+
+    #[test]
+    fn init_partitions_invalid() {
+        let mut filter = PartitionFilter::by_range(10, 3).unwrap();
+        filter.partitions = vec![PartitionStatus::new(10), PartitionStatus::new(11)];
+        assert!(filter.init_partitions().is_err());
+
+        filter.partitions.push(PartitionStatus::new(13));
+        assert!(filter.init_partitions().is_err());
+
+        let mut filter = PartitionFilter::all();
+        filter.count = 4097;
+        assert!(filter.init_partitions().is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "serialization")]
+    fn serialize_cursor() {
+        let mut filter = PartitionFilter::by_range(0, 2).unwrap();
+        filter.init_partitions().unwrap();
+        filter.set_digest(0, [7; 20]);
+        filter.set_done(1);
+
+        let json = serde_json::to_string(&filter).unwrap();
+        let mut resumed: PartitionFilter = serde_json::from_str(&json).unwrap();
+        resumed.init_partitions().unwrap();
+        assert_eq!(resumed, filter);
+    }
+}
@@ -15,26 +15,59 @@
 
 extern crate rand;
 
+use std::cmp;
+use std::future;
+#[cfg(feature = "async")]
+use std::pin::Pin;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Weak;
+#[cfg(feature = "async")]
+use std::task::Context;
+use std::task::{Poll, Waker};
 use std::thread;
 
+#[cfg(feature = "async")]
+use futures_core::Stream;
+
 use crossbeam_queue::SegQueue;
+use parking_lot::Mutex;
 use rand::Rng;
 
+use crate::cluster::partition::Partition;
 use crate::errors::Result;
+use crate::query::PartitionFilter;
 use crate::Record;
 
+enum QueueItem {
+    Record(Result<Record>),
+    PartitionDone(u16),
+}
+
+enum NextRecord {
+    Ready(Result<Record>),
+    Pending,
+    End,
+}
+
 /// Virtual collection of records retrieved through queries and scans. During a query/scan,
 /// multiple threads will retrieve records from the server nodes and put these records on an
 /// internal queue managed by the recordset. The single user thread consumes these records from the
 /// queue.
+///
+/// A scan or query can be cancelled at any time by closing the recordset, e.g. from another
+/// thread, or by dropping it. The node threads then stop sending further requests to the server
+/// nodes, and abort the requests in flight when the next record arrives.
 pub struct Recordset {
     instances: AtomicUsize,
     record_queue_count: AtomicUsize,
     record_queue_size: AtomicUsize,
-    record_queue: SegQueue<Result<Record>>,
+    record_queue: SegQueue<QueueItem>,
     active: AtomicBool,
     task_id: AtomicUsize,
+    partition_filter: Mutex<Option<PartitionFilter>>,
+    waker: Mutex<Option<Waker>>,
+    // Producers waiting for the consumer to make room in the queue.
+    producers: Mutex<Vec<Waker>>,
 }
 
 impl Recordset {
@@ -45,17 +78,23 @@ impl Recordset {
 
         Recordset {
             instances: AtomicUsize::new(nodes),
-            record_queue_size: AtomicUsize::new(rec_queue_size),
+            // a queue without capacity would block the producers forever
+            record_queue_size: AtomicUsize::new(cmp::max(rec_queue_size, 1)),
             record_queue_count: AtomicUsize::new(0),
             record_queue: SegQueue::new(),
             active: AtomicBool::new(true),
             task_id: AtomicUsize::new(task_id),
+            partition_filter: Mutex::new(None),
+            waker: Mutex::new(None),
+            producers: Mutex::new(Vec::new()),
         }
     }
 
-    /// Close the query.
+    /// Close the query. This cancels the scan or query on the server nodes.
     pub fn close(&self) {
-        self.active.store(false, Ordering::Relaxed)
+        self.active.store(false, Ordering::Relaxed);
+        self.wake();
+        self.wake_producers();
     }
 
     /// Check whether the query is still active.
@@ -68,13 +107,71 @@ impl Recordset {
         if self.record_queue_count.fetch_add(1, Ordering::Relaxed)
             < self.record_queue_size.load(Ordering::Relaxed)
         {
-            self.record_queue.push(record);
+            self.record_queue.push(QueueItem::Record(record));
+            self.wake();
             return None;
         }
         self.record_queue_count.fetch_sub(1, Ordering::Relaxed);
         Some(record)
     }
 
+    // Pushes the record, waiting for the consumer to make room in the queue if it is full. The
+    // recordset is only held while pushing, not while waiting, so that the consumer can drop it
+    // to cancel the producer. Returns false if the recordset has been closed or dropped.
+    #[doc(hidden)]
+    pub async fn push_wait(recordset: &Weak<Recordset>, record: Result<Record>) -> bool {
+        let mut record = Some(record);
+        future::poll_fn(|cx| {
+            let recordset = match recordset.upgrade().filter(|r| r.is_active()) {
+                Some(recordset) => recordset,
+                None => return Poll::Ready(false),
+            };
+            let pending = record.take().expect("record pushed after completion");
+            match recordset.push_or_register(pending, cx.waker()) {
+                None => Poll::Ready(true),
+                Some(pending) if recordset.is_active() => {
+                    record = Some(pending);
+                    Poll::Pending
+                }
+                Some(_) => Poll::Ready(false),
+            }
+        })
+        .await
+    }
+
+    // Registers the waker of the producer if the queue is full, and tries again in case the
+    // consumer made room before the waker was registered. Returns the record if the queue is
+    // still full.
+    fn push_or_register(&self, record: Result<Record>, waker: &Waker) -> Option<Result<Record>> {
+        let record = self.push(record)?;
+        self.producers.lock().push(waker.clone());
+        self.push(record)
+    }
+
+    // Queued behind the records of the partition, so the partition is only marked as done once
+    // all of its records have been consumed.
+    #[doc(hidden)]
+    pub fn push_partition_done(&self, partition_id: u16) {
+        if self.partition_filter.lock().is_some() {
+            self.record_queue
+                .push(QueueItem::PartitionDone(partition_id));
+            self.wake();
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn set_partition_filter(&self, partition_filter: PartitionFilter) {
+        *self.partition_filter.lock() = Some(partition_filter);
+    }
+
+    /// Returns the partition filter of a partition scan or query, updated with the records
+    /// consumed from the recordset so far. Pass the filter to a new partition scan or query to
+    /// resume it after the last consumed record. Returns `None` if the recordset was not created
+    /// by a partition scan or query.
+    pub fn partition_filter(&self) -> Option<PartitionFilter> {
+        self.partition_filter.lock().clone()
+    }
+
     /// Returns the task ID for the scan/query.
     pub fn task_id(&self) -> u64 {
         self.task_id.load(Ordering::Relaxed) as u64
@@ -86,6 +183,58 @@ impl Recordset {
             self.close()
         };
     }
+
+    // Wake up the task waiting for records, if the recordset is consumed as a stream.
+    fn wake(&self) {
+        if let Some(waker) = self.waker.lock().take() {
+            waker.wake();
+        }
+    }
+
+    // Wake up the producers waiting for room in the queue.
+    fn wake_producers(&self) {
+        let producers = std::mem::take(&mut *self.producers.lock());
+        for waker in producers {
+            waker.wake();
+        }
+    }
+
+    fn next_record(&self) -> NextRecord {
+        loop {
+            if !self.is_active() && self.record_queue.is_empty() {
+                return NextRecord::End;
+            }
+            match self.record_queue.pop().ok() {
+                Some(QueueItem::Record(result)) => {
+                    self.record_queue_count.fetch_sub(1, Ordering::Relaxed);
+                    self.wake_producers();
+                    if let Ok(Record {
+                        key: Some(ref key), ..
+                    }) = result
+                    {
+                        if let Some(ref mut filter) = *self.partition_filter.lock() {
+                            let partition_id = Partition::new_by_key(key).partition_id;
+                            filter.set_digest(partition_id as u16, key.digest);
+                        }
+                    }
+                    return NextRecord::Ready(result);
+                }
+                Some(QueueItem::PartitionDone(partition_id)) => {
+                    if let Some(ref mut filter) = *self.partition_filter.lock() {
+                        filter.set_done(partition_id);
+                    }
+                }
+                None => return NextRecord::Pending,
+            }
+        }
+    }
+}
+
+// Producers notice that the recordset has been dropped once they are woken up.
+impl Drop for Recordset {
+    fn drop(&mut self) {
+        self.wake_producers();
+    }
 }
 
 impl<'a> Iterator for &'a Recordset {
@@ -93,17 +242,149 @@ impl<'a> Iterator for &'a Recordset {
 
     fn next(&mut self) -> Option<Result<Record>> {
         loop {
-            if self.is_active() || !self.record_queue.is_empty() {
-                let result = self.record_queue.pop().ok();
-                if result.is_some() {
-                    self.record_queue_count.fetch_sub(1, Ordering::Relaxed);
-                    return result;
-                }
-                thread::yield_now();
-                continue;
-            } else {
-                return None;
+            match self.next_record() {
+                NextRecord::Ready(result) => return Some(result),
+                NextRecord::End => return None,
+                NextRecord::Pending => thread::yield_now(),
             }
         }
     }
 }
+
+/// Consume the records of a scan or query asynchronously, e.g. with
+/// `while let Some(record) = (&*recordset).next().await` using `futures::StreamExt`. Requires the
+/// `async` feature.
+#[cfg(feature = "async")]
+impl<'a> Stream for &'a Recordset {
+    type Item = Result<Record>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Record>>> {
+        let recordset = *self.get_mut();
+        let mut next = recordset.next_record();
+        if let NextRecord::Pending = next {
+            *recordset.waker.lock() = Some(cx.waker().clone());
+            // check again, in case a record was pushed before the waker was registered
+            next = recordset.next_record();
+        }
+        match next {
+            NextRecord::Ready(result) => Poll::Ready(Some(result)),
+            NextRecord::End => Poll::Ready(None),
+            NextRecord::Pending => Poll::Pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Recordset;
+    use crate::Record;
+    use std::collections::HashMap;
+
+    fn record() -> Record {
+        Record::new(None, HashMap::new(), 1, 0)
+    }
+
+    #[test]
+    fn push_blocks_when_full() {
+        let recordset = Recordset::new(2, 1);
+        assert!(recordset.push(Ok(record())).is_none());
+        assert!(recordset.push(Ok(record())).is_none());
+        assert!(recordset.push(Ok(record())).is_some());
+
+        assert!((&recordset).next().is_some());
+        assert!(recordset.push(Ok(record())).is_none());
+        assert!(recordset.push(Ok(record())).is_some());
+
+        recordset.signal_end();
+        assert_eq!((&recordset).count(), 2);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn poll_next_wakes_consumer() {
+        use futures_core::Stream;
+        use std::pin::Pin;
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+        use std::task::{Context, Poll, Wake, Waker};
+
+        struct Flag(AtomicBool);
+
+        impl Wake for Flag {
+            fn wake(self: Arc<Self>) {
+                self.0.store(true, Ordering::Relaxed);
+            }
+        }
+
+        let flag = Arc::new(Flag(AtomicBool::new(false)));
+        let waker = Waker::from(flag.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        let recordset = Recordset::new(10, 1);
+        let mut stream = &recordset;
+        assert!(Pin::new(&mut stream).poll_next(&mut cx).is_pending());
+
+        recordset.push(Ok(record()));
+        assert!(flag.0.load(Ordering::Relaxed));
+        match Pin::new(&mut stream).poll_next(&mut cx) {
+            Poll::Ready(Some(Ok(_))) => (),
+            _ => panic!("expected a record"),
+        }
+
+        flag.0.store(false, Ordering::Relaxed);
+        assert!(Pin::new(&mut stream).poll_next(&mut cx).is_pending());
+        recordset.signal_end();
+        assert!(flag.0.load(Ordering::Relaxed));
+        assert!(matches!(
+            Pin::new(&mut stream).poll_next(&mut cx),
+            Poll::Ready(None)
+        ));
+    }
+
+    #[test]
+    fn push_wait_parks_producer() {
+        use std::future::Future;
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+        use std::task::{Context, Poll, Wake, Waker};
+
+        struct Flag(AtomicBool);
+
+        impl Wake for Flag {
+            fn wake(self: Arc<Self>) {
+                self.0.store(true, Ordering::Relaxed);
+            }
+        }
+
+        let flag = Arc::new(Flag(AtomicBool::new(false)));
+        let waker = Waker::from(flag.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        let recordset = Arc::new(Recordset::new(1, 1));
+        let weak = Arc::downgrade(&recordset);
+        assert!(recordset.push(Ok(record())).is_none());
+
+        // the producer waits without being woken until the consumer makes room
+        let mut push = Box::pin(Recordset::push_wait(&weak, Ok(record())));
+        assert!(push.as_mut().poll(&mut cx).is_pending());
+        assert!(!flag.0.load(Ordering::Relaxed));
+        assert!((&*recordset).next().is_some());
+        assert!(flag.0.load(Ordering::Relaxed));
+        assert_eq!(push.as_mut().poll(&mut cx), Poll::Ready(true));
+
+        // dropping the recordset wakes the producer, which gives up
+        flag.0.store(false, Ordering::Relaxed);
+        let mut push = Box::pin(Recordset::push_wait(&weak, Ok(record())));
+        assert!(push.as_mut().poll(&mut cx).is_pending());
+        drop(recordset);
+        assert!(flag.0.load(Ordering::Relaxed));
+        assert_eq!(push.as_mut().poll(&mut cx), Poll::Ready(false));
+    }
+
+    #[test]
+    fn zero_queue_size() {
+        let recordset = Recordset::new(0, 1);
+        assert!(recordset.push(Ok(record())).is_none());
+        assert!(recordset.push(Ok(record())).is_some());
+    }
+}
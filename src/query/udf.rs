@@ -14,16 +14,20 @@
 // the License.
 
 use std::fmt;
+use std::str::FromStr;
+
+use crate::errors::{Error, ErrorKind, Result};
+use crate::info;
 
 /// User-defined function (UDF) language
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum UDFLang {
     /// Lua embedded programming language.
     Lua,
 }
 
 impl fmt::Display for UDFLang {
-    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let s = match *self {
             UDFLang::Lua => "LUA",
         };
@@ -39,3 +43,84 @@ impl<'a> From<UDFLang> for &'a str {
         }
     }
 }
+
+impl FromStr for UDFLang {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_uppercase().as_str() {
+            "LUA" => Ok(UDFLang::Lua),
+            _ => bail!(ErrorKind::BadResponse(format!(
+                "Unknown UDF language: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// Metadata of a user-defined function (UDF) package registered with the cluster, as reported by
+/// the `udf-list` info command.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UDFMeta {
+    /// File name of the package, as used when the package was registered.
+    pub filename: String,
+
+    /// Hash of the package content computed by the server.
+    pub hash: String,
+
+    /// Language of the package.
+    pub language: UDFLang,
+}
+
+impl FromStr for UDFMeta {
+    type Err = Error;
+
+    /// Parse a single package of a `udf-list` response, i.e. a `,` separated list of
+    /// `key=value` pairs such as `filename=example.lua,hash=5e0b...,type=LUA`.
+    fn from_str(s: &str) -> Result<Self> {
+        let fields = info::parse_map_with(s, ',');
+        match (
+            fields.get("filename"),
+            fields.get("hash"),
+            fields.get("type"),
+        ) {
+            (Some(filename), Some(hash), Some(language)) => Ok(UDFMeta {
+                filename: filename.to_string(),
+                hash: hash.to_string(),
+                language: language.parse()?,
+            }),
+            _ => bail!(ErrorKind::BadResponse(format!(
+                "Invalid UDF package definition: {}",
+                s
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{UDFLang, UDFMeta};
+
+    #[test]
+    fn parse_udf_meta() {
+        let meta: UDFMeta = "filename=example.lua,hash=8d5b5d6a8d5f,type=LUA"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            meta,
+            UDFMeta {
+                filename: "example.lua".to_string(),
+                hash: "8d5b5d6a8d5f".to_string(),
+                language: UDFLang::Lua,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_invalid_udf_meta() {
+        assert!("filename=example.lua".parse::<UDFMeta>().is_err());
+        assert!("filename=example.py,hash=abc,type=PYTHON"
+            .parse::<UDFMeta>()
+            .is_err());
+    }
+}
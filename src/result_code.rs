@@ -137,6 +137,9 @@ pub enum ResultCode {
     /// Security credential is invalid.
     InvalidCredential,
 
+    /// Login session has expired.
+    ExpiredSession,
+
     /// Role name is invalid.
     InvalidRole,
 
@@ -146,12 +149,27 @@ pub enum ResultCode {
     /// Privilege is invalid.
     InvalidPrivilege,
 
+    /// Whitelist is invalid.
+    InvalidWhitelist,
+
+    /// Quotas are not enabled on the server.
+    QuotasNotEnabled,
+
+    /// Quota is invalid.
+    InvalidQuota,
+
     /// User must be authentication before performing database operations.
     NotAuthenticated,
 
     /// User does not posses the required role to perform the database operation.
     RoleViolation,
 
+    /// Client address is not allowed by the whitelist of the roles of the user.
+    NotWhitelisted,
+
+    /// Quota of the user has been exceeded.
+    QuotaExceeded,
+
     /// A user defined function returned an error code.
     UdfBadResponse,
 
@@ -254,11 +272,17 @@ impl ResultCode {
             63 => ResultCode::ExpiredPassword,
             64 => ResultCode::ForbiddenPassword,
             65 => ResultCode::InvalidCredential,
+            66 => ResultCode::ExpiredSession,
             70 => ResultCode::InvalidRole,
             71 => ResultCode::RoleAlreadyExists,
             72 => ResultCode::InvalidPrivilege,
+            73 => ResultCode::InvalidWhitelist,
+            74 => ResultCode::QuotasNotEnabled,
+            75 => ResultCode::InvalidQuota,
             80 => ResultCode::NotAuthenticated,
             81 => ResultCode::RoleViolation,
+            82 => ResultCode::NotWhitelisted,
+            83 => ResultCode::QuotaExceeded,
             100 => ResultCode::UdfBadResponse,
             125 => ResultCode::LargeItemNotFound,
             150 => ResultCode::BatchDisabled,
@@ -327,11 +351,17 @@ impl ResultCode {
             ResultCode::ExpiredPassword => String::from("Expired password"),
             ResultCode::ForbiddenPassword => String::from("Forbidden password"),
             ResultCode::InvalidCredential => String::from("Invalid credential"),
+            ResultCode::ExpiredSession => String::from("Login session expired"),
             ResultCode::InvalidRole => String::from("Invalid role"),
             ResultCode::RoleAlreadyExists => String::from("Role already exists"),
             ResultCode::InvalidPrivilege => String::from("Invalid privilege"),
+            ResultCode::InvalidWhitelist => String::from("Invalid whitelist"),
+            ResultCode::QuotasNotEnabled => String::from("Quotas not enabled"),
+            ResultCode::InvalidQuota => String::from("Invalid quota"),
             ResultCode::NotAuthenticated => String::from("Not authenticated"),
             ResultCode::RoleViolation => String::from("Role violation"),
+            ResultCode::NotWhitelisted => String::from("Command not whitelisted"),
+            ResultCode::QuotaExceeded => String::from("Quota exceeded"),
             ResultCode::UdfBadResponse => String::from("Udf returned error"),
             ResultCode::LargeItemNotFound => String::from("Large collection item not found"),
             ResultCode::BatchDisabled => String::from("Batch functionality has been disabled"),
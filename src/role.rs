@@ -0,0 +1,36 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use crate::Privilege;
+
+/// Role and the privileges granted by it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Role {
+    /// Role name.
+    pub name: String,
+
+    /// List of privileges granted by the role.
+    pub privileges: Vec<Privilege>,
+
+    /// List of client IP addresses, or address ranges in CIDR notation, users with this role are
+    /// allowed to connect from. Empty if connections are allowed from any address.
+    pub whitelist: Vec<String>,
+
+    /// Maximum number of records per second users with this role may read; 0 for no limit.
+    pub read_quota: u32,
+
+    /// Maximum number of records per second users with this role may write; 0 for no limit.
+    pub write_quota: u32,
+}
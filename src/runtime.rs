@@ -0,0 +1,235 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use std::future::Future;
+#[cfg(any(feature = "rt-tokio", feature = "rt-async-std"))]
+use std::io;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+use std::time::Duration;
+
+#[cfg(any(feature = "rt-tokio", feature = "rt-async-std"))]
+use crate::errors::Result;
+
+// The commands are implemented once, as futures, and run on the runtime of the client that issued
+// them. The runtime determines the sockets of the connections, and how the commands wait.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Runtime {
+    // Blocking sockets; the futures complete on their first poll, blocking the calling thread.
+    Blocking,
+
+    // Non-blocking sockets, driven by the Tokio runtime of the calling task.
+    #[cfg(feature = "rt-tokio")]
+    Tokio,
+
+    // Non-blocking sockets, driven by the global async-std runtime.
+    #[cfg(feature = "rt-async-std")]
+    AsyncStd,
+}
+
+impl Runtime {
+    // The runtime of the async client: Tokio within the context of a Tokio runtime, async-std
+    // otherwise if it is enabled.
+    #[cfg(all(feature = "rt-tokio", feature = "rt-async-std"))]
+    pub fn current() -> Self {
+        if tokio::runtime::Handle::try_current().is_ok() {
+            Runtime::Tokio
+        } else {
+            Runtime::AsyncStd
+        }
+    }
+
+    #[cfg(all(feature = "rt-tokio", not(feature = "rt-async-std")))]
+    pub const fn current() -> Self {
+        Runtime::Tokio
+    }
+
+    #[cfg(all(feature = "rt-async-std", not(feature = "rt-tokio")))]
+    pub const fn current() -> Self {
+        Runtime::AsyncStd
+    }
+
+    pub async fn sleep(self, duration: Duration) {
+        match self {
+            Runtime::Blocking => thread::sleep(duration),
+            #[cfg(feature = "rt-tokio")]
+            Runtime::Tokio => tokio::time::sleep(duration).await,
+            #[cfg(feature = "rt-async-std")]
+            Runtime::AsyncStd => async_std::task::sleep(duration).await,
+        }
+    }
+
+    // Runs the future in the background: on a new thread with blocking sockets, as a new task of
+    // the runtime otherwise.
+    pub fn spawn<F>(self, future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        match self {
+            Runtime::Blocking => {
+                thread::spawn(move || block_on(future));
+            }
+            #[cfg(feature = "rt-tokio")]
+            Runtime::Tokio => {
+                tokio::spawn(future);
+            }
+            #[cfg(feature = "rt-async-std")]
+            Runtime::AsyncStd => {
+                async_std::task::spawn(future);
+            }
+        }
+    }
+
+    // Runs a blocking function, e.g. the initial tend of the cluster, on a thread where blocking
+    // does not stall the tasks of the runtime.
+    #[cfg(any(feature = "rt-tokio", feature = "rt-async-std"))]
+    pub async fn spawn_blocking<F, T>(self, f: F) -> Result<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        match self {
+            Runtime::Blocking => Ok(f()),
+            #[cfg(feature = "rt-tokio")]
+            Runtime::Tokio => tokio::task::spawn_blocking(f)
+                .await
+                .map_err(|err| crate::errors::ErrorKind::Connection(err.to_string()).into()),
+            #[cfg(feature = "rt-async-std")]
+            Runtime::AsyncStd => Ok(async_std::task::spawn_blocking(f).await),
+        }
+    }
+
+    // Fails the I/O operation with a timeout error if it does not complete in time. Blocking
+    // sockets have their timeouts set on the socket instead.
+    #[cfg(any(feature = "rt-tokio", feature = "rt-async-std"))]
+    pub async fn timeout<T, F>(self, timeout: Option<Duration>, io: F) -> io::Result<T>
+    where
+        F: Future<Output = io::Result<T>>,
+    {
+        match (self, timeout) {
+            (Runtime::Blocking, _) | (_, None) => io.await,
+            #[cfg(feature = "rt-tokio")]
+            (Runtime::Tokio, Some(timeout)) => match tokio::time::timeout(timeout, io).await {
+                Ok(res) => res,
+                Err(_) => Err(io::ErrorKind::TimedOut.into()),
+            },
+            #[cfg(feature = "rt-async-std")]
+            (Runtime::AsyncStd, Some(timeout)) => async_std::io::timeout(timeout, io).await,
+        }
+    }
+}
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+// Runs the future to completion on the calling thread.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = Box::pin(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+// Runs the futures concurrently and waits for all of them to complete.
+pub async fn join_all<F: Future<Output = ()>>(futures: Vec<F>) {
+    let mut futures: Vec<Option<Pin<Box<F>>>> =
+        futures.into_iter().map(|f| Some(Box::pin(f))).collect();
+    std::future::poll_fn(|cx| {
+        let mut pending = false;
+        for slot in &mut futures {
+            if let Some(future) = slot {
+                match future.as_mut().poll(cx) {
+                    Poll::Ready(()) => *slot = None,
+                    Poll::Pending => pending = true,
+                }
+            }
+        }
+        if pending {
+            Poll::Pending
+        } else {
+            Poll::Ready(())
+        }
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{block_on, join_all, Runtime};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    #[test]
+    fn block_on_pending_future() {
+        let (tx, rx) = mpsc::channel();
+        let value = block_on(async move {
+            Runtime::Blocking.spawn(async move {
+                Runtime::Blocking.sleep(Duration::from_millis(10)).await;
+                tx.send(42).unwrap();
+            });
+            // the future is woken up and polled again once the value has been sent
+            let mut received = None;
+            std::future::poll_fn(|cx| match rx.try_recv() {
+                Ok(value) => {
+                    received = Some(value);
+                    std::task::Poll::Ready(())
+                }
+                Err(_) => {
+                    cx.waker().wake_by_ref();
+                    std::task::Poll::Pending
+                }
+            })
+            .await;
+            received.unwrap()
+        });
+        assert_eq!(value, 42);
+    }
+
+    #[test]
+    fn join_all_futures() {
+        let count = AtomicUsize::new(0);
+        let futures = (0..3)
+            .map(|_| async {
+                Runtime::Blocking.sleep(Duration::from_millis(1)).await;
+                count.fetch_add(1, Ordering::Relaxed);
+            })
+            .collect();
+        block_on(join_all(futures));
+        assert_eq!(count.load(Ordering::Relaxed), 3);
+    }
+
+    #[cfg(all(feature = "rt-tokio", feature = "rt-async-std"))]
+    #[test]
+    fn current_runtime() {
+        assert_eq!(Runtime::current(), Runtime::AsyncStd);
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        assert_eq!(rt.block_on(async { Runtime::current() }), Runtime::Tokio);
+    }
+}
@@ -0,0 +1,154 @@
+// Copyright 2015-2023 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use crate::cluster::Cluster;
+use crate::errors::{ErrorKind, Result};
+use crate::runtime;
+#[cfg(feature = "async")]
+use crate::task::TaskFuture;
+use crate::task::{Status, Task};
+use std::sync::Arc;
+
+/// Struct for querying the status of a background query or scan
+#[derive(Debug, Clone)]
+pub struct ExecuteTask {
+    cluster: Arc<Cluster>,
+    task_id: u64,
+}
+
+static STATUS_PATTERN: &str = "status=";
+static NOT_FOUND_PATTERN: &str = "ERROR:2";
+static ERROR_PATTERN: &str = "ERROR:";
+static DELMITER: &str = ":";
+
+impl ExecuteTask {
+    /// Initializes `ExecuteTask` from client, creation should only be expose to Client
+    pub const fn new(cluster: Arc<Cluster>, task_id: u64) -> Self {
+        ExecuteTask { cluster, task_id }
+    }
+
+    /// Id of the background job on the server nodes.
+    pub const fn task_id(&self) -> u64 {
+        self.task_id
+    }
+
+    fn parse_response(response: &str) -> Result<Status> {
+        // The job is unknown to the node. Either it has already completed and been removed from
+        // the job monitor, or it has not been started on this node at all; in both cases there is
+        // nothing left to wait for.
+        if response.starts_with(NOT_FOUND_PATTERN) {
+            return Ok(Status::Complete);
+        }
+        if response.starts_with(ERROR_PATTERN) {
+            bail!(ErrorKind::BadResponse(format!(
+                "Job status query failed. Response: {}",
+                response
+            )));
+        }
+
+        let status_begin = match response.find(STATUS_PATTERN) {
+            None => bail!(ErrorKind::BadResponse(format!(
+                "Job status missing in response. Response: {}",
+                response
+            ))),
+            Some(pattern_index) => pattern_index + STATUS_PATTERN.len(),
+        };
+        let status = match response[status_begin..].find(DELMITER) {
+            None => &response[status_begin..],
+            Some(status_end) => &response[status_begin..status_begin + status_end],
+        };
+
+        // newer servers report "done(ok)", "done(abandoned)", ... while older ones use "DONE"
+        if status.to_lowercase().starts_with("done") {
+            Ok(Status::Complete)
+        } else {
+            Ok(Status::InProgress)
+        }
+    }
+
+    // Queries the status of the background job on all nodes, over the connections of the runtime
+    // of the client if `async_io` is set, over blocking connections otherwise.
+    async fn status(&self, async_io: bool) -> Result<Status> {
+        let nodes = self.cluster.nodes();
+
+        if nodes.is_empty() {
+            bail!(ErrorKind::Connection("No connected node".to_string()))
+        }
+
+        let command = format!("query-show:trid={}", self.task_id);
+        for node in &nodes {
+            let timeout = Some(self.cluster.client_policy().timeout.unwrap());
+            let response = if async_io {
+                node.info_async(timeout, &[&command[..]]).await?
+            } else {
+                node.info(timeout, &[&command[..]])?
+            };
+
+            if !response.contains_key(&command) {
+                return Ok(Status::NotFound);
+            }
+
+            match ExecuteTask::parse_response(&response[&command]) {
+                Ok(Status::Complete) => {}
+                in_progress_or_error => return in_progress_or_error,
+            }
+        }
+        Ok(Status::Complete)
+    }
+}
+
+impl Task for ExecuteTask {
+    /// Query the status of the background job across all nodes
+    fn query_status(&self) -> Result<Status> {
+        runtime::block_on(self.status(false))
+    }
+
+    #[cfg(feature = "async")]
+    fn query_status_async(&self) -> TaskFuture<'_> {
+        TaskFuture::new(self.status(true))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ExecuteTask;
+    use crate::task::Status;
+
+    fn parse(response: &str) -> Option<Status> {
+        ExecuteTask::parse_response(response).ok()
+    }
+
+    #[test]
+    fn parse_response() {
+        assert!(matches!(
+            parse("trid=1234:job-type=basic:status=done(ok):job-progress=100.00"),
+            Some(Status::Complete)
+        ));
+        assert!(matches!(
+            parse("trid=1234:status=DONE"),
+            Some(Status::Complete)
+        ));
+        assert!(matches!(
+            parse("trid=1234:status=active(ok):job-progress=12.50"),
+            Some(Status::InProgress)
+        ));
+        assert!(matches!(
+            parse("ERROR:2:job not found"),
+            Some(Status::Complete)
+        ));
+        assert!(parse("ERROR:4:bad trid").is_none());
+        assert!(parse("trid=1234").is_none());
+    }
+}
@@ -15,6 +15,9 @@
 
 use crate::cluster::Cluster;
 use crate::errors::{ErrorKind, Result};
+use crate::runtime;
+#[cfg(feature = "async")]
+use crate::task::TaskFuture;
 use crate::task::{Status, Task};
 use std::sync::Arc;
 
@@ -78,11 +81,10 @@ impl IndexTask {
             }
         }
     }
-}
 
-impl Task for IndexTask {
-    /// Query the status of index creation across all nodes
-    fn query_status(&self) -> Result<Status> {
+    // Queries the status of the index creation on all nodes, over the connections of the runtime
+    // of the client if `async_io` is set, over blocking connections otherwise.
+    async fn status(&self, async_io: bool) -> Result<Status> {
         let nodes = self.cluster.nodes();
 
         if nodes.is_empty() {
@@ -92,10 +94,12 @@ impl Task for IndexTask {
         for node in &nodes {
             let command =
                 &IndexTask::build_command(self.namespace.to_owned(), self.index_name.to_owned());
-            let response = node.info(
-                Some(self.cluster.client_policy().timeout.unwrap()),
-                &[&command[..]],
-            )?;
+            let timeout = Some(self.cluster.client_policy().timeout.unwrap());
+            let response = if async_io {
+                node.info_async(timeout, &[&command[..]]).await?
+            } else {
+                node.info(timeout, &[&command[..]])?
+            };
 
             if !response.contains_key(command) {
                 return Ok(Status::NotFound);
@@ -109,3 +113,15 @@ impl Task for IndexTask {
         Ok(Status::Complete)
     }
 }
+
+impl Task for IndexTask {
+    /// Query the status of index creation across all nodes
+    fn query_status(&self) -> Result<Status> {
+        runtime::block_on(self.status(false))
+    }
+
+    #[cfg(feature = "async")]
+    fn query_status_async(&self) -> TaskFuture<'_> {
+        TaskFuture::new(self.status(true))
+    }
+}
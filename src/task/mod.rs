@@ -16,11 +16,15 @@
 //! Types and methods used for long running status queries.
 #![allow(clippy::missing_errors_doc)]
 
+pub use self::execute_task::ExecuteTask;
 pub use self::index_task::IndexTask;
 pub use self::register_task::RegisterTask;
 pub use self::task::Status;
 pub use self::task::Task;
+#[cfg(feature = "async")]
+pub use self::task::TaskFuture;
 
+mod execute_task;
 mod index_task;
 mod register_task;
 #[allow(clippy::module_inception)]
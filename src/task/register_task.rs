@@ -15,18 +15,30 @@
 
 use crate::cluster::Cluster;
 use crate::errors::{ErrorKind, Result};
+use crate::info;
+use crate::runtime;
+#[cfg(feature = "async")]
+use crate::task::TaskFuture;
 use crate::task::{Status, Task};
+use crate::UDFMeta;
+use sha1::{Digest, Sha1};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 /// Struct for querying udf register status
 #[derive(Debug, Clone)]
 pub struct RegisterTask {
     cluster: Arc<Cluster>,
     package_name: String,
+    hash: Option<String>,
+    started: Instant,
 }
 
 static COMMAND: &str = "udf-list";
-static RESPONSE_PATTERN: &str = "filename=";
+
+// Time given to the cluster to replace a previous version of the package on all nodes. A node
+// still reporting a different hash afterwards holds a different content than the one registered.
+static HASH_PROPAGATION: Duration = Duration::from_secs(30);
 
 impl RegisterTask {
     /// Initializes `RegisterTask` from client, creation should only be expose to Client
@@ -34,13 +46,62 @@ impl RegisterTask {
         RegisterTask {
             cluster,
             package_name,
+            hash: None,
+            started: Instant::now(),
         }
     }
-}
 
-impl Task for RegisterTask {
-    /// Query the status of index creation across all nodes
-    fn query_status(&self) -> Result<Status> {
+    /// Initializes `RegisterTask` for the given package content. The task only completes once
+    /// every node reports the hash of this content for the package, so that a previously
+    /// registered version of the package, or a corrupted upload, is not mistaken for the new one.
+    /// If a node still reports a different hash 30 seconds after the task was created, querying
+    /// the status fails with a `UdfBadResponse` error.
+    pub fn with_content(cluster: Arc<Cluster>, package_name: String, udf_body: &[u8]) -> Self {
+        RegisterTask {
+            cluster,
+            package_name,
+            hash: Some(RegisterTask::content_hash(udf_body)),
+            started: Instant::now(),
+        }
+    }
+
+    // The server identifies the content of a package by the hex encoded SHA-1 hash of the content.
+    fn content_hash(udf_body: &[u8]) -> String {
+        Sha1::digest(udf_body)
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    // Status of the package on a node, given the packages listed by the node and the time
+    // elapsed since the task was created.
+    fn package_status(
+        package_name: &str,
+        hash: Option<&str>,
+        udfs: &[UDFMeta],
+        elapsed: Duration,
+    ) -> Result<Status> {
+        let udf = match udfs.iter().find(|udf| udf.filename == package_name) {
+            Some(udf) => udf,
+            None => return Ok(Status::InProgress),
+        };
+        match hash {
+            Some(hash) if !hash.eq_ignore_ascii_case(&udf.hash) => {
+                if elapsed < HASH_PROPAGATION {
+                    return Ok(Status::InProgress);
+                }
+                bail!(ErrorKind::UdfBadResponse(format!(
+                    "Package {} has hash {} instead of {}",
+                    package_name, udf.hash, hash
+                )))
+            }
+            _ => Ok(Status::Complete),
+        }
+    }
+
+    // Queries the status of the UDF package registration on all nodes, over the connections of the runtime
+    // of the client if `async_io` is set, over blocking connections otherwise.
+    async fn status(&self, async_io: bool) -> Result<Status> {
         let nodes = self.cluster.nodes();
 
         if nodes.is_empty() {
@@ -48,20 +109,92 @@ impl Task for RegisterTask {
         }
 
         for node in &nodes {
-            let response = node.info(
-                Some(self.cluster.client_policy().timeout.unwrap()),
-                &[&COMMAND[..]],
-            )?;
+            let timeout = Some(self.cluster.client_policy().timeout.unwrap());
+            let response = if async_io {
+                node.info_async(timeout, &[COMMAND]).await?
+            } else {
+                node.info(timeout, &[COMMAND])?
+            };
 
-            if !response.contains_key(COMMAND) {
-                return Ok(Status::NotFound);
-            }
+            let response = match response.get(COMMAND) {
+                Some(response) => response,
+                None => return Ok(Status::NotFound),
+            };
 
-            let response_find = format!("{}{}", RESPONSE_PATTERN, self.package_name);
-            if response[COMMAND].find(&response_find).is_none() {
-                return Ok(Status::InProgress);
+            let udfs: Vec<UDFMeta> = info::parse_list(response)
+                .into_iter()
+                .filter_map(|udf| udf.parse::<UDFMeta>().ok())
+                .collect();
+            let status = RegisterTask::package_status(
+                &self.package_name,
+                self.hash.as_deref(),
+                &udfs,
+                self.started.elapsed(),
+            )?;
+            if status != Status::Complete {
+                return Ok(status);
             }
         }
         Ok(Status::Complete)
     }
 }
+
+impl Task for RegisterTask {
+    /// Query the status of the UDF package registration across all nodes
+    fn query_status(&self) -> Result<Status> {
+        runtime::block_on(self.status(false))
+    }
+
+    #[cfg(feature = "async")]
+    fn query_status_async(&self) -> TaskFuture<'_> {
+        TaskFuture::new(self.status(true))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RegisterTask, HASH_PROPAGATION};
+    use crate::task::Status;
+    use crate::{UDFLang, UDFMeta};
+    use std::time::Duration;
+
+    #[test]
+    fn content_hash() {
+        assert_eq!(
+            RegisterTask::content_hash(b"abc"),
+            "a9993e364706816aba3e25717850c26c9cd0d89d"
+        );
+    }
+
+    #[test]
+    fn package_status() {
+        let hash = "a9993e364706816aba3e25717850c26c9cd0d89d";
+        let udfs = vec![UDFMeta {
+            filename: "test.lua".to_string(),
+            hash: hash.to_uppercase(),
+            language: UDFLang::Lua,
+        }];
+        let status = |package: &str, hash: Option<&str>, elapsed: Duration| {
+            RegisterTask::package_status(package, hash, &udfs, elapsed)
+        };
+        let start = Duration::from_secs(0);
+
+        assert_eq!(status("test.lua", None, start).unwrap(), Status::Complete);
+        assert_eq!(
+            status("test.lua", Some(hash), start).unwrap(),
+            Status::Complete
+        );
+        assert_eq!(
+            status("other.lua", None, start).unwrap(),
+            Status::InProgress
+        );
+
+        // a different hash may be a previous version of the package that is being replaced
+        let other = Some("0000000000000000000000000000000000000000");
+        assert_eq!(
+            status("test.lua", other, start).unwrap(),
+            Status::InProgress
+        );
+        assert!(status("test.lua", other, HASH_PROPAGATION).is_err());
+    }
+}
@@ -14,11 +14,20 @@
 // the License.
 
 use crate::errors::{ErrorKind, Result};
+#[cfg(any(feature = "rt-tokio", feature = "rt-async-std"))]
+use crate::runtime::Runtime;
 use std::thread;
 use std::time::{Duration, Instant};
+#[cfg(feature = "async")]
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
 
 /// Status of task
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Status {
     /// long running task not found
     NotFound,
@@ -58,4 +67,124 @@ pub trait Task {
             }
         }
     }
+
+    /// Returns a future that resolves to the status of the task, like `query_status`. The tasks
+    /// of an `AsyncClient` query the nodes with `Node::info_async`, without blocking the calling
+    /// task.
+    #[cfg(feature = "async")]
+    fn query_status_async(&self) -> TaskFuture<'_>
+    where
+        Self: Sync,
+    {
+        TaskFuture::new(async move { self.query_status() })
+    }
+
+    /// Returns a future that resolves once the query status is complete, an error occurs, or the
+    /// timeout has elapsed. The status is polled with `query_status_async`, sleeping on the
+    /// current Tokio or async-std runtime in between, so the future does not block the executor.
+    #[cfg(any(feature = "rt-tokio", feature = "rt-async-std"))]
+    fn wait_till_complete_async(self, timeout: Option<Duration>) -> TaskFuture<'static>
+    where
+        Self: Sized + Send + Sync + 'static,
+    {
+        TaskFuture::new(async move {
+            let now = Instant::now();
+            let timeout_elapsed = |deadline| now.elapsed() + POLL_INTERVAL > deadline;
+            let runtime = Runtime::current();
+
+            loop {
+                // Sleep first, as in `wait_till_complete`.
+                runtime.sleep(POLL_INTERVAL).await;
+
+                match self.query_status_async().await {
+                    Ok(Status::NotFound) => {
+                        bail!(ErrorKind::BadResponse("task status not found".to_string()))
+                    }
+                    Ok(Status::InProgress) => {} // do nothing and wait
+                    error_or_complete => return error_or_complete,
+                }
+
+                if timeout.map_or(false, timeout_elapsed) {
+                    bail!(ErrorKind::Timeout("Task timeout reached".to_string()))
+                }
+            }
+        })
+    }
+}
+
+/// Future returned by `Task::query_status_async` and `Task::wait_till_complete_async`.
+#[cfg(feature = "async")]
+pub struct TaskFuture<'a> {
+    inner: Pin<Box<dyn Future<Output = Result<Status>> + Send + 'a>>,
+}
+
+#[cfg(feature = "async")]
+impl<'a> TaskFuture<'a> {
+    pub(crate) fn new<F>(future: F) -> Self
+    where
+        F: Future<Output = Result<Status>> + Send + 'a,
+    {
+        TaskFuture {
+            inner: Box::pin(future),
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl fmt::Debug for TaskFuture<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TaskFuture").finish()
+    }
+}
+
+#[cfg(feature = "async")]
+impl Future for TaskFuture<'_> {
+    type Output = Result<Status>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        self.inner.as_mut().poll(cx)
+    }
+}
+
+#[cfg(all(test, feature = "async"))]
+mod tests {
+    use super::{Status, Task};
+    use crate::errors::Result;
+    use crate::runtime;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    // Reports the task as in progress for the given number of queries.
+    struct Countdown(AtomicUsize);
+
+    impl Task for Countdown {
+        fn query_status(&self) -> Result<Status> {
+            match self
+                .0
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            {
+                Ok(_) => Ok(Status::InProgress),
+                Err(_) => Ok(Status::Complete),
+            }
+        }
+    }
+
+    #[test]
+    fn query_status_async() {
+        let task = Countdown(AtomicUsize::new(1));
+        let status = runtime::block_on(task.query_status_async());
+        assert!(matches!(status, Ok(Status::InProgress)));
+        let status = runtime::block_on(task.query_status_async());
+        assert!(matches!(status, Ok(Status::Complete)));
+    }
+
+    #[test]
+    #[cfg(feature = "rt-tokio")]
+    fn wait_till_complete_async() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let status = rt.block_on(Countdown(AtomicUsize::new(1)).wait_till_complete_async(None));
+        assert!(matches!(status, Ok(Status::Complete)));
+    }
 }
@@ -13,10 +13,34 @@
 // limitations under the License.
 
 /// User and assigned roles.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct User {
     /// User name.
     pub user: String,
 
     /// List of assigned roles.
     pub roles: Vec<String>,
+
+    /// Current statistics of the read transactions of the user, by offset:
+    ///
+    /// 0. read quota in records per second
+    /// 1. single record read transaction rate (TPS)
+    /// 2. read scan/query record per second rate (RPS)
+    /// 3. number of limitless read scans/queries
+    ///
+    /// Empty if the server does not report statistics, e.g. if quotas are not enabled.
+    pub read_info: Vec<u32>,
+
+    /// Current statistics of the write transactions of the user, by offset:
+    ///
+    /// 0. write quota in records per second
+    /// 1. single record write transaction rate (TPS)
+    /// 2. write scan/query record per second rate (RPS)
+    /// 3. number of limitless write scans/queries
+    ///
+    /// Empty if the server does not report statistics, e.g. if quotas are not enabled.
+    pub write_info: Vec<u32>,
+
+    /// Number of open connections of the user.
+    pub conns_in_use: u32,
 }
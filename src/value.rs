@@ -17,6 +17,7 @@ use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::fmt;
 use std::hash::{Hash, Hasher};
+use std::mem;
 use std::result::Result as StdResult;
 use std::{f32, f64};
 
@@ -29,7 +30,7 @@ use std::vec::Vec;
 
 use crate::commands::buffer::Buffer;
 use crate::commands::ParticleType;
-use crate::errors::Result;
+use crate::errors::{ErrorKind, Result};
 use crate::msgpack::{decoder, encoder};
 
 #[cfg(feature = "serialization")]
@@ -194,6 +195,16 @@ pub enum Value {
 
     /// HLL value
     HLL(Vec<u8>),
+
+    /// Wildcard value, matching any value. Can only be used in CDT operations and expressions,
+    /// e.g. to match a list element `[x, *]` regardless of its trailing items. Using it as a bin
+    /// value fails with an `InvalidArgument` error. Serialized as the string `"*"`.
+    Wildcard,
+
+    /// Infinity value, greater than any other value. Can only be used in CDT operations and
+    /// expressions, e.g. as the open end of a key or value range. Using it as a bin value fails
+    /// with an `InvalidArgument` error. Serialized as the string `"INF"`.
+    Infinity,
 }
 
 #[allow(clippy::derive_hash_xor_eq)]
@@ -213,6 +224,7 @@ impl Hash for Value {
             Value::List(ref val) => val.hash(state),
             Value::HashMap(_) => panic!("HashMaps cannot be used as map keys."),
             Value::OrderedMap(_) => panic!("OrderedMaps cannot be used as map keys."),
+            Value::Wildcard | Value::Infinity => mem::discriminant(self).hash(state),
         }
     }
 }
@@ -243,6 +255,8 @@ impl Value {
             Value::OrderedMap(_) => panic!("The library never passes ordered maps to the server."),
             Value::GeoJSON(_) => ParticleType::GEOJSON,
             Value::HLL(_) => ParticleType::HLL,
+            // Rejected by estimate_size and write_to before a command is sent.
+            Value::Wildcard | Value::Infinity => ParticleType::NULL,
         }
     }
 
@@ -259,6 +273,8 @@ impl Value {
             Value::List(ref val) => format!("{:?}", val),
             Value::HashMap(ref val) => format!("{:?}", val),
             Value::OrderedMap(ref val) => format!("{:?}", val),
+            Value::Wildcard => "*".to_string(),
+            Value::Infinity => "INF".to_string(),
         }
     }
 
@@ -280,6 +296,10 @@ impl Value {
             Value::OrderedMap(_) => panic!("The library never passes ordered maps to the server."),
             Value::GeoJSON(ref s) => Ok(1 + 2 + s.len()), // flags + ncells + jsonstr
             Value::HLL(ref h) => Ok(h.len()),
+            Value::Wildcard | Value::Infinity => bail!(ErrorKind::InvalidArgument(
+                "Wildcard and Infinity values can only be used in CDT operations and expressions."
+                    .to_string()
+            )),
         }
     }
 
@@ -301,6 +321,10 @@ impl Value {
             Value::List(_) | Value::HashMap(_) => encoder::pack_value(&mut Some(buf), self),
             Value::OrderedMap(_) => panic!("The library never passes ordered maps to the server."),
             Value::GeoJSON(ref val) => buf.write_geo(val),
+            Value::Wildcard | Value::Infinity => bail!(ErrorKind::InvalidArgument(
+                "Wildcard and Infinity values can only be used in CDT operations and expressions."
+                    .to_string()
+            )),
         }
     }
 
@@ -855,6 +879,8 @@ impl Serialize for Value {
                 map.end()
             }
             Value::HLL(b) => serializer.serialize_bytes(&b[..]),
+            Value::Wildcard => serializer.serialize_str("*"),
+            Value::Infinity => serializer.serialize_str("INF"),
         }
     }
 }
@@ -862,6 +888,8 @@ impl Serialize for Value {
 #[cfg(test)]
 mod tests {
     use super::Value;
+    use crate::commands::buffer::Buffer;
+    use crate::msgpack::encoder;
     use std::collections::HashMap;
     use std::convert::TryInto;
 
@@ -896,6 +924,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn wildcard_and_infinity() {
+        for (val, marker) in &[(Value::Wildcard, 0x00), (Value::Infinity, 0x01)] {
+            // packed as an Aerospike msgpack extension inside CDT values
+            let size = encoder::pack_value(&mut None, val).unwrap();
+            let mut buf = Buffer::new(size);
+            buf.resize_buffer(size).unwrap();
+            encoder::pack_value(&mut Some(&mut buf), val).unwrap();
+            assert_eq!(buf.data_buffer, vec![0xd4, 0xff, *marker]);
+
+            // rejected as bin values
+            assert!(val.estimate_size().is_err());
+            assert!(val.write_to(&mut buf).is_err());
+        }
+    }
+
     #[test]
     fn as_geo() {
         let string = String::from(r#"{"type":"Point"}"#);
@@ -931,5 +975,13 @@ mod tests {
         let json = serde_json::to_string(&val);
         // We only check for the len of the String because HashMap serialization does not keep the key order. Comparing like the list above is not possible.
         assert_eq!(json.unwrap().len(), 48, "Map Serialization failed");
+
+        let val: Value = as_list!(Value::Wildcard, Value::Infinity);
+        let json = serde_json::to_string(&val);
+        assert_eq!(
+            json.unwrap(),
+            "[\"*\",\"INF\"]",
+            "Wildcard and Infinity Serialization failed"
+        );
     }
 }
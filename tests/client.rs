@@ -18,7 +18,16 @@ extern crate env_logger;
 extern crate lazy_static;
 extern crate rand;
 
-use aerospike::Client;
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aerospike::{
+    as_bin, as_key, Bins, Client, ClusterEventListener, Feature, Host, Node, ReadPolicy, Resolver,
+    ScanPolicy, WritePolicy,
+};
 
 mod common;
 
@@ -64,3 +73,130 @@ fn close() {
         assert!(false, "Failed to close client");
     }
 }
+
+#[test]
+fn close_gracefully() {
+    let client = Client::new(common::client_policy(), &common::hosts()).unwrap();
+    let key = as_key!(common::namespace(), "test", common::rand_str(10));
+    client
+        .put(&WritePolicy::default(), &key, &[as_bin!("i", 1)])
+        .unwrap();
+
+    let recordset = client
+        .scan(
+            &ScanPolicy::default(),
+            common::namespace(),
+            "test",
+            Bins::None,
+        )
+        .unwrap();
+    let consumer = thread::spawn(move || recordset.filter(Result::is_ok).count());
+    let nodes = client.nodes();
+
+    client.close_gracefully(Duration::from_secs(10)).unwrap();
+    assert!(consumer.join().unwrap() > 0);
+    assert_eq!(client.is_connected(), false);
+    assert!(client.get(&ReadPolicy::default(), &key, Bins::All).is_err());
+
+    // the tend thread ends within one tend interval and closes the nodes
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !client.nodes().is_empty() && Instant::now() < deadline {
+        thread::sleep(Duration::from_millis(50));
+    }
+    assert!(client.nodes().is_empty());
+    assert!(nodes.iter().all(|node| !node.is_active()));
+}
+
+#[test]
+fn seeds() {
+    let client = Client::new(common::client_policy(), &common::hosts()).unwrap();
+    let seeds = client.seeds();
+    assert!(!seeds.is_empty());
+
+    let host = Host::new("10.255.255.1", 3000);
+    client.add_seeds(&vec![host.clone()]).unwrap();
+    client.add_seeds(&vec![host.clone()]).unwrap();
+    assert_eq!(client.seeds().len(), seeds.len() + 1);
+
+    client.remove_seeds(&vec![host]).unwrap();
+    assert_eq!(client.seeds(), seeds);
+    client.close().unwrap();
+}
+
+#[derive(Default)]
+struct NodeCounter {
+    added: AtomicUsize,
+}
+
+impl ClusterEventListener for NodeCounter {
+    fn node_added(&self, _node: &Node) {
+        self.added.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn event_listener() {
+    let listener = Arc::new(NodeCounter::default());
+    let policy = &mut common::client_policy().clone();
+    policy.event_listener = Some(listener.clone());
+    let client = Client::new(policy, &common::hosts()).unwrap();
+
+    assert_eq!(listener.added.load(Ordering::Relaxed), client.nodes().len());
+    client.close().unwrap();
+}
+
+// Resolves any host name to the addresses of the test cluster.
+struct TestResolver {
+    seeds: Vec<SocketAddr>,
+    calls: AtomicUsize,
+}
+
+impl Resolver for TestResolver {
+    fn resolve(&self, _host: &Host) -> aerospike::Result<Vec<SocketAddr>> {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        Ok(self.seeds.clone())
+    }
+}
+
+#[test]
+fn resolver() {
+    let mut seeds = vec![];
+    for host in common::hosts().split(',') {
+        seeds.extend(host.to_socket_addrs().unwrap());
+    }
+    let resolver = Arc::new(TestResolver {
+        seeds,
+        calls: AtomicUsize::new(0),
+    });
+    let policy = &mut common::client_policy().clone();
+    policy.resolver = Some(resolver.clone());
+    let client = Client::new(policy, &"aerospike.invalid:3000").unwrap();
+
+    assert!(client.is_connected());
+    assert!(resolver.calls.load(Ordering::Relaxed) > 0);
+    client.close().unwrap();
+}
+
+#[test]
+fn node_for_key() {
+    let client = common::client();
+    let namespace: &str = common::namespace();
+    let key = as_key!(namespace, "test", common::rand_str(10));
+
+    let node = client.node_for_key(&key).unwrap();
+    let partition_map = client.partition_map();
+    let replicas = &partition_map[namespace];
+    assert_eq!(replicas[0].len(), 4096);
+    assert_eq!(replicas[0][key.partition_id()], node);
+    assert!(node.is_active());
+}
+
+#[test]
+fn node_features() {
+    let client = common::client();
+    for node in client.nodes() {
+        assert_eq!(node.supports(Feature::Float), node.supports_float());
+        assert_eq!(node.supports(Feature::Geo), node.supports_geo());
+        assert!(node.supports(Feature::Peers));
+    }
+}
@@ -16,13 +16,14 @@
 #![allow(dead_code)]
 
 use std::env;
+use std::fs;
 use std::sync::Arc;
 
 use rand;
 use rand::distributions::Alphanumeric;
 use rand::Rng;
 
-use aerospike::{Client, ClientPolicy};
+use aerospike::{Client, ClientPolicy, TlsPolicy};
 
 lazy_static! {
     static ref AEROSPIKE_HOSTS: String =
@@ -37,6 +38,13 @@ lazy_static! {
             policy.set_user_password(user, password).unwrap();
         }
         policy.cluster_name = AEROSPIKE_CLUSTER.clone();
+        // run the tests over TLS if a CA certificate is given; the hosts must be TLS hosts then
+        if let Ok(ca_file) = env::var("AEROSPIKE_TLS_CA_FILE") {
+            policy.tls_policy = Some(TlsPolicy {
+                root_certificates: vec![fs::read(ca_file).unwrap()],
+                ..TlsPolicy::default()
+            });
+        }
         policy
     };
     static ref GLOBAL_CLIENT: Arc<Client> =
@@ -63,3 +71,9 @@ pub fn rand_str(sz: usize) -> String {
     let rng = rand::thread_rng();
     rng.sample_iter(&Alphanumeric).take(sz).collect()
 }
+
+// Security tests only run against servers with security enabled, i.e. when the tests
+// authenticate with a user name.
+pub fn security_enabled() -> bool {
+    client_policy().user_password.is_some()
+}
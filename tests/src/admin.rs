@@ -0,0 +1,250 @@
+// Copyright 2015-2018 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use aerospike::*;
+
+use crate::common;
+use env_logger;
+
+fn user_client(user: &str, password: &str) -> Result<Client> {
+    let mut policy = ClientPolicy::default();
+    policy.set_user_password(user.to_string(), password.to_string())?;
+    policy.cluster_name = common::client_policy().cluster_name.clone();
+    Client::new(&policy, &common::hosts())
+}
+
+#[test]
+fn create_and_drop_user() {
+    let _ = env_logger::try_init();
+    if !common::security_enabled() {
+        return;
+    }
+
+    let client = common::client();
+    let apolicy = AdminPolicy::default();
+    let user = common::rand_str(10);
+
+    client
+        .create_user(&apolicy, &user, "secret", &["read"])
+        .unwrap();
+    assert!(user_client(&user, "secret").is_ok());
+
+    client.set_password(&apolicy, &user, "changed").unwrap();
+    assert!(user_client(&user, "secret").is_err());
+    assert!(user_client(&user, "changed").is_ok());
+
+    client.drop_user(&apolicy, &user).unwrap();
+    assert!(user_client(&user, "changed").is_err());
+}
+
+#[test]
+fn change_own_password() {
+    let _ = env_logger::try_init();
+    if !common::security_enabled() {
+        return;
+    }
+
+    let client = common::client();
+    let apolicy = AdminPolicy::default();
+    let user = common::rand_str(10);
+    client
+        .create_user(&apolicy, &user, "secret", &["read"])
+        .unwrap();
+
+    let own_client = user_client(&user, "secret").unwrap();
+    own_client
+        .change_password(&apolicy, &user, "changed")
+        .unwrap();
+
+    // connections opened after the change authenticate with the new password
+    let rpolicy = ReadPolicy::default();
+    for node in own_client.nodes() {
+        node.info(rpolicy.socket_timeout, &["build"]).unwrap();
+    }
+
+    assert!(user_client(&user, "changed").is_ok());
+
+    client.drop_user(&apolicy, &user).unwrap();
+}
+
+#[test]
+fn create_and_drop_role() {
+    let _ = env_logger::try_init();
+    if !common::security_enabled() {
+        return;
+    }
+
+    let client = common::client();
+    let apolicy = AdminPolicy::default();
+    let namespace = common::namespace();
+    let role = common::rand_str(10);
+    let user = common::rand_str(10);
+
+    client
+        .create_role(
+            &apolicy,
+            &role,
+            &[Privilege::new(PrivilegeCode::Read, namespace, "")],
+            &[],
+            0,
+            0,
+        )
+        .unwrap();
+    client
+        .grant_privileges(
+            &apolicy,
+            &role,
+            &[Privilege::new(PrivilegeCode::Write, namespace, "demo")],
+        )
+        .unwrap();
+    client
+        .revoke_privileges(
+            &apolicy,
+            &role,
+            &[Privilege::new(PrivilegeCode::Write, namespace, "demo")],
+        )
+        .unwrap();
+
+    client.create_user(&apolicy, &user, "secret", &[]).unwrap();
+    client.grant_roles(&apolicy, &user, &[&role]).unwrap();
+    client.revoke_roles(&apolicy, &user, &[&role]).unwrap();
+
+    client.drop_user(&apolicy, &user).unwrap();
+    client.drop_role(&apolicy, &role).unwrap();
+}
+
+#[test]
+fn query_users_and_roles() {
+    let _ = env_logger::try_init();
+    if !common::security_enabled() {
+        return;
+    }
+
+    let client = common::client();
+    let apolicy = AdminPolicy::default();
+    let namespace = common::namespace();
+    let role = common::rand_str(10);
+    let user = common::rand_str(10);
+
+    let privilege = Privilege::new(PrivilegeCode::ReadWrite, namespace, "demo");
+    client
+        .create_role(&apolicy, &role, &[privilege.clone()], &[], 0, 0)
+        .unwrap();
+    client
+        .create_user(&apolicy, &user, "secret", &[&role])
+        .unwrap();
+
+    let found = client.query_role(&apolicy, &role).unwrap().unwrap();
+    assert_eq!(found.name, role);
+    assert_eq!(found.privileges, vec![privilege]);
+    let roles = client.query_roles(&apolicy).unwrap();
+    assert!(roles.iter().any(|r| r.name == role));
+    assert!(roles.iter().any(|r| r.name == "read-write"));
+
+    let found = client.query_user(&apolicy, &user).unwrap().unwrap();
+    assert_eq!(found.user, user);
+    assert_eq!(found.roles, vec![role.clone()]);
+    let users = client.query_users(&apolicy).unwrap();
+    assert!(users.iter().any(|u| u.user == user));
+
+    client.drop_user(&apolicy, &user).unwrap();
+    client.drop_role(&apolicy, &role).unwrap();
+    assert!(client.query_user(&apolicy, &user).is_err());
+}
+
+#[test]
+fn role_quotas() {
+    let _ = env_logger::try_init();
+    if !common::security_enabled() {
+        return;
+    }
+
+    let client = common::client();
+    let apolicy = AdminPolicy::default();
+    let role = common::rand_str(10);
+    let privileges = [Privilege::global(PrivilegeCode::Read)];
+
+    match client.create_role(&apolicy, &role, &privileges, &[], 100, 200) {
+        Err(Error(ErrorKind::ServerError(ResultCode::QuotasNotEnabled), _)) => return,
+        res => res.unwrap(),
+    }
+
+    let found = client.query_role(&apolicy, &role).unwrap().unwrap();
+    assert_eq!((found.read_quota, found.write_quota), (100, 200));
+
+    client.set_quotas(&apolicy, &role, 0, 500).unwrap();
+    let found = client.query_role(&apolicy, &role).unwrap().unwrap();
+    assert_eq!((found.read_quota, found.write_quota), (0, 500));
+
+    client.drop_role(&apolicy, &role).unwrap();
+}
+
+#[test]
+fn external_auth_requires_tls() {
+    let _ = env_logger::try_init();
+
+    let mut policy = common::client_policy().clone();
+    policy.auth_mode = AuthMode::External;
+    policy
+        .set_user_password("ldap-user".to_string(), "secret".to_string())
+        .unwrap();
+    match Client::new(&policy, &common::hosts()) {
+        Err(Error(ErrorKind::InvalidArgument(_), _)) => {}
+        res => panic!("expected invalid argument error, got {:?}", res.err()),
+    }
+}
+
+#[test]
+fn pki_auth_requires_tls() {
+    let _ = env_logger::try_init();
+
+    let mut policy = common::client_policy().clone();
+    policy.auth_mode = AuthMode::PKI;
+    match Client::new(&policy, &common::hosts()) {
+        Err(Error(ErrorKind::InvalidArgument(_), _)) => {}
+        res => panic!("expected invalid argument error, got {:?}", res.err()),
+    }
+}
+
+#[test]
+fn role_whitelist() {
+    let _ = env_logger::try_init();
+    if !common::security_enabled() {
+        return;
+    }
+
+    let client = common::client();
+    let apolicy = AdminPolicy::default();
+    let role = common::rand_str(10);
+    let privileges = [Privilege::global(PrivilegeCode::Read)];
+
+    client
+        .create_role(&apolicy, &role, &privileges, &["10.0.0.1"], 0, 0)
+        .unwrap();
+    let found = client.query_role(&apolicy, &role).unwrap().unwrap();
+    assert_eq!(found.whitelist, vec!["10.0.0.1"]);
+
+    client
+        .set_whitelist(&apolicy, &role, &["10.0.0.2", "10.1.0.0/16"])
+        .unwrap();
+    let found = client.query_role(&apolicy, &role).unwrap().unwrap();
+    assert_eq!(found.whitelist, vec!["10.0.0.2", "10.1.0.0/16"]);
+
+    client.set_whitelist(&apolicy, &role, &[]).unwrap();
+    let found = client.query_role(&apolicy, &role).unwrap().unwrap();
+    assert!(found.whitelist.is_empty());
+
+    client.drop_role(&apolicy, &role).unwrap();
+}
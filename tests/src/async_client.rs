@@ -0,0 +1,178 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use std::future::{poll_fn, Future};
+use std::pin::Pin;
+
+use aerospike::*;
+use futures_core::Stream;
+
+use crate::common;
+use env_logger;
+
+// Runs the test on Tokio if it is enabled, on async-std otherwise.
+#[cfg(feature = "rt-tokio")]
+fn run<F: Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(future)
+}
+
+#[cfg(not(feature = "rt-tokio"))]
+fn run<F: Future>(future: F) -> F::Output {
+    async_std::task::block_on(future)
+}
+
+#[cfg(feature = "rt-tokio")]
+fn spawn<F>(future: F) -> impl Future<Output = F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    let handle = tokio::spawn(future);
+    async { handle.await.unwrap() }
+}
+
+#[cfg(not(feature = "rt-tokio"))]
+fn spawn<F>(future: F) -> impl Future<Output = F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    async_std::task::spawn(future)
+}
+
+async fn client() -> AsyncClient {
+    AsyncClient::new(common::client_policy(), &common::hosts())
+        .await
+        .unwrap()
+}
+
+#[test]
+fn async_put_get_delete() {
+    let _ = env_logger::try_init();
+
+    run(async {
+        let client = client().await;
+        let namespace = common::namespace();
+        let set_name = &common::rand_str(10);
+        let key = as_key!(namespace, set_name, 1);
+        let wpolicy = WritePolicy::default();
+
+        client
+            .put(&wpolicy, &key, &[as_bin!("bin", 42)])
+            .await
+            .unwrap();
+        let record = client
+            .get(&ReadPolicy::default(), &key, Bins::All)
+            .await
+            .unwrap();
+        assert_eq!(record.bins.get("bin"), Some(&as_val!(42)));
+
+        let exists = client
+            .batch_exists(&BatchPolicy::default(), &[key.clone()])
+            .await
+            .unwrap();
+        assert_eq!(exists, vec![true]);
+
+        assert!(client.delete(&wpolicy, &key).await.unwrap());
+        assert!(!client.exists(&wpolicy, &key).await.unwrap());
+        client.close().unwrap();
+    });
+}
+
+#[test]
+fn async_concurrent_commands() {
+    let _ = env_logger::try_init();
+
+    run(async {
+        let client = std::sync::Arc::new(client().await);
+        let namespace = common::namespace();
+        let set_name = common::rand_str(10);
+
+        let tasks: Vec<_> = (0..100i64)
+            .map(|i| {
+                let client = client.clone();
+                let key = as_key!(namespace, &set_name, i);
+                spawn(async move {
+                    let wpolicy = WritePolicy::default();
+                    client
+                        .put(&wpolicy, &key, &[as_bin!("bin", i)])
+                        .await
+                        .unwrap();
+                    client
+                        .get(&ReadPolicy::default(), &key, Bins::All)
+                        .await
+                        .unwrap()
+                })
+            })
+            .collect();
+        for (i, task) in tasks.into_iter().enumerate() {
+            let record = task.await;
+            assert_eq!(record.bins.get("bin"), Some(&as_val!(i as i64)));
+        }
+    });
+}
+
+#[test]
+fn async_scan() {
+    let _ = env_logger::try_init();
+
+    run(async {
+        let client = client().await;
+        let namespace = common::namespace();
+        let set_name = &common::rand_str(10);
+        let wpolicy = WritePolicy::default();
+        for i in 0..100i64 {
+            let key = as_key!(namespace, set_name, i);
+            client
+                .put(&wpolicy, &key, &[as_bin!("bin", i)])
+                .await
+                .unwrap();
+        }
+
+        let rs = client
+            .scan(&ScanPolicy::default(), namespace, set_name, Bins::All)
+            .await
+            .unwrap();
+        let mut stream = &*rs;
+        let mut count = 0;
+        while let Some(res) = poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await {
+            res.unwrap();
+            count += 1;
+        }
+        assert_eq!(count, 100);
+    });
+}
+
+#[test]
+fn async_info() {
+    let _ = env_logger::try_init();
+
+    run(async {
+        let client = client().await;
+        let response = client
+            .info(&ReadPolicy::default(), &["namespaces"])
+            .await
+            .unwrap();
+        assert!(info::parse_list(&response["namespaces"]).contains(&common::namespace()));
+        for node in client.nodes() {
+            let response = node.info_async(None, &["node"]).await.unwrap();
+            assert_eq!(response["node"], node.name());
+        }
+    });
+}
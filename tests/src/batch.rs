@@ -13,9 +13,15 @@
 // License for the specific language governing permissions and limitations under
 // the License.
 
+use aerospike::operations;
+use aerospike::operations::maps;
 use aerospike::BatchRead;
 use aerospike::Bins;
-use aerospike::{as_bin, as_key, BatchPolicy, Concurrency, WritePolicy};
+use aerospike::{
+    as_bin, as_key, as_map, as_val, BatchDelete, BatchPolicy, BatchRecord, BatchWrite,
+    BatchWritePolicy, Concurrency, GenerationPolicy, Key, MapReturnType, ReadPolicy,
+    RecordExistsAction, Replica, ResultCode, Value, WritePolicy,
+};
 
 use env_logger;
 
@@ -77,6 +83,443 @@ fn batch_get() {
 
     let result = results.remove(0);
     assert_eq!(result.key, key4);
+    assert_eq!(result.result_code, Some(ResultCode::KeyNotFoundError));
     let record = result.record;
     assert!(record.is_none());
 }
+
+#[test]
+fn batch_get_ops() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace: &str = common::namespace();
+    let set_name = &common::rand_str(10);
+    let bpolicy = BatchPolicy::default();
+    let wpolicy = WritePolicy::default();
+
+    let map = as_map!("name" => "Jane", "age" => 42);
+    let bin1 = as_bin!("map", map);
+    let bin2 = as_bin!("b", 7);
+
+    let key1 = as_key!(namespace, set_name, 1);
+    client.put(&wpolicy, &key1, &[&bin1, &bin2]).unwrap();
+
+    let key2 = as_key!(namespace, set_name, 2);
+    client.put(&wpolicy, &key2, &[&bin1, &bin2]).unwrap();
+
+    let key3 = as_key!(namespace, set_name, 3);
+    client.put(&wpolicy, &key3, &[&bin1, &bin2]).unwrap();
+
+    let map_key = as_val!("name");
+    let ops = vec![maps::get_by_key("map", &map_key, MapReturnType::Value)];
+    let selected = Bins::from(["b"]);
+    let all = Bins::All;
+
+    let batch = vec![
+        BatchRead::with_ops(key1.clone(), &ops),
+        BatchRead::new(key2.clone(), &selected),
+        BatchRead::new(key3.clone(), &all),
+    ];
+    let mut results = client.batch_get(&bpolicy, batch).unwrap();
+
+    let result = results.remove(0);
+    assert_eq!(result.key, key1);
+    let record = result.record.unwrap();
+    assert_eq!(record.bins.keys().count(), 1);
+    assert_eq!(record.bins.get("map"), Some(&Value::from("Jane")));
+
+    let result = results.remove(0);
+    assert_eq!(result.key, key2);
+    let record = result.record.unwrap();
+    assert_eq!(record.bins.keys().count(), 1);
+    assert_eq!(record.bins.get("b"), Some(&Value::from(7)));
+
+    let result = results.remove(0);
+    assert_eq!(result.key, key3);
+    let record = result.record.unwrap();
+    assert_eq!(record.bins.keys().count(), 2);
+
+    let bin3 = as_bin!("c", 1);
+    let write_ops = vec![operations::put(&bin3)];
+    let batch = vec![BatchRead::with_ops(key1.clone(), &write_ops)];
+    assert!(client.batch_get(&bpolicy, batch).is_err());
+}
+
+#[test]
+fn batch_get_policy_flags() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace: &str = common::namespace();
+    let set_name = &common::rand_str(10);
+    let mut bpolicy = BatchPolicy::default();
+    bpolicy.concurrency = Concurrency::MaxThreads(2);
+    bpolicy.allow_inline = false;
+    bpolicy.allow_inline_ssd = true;
+    bpolicy.respond_all_keys = false;
+    let wpolicy = WritePolicy::default();
+
+    let bin = as_bin!("a", 1);
+    let all = Bins::All;
+    let mut batch = vec![];
+    for i in 0..10 {
+        let key = as_key!(namespace, set_name, i);
+        client.put(&wpolicy, &key, &[&bin]).unwrap();
+        batch.push(BatchRead::new(key, &all));
+    }
+    batch.push(BatchRead::new(as_key!(namespace, set_name, -1), &all));
+
+    let results = client.batch_get(&bpolicy, batch).unwrap();
+    assert_eq!(results.len(), 11);
+    for result in &results[..10] {
+        let record = result.record.as_ref().unwrap();
+        assert_eq!(record.bins.get("a"), Some(&Value::from(1)));
+    }
+    assert!(results[10].record.is_none());
+}
+
+#[test]
+fn batch_exists() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace: &str = common::namespace();
+    let set_name = &common::rand_str(10);
+    let bpolicy = BatchPolicy::default();
+    let wpolicy = WritePolicy::default();
+
+    let bin = as_bin!("a", 1);
+    let mut keys = vec![];
+    for i in 0..5 {
+        let key = as_key!(namespace, set_name, i);
+        client.put(&wpolicy, &key, &[&bin]).unwrap();
+        keys.push(key);
+    }
+    keys.push(as_key!(namespace, set_name, -1));
+    // last key does not exist
+
+    let exists = client.batch_exists(&bpolicy, &keys).unwrap();
+    assert_eq!(exists, vec![true, true, true, true, true, false]);
+}
+
+#[test]
+fn batch_get_replicas() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace: &str = common::namespace();
+    let set_name = &common::rand_str(10);
+    let wpolicy = WritePolicy::default();
+
+    let mut keys = vec![];
+    for i in 0..20 {
+        let key = as_key!(namespace, set_name, i);
+        client.put(&wpolicy, &key, &[as_bin!("i", i)]).unwrap();
+        keys.push(key);
+    }
+
+    for replica in &[
+        Replica::Master,
+        Replica::Any,
+        Replica::Sequence,
+        Replica::PreferRack,
+    ] {
+        let mut bpolicy = BatchPolicy::default();
+        bpolicy.base_policy.replica = *replica;
+        let exists = client.batch_exists(&bpolicy, &keys).unwrap();
+        assert!(exists.into_iter().all(|exists| exists));
+
+        // writes ignore the replica policy
+        let records = vec![BatchRecord::from(BatchDelete::new(keys[0].clone()))];
+        let records = client.batch_operate(&bpolicy, records).unwrap();
+        assert_eq!(records[0].result_code(), Some(ResultCode::Ok));
+        client.put(&wpolicy, &keys[0], &[as_bin!("i", 0)]).unwrap();
+    }
+}
+
+#[test]
+fn batch_get_header() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace: &str = common::namespace();
+    let set_name = &common::rand_str(10);
+    let bpolicy = BatchPolicy::default();
+    let wpolicy = WritePolicy::default();
+
+    let bin = as_bin!("a", 1);
+    let key1 = as_key!(namespace, set_name, 1);
+    client.put(&wpolicy, &key1, &[&bin]).unwrap();
+    let key2 = as_key!(namespace, set_name, 2);
+    client.put(&wpolicy, &key2, &[&bin]).unwrap();
+    client.put(&wpolicy, &key2, &[&bin]).unwrap();
+    let key3 = as_key!(namespace, set_name, -1);
+    // key does not exist
+
+    let keys = vec![key1, key2, key3];
+    let mut headers = client.batch_get_header(&bpolicy, &keys).unwrap();
+    assert_eq!(headers.len(), 3);
+
+    let record = headers.remove(0).unwrap();
+    assert_eq!(record.generation, 1);
+    assert!(record.bins.is_empty());
+
+    let record = headers.remove(0).unwrap();
+    assert_eq!(record.generation, 2);
+    assert!(record.bins.is_empty());
+
+    assert!(headers.remove(0).is_none());
+}
+
+#[test]
+fn batch_get_stream() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace: &str = common::namespace();
+    let set_name = &common::rand_str(10);
+    let mut bpolicy = BatchPolicy::default();
+    bpolicy.record_queue_size = 10;
+    let wpolicy = WritePolicy::default();
+
+    let mut keys = vec![];
+    for i in 0..100 {
+        let key = as_key!(namespace, set_name, i);
+        let bin = as_bin!("i", i);
+        client.put(&wpolicy, &key, &[&bin]).unwrap();
+        keys.push(key);
+    }
+    keys.push(as_key!(namespace, set_name, -1));
+    // last key does not exist
+
+    let recordset = client.batch_get_stream(&bpolicy, &keys, Bins::All).unwrap();
+    let mut count = 0;
+    for record in &*recordset {
+        let record = record.unwrap();
+        assert!(record.key.is_some());
+        assert!(record.bins.contains_key("i"));
+        count += 1;
+    }
+    assert_eq!(count, 100);
+}
+
+#[test]
+fn batch_get_chunked() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace: &str = common::namespace();
+    let set_name = &common::rand_str(10);
+    let mut bpolicy = BatchPolicy::default();
+    bpolicy.concurrency = Concurrency::Parallel;
+    bpolicy.max_keys_per_command = 3;
+    bpolicy.max_commands_per_node = 2;
+    let wpolicy = WritePolicy::default();
+
+    let all = Bins::All;
+    let mut batch = vec![];
+    for i in 0..20 {
+        let key = as_key!(namespace, set_name, i);
+        let bin = as_bin!("i", i);
+        client.put(&wpolicy, &key, &[&bin]).unwrap();
+        batch.push(BatchRead::new(key, &all));
+    }
+
+    let results = client.batch_get(&bpolicy, batch).unwrap();
+    assert_eq!(results.len(), 20);
+    for (i, result) in results.iter().enumerate() {
+        let record = result.record.as_ref().unwrap();
+        assert_eq!(record.bins.get("i"), Some(&Value::from(i as i64)));
+    }
+}
+
+#[test]
+fn batch_write() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace: &str = common::namespace();
+    let set_name = &common::rand_str(10);
+    let mut bpolicy = BatchPolicy::default();
+    bpolicy.concurrency = Concurrency::Parallel;
+    let rpolicy = ReadPolicy::default();
+    let wpolicy = WritePolicy::default();
+
+    let key1 = as_key!(namespace, set_name, 1);
+    let key2 = as_key!(namespace, set_name, 2);
+    let key3 = as_key!(namespace, set_name, 3);
+    for key in &[&key1, &key2, &key3] {
+        client.delete(&wpolicy, key).unwrap();
+    }
+
+    let bin1 = as_bin!("a", "a value");
+    let bin2 = as_bin!("b", 1);
+    let ops = vec![
+        operations::put(&bin1),
+        operations::add(&bin2),
+        operations::get_bin("b"),
+    ];
+    let mut update_only = BatchWritePolicy::default();
+    update_only.record_exists_action = RecordExistsAction::UpdateOnly;
+
+    let batch = vec![
+        BatchWrite::new(key1.clone(), &ops),
+        BatchWrite::new(key2.clone(), &ops),
+        BatchWrite::with_policy(&update_only, key3.clone(), &ops),
+    ];
+    let mut results = client.batch_write(&bpolicy, batch).unwrap();
+
+    for key in &[&key1, &key2] {
+        let result = results.remove(0);
+        assert_eq!(&result.key, *key);
+        assert_eq!(result.result_code, Some(ResultCode::Ok));
+        let record = result.record.unwrap();
+        assert_eq!(
+            record.bins.get("b"),
+            Some(&Value::from(vec![Value::Nil, Value::from(1)]))
+        );
+
+        let record = client.get(&rpolicy, key, Bins::All).unwrap();
+        assert_eq!(record.bins.get("a"), Some(&Value::from("a value")));
+        assert_eq!(record.bins.get("b"), Some(&Value::from(1)));
+    }
+
+    let result = results.remove(0);
+    assert_eq!(result.key, key3);
+    assert_eq!(result.result_code, Some(ResultCode::KeyNotFoundError));
+    assert!(result.record.is_none());
+    assert!(!client.exists(&wpolicy, &key3).unwrap());
+
+    let reads = vec![operations::get_bin("a")];
+    let batch = vec![BatchWrite::new(key1.clone(), &reads)];
+    assert!(client.batch_write(&bpolicy, batch).is_err());
+}
+
+#[test]
+fn batch_delete() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace: &str = common::namespace();
+    let set_name = &common::rand_str(10);
+    let bpolicy = BatchPolicy::default();
+    let wpolicy = WritePolicy::default();
+
+    let bin = as_bin!("a", 1);
+    let key1 = as_key!(namespace, set_name, 1);
+    client.put(&wpolicy, &key1, &[&bin]).unwrap();
+    let key2 = as_key!(namespace, set_name, 2);
+    client.put(&wpolicy, &key2, &[&bin]).unwrap();
+    let key3 = as_key!(namespace, set_name, -1);
+    // key does not exist
+
+    let keys: Vec<Key> = vec![key1.clone(), key2.clone(), key3.clone()];
+    let results = client.batch_delete(&bpolicy, &keys).unwrap();
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].key, key1);
+    assert_eq!(results[0].result_code, Some(ResultCode::Ok));
+    assert_eq!(results[1].key, key2);
+    assert_eq!(results[1].result_code, Some(ResultCode::Ok));
+    assert_eq!(results[2].key, key3);
+    assert_eq!(results[2].result_code, Some(ResultCode::KeyNotFoundError));
+
+    assert!(!client.exists(&wpolicy, &key1).unwrap());
+    assert!(!client.exists(&wpolicy, &key2).unwrap());
+}
+
+#[test]
+fn batch_operate() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace: &str = common::namespace();
+    let set_name = &common::rand_str(10);
+    let bpolicy = BatchPolicy::default();
+    let rpolicy = ReadPolicy::default();
+    let wpolicy = WritePolicy::default();
+
+    let bin = as_bin!("a", 1);
+    let key1 = as_key!(namespace, set_name, 1);
+    client.put(&wpolicy, &key1, &[&bin]).unwrap();
+    let key2 = as_key!(namespace, set_name, 2);
+    client.put(&wpolicy, &key2, &[&bin]).unwrap();
+    let key3 = as_key!(namespace, set_name, 3);
+    client.put(&wpolicy, &key3, &[&bin]).unwrap();
+
+    let all = Bins::All;
+    let bin2 = as_bin!("b", "new value");
+    let ops = vec![operations::put(&bin2)];
+    let batch = vec![
+        BatchRecord::from(BatchRead::new(key1.clone(), &all)),
+        BatchRecord::from(BatchWrite::new(key2.clone(), &ops)),
+        BatchRecord::from(BatchDelete::new(key3.clone())),
+    ];
+    let results = client.batch_operate(&bpolicy, batch).unwrap();
+    assert_eq!(results.len(), 3);
+
+    assert_eq!(results[0].key(), &key1);
+    assert_eq!(results[0].result_code(), Some(ResultCode::Ok));
+    assert!(!results[0].in_doubt());
+    let record = results[0].record().unwrap();
+    assert_eq!(record.bins.get("a"), Some(&Value::from(1)));
+
+    match results[1] {
+        BatchRecord::Write(ref batch_write) => {
+            assert_eq!(batch_write.key, key2);
+            assert_eq!(batch_write.result_code, Some(ResultCode::Ok));
+            assert!(!batch_write.in_doubt);
+        }
+        _ => panic!("Expected batch write result"),
+    }
+    let record = client.get(&rpolicy, &key2, Bins::All).unwrap();
+    assert_eq!(record.bins.get("b"), Some(&Value::from("new value")));
+
+    match results[2] {
+        BatchRecord::Delete(ref batch_delete) => {
+            assert_eq!(batch_delete.key, key3);
+            assert_eq!(batch_delete.result_code, Some(ResultCode::Ok));
+        }
+        _ => panic!("Expected batch delete result"),
+    }
+    assert!(!client.exists(&wpolicy, &key3).unwrap());
+}
+
+#[test]
+fn batch_operate_result_codes() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace: &str = common::namespace();
+    let set_name = &common::rand_str(10);
+    let bpolicy = BatchPolicy::default();
+    let wpolicy = WritePolicy::default();
+
+    let bin = as_bin!("a", 1);
+    let key1 = as_key!(namespace, set_name, 1);
+    client.put(&wpolicy, &key1, &[&bin]).unwrap();
+    let key2 = as_key!(namespace, set_name, 2);
+    client.put(&wpolicy, &key2, &[&bin]).unwrap();
+    let key3 = as_key!(namespace, set_name, -1);
+    // key does not exist
+
+    let mut gen_policy = BatchWritePolicy::default();
+    gen_policy.generation_policy = GenerationPolicy::ExpectGenEqual;
+    gen_policy.generation = 100;
+    let all = Bins::All;
+    let bin2 = as_bin!("b", 2);
+    let ops = vec![operations::put(&bin2)];
+    let batch = vec![
+        BatchRecord::from(BatchWrite::with_policy(&gen_policy, key1.clone(), &ops)),
+        BatchRecord::from(BatchWrite::new(key2.clone(), &ops)),
+        BatchRecord::from(BatchRead::new(key3.clone(), &all)),
+    ];
+    let results = client.batch_operate(&bpolicy, batch).unwrap();
+
+    assert_eq!(results[0].result_code(), Some(ResultCode::GenerationError));
+    assert!(results[0].record().is_none());
+    assert!(!results[0].in_doubt());
+    assert_eq!(results[1].result_code(), Some(ResultCode::Ok));
+    assert_eq!(results[2].result_code(), Some(ResultCode::KeyNotFoundError));
+    assert!(results[2].record().is_none());
+}
@@ -17,8 +17,8 @@ use crate::common;
 use env_logger;
 
 use aerospike::operations::bitwise;
-use aerospike::operations::bitwise::{BitPolicy, BitwiseOverflowActions};
-use aerospike::{as_key, Value, WritePolicy};
+use aerospike::operations::bitwise::{BitPolicy, BitwiseOverflowActions, BitwiseResizeFlags};
+use aerospike::{as_bin, as_key, Value, WritePolicy};
 
 #[test]
 fn cdt_bitwise() {
@@ -190,3 +190,87 @@ fn cdt_bitwise() {
     let rec = client.operate(&wpolicy, &key, ops).unwrap();
     assert_eq!(*rec.bins.get("bin").unwrap(), Value::Int(7));
 }
+
+#[test]
+fn cdt_bitwise_variants() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = &common::rand_str(10);
+
+    let wpolicy = WritePolicy::default();
+    let key = as_key!(namespace, set_name, -1);
+    let bpolicy = BitPolicy::default();
+
+    client.delete(&wpolicy, &key).unwrap();
+    let bin = as_bin!("bin", Value::Blob(vec![0b00000001, 0b01000010]));
+    client.put(&wpolicy, &key, &vec![&bin]).unwrap();
+
+    // Verify resize from the front
+    let ops = &vec![
+        bitwise::resize("bin", 4, Some(BitwiseResizeFlags::FromFront), &bpolicy),
+        bitwise::get("bin", 0, 32),
+    ];
+    let rec = client.operate(&wpolicy, &key, ops).unwrap();
+    assert_eq!(
+        *rec.bins.get("bin").unwrap(),
+        Value::Blob(vec![0b00000000, 0b00000000, 0b00000001, 0b01000010])
+    );
+
+    // Shrink only must not grow the bitmap
+    let ops = &vec![bitwise::resize(
+        "bin",
+        6,
+        Some(BitwiseResizeFlags::ShrinkOnly),
+        &bpolicy,
+    )];
+    assert!(client.operate(&wpolicy, &key, ops).is_err());
+
+    // Verify subtract overflow actions
+    let ops = &vec![bitwise::subtract(
+        "bin",
+        0,
+        8,
+        1,
+        false,
+        BitwiseOverflowActions::Fail,
+        &bpolicy,
+    )];
+    assert!(client.operate(&wpolicy, &key, ops).is_err());
+
+    let ops = &vec![
+        bitwise::subtract(
+            "bin",
+            0,
+            8,
+            1,
+            false,
+            BitwiseOverflowActions::Saturate,
+            &bpolicy,
+        ),
+        bitwise::get_int("bin", 0, 8, false),
+    ];
+    let rec = client.operate(&wpolicy, &key, ops).unwrap();
+    assert_eq!(*rec.bins.get("bin").unwrap(), Value::Int(0));
+
+    let ops = &vec![
+        bitwise::subtract(
+            "bin",
+            0,
+            8,
+            1,
+            false,
+            BitwiseOverflowActions::Wrap,
+            &bpolicy,
+        ),
+        bitwise::get_int("bin", 0, 8, false),
+    ];
+    let rec = client.operate(&wpolicy, &key, ops).unwrap();
+    assert_eq!(*rec.bins.get("bin").unwrap(), Value::Int(255));
+
+    // Verify signed get int
+    let ops = &vec![bitwise::get_int("bin", 0, 8, true)];
+    let rec = client.operate(&wpolicy, &key, ops).unwrap();
+    assert_eq!(*rec.bins.get("bin").unwrap(), Value::Int(-1));
+}
@@ -17,8 +17,11 @@ use crate::common;
 use env_logger;
 
 use aerospike::operations;
+use aerospike::operations::cdt_context::{ctx_list_index, ctx_list_index_create};
 use aerospike::operations::lists;
-use aerospike::operations::lists::{ListPolicy, ListReturnType, ListSortFlags};
+use aerospike::operations::lists::{
+    ListOrderType, ListPolicy, ListReturnType, ListSortFlags, ListWriteFlags,
+};
 use aerospike::{as_bin, as_key, as_list, as_val, as_values, Bins, ReadPolicy, Value, WritePolicy};
 
 #[test]
@@ -449,3 +452,182 @@ fn cdt_list() {
     let rec = client.operate(&wpolicy, &key, ops).unwrap();
     assert_eq!(*rec.bins.get("bin").unwrap(), as_list!(8, 9));
 }
+
+#[test]
+fn cdt_list_ctx_create() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = &common::rand_str(10);
+
+    let wpolicy = WritePolicy::default();
+    let key = as_key!(namespace, set_name, -1);
+    let wbin = as_bin!("bin", as_list!(as_list!(1)));
+    let bins = vec![&wbin];
+    let lpolicy = ListPolicy::default();
+
+    client.delete(&wpolicy, &key).unwrap();
+    client.put(&wpolicy, &key, &bins).unwrap();
+
+    let ctx = &vec![ctx_list_index_create(2, ListOrderType::Unordered, true)];
+    let val = as_val!(5);
+    let ops = &vec![
+        lists::append(&lpolicy, "bin", &val).set_context(ctx),
+        operations::get_bin("bin"),
+    ];
+    let rec = client.operate(&wpolicy, &key, ops).unwrap();
+    assert_eq!(
+        *rec.bins.get("bin").unwrap(),
+        as_list!(1, as_list!(as_list!(1), Value::Nil, as_list!(5)))
+    );
+}
+
+#[test]
+fn cdt_list_create() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = &common::rand_str(10);
+
+    let wpolicy = WritePolicy::default();
+    let key = as_key!(namespace, set_name, -1);
+    let lpolicy = ListPolicy::default();
+
+    client.delete(&wpolicy, &key).unwrap();
+
+    let values = vec![as_val!(3), as_val!(1), as_val!(2)];
+    let ops = &vec![lists::create("bin", ListOrderType::Ordered, false)];
+    client.operate(&wpolicy, &key, ops).unwrap();
+
+    let ops = &vec![
+        lists::append_items(&lpolicy, "bin", &values),
+        operations::get_bin("bin"),
+    ];
+    let rec = client.operate(&wpolicy, &key, ops).unwrap();
+    assert_eq!(
+        *rec.bins.get("bin").unwrap(),
+        as_list!(3, as_list!(1, 2, 3))
+    );
+
+    let wbin = as_bin!("bin2", as_list!(1));
+    client.put(&wpolicy, &key, &vec![&wbin]).unwrap();
+
+    let ctx = &vec![ctx_list_index(2)];
+    let val = as_val!(5);
+    let ops = &vec![lists::create("bin2", ListOrderType::Unordered, true).set_context(ctx)];
+    client.operate(&wpolicy, &key, ops).unwrap();
+
+    let ops = &vec![
+        lists::append(&lpolicy, "bin2", &val).set_context(ctx),
+        operations::get_bin("bin2"),
+    ];
+    let rec = client.operate(&wpolicy, &key, ops).unwrap();
+    assert_eq!(
+        *rec.bins.get("bin2").unwrap(),
+        as_list!(1, as_list!(1, Value::Nil, as_list!(5)))
+    );
+}
+
+#[test]
+fn cdt_list_write_flags() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = &common::rand_str(10);
+
+    let wpolicy = WritePolicy::default();
+    let key = as_key!(namespace, set_name, -1);
+    let wbin = as_bin!("bin", as_list!(1, 2));
+    let bins = vec![&wbin];
+
+    client.delete(&wpolicy, &key).unwrap();
+    client.put(&wpolicy, &key, &bins).unwrap();
+
+    let lpolicy = ListPolicy::new(ListOrderType::Unordered, ListWriteFlags::AddUnique);
+    let values = vec![as_val!(2), as_val!(3)];
+    let ops = &vec![lists::append_items(&lpolicy, "bin", &values)];
+    assert!(client.operate(&wpolicy, &key, ops).is_err());
+
+    let flags = ListWriteFlags::AddUnique | ListWriteFlags::NoFail | ListWriteFlags::Partial;
+    let lpolicy = ListPolicy::new(ListOrderType::Unordered, flags);
+    let ops = &vec![
+        lists::append_items(&lpolicy, "bin", &values),
+        operations::get_bin("bin"),
+    ];
+    let rec = client.operate(&wpolicy, &key, ops).unwrap();
+    assert_eq!(
+        *rec.bins.get("bin").unwrap(),
+        as_list!(3, as_list!(1, 2, 3))
+    );
+
+    let lpolicy = ListPolicy::new(ListOrderType::Unordered, ListWriteFlags::InsertBounded);
+    let val = as_val!(9);
+    let ops = &vec![lists::insert(&lpolicy, "bin", 10, &val)];
+    assert!(client.operate(&wpolicy, &key, ops).is_err());
+}
+
+#[test]
+fn cdt_list_relative_rank_inverted() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = &common::rand_str(10);
+
+    let wpolicy = WritePolicy::default();
+    let key = as_key!(namespace, set_name, -1);
+    let wbin = as_bin!("bin", as_list!(0, 4, 5, 9, 11, 15));
+    let bins = vec![&wbin];
+
+    client.delete(&wpolicy, &key).unwrap();
+    client.put(&wpolicy, &key, &bins).unwrap();
+
+    let ops = &vec![lists::set_order("bin", ListOrderType::Ordered, &[])];
+    client.operate(&wpolicy, &key, ops).unwrap();
+
+    let val = as_val!(5);
+    let ops = &vec![lists::remove_by_value_relative_rank_range(
+        "bin",
+        ListReturnType::Inverted,
+        &val,
+        0,
+    )];
+    client.operate(&wpolicy, &key, ops).unwrap();
+
+    let rec = client.get(&ReadPolicy::default(), &key, Bins::All).unwrap();
+    assert_eq!(*rec.bins.get("bin").unwrap(), as_list!(5, 9, 11, 15));
+}
+
+#[test]
+fn cdt_list_respond_per_each_op() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = &common::rand_str(10);
+
+    let mut wpolicy = WritePolicy::default();
+    let key = as_key!(namespace, set_name, -1);
+    let wbin = as_bin!("bin", as_list!(1, 2));
+    let lpolicy = ListPolicy::default();
+
+    client.delete(&wpolicy, &key).unwrap();
+    client.put(&wpolicy, &key, &vec![&wbin]).unwrap();
+
+    wpolicy.respond_per_each_op = true;
+    let val = as_val!(5);
+    let ops = &vec![
+        lists::get_range("bin", 0, 2),
+        lists::clear("bin"),
+        lists::append(&lpolicy, "bin", &val),
+        lists::size("bin"),
+    ];
+    let rec = client.operate(&wpolicy, &key, ops).unwrap();
+    assert_eq!(
+        *rec.bins.get("bin").unwrap(),
+        as_list!(as_list!(1, 2), Value::Nil, 1, 1)
+    );
+}
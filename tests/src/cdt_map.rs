@@ -19,7 +19,7 @@ use crate::common;
 use env_logger;
 
 use aerospike::operations::cdt_context::{ctx_map_key, ctx_map_key_create};
-use aerospike::operations::{maps, MapOrder};
+use aerospike::operations::{maps, MapOrder, MapWriteFlags};
 use aerospike::{
     as_bin, as_key, as_list, as_map, as_val, Bins, MapPolicy, MapReturnType, ReadPolicy,
     WritePolicy,
@@ -321,3 +321,141 @@ fn map_operations() {
     let rec = client.operate(&wpolicy, &key, &op).unwrap();
     assert_eq!(*rec.bins.get(bin_name).unwrap(), as_val!(9));
 }
+
+#[test]
+fn map_create() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = &common::rand_str(10);
+
+    let wpolicy = WritePolicy::default();
+    let mpolicy = MapPolicy::default();
+
+    let key = common::rand_str(10);
+    let key = as_key!(namespace, set_name, &key);
+    let bin_name = "bin";
+
+    client.delete(&wpolicy, &key).unwrap();
+
+    let (c, a, b) = (as_val!("c"), as_val!("a"), as_val!("b"));
+    let v = as_val!(1);
+    let ops = [
+        maps::create(bin_name, MapOrder::KeyOrdered, false),
+        maps::put(&mpolicy, bin_name, &c, &v),
+        maps::put(&mpolicy, bin_name, &a, &v),
+        maps::put(&mpolicy, bin_name, &b, &v),
+    ];
+    client.operate(&wpolicy, &key, &ops).unwrap();
+
+    let op = maps::get_by_index(bin_name, 0, MapReturnType::Key);
+    let rec = client.operate(&wpolicy, &key, &[op]).unwrap();
+    assert_eq!(*rec.bins.get(bin_name).unwrap(), as_val!("a"));
+}
+
+#[test]
+fn map_create_ctx() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = &common::rand_str(10);
+
+    let wpolicy = WritePolicy::default();
+    let mpolicy = MapPolicy::default();
+
+    let key = common::rand_str(10);
+    let key = as_key!(namespace, set_name, &key);
+    let bin_name = "bin";
+
+    client.delete(&wpolicy, &key).unwrap();
+
+    let (nested, c, a, b) = (as_val!("nested"), as_val!("c"), as_val!("a"), as_val!("b"));
+    let v = as_val!(1);
+    let ctx = &vec![ctx_map_key(nested)];
+    let ops = [
+        maps::create(bin_name, MapOrder::KeyOrdered, true).set_context(ctx),
+        maps::put(&mpolicy, bin_name, &c, &v).set_context(ctx),
+        maps::put(&mpolicy, bin_name, &a, &v).set_context(ctx),
+        maps::put(&mpolicy, bin_name, &b, &v).set_context(ctx),
+    ];
+    client.operate(&wpolicy, &key, &ops).unwrap();
+
+    let op = maps::get_by_index(bin_name, 0, MapReturnType::Key).set_context(ctx);
+    let rec = client.operate(&wpolicy, &key, &[op]).unwrap();
+    assert_eq!(*rec.bins.get(bin_name).unwrap(), as_val!("a"));
+}
+
+#[test]
+fn map_write_flags() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = &common::rand_str(10);
+
+    let wpolicy = WritePolicy::default();
+    let rpolicy = ReadPolicy::default();
+
+    let key = common::rand_str(10);
+    let key = as_key!(namespace, set_name, &key);
+    let bin_name = "bin";
+
+    client.delete(&wpolicy, &key).unwrap();
+
+    let bin = as_bin!(bin_name, as_map!("a" => 1, "b" => 2));
+    client.put(&wpolicy, &key, &vec![&bin]).unwrap();
+
+    let (k, v) = (as_val!("a"), as_val!(10));
+    let flags = MapWriteFlags::CreateOnly;
+    let mpolicy = MapPolicy::new_with_flags(MapOrder::Unordered, flags);
+    let op = maps::put(&mpolicy, bin_name, &k, &v);
+    assert!(client.operate(&wpolicy, &key, &[op]).is_err());
+
+    let flags = flags | MapWriteFlags::NoFail | MapWriteFlags::Partial;
+    let mpolicy = MapPolicy::new_with_flags(MapOrder::Unordered, flags);
+    let mut items = HashMap::new();
+    items.insert(as_val!("a"), as_val!(10));
+    items.insert(as_val!("c"), as_val!(3));
+    let op = maps::put_items(&mpolicy, bin_name, &items);
+    let rec = client.operate(&wpolicy, &key, &[op]).unwrap();
+    assert_eq!(*rec.bins.get(bin_name).unwrap(), as_val!(3));
+
+    let rec = client.get(&rpolicy, &key, Bins::All).unwrap();
+    assert_eq!(
+        *rec.bins.get(bin_name).unwrap(),
+        as_map!("a" => 1, "b" => 2, "c" => 3)
+    );
+}
+
+#[test]
+fn map_key_list_inverted() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = &common::rand_str(10);
+
+    let wpolicy = WritePolicy::default();
+    let rpolicy = ReadPolicy::default();
+
+    let key = common::rand_str(10);
+    let key = as_key!(namespace, set_name, &key);
+    let bin_name = "bin";
+
+    client.delete(&wpolicy, &key).unwrap();
+
+    let bin = as_bin!(bin_name, as_map!("a" => 1, "b" => 2, "c" => 3, "d" => 4));
+    client.put(&wpolicy, &key, &vec![&bin]).unwrap();
+
+    let keys = vec![as_val!("a"), as_val!("c")];
+    let op = maps::remove_by_key_list(bin_name, &keys, MapReturnType::Inverted);
+    client.operate(&wpolicy, &key, &[op]).unwrap();
+
+    let rec = client.get(&rpolicy, &key, Bins::All).unwrap();
+    assert_eq!(
+        *rec.bins.get(bin_name).unwrap(),
+        as_map!("a" => 1, "c" => 3)
+    );
+}
@@ -19,6 +19,7 @@ use aerospike::expressions::*;
 use aerospike::ParticleType;
 use aerospike::*;
 use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 
 const EXPECTED: usize = 100;
 
@@ -421,6 +422,19 @@ fn expression_rec_ops() {
     }
     assert_eq!(count, 100, "DEVICE SIZE Test Failed");
 
+    let rs = test_filter(le(memory_size(), int_val(0)), &set_name);
+    let mut count = count_results(rs);
+    if count == 0 {
+        // In-memory
+        let rs = test_filter(gt(memory_size(), int_val(0)), &set_name);
+        count = count_results(rs);
+    }
+    assert_eq!(count, 100, "MEMORY SIZE Test Failed");
+
+    let rs = test_filter(gt(record_size(), int_val(0)), &set_name);
+    let count = count_results(rs);
+    assert_eq!(count, 100, "RECORD SIZE Test Failed");
+
     let rs = test_filter(gt(last_update(), int_val(15000)), &set_name);
     let count = count_results(rs);
     assert_eq!(count, 100, "LAST UPDATE Test Failed");
@@ -429,6 +443,26 @@ fn expression_rec_ops() {
     let count = count_results(rs);
     assert_eq!(count, 100, "SINCE UPDATE Test Failed");
 
+    let rs = test_filter(
+        lt(
+            last_update(),
+            last_update_val(SystemTime::now() + Duration::from_secs(60)),
+        ),
+        &set_name,
+    );
+    let count = count_results(rs);
+    assert_eq!(count, 100, "LAST UPDATE SYSTEMTIME Test Failed");
+
+    let rs = test_filter(
+        lt(
+            since_update(),
+            since_update_val(Duration::from_secs(60 * 60)),
+        ),
+        &set_name,
+    );
+    let count = count_results(rs);
+    assert_eq!(count, 100, "SINCE UPDATE DURATION Test Failed");
+
     // Records dont expire
     let rs = test_filter(le(void_time(), int_val(0)), &set_name);
     let count = count_results(rs);
@@ -628,6 +662,27 @@ fn expression_commands() {
     }
 }
 
+#[test]
+fn expression_bool_bin() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = common::rand_str(10);
+
+    let wpolicy = WritePolicy::default();
+    for i in 0..EXPECTED as i64 {
+        let key = as_key!(namespace, &set_name, i);
+        let bbin = as_bin!("bin", i % 2 == 0);
+        client.delete(&wpolicy, &key).unwrap();
+        client.put(&wpolicy, &key, &[&bbin]).unwrap();
+    }
+
+    let rs = test_filter(eq(bool_bin("bin".to_string()), bool_val(true)), &set_name);
+    let count = count_results(rs);
+    assert_eq!(count, EXPECTED / 2, "BOOL BIN Test Failed");
+}
+
 fn test_filter(filter: FilterExpression, set_name: &str) -> Arc<Recordset> {
     let client = common::client();
     let namespace = common::namespace();
@@ -368,6 +368,80 @@ fn expression_bitwise() {
     assert_eq!(item_count, 100, "RSCAN Test Failed");
 }
 
+#[test]
+fn expression_bitwise_signed_and_flags() {
+    let _ = env_logger::try_init();
+
+    let set_name = create_test_set(EXPECTED);
+
+    // bin = [0b00000001, 0b01000010]; 1 - 2 = -1 when treated as signed
+    let rs = test_filter(
+        eq(
+            get_int(
+                int_val(0),
+                int_val(8),
+                true,
+                subtract(
+                    &BitPolicy::default(),
+                    int_val(0),
+                    int_val(8),
+                    int_val(2),
+                    true,
+                    BitwiseOverflowActions::Wrap,
+                    blob_bin("bin"),
+                ),
+            ),
+            int_val(-1),
+        ),
+        &set_name,
+    );
+    let item_count = count_results(rs);
+    assert_eq!(item_count, 100, "SIGNED SUBTRACT/GET INT Test Failed");
+
+    let rs = test_filter(
+        eq(
+            get_int(
+                int_val(0),
+                int_val(8),
+                false,
+                subtract(
+                    &BitPolicy::default(),
+                    int_val(0),
+                    int_val(8),
+                    int_val(2),
+                    false,
+                    BitwiseOverflowActions::Wrap,
+                    blob_bin("bin"),
+                ),
+            ),
+            int_val(255),
+        ),
+        &set_name,
+    );
+    let item_count = count_results(rs);
+    assert_eq!(item_count, 100, "UNSIGNED SUBTRACT/GET INT Test Failed");
+
+    // Shrinking from the front drops the first byte
+    let rs = test_filter(
+        eq(
+            get(
+                int_val(0),
+                int_val(8),
+                resize(
+                    &BitPolicy::default(),
+                    int_val(1),
+                    BitwiseResizeFlags::FromFront,
+                    blob_bin("bin"),
+                ),
+            ),
+            blob_val(vec![0b01000010]),
+        ),
+        &set_name,
+    );
+    let item_count = count_results(rs);
+    assert_eq!(item_count, 100, "RESIZE FROM FRONT Test Failed");
+}
+
 fn test_filter(filter: FilterExpression, set_name: &str) -> Arc<Recordset> {
     let client = common::client();
     let namespace = common::namespace();
@@ -308,7 +308,12 @@ fn expression_list() {
     let rs = test_filter(
         eq(
             size(
-                remove_by_value(int_val(3), list_bin("bin".to_string()), &[]),
+                remove_by_value(
+                    ListReturnType::None,
+                    int_val(3),
+                    list_bin("bin".to_string()),
+                    &[],
+                ),
                 &[],
             ),
             int_val(3),
@@ -322,6 +327,7 @@ fn expression_list() {
         eq(
             size(
                 remove_by_value_list(
+                    ListReturnType::None,
                     list_val(vec![Value::from(1), Value::from(2)]),
                     list_bin("bin".to_string()),
                     &[],
@@ -335,10 +341,29 @@ fn expression_list() {
     let count = count_results(rs);
     assert_eq!(count, 98, "REMOVE BY VALUE LIST Test Failed");
 
+    let rs = test_filter(
+        eq(
+            size(
+                remove_by_value_list(
+                    ListReturnType::Inverted,
+                    list_val(vec![Value::from(1), Value::from(2)]),
+                    list_bin("bin".to_string()),
+                    &[],
+                ),
+                &[],
+            ),
+            int_val(2),
+        ),
+        &set_name,
+    );
+    let count = count_results(rs);
+    assert_eq!(count, 98, "REMOVE BY VALUE LIST INVERTED Test Failed");
+
     let rs = test_filter(
         eq(
             size(
                 remove_by_value_range(
+                    ListReturnType::None,
                     Some(int_val(1)),
                     Some(int_val(3)),
                     list_bin("bin".to_string()),
@@ -357,6 +382,7 @@ fn expression_list() {
         eq(
             size(
                 remove_by_value_relative_rank_range(
+                    ListReturnType::None,
                     int_val(3),
                     int_val(1),
                     list_bin("bin".to_string()),
@@ -375,6 +401,7 @@ fn expression_list() {
         eq(
             size(
                 remove_by_value_relative_rank_range_count(
+                    ListReturnType::None,
                     int_val(2),
                     int_val(1),
                     int_val(1),
@@ -396,7 +423,12 @@ fn expression_list() {
     let rs = test_filter(
         eq(
             size(
-                remove_by_index(int_val(0), list_bin("bin".to_string()), &[]),
+                remove_by_index(
+                    ListReturnType::None,
+                    int_val(0),
+                    list_bin("bin".to_string()),
+                    &[],
+                ),
                 &[],
             ),
             int_val(3),
@@ -409,7 +441,12 @@ fn expression_list() {
     let rs = test_filter(
         eq(
             size(
-                remove_by_index_range(int_val(2), list_bin("bin".to_string()), &[]),
+                remove_by_index_range(
+                    ListReturnType::None,
+                    int_val(2),
+                    list_bin("bin".to_string()),
+                    &[],
+                ),
                 &[],
             ),
             int_val(2),
@@ -423,6 +460,7 @@ fn expression_list() {
         eq(
             size(
                 remove_by_index_range_count(
+                    ListReturnType::None,
                     int_val(2),
                     int_val(1),
                     list_bin("bin".to_string()),
@@ -441,6 +479,7 @@ fn expression_list() {
         eq(
             size(
                 remove_by_index_range_count(
+                    ListReturnType::None,
                     int_val(2),
                     int_val(1),
                     list_bin("bin".to_string()),
@@ -458,7 +497,12 @@ fn expression_list() {
     let rs = test_filter(
         eq(
             size(
-                remove_by_rank(int_val(2), list_bin("bin".to_string()), &[]),
+                remove_by_rank(
+                    ListReturnType::None,
+                    int_val(2),
+                    list_bin("bin".to_string()),
+                    &[],
+                ),
                 &[],
             ),
             int_val(3),
@@ -471,7 +515,12 @@ fn expression_list() {
     let rs = test_filter(
         eq(
             size(
-                remove_by_rank_range(int_val(2), list_bin("bin".to_string()), &[]),
+                remove_by_rank_range(
+                    ListReturnType::None,
+                    int_val(2),
+                    list_bin("bin".to_string()),
+                    &[],
+                ),
                 &[],
             ),
             int_val(2),
@@ -485,6 +534,7 @@ fn expression_list() {
         eq(
             size(
                 remove_by_rank_range_count(
+                    ListReturnType::None,
                     int_val(2),
                     int_val(1),
                     list_bin("bin".to_string()),
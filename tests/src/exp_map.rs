@@ -370,6 +370,7 @@ fn expression_map() {
         eq(
             size(
                 remove_by_key(
+                    MapReturnType::None,
                     string_val("test".to_string()),
                     map_bin("bin".to_string()),
                     &[],
@@ -387,6 +388,7 @@ fn expression_map() {
         eq(
             size(
                 remove_by_key_list(
+                    MapReturnType::None,
                     list_val(vec![Value::from("test"), Value::from("test2")]),
                     map_bin("bin".to_string()),
                     &[],
@@ -400,10 +402,29 @@ fn expression_map() {
     let count = count_results(rs);
     assert_eq!(count, 100, "REMOVE BY KEY LIST Test Failed");
 
+    let rs = test_filter(
+        eq(
+            size(
+                remove_by_key_list(
+                    MapReturnType::Inverted,
+                    list_val(vec![Value::from("test")]),
+                    map_bin("bin".to_string()),
+                    &[],
+                ),
+                &[],
+            ),
+            int_val(1),
+        ),
+        &set_name,
+    );
+    let count = count_results(rs);
+    assert_eq!(count, 100, "REMOVE BY KEY LIST INVERTED Test Failed");
+
     let rs = test_filter(
         eq(
             size(
                 remove_by_key_range(
+                    MapReturnType::None,
                     Some(string_val("test".to_string())),
                     None,
                     map_bin("bin".to_string()),
@@ -422,6 +443,7 @@ fn expression_map() {
         eq(
             size(
                 remove_by_key_relative_index_range(
+                    MapReturnType::None,
                     string_val("test".to_string()),
                     int_val(0),
                     map_bin("bin".to_string()),
@@ -440,6 +462,7 @@ fn expression_map() {
         eq(
             size(
                 remove_by_key_relative_index_range_count(
+                    MapReturnType::None,
                     string_val("test".to_string()),
                     int_val(0),
                     int_val(1),
@@ -461,7 +484,12 @@ fn expression_map() {
     let rs = test_filter(
         eq(
             size(
-                remove_by_value(int_val(5), map_bin("bin".to_string()), &[]),
+                remove_by_value(
+                    MapReturnType::None,
+                    int_val(5),
+                    map_bin("bin".to_string()),
+                    &[],
+                ),
                 &[],
             ),
             int_val(1),
@@ -475,6 +503,7 @@ fn expression_map() {
         eq(
             size(
                 remove_by_value_list(
+                    MapReturnType::None,
                     list_val(vec![Value::from("a"), Value::from(15)]),
                     map_bin("bin".to_string()),
                     &[],
@@ -492,6 +521,7 @@ fn expression_map() {
         eq(
             size(
                 remove_by_value_range(
+                    MapReturnType::None,
                     Some(int_val(5)),
                     Some(int_val(15)),
                     map_bin("bin".to_string()),
@@ -509,7 +539,12 @@ fn expression_map() {
     let rs = test_filter(
         eq(
             size(
-                remove_by_index(int_val(0), map_bin("bin".to_string()), &[]),
+                remove_by_index(
+                    MapReturnType::None,
+                    int_val(0),
+                    map_bin("bin".to_string()),
+                    &[],
+                ),
                 &[],
             ),
             int_val(1),
@@ -522,7 +557,12 @@ fn expression_map() {
     let rs = test_filter(
         eq(
             size(
-                remove_by_index_range(int_val(0), map_bin("bin".to_string()), &[]),
+                remove_by_index_range(
+                    MapReturnType::None,
+                    int_val(0),
+                    map_bin("bin".to_string()),
+                    &[],
+                ),
                 &[],
             ),
             int_val(0),
@@ -536,6 +576,7 @@ fn expression_map() {
         eq(
             size(
                 remove_by_index_range_count(
+                    MapReturnType::None,
                     int_val(0),
                     int_val(1),
                     map_bin("bin".to_string()),
@@ -553,7 +594,12 @@ fn expression_map() {
     let rs = test_filter(
         eq(
             size(
-                remove_by_rank(int_val(0), map_bin("bin".to_string()), &[]),
+                remove_by_rank(
+                    MapReturnType::None,
+                    int_val(0),
+                    map_bin("bin".to_string()),
+                    &[],
+                ),
                 &[],
             ),
             int_val(1),
@@ -566,7 +612,12 @@ fn expression_map() {
     let rs = test_filter(
         eq(
             size(
-                remove_by_rank_range(int_val(0), map_bin("bin".to_string()), &[]),
+                remove_by_rank_range(
+                    MapReturnType::None,
+                    int_val(0),
+                    map_bin("bin".to_string()),
+                    &[],
+                ),
                 &[],
             ),
             int_val(0),
@@ -579,7 +630,13 @@ fn expression_map() {
     let rs = test_filter(
         eq(
             size(
-                remove_by_rank_range_count(int_val(0), int_val(1), map_bin("bin".to_string()), &[]),
+                remove_by_rank_range_count(
+                    MapReturnType::None,
+                    int_val(0),
+                    int_val(1),
+                    map_bin("bin".to_string()),
+                    &[],
+                ),
                 &[],
             ),
             int_val(1),
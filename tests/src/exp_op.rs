@@ -1,7 +1,9 @@
 use crate::common;
 use aerospike::expressions::{int_bin, int_val, num_add};
 use aerospike::operations::exp::{read_exp, write_exp, ExpReadFlags, ExpWriteFlags};
-use aerospike::{as_bin, as_key, as_val, Bins, ReadPolicy, WritePolicy};
+use aerospike::{
+    as_bin, as_key, as_val, Bins, Error, ErrorKind, ReadPolicy, ResultCode, Value, WritePolicy,
+};
 
 #[test]
 fn exp_ops() {
@@ -53,3 +55,45 @@ fn exp_ops() {
         "EXP OPs write failed"
     );
 }
+
+#[test]
+fn exp_ops_write_flags() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = &common::rand_str(10);
+
+    let wpolicy = WritePolicy::default();
+    let key = as_key!(namespace, set_name, -1);
+    let wbin = as_bin!("bin", as_val!(25));
+    let bins = vec![&wbin];
+
+    client.delete(&wpolicy, &key).unwrap();
+    client.put(&wpolicy, &key, &bins).unwrap();
+
+    let flt = num_add(vec![int_bin("bin"), int_val(4)]);
+    let ops = &vec![write_exp("bin", &flt, ExpWriteFlags::CreateOnly)];
+    match client.operate(&wpolicy, &key, ops) {
+        Err(Error(ErrorKind::ServerError(ResultCode::BinExistsError), _)) => {}
+        res => panic!("Expected BinExistsError, got {:?}", res),
+    }
+
+    let ops = &vec![write_exp("bin", &flt, ExpWriteFlags::PolicyNoFail)];
+    client.operate(&wpolicy, &key, ops).unwrap();
+
+    let flt = int_bin("missing");
+    let ops = &vec![read_exp("example", &flt, ExpReadFlags::EvalNoFail)];
+    let rec = client.operate(&wpolicy, &key, ops).unwrap();
+    assert!(
+        matches!(rec.bins.get("example"), None | Some(Value::Nil)),
+        "EXP OPs eval no fail failed"
+    );
+
+    let rec = client.get(&ReadPolicy::default(), &key, Bins::All).unwrap();
+    assert_eq!(
+        *rec.bins.get("bin").unwrap(),
+        as_val!(29),
+        "EXP OPs update failed"
+    );
+}
@@ -2,7 +2,7 @@ use crate::common;
 use env_logger;
 
 use aerospike::operations::hll;
-use aerospike::operations::hll::HLLPolicy;
+use aerospike::operations::hll::{HLLPolicy, HLLWriteFlags};
 use aerospike::{as_key, as_list, as_val, Bins, FloatValue, ReadPolicy, Value, WritePolicy};
 
 #[test]
@@ -39,6 +39,14 @@ fn hll() {
         "HLL Count did not match"
     );
 
+    let ops = &vec![hll::may_contain("bin", &v)];
+    let rec = client.operate(&wpolicy, &key, ops).unwrap();
+    assert_eq!(
+        *rec.bins.get("bin").unwrap(),
+        Value::Int(1),
+        "HLL May Contain did not match"
+    );
+
     let ops = &vec![hll::init_with_min_hash(&hpolicy, "bin2", 8, 0)];
     client.operate(&wpolicy, &key, ops).unwrap();
 
@@ -115,3 +123,34 @@ fn hll() {
         "Similarity failed"
     );
 }
+
+#[test]
+fn hll_write_flags() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = &common::rand_str(10);
+
+    let key = as_key!(namespace, set_name, "test");
+    let wpolicy = WritePolicy::default();
+
+    client.delete(&wpolicy, &key).unwrap();
+
+    let hpolicy = HLLPolicy::new(HLLWriteFlags::CreateOnly);
+    let ops = &vec![hll::init(&hpolicy, "bin", 4)];
+    client.operate(&wpolicy, &key, ops).unwrap();
+    assert!(client.operate(&wpolicy, &key, ops).is_err());
+
+    let hpolicy = HLLPolicy::new(HLLWriteFlags::CreateOnly | HLLWriteFlags::NoFail);
+    let ops = &vec![hll::init(&hpolicy, "bin", 8)];
+    client.operate(&wpolicy, &key, ops).unwrap();
+
+    let ops = &vec![hll::describe("bin")];
+    let rec = client.operate(&wpolicy, &key, ops).unwrap();
+    assert_eq!(
+        *rec.bins.get("bin").unwrap(),
+        as_list!(4, 0),
+        "HLL was re-initialized"
+    );
+}
@@ -60,3 +60,34 @@ fn create_index() {
         .expect("Failed to create index");
     task.wait_till_complete(None).unwrap();
 }
+
+#[test]
+fn list_indexes() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let ns = common::namespace();
+    let set = create_test_set(EXPECTED);
+    let bin = "bin";
+    let index = format!("{}_{}_{}", ns, set, bin);
+    let policy = WritePolicy::default();
+
+    let task = client
+        .create_index(&policy, ns, &set, bin, &index, IndexType::Numeric)
+        .expect("Failed to create index");
+    task.wait_till_complete(None).unwrap();
+
+    let indexes = client.list_indexes(&ReadPolicy::default(), ns).unwrap();
+    let info = indexes
+        .iter()
+        .find(|info| info.name == index)
+        .expect("Index not listed");
+    assert_eq!(info.namespace, ns);
+    assert_eq!(info.set_name, set);
+    assert_eq!(info.bin_name, bin);
+    assert_eq!(info.index_type, IndexType::Numeric);
+    assert_eq!(info.collection_index_type, CollectionIndexType::Default);
+    assert!(info.is_ready());
+
+    client.drop_index(&policy, ns, &set, &index).unwrap();
+}
@@ -0,0 +1,55 @@
+// Copyright 2015-2018 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use aerospike::{info, ReadPolicy};
+
+use crate::common;
+use env_logger;
+
+#[test]
+fn client_info() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let response = client
+        .info(&ReadPolicy::default(), &["namespaces", "statistics"])
+        .unwrap();
+    assert!(info::parse_list(&response["namespaces"]).contains(&common::namespace()));
+    assert!(info::parse_map(&response["statistics"]).contains_key("cluster_size"));
+}
+
+#[test]
+fn node_info() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    for node in client.nodes() {
+        let response = node.info(None, &["node"]).unwrap();
+        assert_eq!(response["node"], node.name());
+    }
+}
+
+#[test]
+fn node_stats() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    for node in client.nodes() {
+        let stats = node.stats(None).unwrap();
+        assert_eq!(stats.cluster_size, Some(client.nodes().len() as u64));
+        assert!(stats.uptime.is_some());
+        assert!(stats.client_connections.unwrap() > 0);
+    }
+}
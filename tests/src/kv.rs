@@ -14,7 +14,8 @@
 // the License.
 use aerospike::operations;
 use aerospike::{
-    as_bin, as_blob, as_geo, as_key, as_list, as_map, as_val, Bins, ReadPolicy, Value, WritePolicy,
+    as_bin, as_blob, as_geo, as_key, as_list, as_map, as_val, Bins, Client, Expiration, ReadPolicy,
+    Replica, Value, WritePolicy,
 };
 use env_logger;
 
@@ -80,6 +81,14 @@ fn connect() {
 
     client.touch(&wpolicy, &key).unwrap();
 
+    let mut touch_policy = WritePolicy::default();
+    touch_policy.expiration = Expiration::Seconds(1000);
+    let record = client.touch_with_response(&touch_policy, &key).unwrap();
+    assert!(record.bins.is_empty());
+    assert!(record.generation > 1);
+    let ttl = record.time_to_live().unwrap().as_secs();
+    assert!(ttl > 900 && ttl <= 1000);
+
     let bins = Bins::from(["bin999", "bin f64"]);
     let record = client.get(&policy, &key, bins).unwrap();
     assert_eq!(record.bins.len(), 2);
@@ -100,3 +109,122 @@ fn connect() {
     let existed = client.delete(&wpolicy, &key).unwrap();
     assert!(!existed);
 }
+
+#[test]
+fn operate_delete() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace: &str = common::namespace();
+    let set_name = &common::rand_str(10);
+    let wpolicy = WritePolicy::default();
+    let key = as_key!(namespace, set_name, -1);
+
+    client.delete(&wpolicy, &key).unwrap();
+
+    let bin = as_bin!("a", 1);
+    client.put(&wpolicy, &key, &[&bin]).unwrap();
+
+    let ops = &vec![
+        operations::add(&bin),
+        operations::get_bin("a"),
+        operations::delete(),
+    ];
+    let record = client.operate(&wpolicy, &key, ops).unwrap();
+    assert_eq!(record.bins.get("a"), Some(&Value::from(2)));
+
+    let exists = client.exists(&wpolicy, &key).unwrap();
+    assert!(!exists);
+}
+
+#[test]
+fn operate_get_header() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace: &str = common::namespace();
+    let set_name = &common::rand_str(10);
+    let wpolicy = WritePolicy::default();
+    let key = as_key!(namespace, set_name, -1);
+
+    client.delete(&wpolicy, &key).unwrap();
+
+    let bin = as_bin!("a", 1);
+    client.put(&wpolicy, &key, &[&bin]).unwrap();
+
+    let ops = &vec![operations::add(&bin), operations::get_header()];
+    let record = client.operate(&wpolicy, &key, ops).unwrap();
+    assert!(record.bins.is_empty());
+    assert_eq!(record.generation, 2);
+
+    let ops = &vec![operations::get_header(), operations::get_bin("a")];
+    let record = client.operate(&wpolicy, &key, ops).unwrap();
+    assert_eq!(record.bins.get("a"), Some(&Value::from(2)));
+    assert_eq!(record.generation, 2);
+}
+
+#[test]
+fn get_prefer_rack() {
+    let _ = env_logger::try_init();
+
+    let mut cpolicy = common::client_policy().clone();
+    cpolicy.rack_aware = true;
+    cpolicy.rack_ids = vec![1, 0];
+    let client = Client::new(&cpolicy, &common::hosts()).unwrap();
+    let namespace: &str = common::namespace();
+    let set_name = &common::rand_str(10);
+    let wpolicy = WritePolicy::default();
+
+    let mut rpolicy = ReadPolicy::default();
+    rpolicy.replica = Replica::PreferRack;
+    for i in 0..10 {
+        let key = as_key!(namespace, set_name, i);
+        client.put(&wpolicy, &key, &[as_bin!("i", i)]).unwrap();
+        let record = client.get(&rpolicy, &key, Bins::All).unwrap();
+        assert_eq!(record.bins.get("i"), Some(&Value::from(i)));
+    }
+
+    for node in client.nodes() {
+        assert!(node.rack_id(namespace).is_some());
+    }
+    client.close().unwrap();
+}
+
+#[test]
+fn get_replicas() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace: &str = common::namespace();
+    let set_name = &common::rand_str(10);
+    let wpolicy = WritePolicy::default();
+    let key = as_key!(namespace, set_name, 1);
+    client.put(&wpolicy, &key, &[as_bin!("a", 1)]).unwrap();
+
+    for replica in &[
+        Replica::Master,
+        Replica::Any,
+        Replica::Sequence,
+        Replica::PreferRack,
+    ] {
+        let mut rpolicy = ReadPolicy::default();
+        rpolicy.replica = *replica;
+        for _ in 0..5 {
+            let record = client.get(&rpolicy, &key, Bins::All).unwrap();
+            assert_eq!(record.bins.get("a"), Some(&Value::from(1)));
+        }
+
+        // operations that write go to the master, reads may go to any replica
+        let mut wpolicy = WritePolicy::default();
+        wpolicy.base_policy.replica = *replica;
+        let bin = as_bin!("a", 1);
+        let ops = &vec![operations::add(&bin), operations::get_bin("a")];
+        let record = client.operate(&wpolicy, &key, ops).unwrap();
+        assert_eq!(record.bins.get("a"), Some(&Value::from(2)));
+        let record = client
+            .operate(&wpolicy, &key, &[operations::get_bin("a")])
+            .unwrap();
+        assert_eq!(record.bins.get("a"), Some(&Value::from(2)));
+        client.put(&wpolicy, &key, &[as_bin!("a", 1)]).unwrap();
+    }
+}
@@ -13,6 +13,9 @@
 // License for the specific language governing permissions and limitations under
 // the License.
 
+mod admin;
+#[cfg(any(feature = "rt-tokio", feature = "rt-async-std"))]
+mod async_client;
 mod batch;
 mod cdt_bitwise;
 mod cdt_list;
@@ -25,11 +28,13 @@ mod exp_map;
 mod exp_op;
 mod hll;
 mod index;
+mod info;
 mod kv;
 mod query;
 mod scan;
 #[cfg(feature = "serialization")]
 mod serialization;
 mod task;
+mod tls;
 mod truncate;
 mod udf;
@@ -215,7 +215,7 @@ fn query_large_i64() {
     assert!(res.is_ok());
 
     let mut qpolicy = aerospike::QueryPolicy::new();
-    let bin_name = aerospike::expressions::int_bin(BIN.into());
+    let bin_name = aerospike::expressions::int_bin(BIN);
     let bin_val = aerospike::expressions::int_val(i64::max_value());
     qpolicy
         .filter_expression
@@ -231,3 +231,216 @@ fn query_large_i64() {
 
     let _ = client.truncate(&wpolicy, common::namespace(), SET, 0);
 }
+
+#[test]
+fn query_partitions() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = create_test_set(EXPECTED);
+    let qpolicy = QueryPolicy::default();
+
+    let mut count = 0;
+    let mut cursor = PartitionFilter::all();
+    while !cursor.is_done() {
+        let statement = Statement::new(namespace, &set_name, Bins::All);
+        let rs = client
+            .query_partitions(&qpolicy, &cursor, statement)
+            .unwrap();
+        count += (&*rs).take(300).filter(Result::is_ok).count();
+        rs.close();
+        cursor = rs.partition_filter().unwrap();
+    }
+    assert_eq!(count, EXPECTED);
+
+    let mut statement = Statement::new(namespace, &set_name, Bins::All);
+    statement.add_filter(as_eq!("bin", 1));
+    assert!(client
+        .query_partitions(&qpolicy, &PartitionFilter::all(), statement)
+        .is_err());
+}
+
+#[test]
+fn query_max_records() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = create_test_set(EXPECTED);
+    let mut qpolicy = QueryPolicy::default();
+    qpolicy.max_records = 10;
+
+    let mut statement = Statement::new(namespace, &set_name, Bins::All);
+    statement.add_filter(as_range!("bin", 0, 99));
+    let rs = client.query(&qpolicy, statement).unwrap();
+    let count = (&*rs).filter(Result::is_ok).count();
+    assert!(count > 0);
+    assert!(count <= 10);
+}
+
+#[test]
+fn query_records_per_second() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = create_test_set(EXPECTED);
+    let mut qpolicy = QueryPolicy::default();
+    qpolicy.records_per_second = 5000;
+
+    let mut statement = Statement::new(namespace, &set_name, Bins::All);
+    statement.add_filter(as_range!("bin", 0, 9));
+    let rs = client.query(&qpolicy, statement).unwrap();
+    let count = (&*rs).filter(Result::is_ok).count();
+    assert_eq!(count, 10);
+}
+
+#[test]
+fn query_expected_duration() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = create_test_set(EXPECTED);
+
+    for duration in &[
+        QueryDuration::Long,
+        QueryDuration::Short,
+        QueryDuration::LongRelaxAP,
+    ] {
+        let mut qpolicy = QueryPolicy::default();
+        qpolicy.expected_duration = *duration;
+
+        let mut statement = Statement::new(namespace, &set_name, Bins::All);
+        statement.add_filter(as_eq!("bin", 1));
+        let rs = client.query(&qpolicy, statement).unwrap();
+        let count = (&*rs).filter(Result::is_ok).count();
+        assert_eq!(count, 1);
+    }
+}
+
+#[test]
+fn query_execute() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = create_test_set(EXPECTED);
+    let wpolicy = WritePolicy::default();
+
+    let mut statement = Statement::new(namespace, &set_name, Bins::None);
+    statement.add_filter(as_range!("bin", 0, 9));
+    let bin = as_bin!("flag", 1);
+    let ops = [operations::put(&bin)];
+    let task = client.query_execute(&wpolicy, statement, &ops).unwrap();
+    task.wait_till_complete(None).unwrap();
+
+    let qpolicy = QueryPolicy::default();
+    let statement = Statement::new(namespace, &set_name, Bins::All);
+    let rs = client.query(&qpolicy, statement).unwrap();
+    let flagged = (&*rs)
+        .map(Result::unwrap)
+        .filter(|rec| rec.bins.get("flag") == Some(&as_val!(1)))
+        .count();
+    assert_eq!(flagged, 10);
+
+    let statement = Statement::new(namespace, &set_name, Bins::None);
+    let ops = [operations::get_bin("bin")];
+    assert!(client.query_execute(&wpolicy, statement, &ops).is_err());
+}
+
+#[test]
+fn query_aggregate() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = create_test_set(EXPECTED);
+
+    let udf_body = r#"
+local function add_bin(total, rec)
+  return total + rec['bin']
+end
+
+local function add(a, b)
+  return a + b
+end
+
+function sum_bin(stream)
+  return stream : aggregate(0, add_bin) : reduce(add)
+end
+
+function fail(stream)
+  return stream : map(function(rec) error('expected failure') end)
+end
+"#;
+    let task = client
+        .register_udf(
+            &WritePolicy::default(),
+            udf_body.as_bytes(),
+            "test_aggregate.lua",
+            UDFLang::Lua,
+        )
+        .unwrap();
+    task.wait_till_complete(None).unwrap();
+
+    let qpolicy = QueryPolicy::default();
+    let mut statement = Statement::new(namespace, &set_name, Bins::All);
+    statement.add_filter(as_range!("bin", 0, 9));
+    let rs = client
+        .query_aggregate(&qpolicy, statement, "test_aggregate", "sum_bin", None)
+        .unwrap();
+    let mut sum = 0;
+    for rec in &*rs {
+        let partial: i64 = rec.unwrap().bins["SUCCESS"].clone().into();
+        sum += partial;
+    }
+    assert_eq!(sum, 45);
+
+    let statement = Statement::new(namespace, &set_name, Bins::All);
+    let rs = client
+        .query_aggregate(&qpolicy, statement, "test_aggregate", "fail", None)
+        .unwrap();
+    assert!((&*rs).any(|rec| rec.is_err()));
+}
+
+#[test]
+fn query_without_bin_data() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = create_test_set(EXPECTED);
+    let mut qpolicy = QueryPolicy::default();
+    qpolicy.include_bin_data = false;
+
+    let mut statement = Statement::new(namespace, &set_name, Bins::All);
+    statement.add_filter(as_range!("bin", 0, 9));
+    let rs = client.query(&qpolicy, statement).unwrap();
+    let mut count = 0;
+    for rec in &*rs {
+        let rec = rec.unwrap();
+        assert!(rec.bins.is_empty());
+        assert!(rec.key.is_some());
+        count += 1;
+    }
+    assert_eq!(count, 10);
+}
+
+#[test]
+fn query_max_concurrent_nodes() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = create_test_set(EXPECTED);
+    let mut qpolicy = QueryPolicy::default();
+    qpolicy.max_concurrent_nodes = 1;
+
+    let mut statement = Statement::new(namespace, &set_name, Bins::All);
+    statement.add_filter(as_range!("bin", 0, 9));
+    let rs = client.query(&qpolicy, statement).unwrap();
+    let count = (&*rs).filter(Result::is_ok).count();
+    assert_eq!(count, 10);
+}
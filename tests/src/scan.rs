@@ -13,6 +13,7 @@
 // License for the specific language governing permissions and limitations under
 // the License.
 
+use std::collections::HashSet;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::thread;
@@ -122,3 +123,260 @@ fn scan_node() {
 
     assert_eq!(count.load(Ordering::Relaxed), EXPECTED);
 }
+
+#[test]
+fn scan_partitions() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = create_test_set(EXPECTED);
+
+    let spolicy = ScanPolicy::default();
+    let mut count = 0;
+    for i in 0..4 {
+        let filter = PartitionFilter::by_range(i * 1024, 1024).unwrap();
+        let rs = client
+            .scan_partitions(&spolicy, &filter, namespace, &set_name, Bins::All)
+            .unwrap();
+        count += (&*rs).filter(Result::is_ok).count();
+    }
+    assert_eq!(count, EXPECTED);
+
+    let key = as_key!(namespace, &set_name, 1);
+    let partition_id = u16::from_le_bytes([key.digest[0], key.digest[1]]) & 4095;
+    let filter = PartitionFilter::by_id(partition_id).unwrap();
+    let rs = client
+        .scan_partitions(&spolicy, &filter, namespace, &set_name, Bins::All)
+        .unwrap();
+    let keys: Vec<Key> = (&*rs).map(|rec| rec.unwrap().key.unwrap()).collect();
+    assert!(keys.iter().any(|k| k.digest == key.digest));
+
+    assert!(PartitionFilter::by_range(4000, 100).is_err());
+}
+
+#[test]
+fn scan_partitions_resume() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = create_test_set(EXPECTED);
+
+    let mut spolicy = ScanPolicy::default();
+    spolicy.record_queue_size = 10;
+
+    let mut digests = HashSet::new();
+    let mut cursor = PartitionFilter::all();
+    while !cursor.is_done() {
+        let rs = client
+            .scan_partitions(&spolicy, &cursor, namespace, &set_name, Bins::All)
+            .unwrap();
+        for rec in (&*rs).take(100) {
+            let key = rec.unwrap().key.unwrap();
+            assert!(digests.insert(key.digest), "record returned twice");
+        }
+        rs.close();
+        cursor = rs.partition_filter().unwrap();
+    }
+    assert_eq!(digests.len(), EXPECTED);
+}
+
+#[test]
+fn scan_max_records() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = create_test_set(EXPECTED);
+
+    let mut spolicy = ScanPolicy::default();
+    spolicy.max_records = 100;
+    let rs = client
+        .scan(&spolicy, namespace, &set_name, Bins::All)
+        .unwrap();
+    let count = (&*rs).filter(Result::is_ok).count();
+    assert!(count > 0);
+    assert!(count <= 100);
+
+    // page through the set using the partition filter as cursor
+    let mut count = 0;
+    let mut cursor = PartitionFilter::all();
+    while !cursor.is_done() {
+        let rs = client
+            .scan_partitions(&spolicy, &cursor, namespace, &set_name, Bins::All)
+            .unwrap();
+        let page = (&*rs).filter(Result::is_ok).count();
+        assert!(page <= 100);
+        count += page;
+        cursor = rs.partition_filter().unwrap();
+    }
+    assert_eq!(count, EXPECTED);
+}
+
+#[test]
+fn scan_sample() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = create_test_set(EXPECTED);
+
+    let mut spolicy = ScanPolicy::default();
+    spolicy.max_records = 100;
+    spolicy.sample = true;
+    let rs = client
+        .scan(&spolicy, namespace, &set_name, Bins::All)
+        .unwrap();
+    let count = (&*rs).filter(Result::is_ok).count();
+    assert!(count > 0);
+    assert!(count <= 100);
+}
+
+#[test]
+fn scan_records_per_second() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = create_test_set(EXPECTED);
+
+    let mut spolicy = ScanPolicy::default();
+    spolicy.records_per_second = 5000;
+    let rs = client
+        .scan(&spolicy, namespace, &set_name, Bins::All)
+        .unwrap();
+    let count = (&*rs).filter(Result::is_ok).count();
+    assert_eq!(count, EXPECTED);
+}
+
+#[test]
+fn scan_execute() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = create_test_set(EXPECTED);
+
+    let wpolicy = WritePolicy::default();
+    let bin = as_bin!("bin", 1);
+    let ops = [operations::add(&bin)];
+    let task = client
+        .scan_execute(&wpolicy, namespace, &set_name, &ops)
+        .unwrap();
+    task.wait_till_complete(None).unwrap();
+
+    let spolicy = ScanPolicy::default();
+    let rs = client
+        .scan(&spolicy, namespace, &set_name, Bins::All)
+        .unwrap();
+    let mut count = 0;
+    let mut sum = 0;
+    for rec in &*rs {
+        let value: i64 = rec.unwrap().bins["bin"].clone().into();
+        sum += value;
+        count += 1;
+    }
+    assert_eq!(count, EXPECTED);
+    // every bin has been incremented by one
+    assert_eq!(sum, (1..=EXPECTED as i64).sum::<i64>());
+}
+
+#[test]
+fn scan_without_bin_data() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = create_test_set(EXPECTED);
+
+    let mut spolicy = ScanPolicy::default();
+    spolicy.include_bin_data = false;
+    let rs = client
+        .scan(&spolicy, namespace, &set_name, Bins::All)
+        .unwrap();
+    let mut count = 0;
+    for rec in &*rs {
+        let rec = rec.unwrap();
+        assert!(rec.bins.is_empty());
+        assert!(rec.generation > 0);
+        count += 1;
+    }
+    assert_eq!(count, EXPECTED);
+}
+
+#[test]
+fn scan_max_concurrent_nodes() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = create_test_set(EXPECTED);
+
+    let mut spolicy = ScanPolicy::default();
+    spolicy.max_concurrent_nodes = 1;
+    let rs = client
+        .scan(&spolicy, namespace, &set_name, Bins::All)
+        .unwrap();
+    let count = (&*rs).filter(Result::is_ok).count();
+    assert_eq!(count, EXPECTED);
+}
+
+#[test]
+fn scan_node_by_name() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = create_test_set(EXPECTED);
+
+    let spolicy = ScanPolicy::default();
+    let mut count = 0;
+    for name in client.node_names() {
+        let node = client.get_node(&name).unwrap();
+        let rs = client
+            .scan_node(&spolicy, node, namespace, &set_name, Bins::All)
+            .unwrap();
+        for rec in &*rs {
+            rec.unwrap();
+            count += 1;
+        }
+    }
+    assert_eq!(count, EXPECTED);
+
+    assert!(client.get_node("no-such-node").is_err());
+}
+
+#[test]
+fn scan_cancel() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = create_test_set(EXPECTED);
+
+    let mut spolicy = ScanPolicy::default();
+    spolicy.record_queue_size = 10;
+
+    // dropping the recordset cancels the scan
+    let rs = client
+        .scan(&spolicy, namespace, &set_name, Bins::All)
+        .unwrap();
+    assert_eq!((&*rs).take(5).count(), 5);
+    drop(rs);
+
+    // closing the recordset from another thread cancels the scan
+    let rs = client
+        .scan(&spolicy, namespace, &set_name, Bins::All)
+        .unwrap();
+    let closer = rs.clone();
+    thread::spawn(move || closer.close()).join().unwrap();
+    assert!((&*rs).count() < EXPECTED);
+
+    // the client's connections are still usable after the cancelled scans
+    let rs = client
+        .scan(&spolicy, namespace, &set_name, Bins::All)
+        .unwrap();
+    let count = (&*rs).filter(Result::is_ok).count();
+    assert_eq!(count, EXPECTED);
+}
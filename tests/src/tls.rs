@@ -0,0 +1,47 @@
+// Copyright 2015-2020 Aerospike, Inc.
+//
+// Portions may be licensed to Aerospike, Inc. under one or more contributor
+// license agreements.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy of
+// the License at http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations under
+// the License.
+
+use crate::common;
+use env_logger;
+
+#[test]
+#[cfg(not(feature = "native-tls"))]
+fn tls_requires_feature() {
+    use aerospike::{Client, Error, ErrorKind, TlsPolicy};
+
+    let _ = env_logger::try_init();
+
+    let mut policy = common::client_policy().clone();
+    policy.tls_policy = Some(TlsPolicy::default());
+    match Client::new(&policy, &common::hosts()) {
+        Err(Error(ErrorKind::InvalidArgument(_), _)) => {}
+        res => panic!("expected invalid argument error, got {:?}", res.err()),
+    }
+}
+
+#[test]
+#[cfg(feature = "native-tls")]
+fn tls_connection() {
+    let _ = env_logger::try_init();
+    if common::client_policy().tls_policy.is_none() {
+        return;
+    }
+
+    let client = common::client();
+    assert!(client.is_connected());
+    for node in client.nodes() {
+        assert!(node.is_active());
+    }
+}
@@ -13,7 +13,7 @@
 // License for the specific language governing permissions and limitations under
 // the License.
 
-use aerospike::WritePolicy;
+use aerospike::{as_bin, as_key, WritePolicy};
 
 use crate::common;
 use env_logger;
@@ -30,3 +30,26 @@ fn truncate() {
     let result = client.truncate(&wpolicy, namespace, set_name, 0);
     assert!(result.is_ok());
 }
+
+#[test]
+fn truncate_before() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace: &str = common::namespace();
+    let set_name = &common::rand_str(10);
+    let wpolicy = WritePolicy::default();
+
+    let key = as_key!(namespace, set_name, 1);
+    client.put(&wpolicy, &key, &[as_bin!("bin", 1)]).unwrap();
+
+    // records updated after the cut-off time (July 2017) are kept
+    let before_nanos = 1_500_000_000 * 1_000_000_000;
+    client
+        .truncate(&wpolicy, namespace, set_name, before_nanos)
+        .unwrap();
+    assert!(client.exists(&wpolicy, &key).unwrap());
+
+    client.truncate(&wpolicy, namespace, set_name, 0).unwrap();
+    assert!(!client.exists(&wpolicy, &key).unwrap());
+}
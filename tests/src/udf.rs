@@ -13,10 +13,13 @@
 // License for the specific language governing permissions and limitations under
 // the License.
 
+use std::thread;
+use std::time::Duration;
+
 use crate::common;
 use env_logger;
 
-use aerospike::Task;
+use aerospike::task::{Status, Task};
 use aerospike::*;
 
 #[test]
@@ -95,3 +98,138 @@ end
         panic!("UDF function did not return the expected error");
     }
 }
+
+#[test]
+fn batch_execute_udf() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let namespace = common::namespace();
+    let set_name = &common::rand_str(10);
+
+    let wpolicy = WritePolicy::default();
+    let bpolicy = BatchPolicy::default();
+    let keys: Vec<Key> = (0..3).map(|i| as_key!(namespace, set_name, i)).collect();
+    for (i, key) in keys.iter().enumerate() {
+        let wbin = as_bin!("bin", i as i64 * 10);
+        client.put(&wpolicy, key, &[&wbin]).unwrap();
+    }
+
+    let udf_body = r#"
+function batch_div(rec, div)
+  if div == 0 then
+    error('division by zero')
+  end
+  rec['bin'] = math.floor(rec['bin'] / div)
+  aerospike:update(rec)
+  return rec['bin']
+end
+"#;
+
+    let task = client
+        .register_udf(
+            &wpolicy,
+            udf_body.as_bytes(),
+            "test_batch_udf.lua",
+            UDFLang::Lua,
+        )
+        .unwrap();
+    task.wait_till_complete(None).unwrap();
+
+    let args = [as_val!(2)];
+    let results = client
+        .batch_execute_udf(&bpolicy, &keys, "test_batch_udf", "batch_div", Some(&args))
+        .unwrap();
+    assert_eq!(results.len(), 3);
+    for (i, result) in results.iter().enumerate() {
+        assert_eq!(result.key, keys[i]);
+        assert_eq!(result.result_code, Some(ResultCode::Ok));
+        assert_eq!(result.return_value(), Some(&as_val!(i as i64 * 5)));
+        assert!(result.error().is_none());
+    }
+
+    let args = [as_val!(0)];
+    let results = client
+        .batch_execute_udf(&bpolicy, &keys, "test_batch_udf", "batch_div", Some(&args))
+        .unwrap();
+    for result in &results {
+        assert_eq!(result.result_code, Some(ResultCode::UdfBadResponse));
+        assert!(result.return_value().is_none());
+        assert!(result.error().is_some());
+    }
+}
+
+#[test]
+fn list_udf() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let wpolicy = WritePolicy::default();
+    let rpolicy = ReadPolicy::default();
+
+    let udf_name = common::rand_str(10);
+    let udf_file_name = format!("{}.LUA", udf_name);
+    let udf_body = r#"
+function echo(rec, val)
+  return val
+end
+"#;
+
+    let task = client
+        .register_udf(&wpolicy, udf_body.as_bytes(), &udf_file_name, UDFLang::Lua)
+        .unwrap();
+    task.wait_till_complete(None).unwrap();
+
+    let udfs = client.list_udf(&rpolicy).unwrap();
+    let udf = udfs
+        .iter()
+        .find(|udf| udf.filename == udf_file_name)
+        .expect("UDF not listed");
+    assert_eq!(udf.language, UDFLang::Lua);
+    assert!(!udf.hash.is_empty());
+
+    client
+        .remove_udf(&wpolicy, &udf_name, UDFLang::Lua)
+        .unwrap();
+    // Wait for some time to ensure UDF has been unregistered on all nodes.
+    thread::sleep(Duration::from_secs(2));
+
+    let udfs = client.list_udf(&rpolicy).unwrap();
+    assert!(udfs.iter().all(|udf| udf.filename != udf_file_name));
+}
+
+#[test]
+fn register_udf_from_file() {
+    let _ = env_logger::try_init();
+
+    let client = common::client();
+    let wpolicy = WritePolicy::default();
+
+    let udf_name = common::rand_str(10);
+    let udf_file_name = format!("{}.LUA", udf_name);
+    let udf_body = r#"
+function echo(rec, val)
+  return val
+end
+"#;
+    let path = std::env::temp_dir().join(&udf_file_name);
+    std::fs::write(&path, udf_body).unwrap();
+
+    let task = client
+        .register_udf_from_file(
+            &wpolicy,
+            path.to_str().unwrap(),
+            &udf_file_name,
+            UDFLang::Lua,
+        )
+        .unwrap();
+    assert!(matches!(
+        task.wait_till_complete(Some(Duration::from_secs(10))),
+        Ok(Status::Complete)
+    ));
+
+    std::fs::remove_file(&path).unwrap();
+    client
+        .remove_udf(&wpolicy, &udf_name, UDFLang::Lua)
+        .unwrap();
+}